@@ -14,7 +14,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use anyhow::bail;
+use anyhow::{anyhow, bail};
 use chrono::{DateTime, TimeZone, Utc};
 use itertools::Itertools;
 use once_cell::sync::Lazy;
@@ -35,6 +35,7 @@ use mz_expr::{MirScalarExpr, OptimizedMirRelationExpr};
 use mz_ore::collections::CollectionExt;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::{to_datetime, EpochMillis, NowFn};
+use mz_persist_types::Codec;
 use mz_pgrepr::oid::FIRST_USER_OID;
 use mz_repr::{explain_new::ExprHumanizer, Diff, GlobalId, RelationDesc, ScalarType};
 use mz_sql::ast::display::AstDisplay;
@@ -50,12 +51,14 @@ use mz_sql::names::{
     SchemaSpecifier,
 };
 use mz_sql::plan::{
-    ComputeInstanceIntrospectionConfig, ComputeInstanceReplicaConfig, CreateConnectionPlan,
-    CreateIndexPlan, CreateRecordedViewPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
-    CreateTablePlan, CreateTypePlan, CreateViewPlan, Params, Plan, PlanContext, StatementDesc,
+    CheckConstraint, ComputeInstanceIntrospectionConfig, ComputeInstanceReplicaConfig,
+    CreateConnectionPlan, CreateIndexPlan, CreateRecordedViewPlan, CreateSecretPlan,
+    CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, Params,
+    Plan, PlanContext, StatementDesc, UniqueConstraint,
 };
 use mz_sql::DEFAULT_SCHEMA;
 use mz_stash::{Append, Postgres, Sqlite};
+use mz_storage::controller::ColumnLayout;
 use mz_storage::types::sinks::{SinkConnection, SinkConnectionBuilder, SinkEnvelope};
 use mz_storage::types::sources::{SourceDesc, Timeline};
 use mz_transform::Optimizer;
@@ -141,6 +144,10 @@ pub struct CatalogState {
     oid_counter: u32,
     replica_sizes: ClusterReplicaSizeMap,
     availability_zones: Vec<String>,
+    /// Indexes that were built before the item they index was altered. They
+    /// maintain the item's previous definition, so they can't serve reads of
+    /// its current one, and are rebuilt when the catalog is next loaded.
+    stale_indexes: HashSet<GlobalId>,
 }
 
 impl CatalogState {
@@ -327,6 +334,7 @@ impl CatalogState {
         self.get_entry(&id)
             .used_by()
             .iter()
+            .filter(move |uses_id| !self.stale_indexes.contains(uses_id))
             .filter_map(move |uses_id| match self.get_entry(uses_id).item() {
                 CatalogItem::Index(index) if index_matches(index) => Some((*uses_id, index)),
                 _ => None,
@@ -974,8 +982,13 @@ pub struct Table {
     pub desc: RelationDesc,
     #[serde(skip)]
     pub defaults: Vec<Expr<Aug>>,
+    pub checks: Vec<CheckConstraint>,
+    pub uniques: Vec<UniqueConstraint>,
     pub conn_id: Option<ConnectionId>,
     pub depends_on: Vec<GlobalId>,
+    /// How the rows of the table are stored in its shard, if the table has
+    /// been altered since it was created.
+    pub layout: Option<ColumnLayout>,
 }
 
 impl Table {
@@ -984,6 +997,17 @@ impl Table {
     pub fn timeline(&self) -> Timeline {
         Timeline::EpochMilliseconds
     }
+
+    /// Returns how the rows of the table are stored in its shard.
+    ///
+    /// The storage collection of every table is given a layout, even if it
+    /// stores its rows as is, so that dataflows that read the table before it
+    /// is altered keep reading its rows with the columns they expect.
+    pub fn storage_layout(&self) -> ColumnLayout {
+        self.layout
+            .clone()
+            .unwrap_or_else(|| ColumnLayout::identity(self.desc.typ()))
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -1416,6 +1440,7 @@ impl<S: Append> Catalog<S> {
                 oid_counter: FIRST_USER_OID,
                 replica_sizes: config.replica_sizes,
                 availability_zones: config.availability_zones,
+                stale_indexes: HashSet::new(),
             },
             transient_revision: 0,
             storage: Arc::new(Mutex::new(config.storage)),
@@ -1541,8 +1566,11 @@ impl<S: Append> Catalog<S> {
                             create_sql: "TODO".to_string(),
                             desc: table.desc.clone(),
                             defaults: vec![Expr::null(); table.desc.arity()],
+                            checks: vec![],
+                            uniques: vec![],
                             conn_id: None,
                             depends_on: vec![],
+                            layout: None,
                         }),
                     );
                 }
@@ -2978,6 +3006,43 @@ impl<S: Append> Catalog<S> {
                     });
                     actions
                 }
+                Op::AlterTable { id, table } => {
+                    let entry = self.get_entry(&id);
+                    if Self::should_audit_log_item(&entry.item) {
+                        self.add_to_audit_log(
+                            session,
+                            &mut tx,
+                            &mut builtin_table_updates,
+                            EventType::Alter,
+                            sql_type_to_object_type(entry.item().typ()),
+                            EventDetails::FullNameV1(
+                                self.resolve_full_name_detail(&entry.name, session),
+                            ),
+                        )?;
+                    }
+
+                    // Items that depend on the table are re-planned against
+                    // its new definition, which they will be planned against
+                    // when the catalog is next loaded anyway.
+                    let dependents = self.plan_altered_table_dependents(id, &table)?;
+                    let mut actions = Vec::new();
+                    for (id, to_item) in
+                        std::iter::once((id, CatalogItem::Table(table))).chain(dependents)
+                    {
+                        let entry = self.get_entry(&id);
+                        if !to_item.is_temporary() {
+                            let serialized_item = self.serialize_item(&to_item);
+                            tx.update_item(id, &entry.name().item, &serialized_item)?;
+                        }
+                        builtin_table_updates.extend(self.state.pack_item_update(id, -1));
+                        actions.push(Action::UpdateItem {
+                            id,
+                            to_name: entry.name().clone(),
+                            to_item,
+                        });
+                    }
+                    actions
+                }
                 Op::UpdateComputeInstanceStatus { event } => {
                     // When we receive the first status update for a given
                     // replica process, there is no entry in the builtin table
@@ -3184,6 +3249,7 @@ impl<S: Append> Catalog<S> {
 
                 Action::DropItem(id) => {
                     let metadata = state.entry_by_id.remove(&id).unwrap();
+                    state.stale_indexes.remove(&id);
                     if !metadata.item.is_placeholder() {
                         info!(
                             "drop {} {} ({})",
@@ -3243,7 +3309,46 @@ impl<S: Append> Catalog<S> {
                         state.resolve_full_name(&old_entry.name, old_entry.conn_id()),
                         id
                     );
-                    assert_eq!(old_entry.uses(), to_item.uses());
+                    // Renaming an item never changes its dependencies, but
+                    // altering a table can, if the added or dropped column
+                    // has a user-defined type.
+                    for u in old_entry.uses() {
+                        if !to_item.uses().contains(u) {
+                            if let Some(dep_metadata) = state.entry_by_id.get_mut(u) {
+                                dep_metadata.used_by.retain(|u| *u != id)
+                            }
+                        }
+                    }
+                    for u in to_item.uses() {
+                        if !old_entry.uses().contains(u) {
+                            match state.entry_by_id.get_mut(u) {
+                                Some(metadata) => metadata.used_by.push(id),
+                                None => panic!(
+                                    "Catalog: missing dependent catalog item {} while updating {}",
+                                    u,
+                                    state.resolve_full_name(&old_entry.name, old_entry.conn_id())
+                                ),
+                            }
+                        }
+                    }
+                    // Indexes on a table or view whose columns change keep
+                    // maintaining its previous columns.
+                    let desc_changed = match (old_entry.item(), &to_item) {
+                        (CatalogItem::Table(old), CatalogItem::Table(new)) => old.desc != new.desc,
+                        (CatalogItem::View(old), CatalogItem::View(new)) => old.desc != new.desc,
+                        _ => false,
+                    };
+                    if desc_changed {
+                        for dependent in old_entry.used_by() {
+                            if let Some(CatalogItem::Index(index)) =
+                                state.entry_by_id.get(dependent).map(|entry| entry.item())
+                            {
+                                if index.on == id {
+                                    state.stale_indexes.insert(*dependent);
+                                }
+                            }
+                        }
+                    }
                     let conn_id = old_entry.item().conn_id().unwrap_or(SYSTEM_CONN_ID);
                     let schema = &mut state.get_schema_mut(
                         &old_entry.name().qualifiers.database_spec,
@@ -3295,39 +3400,52 @@ impl<S: Append> Catalog<S> {
             CatalogItem::Table(table) => SerializedCatalogItem::V1 {
                 create_sql: table.create_sql.clone(),
                 eval_env: None,
+                layout: table.layout.as_ref().map(|layout| {
+                    let mut buf = Vec::new();
+                    layout.encode(&mut buf);
+                    buf
+                }),
             },
             CatalogItem::Log(_) => unreachable!("builtin logs cannot be serialized"),
             CatalogItem::Source(source) => SerializedCatalogItem::V1 {
                 create_sql: source.create_sql.clone(),
                 eval_env: None,
+                layout: None,
             },
             CatalogItem::View(view) => SerializedCatalogItem::V1 {
                 create_sql: view.create_sql.clone(),
                 eval_env: None,
+                layout: None,
             },
             CatalogItem::RecordedView(rview) => SerializedCatalogItem::V1 {
                 create_sql: rview.create_sql.clone(),
                 eval_env: None,
+                layout: None,
             },
             CatalogItem::Index(index) => SerializedCatalogItem::V1 {
                 create_sql: index.create_sql.clone(),
                 eval_env: None,
+                layout: None,
             },
             CatalogItem::Sink(sink) => SerializedCatalogItem::V1 {
                 create_sql: sink.create_sql.clone(),
                 eval_env: None,
+                layout: None,
             },
             CatalogItem::Type(typ) => SerializedCatalogItem::V1 {
                 create_sql: typ.create_sql.clone(),
                 eval_env: None,
+                layout: None,
             },
             CatalogItem::Secret(secret) => SerializedCatalogItem::V1 {
                 create_sql: secret.create_sql.clone(),
                 eval_env: None,
+                layout: None,
             },
             CatalogItem::Connection(connection) => SerializedCatalogItem::V1 {
                 create_sql: connection.create_sql.clone(),
                 eval_env: None,
+                layout: None,
             },
             CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
         };
@@ -3338,8 +3456,107 @@ impl<S: Append> Catalog<S> {
         let SerializedCatalogItem::V1 {
             create_sql,
             eval_env: _,
+            layout,
         } = serde_json::from_slice(&bytes)?;
-        self.parse_item(create_sql, Some(&PlanContext::zero()))
+        let mut item = self.parse_item(create_sql, Some(&PlanContext::zero()))?;
+        if let (CatalogItem::Table(table), Some(layout)) = (&mut item, layout) {
+            table.layout = Some(ColumnLayout::decode(&layout).map_err(|e| anyhow!(e))?);
+        }
+        Ok(item)
+    }
+
+    /// Re-plans the items that transitively depend on the table `id` against
+    /// `table`, its altered definition.
+    ///
+    /// Fails if a dependent no longer plans, e.g. because it refers to a
+    /// dropped column, or if the columns of a recorded view or of the relation
+    /// that a sink reads from would change, as their outputs can't be changed
+    /// after the fact.
+    fn plan_altered_table_dependents(
+        &self,
+        id: GlobalId,
+        table: &Table,
+    ) -> Result<Vec<(GlobalId, CatalogItem)>, Error> {
+        let entry = self.get_entry(&id);
+        let table_name = self
+            .resolve_full_name(entry.name(), entry.conn_id())
+            .to_string();
+        let mut dependents = BTreeSet::new();
+        let mut todo = vec![id];
+        while let Some(id) = todo.pop() {
+            for dependent in self.get_entry(&id).used_by() {
+                if dependents.insert(*dependent) {
+                    todo.push(*dependent);
+                }
+            }
+        }
+
+        // Items only depend on items created before them, so planning the
+        // dependents in the order of their IDs plans each one against the
+        // new definitions of its own dependencies.
+        let mut catalog = self.clone();
+        catalog
+            .state
+            .entry_by_id
+            .get_mut(&id)
+            .expect("altered table exists")
+            .item = CatalogItem::Table(table.clone());
+        let mut changed = HashSet::from([id]);
+        let mut items = Vec::new();
+        for dependent in dependents {
+            let entry = self.get_entry(&dependent);
+            let error = |cause: String| {
+                Error::new(ErrorKind::AlterTableDependency {
+                    table: table_name.clone(),
+                    dependent: format!(
+                        "{} {}",
+                        entry.item_type(),
+                        self.resolve_full_name(entry.name(), entry.conn_id())
+                    ),
+                    cause,
+                })
+            };
+            let create_sql = match entry.item() {
+                CatalogItem::View(View { create_sql, .. })
+                | CatalogItem::RecordedView(RecordedView { create_sql, .. })
+                | CatalogItem::Index(Index { create_sql, .. }) => create_sql.clone(),
+                CatalogItem::Sink(Sink { from, .. }) if changed.contains(from) => {
+                    return Err(error(
+                        "the columns of the relation it reads from would change".into(),
+                    ));
+                }
+                _ => continue,
+            };
+            let conn_id = entry.conn_id().unwrap_or(SYSTEM_CONN_ID);
+            let mut item = catalog
+                .parse_item_for_conn(create_sql, Some(&PlanContext::zero()), conn_id)
+                .map_err(|e| error(e.to_string()))?;
+            match (entry.item(), &mut item) {
+                (CatalogItem::View(old), CatalogItem::View(new)) => {
+                    new.conn_id = old.conn_id;
+                    if old.desc != new.desc {
+                        changed.insert(dependent);
+                    }
+                }
+                (CatalogItem::RecordedView(old), CatalogItem::RecordedView(new)) => {
+                    if old.desc != new.desc {
+                        return Err(error("its columns would change".into()));
+                    }
+                }
+                (CatalogItem::Index(old), CatalogItem::Index(new)) => {
+                    new.conn_id = old.conn_id;
+                }
+                _ => unreachable!("re-planning an item preserves its type"),
+            }
+            catalog
+                .state
+                .entry_by_id
+                .get_mut(&dependent)
+                .expect("dependent exists")
+                .item = item.clone();
+            items.push((dependent, item));
+        }
+        Ok(items)
     }
 
     // Parses the given SQL string into a `CatalogItem`.
@@ -3348,7 +3565,19 @@ impl<S: Append> Catalog<S> {
         create_sql: String,
         pcx: Option<&PlanContext>,
     ) -> Result<CatalogItem, anyhow::Error> {
-        let session_catalog = self.for_system_session();
+        self.parse_item_for_conn(create_sql, pcx, SYSTEM_CONN_ID)
+    }
+
+    // Parses the given SQL string into a `CatalogItem`, resolving the temporary
+    // items of connection `conn_id`.
+    fn parse_item_for_conn(
+        &self,
+        create_sql: String,
+        pcx: Option<&PlanContext>,
+        conn_id: ConnectionId,
+    ) -> Result<CatalogItem, anyhow::Error> {
+        let mut session_catalog = self.for_system_session();
+        session_catalog.conn_id = conn_id;
         let stmt = mz_sql::parse::parse(&create_sql)?.into_element();
         let (stmt, depends_on) = mz_sql::names::resolve(&session_catalog, stmt)?;
         let depends_on = depends_on.into_iter().collect();
@@ -3358,8 +3587,11 @@ impl<S: Append> Catalog<S> {
                 create_sql: table.create_sql,
                 desc: table.desc,
                 defaults: table.defaults,
+                checks: table.checks,
+                uniques: table.uniques,
                 conn_id: None,
                 depends_on,
+                layout: None,
             }),
             Plan::CreateSource(CreateSourcePlan {
                 source,
@@ -3555,6 +3787,12 @@ pub enum Op {
         current_full_name: FullObjectName,
         to_name: String,
     },
+    /// Replaces the definition of the identified table, e.g. after a column
+    /// has been added or dropped.
+    AlterTable {
+        id: GlobalId,
+        table: Table,
+    },
     UpdateComputeInstanceStatus {
         event: ComputeInstanceEvent,
     },
@@ -3566,6 +3804,9 @@ enum SerializedCatalogItem {
         create_sql: String,
         // The name "eval_env" is historical.
         eval_env: Option<SerializedPlanContext>,
+        /// The encoded [`ColumnLayout`] of an altered table.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        layout: Option<Vec<u8>>,
     },
 }
 
@@ -4048,6 +4289,14 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
         }
     }
 
+    fn table_unique_constraints(&self) -> Option<&[UniqueConstraint]> {
+        if let CatalogItem::Table(Table { uniques, .. }) = self.item() {
+            Some(uniques)
+        } else {
+            None
+        }
+    }
+
     fn type_details(&self) -> Option<&CatalogTypeDetails<IdReference>> {
         if let CatalogItem::Type(Type { details, .. }) = self.item() {
             Some(details)
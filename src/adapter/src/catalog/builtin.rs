@@ -898,6 +898,12 @@ pub const MZ_PEEK_DURATIONS: BuiltinLog = BuiltinLog {
     variant: LogVariant::Compute(ComputeLog::PeekDuration),
 };
 
+pub const MZ_SINK_STATUS: BuiltinLog = BuiltinLog {
+    name: "mz_sink_status",
+    schema: MZ_CATALOG_SCHEMA,
+    variant: LogVariant::Compute(ComputeLog::SinkStatus),
+};
+
 pub const MZ_MESSAGE_COUNTS_RECEIVED_INTERNAL: BuiltinLog = BuiltinLog {
     name: "mz_message_counts_received_internal",
     schema: MZ_CATALOG_SCHEMA,
@@ -2096,6 +2102,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Log(&MZ_SCHEDULING_ELAPSED_INTERNAL),
         Builtin::Log(&MZ_SCHEDULING_HISTOGRAM_INTERNAL),
         Builtin::Log(&MZ_SCHEDULING_PARKS_INTERNAL),
+        Builtin::Log(&MZ_SINK_STATUS),
         Builtin::Log(&MZ_WORKER_MATERIALIZATION_FRONTIERS),
         Builtin::Table(&MZ_VIEW_KEYS),
         Builtin::Table(&MZ_VIEW_FOREIGN_KEYS),
@@ -479,7 +479,8 @@ impl SessionClient {
                 ExecuteResponse::Canceled => {
                     results.push(SimpleResult::err("statement canceled due to user request"));
                 }
-                ExecuteResponse::CreatedConnection { existed: _ }
+                ExecuteResponse::Copied(_)
+                | ExecuteResponse::CreatedConnection { existed: _ }
                 | ExecuteResponse::CreatedDatabase { existed: _ }
                 | ExecuteResponse::CreatedSchema { existed: _ }
                 | ExecuteResponse::CreatedRole
@@ -518,6 +519,7 @@ impl SessionClient {
                     tag: _,
                     was_implicit: _,
                 }
+                | ExecuteResponse::Truncated
                 | ExecuteResponse::Updated(_)
                 | ExecuteResponse::AlteredObject(_)
                 | ExecuteResponse::AlteredIndexLogicalCompaction
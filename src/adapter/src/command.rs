@@ -172,7 +172,7 @@ pub enum ExecuteResponse {
     /// The requested cursor was closed.
     ClosedCursor,
     CopyTo {
-        format: mz_sql::plan::CopyFormat,
+        format: CopyFormatParams<'static>,
         resp: Box<ExecuteResponse>,
     },
     CopyFrom {
@@ -180,6 +180,9 @@ pub enum ExecuteResponse {
         columns: Vec<usize>,
         params: CopyFormatParams<'static>,
     },
+    /// The specified number of rows were copied to or from a location outside
+    /// of Materialize.
+    Copied(usize),
     /// The requested connection was created.
     CreatedConnection {
         existed: bool,
@@ -310,6 +313,8 @@ pub enum ExecuteResponse {
     Tailing {
         rx: RowBatchStream,
     },
+    /// The requested tables were truncated.
+    Truncated,
     /// The specified number of rows were updated in the requested table.
     Updated(usize),
     /// Raise a warning.
@@ -68,6 +68,7 @@
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::fmt::Write;
+use std::io;
 use std::num::{NonZeroI64, NonZeroUsize};
 use std::ops::Neg;
 use std::sync::Arc;
@@ -75,6 +76,7 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Context};
+use bytes::BytesMut;
 use chrono::{DateTime, DurationRound, Utc};
 use derivative::Derivative;
 use differential_dataflow::lattice::Lattice;
@@ -113,11 +115,13 @@ use mz_ore::now::{to_datetime, EpochMillis, NowFn};
 use mz_ore::retry::Retry;
 use mz_ore::thread::JoinHandleExt;
 use mz_ore::{stack, task};
+use mz_pgcopy::CopyFormatParams;
 use mz_repr::adt::interval::Interval;
 use mz_repr::adt::numeric::{Numeric, NumericMaxScale};
 use mz_repr::explain_new::ExprHumanizer;
 use mz_repr::{
-    Datum, Diff, GlobalId, RelationDesc, RelationType, Row, RowArena, ScalarType, Timestamp,
+    ColumnType, Datum, Diff, GlobalId, RelationDesc, RelationType, Row, RowArena, ScalarType,
+    Timestamp,
 };
 use mz_secrets::SecretsController;
 use mz_sql::ast::display::AstDisplay;
@@ -134,18 +138,20 @@ use mz_sql::names::{
 };
 use mz_sql::plan::{
     AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan, AlterSecretPlan,
+    AlterTableColumn, AlterTablePlan, CheckConstraint, CopyFromPlan, CopyToPlan,
     CreateComputeInstancePlan, CreateComputeInstanceReplicaPlan, CreateConnectionPlan,
     CreateDatabasePlan, CreateIndexPlan, CreateRecordedViewPlan, CreateRolePlan, CreateSchemaPlan,
     CreateSecretPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
     CreateViewPlan, CreateViewsPlan, DropComputeInstanceReplicaPlan, DropComputeInstancesPlan,
     DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan, ExecutePlan, ExplainPlan,
     ExplainPlanNew, ExplainPlanOld, FetchPlan, HirRelationExpr, IndexOption, InsertPlan,
-    MutationKind, OptimizerConfig, Params, PeekPlan, Plan, QueryWhen, RaisePlan, ReadThenWritePlan,
-    RecordedView, ResetVariablePlan, SendDiffsPlan, SetVariablePlan, ShowVariablePlan,
-    StatementDesc, TailFrom, TailPlan, View,
+    MutationKind, OnConflictPlan, OptimizerConfig, Params, PeekPlan, Plan, QueryWhen, RaisePlan,
+    ReadThenWritePlan, RecordedView, ResetVariablePlan, SendDiffsPlan, SetVariablePlan,
+    ShowVariablePlan, StatementDesc, TailFrom, TailPlan, TruncatePlan, UniqueConstraint, View,
 };
 use mz_stash::Append;
 use mz_storage::controller::{CollectionDescription, ReadPolicy};
+use mz_storage::copy::CopyLocation;
 use mz_storage::protocol::client::Update;
 use mz_storage::types::connections::ConnectionContext;
 use mz_storage::types::sinks::{SinkAsOf, SinkConnection, SinkDesc, TailSinkConnection};
@@ -189,7 +195,9 @@ pub enum Message<T = mz_repr::Timestamp> {
     ControllerReady,
     CreateSourceStatementReady(CreateSourceStatementReady),
     SinkConnectionReady(SinkConnectionReady),
+    CopyFromLocationRows(CopyFromLocationRows),
     SendDiffs(SendDiffs),
+    ResolveInsertConflicts(ResolveInsertConflicts),
     WriteLockGrant(tokio::sync::OwnedMutexGuard<()>),
     AdvanceTimelines,
     AdvanceLocalInput(AdvanceLocalInput<T>),
@@ -216,6 +224,21 @@ pub struct SendDiffs {
     pub returning: Vec<(Row, NonZeroUsize)>,
 }
 
+/// The rows proposed for insertion by an `INSERT ... ON CONFLICT`, whose
+/// conflicts with the table's rows are yet to be resolved.
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct ResolveInsertConflicts {
+    session: Session,
+    #[derivative(Debug = "ignore")]
+    tx: ClientTransmitter<ExecuteResponse>,
+    pub id: GlobalId,
+    pub desc: RelationDesc,
+    pub on_conflict: OnConflictPlan,
+    pub inserts: Vec<Row>,
+    pub returning: Vec<MirScalarExpr>,
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct CreateSourceStatementReady {
@@ -228,6 +251,18 @@ pub struct CreateSourceStatementReady {
     pub original_stmt: Statement<Raw>,
 }
 
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct CopyFromLocationRows {
+    pub session: Session,
+    #[derivative(Debug = "ignore")]
+    pub tx: ClientTransmitter<ExecuteResponse>,
+    pub id: GlobalId,
+    pub columns: Vec<usize>,
+    #[derivative(Debug = "ignore")]
+    pub rows: Vec<Row>,
+}
+
 /// An operation that is deferred while waiting for a lock.
 enum Deferred {
     Plan(DeferredPlan),
@@ -839,6 +874,7 @@ impl<S: Append + 'static> Coordinator<S> {
                                 desc: source.desc.clone(),
                                 ingestion: Some(ingestion),
                                 remote_addr: source.remote_addr.clone(),
+                                layout: None,
                             },
                         )])
                         .await
@@ -855,6 +891,7 @@ impl<S: Append + 'static> Coordinator<S> {
                                 desc: table.desc.clone(),
                                 ingestion: None,
                                 remote_addr: None,
+                                layout: Some(table.storage_layout()),
                             },
                         )])
                         .await
@@ -901,6 +938,7 @@ impl<S: Append + 'static> Coordinator<S> {
                                 desc: rview.desc.clone(),
                                 ingestion: None,
                                 remote_addr: None,
+                                layout: None,
                             },
                         )])
                         .await
@@ -1142,6 +1180,9 @@ impl<S: Append + 'static> Coordinator<S> {
                 Message::SinkConnectionReady(ready) => {
                     self.message_sink_connection_ready(ready).await
                 }
+                Message::CopyFromLocationRows(rows) => {
+                    self.message_copy_from_location_rows(rows).await
+                }
                 Message::WriteLockGrant(write_lock_guard) => {
                     // It's possible to have more incoming write lock grants
                     // than pending writes because of cancellations.
@@ -1161,7 +1202,10 @@ impl<S: Append + 'static> Coordinator<S> {
                     // N.B. if no deferred plans, write lock is released by drop
                     // here.
                 }
-                Message::SendDiffs(diffs) => self.message_send_diffs(diffs),
+                Message::SendDiffs(diffs) => self.message_send_diffs(diffs).await,
+                Message::ResolveInsertConflicts(inserts) => {
+                    self.message_resolve_insert_conflicts(inserts).await
+                }
                 Message::AdvanceTimelines => {
                     self.message_advance_timelines().await;
                 }
@@ -1340,6 +1384,7 @@ impl<S: Append + 'static> Coordinator<S> {
         } = self.get_and_step_local_write_ts().await;
         let mut appends: HashMap<GlobalId, Vec<Update<Timestamp>>> =
             HashMap::with_capacity(self.pending_writes.len());
+        let mut truncations = Vec::new();
         let mut responses = Vec::with_capacity(self.pending_writes.len());
         for PendingWriteTxn {
             writes,
@@ -1350,7 +1395,7 @@ impl<S: Append + 'static> Coordinator<S> {
             write_lock_guard: _,
         } in self.pending_writes.drain(..)
         {
-            for WriteOp { id, rows } in writes {
+            for WriteOp { id, rows, truncate } in writes {
                 // If the table that some write was targeting has been deleted while the write was
                 // waiting, then the write will be ignored and we respond to the client that the
                 // write was successful. This is only possible if the write and the delete were
@@ -1366,6 +1411,12 @@ impl<S: Append + 'static> Coordinator<S> {
                         })
                         .collect::<Vec<_>>();
                     appends.entry(id).or_default().extend(updates);
+                    // The storage controller retracts the table's contents
+                    // itself, so that they never pass through the
+                    // coordinator.
+                    if truncate {
+                        truncations.push((id, timestamp));
+                    }
                 }
             }
             responses.push((client_transmitter, response, session, action));
@@ -1374,7 +1425,11 @@ impl<S: Append + 'static> Coordinator<S> {
             .into_iter()
             .map(|(id, updates)| (id, updates, advance_to))
             .collect();
-        self.controller.storage_mut().append(appends).await.unwrap();
+        self.controller
+            .storage_mut()
+            .append_with_truncations(appends, truncations)
+            .await
+            .unwrap();
         for (client_transmitter, response, mut session, action) in responses {
             session.vars_mut().end_transaction(action);
             client_transmitter.send(response, session);
@@ -1573,8 +1628,34 @@ impl<S: Append + 'static> Coordinator<S> {
         }
     }
 
+    async fn message_copy_from_location_rows(
+        &mut self,
+        CopyFromLocationRows {
+            session,
+            tx,
+            id,
+            columns,
+            rows,
+        }: CopyFromLocationRows,
+    ) {
+        // The table may have been dropped while we were reading the data.
+        if self.catalog.try_get_entry(&id).is_none() {
+            tx.send(
+                Err(AdapterError::SqlCatalog(CatalogError::UnknownItem(
+                    id.to_string(),
+                ))),
+                session,
+            );
+            return;
+        }
+
+        // Insert the rows as if they had been sent by the client.
+        self.sequence_copy_rows(tx, session, id, columns, rows)
+            .await;
+    }
+
     #[tracing::instrument(level = "debug", skip_all, fields(id, kind))]
-    fn message_send_diffs(
+    async fn message_send_diffs(
         &mut self,
         SendDiffs {
             mut session,
@@ -1588,8 +1669,8 @@ impl<S: Append + 'static> Coordinator<S> {
         event!(Level::TRACE, diffs = format!("{:?}", diffs));
         match diffs {
             Ok(diffs) => {
-                tx.send(
-                    self.sequence_send_diffs(
+                let result = self
+                    .sequence_send_diffs(
                         &mut session,
                         SendDiffsPlan {
                             id,
@@ -1597,9 +1678,9 @@ impl<S: Append + 'static> Coordinator<S> {
                             kind,
                             returning,
                         },
-                    ),
-                    session,
-                );
+                    )
+                    .await;
+                tx.send(result, session);
             }
             Err(e) => {
                 tx.send(Err(e), session);
@@ -1607,6 +1688,48 @@ impl<S: Append + 'static> Coordinator<S> {
         }
     }
 
+    /// Resolves the conflicts of the rows proposed for insertion with the rows
+    /// of the table that have their keys, and sends the resulting diffs.
+    ///
+    /// Like the read that produced the proposed rows, this runs while the
+    /// session holds the write lock, so no other writes to the table commit in
+    /// between.
+    #[tracing::instrument(level = "debug", skip_all, fields(id))]
+    async fn message_resolve_insert_conflicts(
+        &mut self,
+        ResolveInsertConflicts {
+            session,
+            tx,
+            id,
+            desc,
+            on_conflict,
+            inserts,
+            returning,
+        }: ResolveInsertConflicts,
+    ) {
+        let lookups = unique_key_lookups(&on_conflict.constraints, &inserts);
+        let diffs = match self.table_contents(&session, id, Some(&lookups)).await {
+            Ok(contents) => resolve_insert_conflicts(&desc, &on_conflict, contents, inserts),
+            Err(e) => Err(e),
+        };
+        let (diffs, returning) = match diffs {
+            Ok(diffs) => match eval_returning(&returning, &diffs) {
+                Ok(returning) => (Ok(diffs), returning),
+                Err(e) => (Err(e), vec![]),
+            },
+            Err(e) => (Err(e), vec![]),
+        };
+        self.message_send_diffs(SendDiffs {
+            session,
+            tx,
+            id,
+            diffs,
+            kind: MutationKind::Insert,
+            returning,
+        })
+        .await
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     async fn message_advance_timelines(&mut self) {
         // Convince the coordinator it needs to open a new timestamp
@@ -1839,11 +1962,12 @@ impl<S: Append + 'static> Coordinator<S> {
                 id,
                 columns,
                 rows,
-                mut session,
+                session,
                 tx,
             } => {
-                let result = self.sequence_copy_rows(&mut session, id, columns, rows);
-                let _ = tx.send(Response { result, session });
+                let tx = ClientTransmitter::new(tx, self.internal_cmd_tx.clone());
+                self.sequence_copy_rows(tx, session, id, columns, rows)
+                    .await;
             }
 
             Command::Terminate { mut session } => {
@@ -2144,6 +2268,7 @@ impl<S: Append + 'static> Coordinator<S> {
                     // Statements below must by run singly (in Started).
                     Statement::AlterIndex(_)
                     | Statement::AlterSecret(_)
+                    | Statement::AlterTable(_)
                     | Statement::AlterObjectRename(_)
                     | Statement::CreateConnection(_)
                     | Statement::CreateDatabase(_)
@@ -2168,6 +2293,7 @@ impl<S: Append + 'static> Coordinator<S> {
                     | Statement::DropClusters(_)
                     | Statement::DropClusterReplicas(_)
                     | Statement::Insert(_)
+                    | Statement::Truncate(_)
                     | Statement::Update(_) => {
                         return tx.send(
                             Err(AdapterError::OperationProhibitsTransaction(
@@ -2540,21 +2666,38 @@ impl<S: Append + 'static> Coordinator<S> {
                 tx.send(Ok(send_immediate_rows(plan.rows)), session);
             }
 
-            Plan::CopyFrom(plan) => {
+            Plan::CopyFrom(CopyFromPlan {
+                id,
+                columns,
+                params,
+                location: None,
+            }) => {
                 tx.send(
                     Ok(ExecuteResponse::CopyFrom {
-                        id: plan.id,
-                        columns: plan.columns,
-                        params: plan.params,
+                        id,
+                        columns,
+                        params,
                     }),
                     session,
                 );
             }
+            Plan::CopyFrom(CopyFromPlan {
+                id,
+                columns,
+                params,
+                location: Some(location),
+            }) => {
+                self.sequence_copy_from_location(tx, session, id, columns, params, location);
+            }
+            Plan::CopyTo(plan) => {
+                self.sequence_copy_to(tx, session, plan).await;
+            }
             Plan::Explain(plan) => {
                 tx.send(self.sequence_explain(&session, plan), session);
             }
             Plan::SendDiffs(plan) => {
-                tx.send(self.sequence_send_diffs(&mut session, plan), session);
+                let result = self.sequence_send_diffs(&mut session, plan).await;
+                tx.send(result, session);
             }
             Plan::Insert(plan) => {
                 self.sequence_insert(tx, session, plan).await;
@@ -2562,6 +2705,9 @@ impl<S: Append + 'static> Coordinator<S> {
             Plan::ReadThenWrite(plan) => {
                 self.sequence_read_then_write(tx, session, plan).await;
             }
+            Plan::Truncate(plan) => {
+                self.sequence_truncate(tx, session, plan).await;
+            }
             Plan::AlterNoop(plan) => {
                 tx.send(
                     Ok(ExecuteResponse::AlteredObject(plan.object_type)),
@@ -2583,6 +2729,9 @@ impl<S: Append + 'static> Coordinator<S> {
             Plan::AlterSecret(plan) => {
                 tx.send(self.sequence_alter_secret(&session, plan).await, session);
             }
+            Plan::AlterTable(plan) => {
+                self.sequence_alter_table(tx, session, plan).await;
+            }
             Plan::DiscardTemp => {
                 self.drop_temp_items(&session).await;
                 tx.send(Ok(ExecuteResponse::DiscardedTemp), session);
@@ -2931,6 +3080,8 @@ impl<S: Append + 'static> Coordinator<S> {
             create_sql: table.create_sql,
             desc: table.desc,
             defaults: table.defaults,
+            checks: table.checks,
+            uniques: table.uniques,
             conn_id,
             depends_on,
         };
@@ -2954,6 +3105,7 @@ impl<S: Append + 'static> Coordinator<S> {
                             desc: table.desc.clone(),
                             ingestion: None,
                             remote_addr: None,
+                            layout: Some(table.storage_layout()),
                         },
                     )])
                     .await
@@ -3079,6 +3231,7 @@ impl<S: Append + 'static> Coordinator<S> {
                             desc: source.desc.clone(),
                             ingestion: Some(ingestion),
                             remote_addr: source.remote_addr,
+                            layout: None,
                         },
                     )])
                     .await
@@ -3494,6 +3647,7 @@ impl<S: Append + 'static> Coordinator<S> {
                             desc,
                             ingestion: None,
                             remote_addr: None,
+                            layout: None,
                         },
                     )])
                     .await
@@ -3867,15 +4021,27 @@ impl<S: Append + 'static> Coordinator<S> {
         if let EndTransactionAction::Commit = action {
             if let (Some(ops), write_lock_guard) = txn.into_ops_and_lock_guard() {
                 if let TransactionOps::Writes(mut writes) = ops {
-                    for WriteOp { id, .. } in &writes {
+                    for WriteOp { id, rows, .. } in &writes {
                         // Re-verify this id exists.
-                        let _ = self.catalog.try_get_entry(&id).ok_or_else(|| {
+                        let entry = self.catalog.try_get_entry(&id).ok_or_else(|| {
                             AdapterError::SqlCatalog(CatalogError::UnknownItem(id.to_string()))
                         })?;
+                        // Re-verify that the table has not been altered since
+                        // the rows were planned.
+                        if let CatalogItem::Table(table) = entry.item() {
+                            let arity = table.desc.arity();
+                            if rows.iter().any(|(row, _)| row.iter().count() != arity) {
+                                let name = self
+                                    .catalog
+                                    .resolve_full_name(entry.name(), entry.conn_id())
+                                    .to_string();
+                                return Err(AdapterError::ChangedTable(name));
+                            }
+                        }
                     }
 
                     // `rows` can be empty if, say, a DELETE's WHERE clause had 0 results.
-                    writes.retain(|WriteOp { rows, .. }| !rows.is_empty());
+                    writes.retain(|WriteOp { rows, truncate, .. }| !rows.is_empty() || *truncate);
                     return Ok((Some(writes), write_lock_guard));
                 }
             }
@@ -4253,6 +4419,123 @@ impl<S: Append + 'static> Coordinator<S> {
         }
     }
 
+    /// Reads and decodes the data for a `COPY ... FROM` a location outside of
+    /// Materialize in a background task, inserting the decoded rows in batches
+    /// of at most [`COPY_FROM_LOCATION_BATCH_SIZE`] rows as they are decoded.
+    fn sequence_copy_from_location(
+        &mut self,
+        tx: ClientTransmitter<ExecuteResponse>,
+        session: Session,
+        id: GlobalId,
+        columns: Vec<usize>,
+        params: CopyFormatParams<'static>,
+        location: CopyLocation,
+    ) {
+        let entry = self.catalog.get_entry(&id);
+        let desc = entry
+            .desc(
+                &self
+                    .catalog
+                    .resolve_full_name(entry.name(), Some(session.conn_id())),
+            )
+            .expect("desc called on table");
+        let column_types = columns
+            .iter()
+            .map(|i| mz_pgrepr::Type::from(&desc.typ().column_types[*i].scalar_type))
+            .collect::<Vec<_>>();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let connection_context = self.connection_context.clone();
+        task::spawn(|| format!("copy_from_location:{}", id), async move {
+            // Only one decoded batch waits to be inserted at a time, so that
+            // reading can't get arbitrarily far ahead of inserting.
+            let (rows_tx, mut rows_rx) = mpsc::channel(1);
+            let read = read_copy_location(
+                &location,
+                &connection_context,
+                column_types,
+                params,
+                rows_tx,
+            );
+            // Dropping `rows_rx` when an insert fails stops the read.
+            let insert = async move {
+                let mut session = session;
+                let mut count = 0;
+                while let Some(rows) = rows_rx.recv().await {
+                    count += rows.len();
+                    let (insert_tx, insert_rx) = oneshot::channel();
+                    internal_cmd_tx
+                        .send(Message::CopyFromLocationRows(CopyFromLocationRows {
+                            session,
+                            tx: ClientTransmitter::new(insert_tx, internal_cmd_tx.clone()),
+                            id,
+                            columns: columns.clone(),
+                            rows,
+                        }))
+                        .expect("sending to internal_cmd_tx cannot fail");
+                    let response = insert_rx
+                        .await
+                        .expect("insert response sender dropped without send");
+                    session = response.session;
+                    if let Err(e) = response.result {
+                        return (session, Err(e));
+                    }
+                }
+                (session, Ok(count))
+            };
+            let (read_result, (session, insert_result)) = futures::join!(read, insert);
+            // The read fails if an insert did, so the insert's error takes
+            // precedence.
+            let result = insert_result
+                .and_then(|count| read_result.map(|()| ExecuteResponse::Copied(count)));
+            tx.send(result, session);
+        });
+    }
+
+    /// Peeks the query of a `COPY ... TO` a location outside of Materialize,
+    /// then encodes and writes the results in a background task.
+    async fn sequence_copy_to(
+        &mut self,
+        tx: ClientTransmitter<ExecuteResponse>,
+        mut session: Session,
+        plan: CopyToPlan,
+    ) {
+        let CopyToPlan {
+            peek,
+            desc,
+            params,
+            location,
+        } = plan;
+        let rows = match self.sequence_peek(&mut session, peek).await {
+            Ok(ExecuteResponse::SendingRows { future, span: _ }) => future,
+            Ok(_) => {
+                tx.send(
+                    Err(AdapterError::Internal(
+                        "unexpected response to COPY TO peek".into(),
+                    )),
+                    session,
+                );
+                return;
+            }
+            Err(e) => {
+                tx.send(Err(e), session);
+                return;
+            }
+        };
+        let connection_context = self.connection_context.clone();
+        task::spawn(|| "copy_to_location", async move {
+            let result = match rows.await {
+                PeekResponseUnary::Rows(rows) => {
+                    write_copy_location(&location, &connection_context, &desc, &params, rows)
+                        .await
+                        .map(ExecuteResponse::Copied)
+                }
+                PeekResponseUnary::Error(e) => Err(AdapterError::Unstructured(anyhow!(e))),
+                PeekResponseUnary::Canceled => Ok(ExecuteResponse::Canceled),
+            };
+            tx.send(result, session);
+        });
+    }
+
     async fn sequence_tail(
         &mut self,
         session: &mut Session,
@@ -4977,11 +5260,14 @@ impl<S: Append + 'static> Coordinator<S> {
     }
 
     #[tracing::instrument(level = "debug", skip_all)]
-    fn sequence_send_diffs(
+    async fn sequence_send_diffs(
         &mut self,
         session: &mut Session,
         mut plan: SendDiffsPlan,
     ) -> Result<ExecuteResponse, AdapterError> {
+        self.check_table_constraints(session, plan.id, &plan.updates)
+            .await?;
+
         let affected_rows = {
             let mut affected_rows = Diff::from(0);
             let mut all_positive_diffs = true;
@@ -4996,7 +5282,17 @@ impl<S: Append + 'static> Coordinator<S> {
                 affected_rows += diff;
             }
 
-            if !all_positive_diffs {
+            if !all_positive_diffs && matches!(plan.kind, MutationKind::Insert) {
+                // Inserts only retract rows when they resolve a conflict by
+                // updating the existing row, which counts as a single affected
+                // row.
+                affected_rows = plan
+                    .updates
+                    .iter()
+                    .map(|(_, diff)| *diff)
+                    .filter(|diff| *diff > 0)
+                    .sum();
+            } else if !all_positive_diffs {
                 // Consolidate rows. This is useful e.g. for an UPDATE where the row
                 // doesn't change, and we need to reflect that in the number of
                 // affected rows.
@@ -5026,6 +5322,7 @@ impl<S: Append + 'static> Coordinator<S> {
         session.add_transaction_ops(TransactionOps::Writes(vec![WriteOp {
             id: plan.id,
             rows: plan.updates,
+            truncate: false,
         }]))?;
         if !plan.returning.is_empty() {
             let finishing = RowSetFinishing {
@@ -5043,12 +5340,110 @@ impl<S: Append + 'static> Coordinator<S> {
         })
     }
 
+    /// Reports whether the table `id` has any `UNIQUE` or `PRIMARY KEY`
+    /// constraints.
+    fn table_has_unique_constraints(&self, id: &GlobalId) -> bool {
+        match self.catalog.try_get_entry(id).map(|entry| entry.item()) {
+            Some(CatalogItem::Table(table)) => !table.uniques.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Verifies that writing `updates` to the table `id` in `session`'s
+    /// transaction leaves the table's constraints satisfied.
+    ///
+    /// The table's unique constraints are verified against the rows of its
+    /// current contents and of the transaction's earlier writes to it that
+    /// have the keys being added, so the caller must hold the write lock if the
+    /// table has any.
+    async fn check_table_constraints(
+        &mut self,
+        session: &Session,
+        id: GlobalId,
+        updates: &[(Row, Diff)],
+    ) -> Result<(), AdapterError> {
+        let (relation, desc, checks, uniques) = match self.catalog.try_get_entry(&id) {
+            Some(entry) => match entry.item() {
+                CatalogItem::Table(table) => (
+                    entry.name().item.clone(),
+                    table.desc.clone(),
+                    table.checks.clone(),
+                    table.uniques.clone(),
+                ),
+                _ => return Ok(()),
+            },
+            None => return Ok(()),
+        };
+        check_check_constraints(&relation, &desc, &checks, updates)?;
+
+        // Only the rows with the keys that are being added can conflict with
+        // them.
+        let added = updates
+            .iter()
+            .filter(|(_, diff)| *diff > 0)
+            .map(|(row, _)| row.clone())
+            .collect::<Vec<_>>();
+        let lookups = unique_key_lookups(&uniques, &added);
+        if lookups.is_empty() {
+            return Ok(());
+        }
+
+        let mut contents = self.table_contents(session, id, Some(&lookups)).await?;
+        contents.extend(updates.iter().cloned());
+        differential_dataflow::consolidation::consolidate(&mut contents);
+        check_unique_constraints(&desc, &uniques, &contents, updates)
+    }
+
+    /// Returns the consolidated contents of the table `id` as seen by
+    /// `session`'s transaction, i.e., including the transaction's uncommitted
+    /// writes to it.
+    ///
+    /// If `lookups` is given, only the committed rows that match one of them
+    /// are read, as described by the storage controller's `lookup`.
+    async fn table_contents(
+        &mut self,
+        session: &Session,
+        id: GlobalId,
+        lookups: Option<&[(Vec<usize>, Vec<Row>)]>,
+    ) -> Result<Vec<(Row, Diff)>, AdapterError> {
+        let writes = match session.transaction().inner().map(|txn| &txn.ops) {
+            Some(TransactionOps::Writes(writes)) => {
+                writes.iter().filter(|write| write.id == id).collect()
+            }
+            _ => vec![],
+        };
+        // If the transaction truncated the table, its committed contents are
+        // irrelevant.
+        let mut contents = if writes.iter().any(|write| write.truncate) {
+            vec![]
+        } else {
+            let read_ts = self.get_local_read_ts();
+            let storage = self.controller.storage_mut();
+            match lookups {
+                Some(lookups) => storage.lookup(id, read_ts, lookups).await?,
+                None => storage.snapshot(id, read_ts).await?,
+            }
+        };
+        for write in writes {
+            contents.extend(write.rows.iter().cloned());
+        }
+        differential_dataflow::consolidation::consolidate(&mut contents);
+        Ok(contents)
+    }
+
     async fn sequence_insert(
         &mut self,
         tx: ClientTransmitter<ExecuteResponse>,
         mut session: Session,
         plan: InsertPlan,
     ) {
+        // Verifying the table's unique constraints, or resolving conflicts
+        // with its existing rows, requires that no other writes to the table
+        // commit between reading its contents and committing this write.
+        if self.table_has_unique_constraints(&plan.id) || plan.on_conflict.is_some() {
+            guard_write_critical_section!(self, tx, session, Plan::Insert(plan));
+        }
+
         let optimized_mir = if let MirRelationExpr::Constant { .. } = &plan.values {
             // We don't perform any optimizations on an expression that is already
             // a constant for writes, as we want to maximize bulk-insert throughput.
@@ -5064,10 +5459,14 @@ impl<S: Append + 'static> Coordinator<S> {
         };
 
         match optimized_mir.into_inner() {
-            constants @ MirRelationExpr::Constant { .. } if plan.returning.is_empty() => tx.send(
-                self.sequence_insert_constant(&mut session, plan.id, constants),
-                session,
-            ),
+            constants @ MirRelationExpr::Constant { .. }
+                if plan.returning.is_empty() && plan.on_conflict.is_none() =>
+            {
+                let result = self
+                    .sequence_insert_constant(&mut session, plan.id, constants)
+                    .await;
+                tx.send(result, session);
+            }
             // All non-constant values must be planned as read-then-writes.
             mut selection => {
                 let desc_arity = match self.catalog.try_get_entry(&plan.id) {
@@ -5113,6 +5512,7 @@ impl<S: Append + 'static> Coordinator<S> {
                     finishing,
                     assignments: HashMap::new(),
                     kind: MutationKind::Insert,
+                    on_conflict: plan.on_conflict,
                     returning: plan.returning,
                 };
 
@@ -5122,7 +5522,7 @@ impl<S: Append + 'static> Coordinator<S> {
         }
     }
 
-    fn sequence_insert_constant(
+    async fn sequence_insert_constant(
         &mut self,
         session: &mut Session,
         id: GlobalId,
@@ -5156,7 +5556,7 @@ impl<S: Append + 'static> Coordinator<S> {
                     kind: MutationKind::Insert,
                     returning: Vec::new(),
                 };
-                self.sequence_send_diffs(session, diffs_plan)
+                self.sequence_send_diffs(session, diffs_plan).await
             }
             o => panic!(
                 "tried using sequence_insert_constant on non-constant MirRelationExpr {:?}",
@@ -5165,18 +5565,32 @@ impl<S: Append + 'static> Coordinator<S> {
         }
     }
 
-    fn sequence_copy_rows(
+    async fn sequence_copy_rows(
         &mut self,
-        session: &mut Session,
+        tx: ClientTransmitter<ExecuteResponse>,
+        session: Session,
         id: GlobalId,
         columns: Vec<usize>,
         rows: Vec<Row>,
-    ) -> Result<ExecuteResponse, AdapterError> {
-        let catalog = self.catalog.for_session(session);
-        let values = mz_sql::plan::plan_copy_from(&session.pcx(), &catalog, id, columns, rows)?;
-        let values = self.view_optimizer.optimize(values.lower())?;
-        // Copied rows must always be constants.
-        self.sequence_insert_constant(session, id, values.into_inner())
+    ) {
+        let catalog = self.catalog.for_session(&session);
+        let values = match mz_sql::plan::plan_copy_from(&session.pcx(), &catalog, id, columns, rows)
+        {
+            Ok(values) => values,
+            Err(e) => {
+                tx.send(Err(e.into()), session);
+                return;
+            }
+        };
+        // Copied rows always optimize to constants, which are inserted
+        // directly.
+        let plan = InsertPlan {
+            id,
+            values: values.lower(),
+            on_conflict: None,
+            returning: vec![],
+        };
+        self.sequence_insert(tx, session, plan).await;
     }
 
     // ReadThenWrite is a plan whose writes depend on the results of a
@@ -5197,6 +5611,7 @@ impl<S: Append + 'static> Coordinator<S> {
             selection,
             assignments,
             finishing,
+            on_conflict,
             returning,
         } = plan;
 
@@ -5309,6 +5724,24 @@ impl<S: Append + 'static> Coordinator<S> {
                     match tokio::time::timeout(timeout_dur, batch).await {
                         Ok(res) => match res {
                             PeekResponseUnary::Rows(rows) => {
+                                if let Some(on_conflict) = on_conflict {
+                                    // Resolving conflicts requires reading the
+                                    // table's rows, which the coordinator does.
+                                    internal_cmd_tx
+                                        .send(Message::ResolveInsertConflicts(
+                                            ResolveInsertConflicts {
+                                                session,
+                                                tx,
+                                                id,
+                                                desc,
+                                                on_conflict,
+                                                inserts: rows,
+                                                returning,
+                                            },
+                                        ))
+                                        .expect("sending to internal_cmd_tx cannot fail");
+                                    return;
+                                }
                                 |rows: Vec<Row>| -> Result<Vec<(Row, Diff)>, AdapterError> {
                                     // Use 2x row len incase there's some assignments.
                                     let mut diffs = Vec::with_capacity(rows.len() * 2);
@@ -5374,46 +5807,12 @@ impl<S: Append + 'static> Coordinator<S> {
                 }
                 _ => Err(AdapterError::Unstructured(anyhow!("expected SendingRows"))),
             };
-            let mut returning_rows = Vec::new();
-            let mut diff_err: Option<AdapterError> = None;
-            if !returning.is_empty() && diffs.is_ok() {
-                let arena = RowArena::new();
-                for (row, diff) in diffs.as_ref().unwrap() {
-                    if diff < &1 {
-                        continue;
-                    }
-                    let mut returning_row = Row::with_capacity(returning.len());
-                    let mut packer = returning_row.packer();
-                    for expr in &returning {
-                        let datums: Vec<_> = row.iter().collect();
-                        match expr.eval(&datums, &arena) {
-                            Ok(datum) => {
-                                packer.push(datum);
-                            }
-                            Err(err) => {
-                                diff_err = Some(err.into());
-                                break;
-                            }
-                        }
-                    }
-                    let diff = NonZeroI64::try_from(*diff).expect("known to be >= 1");
-                    let diff = match NonZeroUsize::try_from(diff) {
-                        Ok(diff) => diff,
-                        Err(err) => {
-                            diff_err = Some(err.into());
-                            break;
-                        }
-                    };
-                    returning_rows.push((returning_row, diff));
-                    if diff_err.is_some() {
-                        break;
-                    }
-                }
-            }
-            let diffs = if let Some(err) = diff_err {
-                Err(err)
-            } else {
-                diffs
+            let (diffs, returning_rows) = match diffs {
+                Ok(diffs) => match eval_returning(&returning, &diffs) {
+                    Ok(returning_rows) => (Ok(diffs), returning_rows),
+                    Err(e) => (Err(e), vec![]),
+                },
+                Err(e) => (Err(e), vec![]),
             };
             internal_cmd_tx
                 .send(Message::SendDiffs(SendDiffs {
@@ -5428,6 +5827,44 @@ impl<S: Append + 'static> Coordinator<S> {
         });
     }
 
+    async fn sequence_truncate(
+        &mut self,
+        tx: ClientTransmitter<ExecuteResponse>,
+        mut session: Session,
+        plan: TruncatePlan,
+    ) {
+        // Read-then-writes compute their retractions from the contents of the
+        // table as of their read, so no truncation may commit in between.
+        guard_write_critical_section!(self, tx, session, Plan::Truncate(plan));
+
+        // Truncations can be queued, so re-verify the ids exist.
+        for id in &plan.ids {
+            if self.catalog.try_get_entry(id).is_none() {
+                tx.send(
+                    Err(AdapterError::SqlCatalog(CatalogError::UnknownItem(
+                        id.to_string(),
+                    ))),
+                    session,
+                );
+                return;
+            }
+        }
+
+        let writes = plan
+            .ids
+            .into_iter()
+            .map(|id| WriteOp {
+                id,
+                rows: vec![],
+                truncate: true,
+            })
+            .collect();
+        let result = session
+            .add_transaction_ops(TransactionOps::Writes(writes))
+            .map(|()| ExecuteResponse::Truncated);
+        tx.send(result, session);
+    }
+
     async fn sequence_alter_item_rename(
         &mut self,
         session: &Session,
@@ -5487,6 +5924,169 @@ impl<S: Append + 'static> Coordinator<S> {
         Ok(ExecuteResponse::AlteredObject(ObjectType::Secret))
     }
 
+    async fn sequence_alter_table(
+        &mut self,
+        tx: ClientTransmitter<ExecuteResponse>,
+        mut session: Session,
+        plan: AlterTablePlan,
+    ) {
+        // The constraints of an added column are checked against the contents
+        // of the table, which must not change until the table is altered.
+        guard_write_critical_section!(self, tx, session, Plan::AlterTable(plan));
+        let result = self.sequence_alter_table_inner(&session, plan).await;
+        tx.send(result, session);
+    }
+
+    async fn sequence_alter_table_inner(
+        &mut self,
+        session: &Session,
+        plan: AlterTablePlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let AlterTablePlan {
+            id,
+            old_create_sql,
+            table,
+            column,
+            depends_on,
+        } = plan;
+
+        // The table may have been dropped or altered while this statement
+        // waited for the write lock.
+        let entry = self
+            .catalog
+            .try_get_entry(&id)
+            .ok_or_else(|| AdapterError::SqlCatalog(CatalogError::UnknownItem(id.to_string())))?;
+        let old_table = match entry.item() {
+            CatalogItem::Table(old_table) if old_table.create_sql == old_create_sql => old_table,
+            _ => {
+                let name = self
+                    .catalog
+                    .resolve_full_name(entry.name(), entry.conn_id())
+                    .to_string();
+                return Err(AdapterError::ChangedTable(name));
+            }
+        };
+        let conn_id = entry.conn_id();
+        let relation = entry.name().item.clone();
+        let layout = old_table.storage_layout();
+
+        let layout = match column {
+            AlterTableColumn::Add { mut default } => {
+                let temp_storage = RowArena::new();
+                prep_scalar_expr(
+                    self.catalog.state(),
+                    &mut default,
+                    ExprPrepStyle::OneShot {
+                        logical_time: None,
+                        session,
+                    },
+                )?;
+                let default = default.eval(&[], &temp_storage)?;
+                let index = table.desc.arity() - 1;
+                let typ = table.desc.typ().column_types[index].clone();
+
+                // Every existing row takes `default` in the new column, so the
+                // column's constraints hold for the existing rows exactly when
+                // they hold for `default`, or when there are no rows at all.
+                // The planner ensures that its `CHECK` constraints refer to no
+                // other column.
+                let mut datums = vec![Datum::Null; table.desc.arity()];
+                datums[index] = default;
+                let mut violation = table
+                    .desc
+                    .constraints_met(index, &default)
+                    .err()
+                    .map(AdapterError::from);
+                for check in &table.checks {
+                    if violation.is_some() {
+                        break;
+                    }
+                    if check.expr.support().contains(&index)
+                        && check.expr.eval(&datums, &temp_storage)? == Datum::False
+                    {
+                        violation = Some(AdapterError::CheckViolatedByExistingRows {
+                            relation: relation.clone(),
+                            constraint: check.name.clone(),
+                        });
+                    }
+                }
+                let mut unique_violation = None;
+                if default != Datum::Null {
+                    for unique in &table.uniques {
+                        if unique.columns.contains(&index) && unique_violation.is_none() {
+                            unique_violation = Some(AdapterError::UniqueViolation {
+                                constraint: unique.name.clone(),
+                                columns: unique
+                                    .columns
+                                    .iter()
+                                    .map(|i| table.desc.get_name(*i).to_string())
+                                    .collect(),
+                                key: format_datums(std::iter::once((default, &typ))),
+                            });
+                        }
+                    }
+                }
+
+                if violation.is_some() || unique_violation.is_some() {
+                    // Commit any writes that are waiting for the write lock,
+                    // so that they are counted.
+                    self.group_commit().await;
+                    let WriteTimestamp {
+                        timestamp: _,
+                        advance_to,
+                    } = self.get_and_step_local_write_ts().await;
+                    self.controller
+                        .storage_mut()
+                        .append(vec![(id, vec![], advance_to)])
+                        .await?;
+                    let read_ts = self.get_local_read_ts();
+                    let count = self
+                        .controller
+                        .storage_mut()
+                        .snapshot_count(id, read_ts)
+                        .await?;
+                    if let Some(violation) = violation {
+                        if count > 0 {
+                            return Err(violation);
+                        }
+                    }
+                    if let Some(violation) = unique_violation {
+                        if count > 1 {
+                            return Err(violation);
+                        }
+                    }
+                }
+                layout.add_column(typ, default)
+            }
+            AlterTableColumn::Drop { index } => layout.drop_column(index),
+        };
+
+        let table = catalog::Table {
+            create_sql: table.create_sql,
+            desc: table.desc,
+            defaults: table.defaults,
+            checks: table.checks,
+            uniques: table.uniques,
+            conn_id,
+            depends_on,
+            layout: Some(layout.clone()),
+        };
+        let desc = table.desc.clone();
+        let ops = vec![catalog::Op::AlterTable { id, table }];
+        self.catalog_transact(Some(session), ops, |_| Ok(()))
+            .await?;
+
+        // The catalog is the source of truth for the table's layout: if we
+        // crash before this point, the storage controller picks up the new
+        // layout from the catalog when the table is recreated on restart.
+        self.controller
+            .storage_mut()
+            .alter_collection(id, desc, layout)
+            .await?;
+
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Table))
+    }
+
     fn extract_secret(
         &mut self,
         session: &Session,
@@ -6231,6 +6831,76 @@ fn send_immediate_rows(rows: Vec<Row>) -> ExecuteResponse {
     }
 }
 
+/// The maximum number of rows that a `COPY ... FROM` a location outside of
+/// Materialize inserts at once.
+const COPY_FROM_LOCATION_BATCH_SIZE: usize = 10_000;
+
+/// Reads the data at `location`, decodes it into rows with the given column
+/// types and sends them to `rows_tx` in batches of at most
+/// [`COPY_FROM_LOCATION_BATCH_SIZE`] rows.
+async fn read_copy_location(
+    location: &CopyLocation,
+    connection_context: &ConnectionContext,
+    column_types: Vec<mz_pgrepr::Type>,
+    params: CopyFormatParams<'static>,
+    rows_tx: mpsc::Sender<Vec<Row>>,
+) -> Result<(), AdapterError> {
+    location
+        .read(connection_context, move |reader| {
+            let send = |rows| {
+                rows_tx
+                    .blocking_send(rows)
+                    .map_err(|_| io::Error::new(io::ErrorKind::Other, "COPY canceled"))
+            };
+            // Each object or file is a complete COPY stream of its own, with
+            // its own header and trailer.
+            let mut rows = Vec::new();
+            mz_pgcopy::decode_copy_format_from_reader(
+                reader,
+                &column_types,
+                params.clone(),
+                |row| {
+                    rows.push(row);
+                    if rows.len() == COPY_FROM_LOCATION_BATCH_SIZE {
+                        send(std::mem::take(&mut rows))?;
+                    }
+                    Ok(())
+                },
+            )
+            .and_then(|()| if rows.is_empty() { Ok(()) } else { send(rows) })
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::InvalidData => AdapterError::BadCopyFile(e.to_string()),
+                _ => AdapterError::Unstructured(e.into()),
+            })
+        })
+        .await
+}
+
+/// Encodes `rows` in the format described by `params` and writes them to
+/// `location`, returning the number of rows written.
+async fn write_copy_location(
+    location: &CopyLocation,
+    connection_context: &ConnectionContext,
+    desc: &RelationDesc,
+    params: &CopyFormatParams<'static>,
+    rows: Vec<Row>,
+) -> Result<usize, AdapterError> {
+    let count = rows.len();
+    let typ = desc.typ();
+    let mut out = vec![];
+    let encode = |out: &mut Vec<u8>| -> Result<(), std::io::Error> {
+        mz_pgcopy::encode_copy_header(params, desc, out)?;
+        for row in rows {
+            mz_pgcopy::encode_copy_format(params, row, typ, out)?;
+        }
+        mz_pgcopy::encode_copy_trailer(params, out);
+        Ok(())
+    };
+    encode(&mut out).map_err(|e| AdapterError::Unstructured(e.into()))?;
+    location.write(out, connection_context).await?;
+    Ok(count)
+}
+
 fn auto_generate_primary_idx(
     index_name: String,
     compute_instance: ComputeInstanceId,
@@ -6302,6 +6972,241 @@ fn duration_to_timestamp_millis(d: Duration) -> Timestamp {
     }
 }
 
+/// Verifies that none of the rows added by `updates` to the table named
+/// `relation` with description `desc` falsify any of `checks`.
+fn check_check_constraints(
+    relation: &str,
+    desc: &RelationDesc,
+    checks: &[CheckConstraint],
+    updates: &[(Row, Diff)],
+) -> Result<(), AdapterError> {
+    if checks.is_empty() {
+        return Ok(());
+    }
+    let arena = RowArena::new();
+    let mut datum_vec = mz_repr::DatumVec::new();
+    for (row, diff) in updates {
+        if *diff <= 0 {
+            continue;
+        }
+        let datums = datum_vec.borrow_with(row);
+        for check in checks {
+            // As in PostgreSQL, a constraint that evaluates to `NULL` is
+            // satisfied.
+            if check.expr.eval(&datums, &arena)? == Datum::False {
+                let row = datums.iter().copied().zip(&desc.typ().column_types);
+                return Err(AdapterError::CheckViolation {
+                    relation: relation.into(),
+                    constraint: check.name.clone(),
+                    row: format_datums(row),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Verifies that `contents`, the consolidated contents of a table with
+/// description `desc` after applying `updates`, contain no two rows that agree
+/// on the columns of any of `uniques` where one of the rows was added by
+/// `updates`.
+///
+/// Rows with a `NULL` in any of the constrained columns never conflict.
+fn check_unique_constraints(
+    desc: &RelationDesc,
+    uniques: &[UniqueConstraint],
+    contents: &[(Row, Diff)],
+    updates: &[(Row, Diff)],
+) -> Result<(), AdapterError> {
+    for unique in uniques {
+        let added: HashSet<Row> = updates
+            .iter()
+            .filter(|(_, diff)| *diff > 0)
+            .filter_map(|(row, _)| unique_key(row, &unique.columns))
+            .collect();
+        if added.is_empty() {
+            continue;
+        }
+        let mut counts = HashMap::new();
+        for (row, diff) in contents {
+            if let Some(key) = unique_key(row, &unique.columns) {
+                if added.contains(&key) {
+                    *counts.entry(key).or_insert(0) += *diff;
+                }
+            }
+        }
+        if let Some((key, _)) = counts.into_iter().find(|(_, count)| *count > 1) {
+            let unique_types: Vec<_> = unique
+                .columns
+                .iter()
+                .map(|i| desc.typ().column_types[*i].clone())
+                .collect();
+            return Err(AdapterError::UniqueViolation {
+                constraint: unique.name.clone(),
+                columns: unique
+                    .columns
+                    .iter()
+                    .map(|i| desc.get_name(*i).to_string())
+                    .collect(),
+                key: format_datums(key.iter().zip(&unique_types)),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Resolves the conflicts between `inserts`, the rows proposed for insertion
+/// into a table with description `desc`, and the table's consolidated
+/// `contents`, as directed by `on_conflict`. Returns the updates to apply to
+/// the table.
+///
+/// Proposed rows are also checked for conflicts with the rows inserted or
+/// updated before them, but `DO UPDATE` may not update such a row.
+fn resolve_insert_conflicts(
+    desc: &RelationDesc,
+    on_conflict: &OnConflictPlan,
+    contents: Vec<(Row, Diff)>,
+    inserts: Vec<Row>,
+) -> Result<Vec<(Row, Diff)>, AdapterError> {
+    let constraints = &on_conflict.constraints;
+    // For each constraint, the rows of the table by their key.
+    let mut indexes: Vec<HashMap<Row, Row>> = vec![HashMap::new(); constraints.len()];
+    for (row, _) in contents {
+        for (unique, index) in constraints.iter().zip(&mut indexes) {
+            if let Some(key) = unique_key(&row, &unique.columns) {
+                index.insert(key, row.clone());
+            }
+        }
+    }
+
+    let arena = RowArena::new();
+    let mut affected = HashSet::new();
+    let mut updates = Vec::with_capacity(inserts.len());
+    for row in inserts {
+        let existing = constraints
+            .iter()
+            .zip(&indexes)
+            .find_map(|(unique, index)| index.get(&unique_key(&row, &unique.columns)?))
+            .cloned();
+        let (old, new) = match (existing, &on_conflict.update) {
+            (None, _) => {
+                for (i, datum) in row.iter().enumerate() {
+                    desc.constraints_met(i, &datum)?;
+                }
+                (None, row)
+            }
+            (Some(_), None) => continue,
+            (Some(existing), Some(update)) => {
+                if affected.contains(&existing) {
+                    return Err(AdapterError::ConflictAffectsRowTwice);
+                }
+                let datums: Vec<_> = existing.iter().chain(row.iter()).collect();
+                if let Some(selection) = &update.selection {
+                    if selection.eval(&datums, &arena)? != Datum::True {
+                        continue;
+                    }
+                }
+                let mut new = datums[..desc.arity()].to_vec();
+                for (idx, expr) in &update.assignments {
+                    let datum = expr.eval(&datums, &arena)?;
+                    desc.constraints_met(*idx, &datum)?;
+                    new[*idx] = datum;
+                }
+                let new = Row::pack_slice(&new);
+                (Some(existing), new)
+            }
+        };
+        if let Some(old) = old {
+            for (unique, index) in constraints.iter().zip(&mut indexes) {
+                if let Some(key) = unique_key(&old, &unique.columns) {
+                    index.remove(&key);
+                }
+            }
+            updates.push((old, -1));
+        }
+        for (unique, index) in constraints.iter().zip(&mut indexes) {
+            if let Some(key) = unique_key(&new, &unique.columns) {
+                index.insert(key, new.clone());
+            }
+        }
+        affected.insert(new.clone());
+        updates.push((new, 1));
+    }
+    Ok(updates)
+}
+
+/// Evaluates the `RETURNING` expressions `returning` over the rows added by
+/// `diffs`.
+fn eval_returning(
+    returning: &[MirScalarExpr],
+    diffs: &[(Row, Diff)],
+) -> Result<Vec<(Row, NonZeroUsize)>, AdapterError> {
+    let mut returning_rows = Vec::new();
+    if returning.is_empty() {
+        return Ok(returning_rows);
+    }
+    let arena = RowArena::new();
+    for (row, diff) in diffs {
+        if *diff < 1 {
+            continue;
+        }
+        let datums: Vec<_> = row.iter().collect();
+        let mut returning_row = Row::with_capacity(returning.len());
+        let mut packer = returning_row.packer();
+        for expr in returning {
+            packer.push(expr.eval(&datums, &arena)?);
+        }
+        let diff = NonZeroI64::try_from(*diff).expect("known to be >= 1");
+        let diff = NonZeroUsize::try_from(diff)?;
+        returning_rows.push((returning_row, diff));
+    }
+    Ok(returning_rows)
+}
+
+/// Returns the lookups that find the rows that conflict with `rows` on any of
+/// `uniques`, as accepted by the storage controller's `lookup`.
+fn unique_key_lookups(uniques: &[UniqueConstraint], rows: &[Row]) -> Vec<(Vec<usize>, Vec<Row>)> {
+    uniques
+        .iter()
+        .filter_map(|unique| {
+            let keys = rows
+                .iter()
+                .filter_map(|row| unique_key(row, &unique.columns))
+                .collect::<Vec<_>>();
+            (!keys.is_empty()).then(|| (unique.columns.clone(), keys))
+        })
+        .collect()
+}
+
+/// Returns the values of `columns` in `row`, or `None` if any of them is
+/// `NULL`, in which case the row cannot conflict with any other row.
+fn unique_key(row: &Row, columns: &[usize]) -> Option<Row> {
+    let datums = row.unpack();
+    let key = columns.iter().map(|i| datums[*i]);
+    if key.clone().any(|datum| datum.is_null()) {
+        None
+    } else {
+        Some(Row::pack(key))
+    }
+}
+
+/// Formats `datums` of the given types as a comma-separated list, as in the
+/// details of PostgreSQL's constraint violation errors.
+fn format_datums<'a, 'b>(datums: impl Iterator<Item = (Datum<'a>, &'b ColumnType)>) -> String {
+    datums
+        .map(
+            |(datum, typ)| match mz_pgrepr::Value::from_datum(datum, &typ.scalar_type) {
+                None => "null".into(),
+                Some(value) => {
+                    let mut buf = BytesMut::new();
+                    value.encode_text(&mut buf);
+                    String::from_utf8_lossy(&buf).into_owned()
+                }
+            },
+        )
+        .join(", ")
+}
+
 /// Creates a description of the statement `stmt`.
 ///
 /// This function is identical to sql::plan::describe except this is also
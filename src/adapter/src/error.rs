@@ -21,6 +21,7 @@ use mz_repr::explain_new::ExplainError;
 use mz_repr::NotNullViolation;
 use mz_sql::plan::PlanError;
 use mz_sql::query_model::QGMError;
+use mz_storage::controller::StorageError;
 use mz_transform::TransformError;
 
 use crate::catalog;
@@ -29,10 +30,29 @@ use crate::session::Var;
 /// Errors that can occur in the coordinator.
 #[derive(Debug)]
 pub enum AdapterError {
+    /// The data read by a `COPY ... FROM` was malformed.
+    BadCopyFile(String),
     /// An error occurred in a catalog operation.
     Catalog(catalog::Error),
     /// The cached plan or descriptor changed.
     ChangedPlan,
+    /// The named table was altered concurrently.
+    ChangedTable(String),
+    /// A row violated a `CHECK` constraint.
+    CheckViolation {
+        relation: String,
+        constraint: String,
+        row: String,
+    },
+    /// A `CHECK` constraint added by `ALTER TABLE` is violated by the existing
+    /// rows of the table.
+    CheckViolatedByExistingRows {
+        relation: String,
+        constraint: String,
+    },
+    /// An `INSERT ... ON CONFLICT DO UPDATE` attempted to update the same row
+    /// twice.
+    ConflictAffectsRowTwice,
     /// The specified session parameter is constrained to a finite set of values.
     ConstrainedParameter {
         parameter: &'static (dyn Var + Send + Sync),
@@ -148,6 +168,13 @@ pub enum AdapterError {
     UntargetedLogRead {
         log_names: Vec<String>,
     },
+    /// A write would have left two rows that agree on the columns of a
+    /// `UNIQUE` or `PRIMARY KEY` constraint.
+    UniqueViolation {
+        constraint: String,
+        columns: Vec<String>,
+        key: String,
+    },
     /// The transaction is in write-only mode.
     WriteOnlyTransaction,
     /// The transaction only supports single table writes
@@ -159,6 +186,9 @@ impl AdapterError {
     pub fn detail(&self) -> Option<String> {
         match self {
             AdapterError::Catalog(c) => c.detail(),
+            AdapterError::CheckViolation { row, .. } => {
+                Some(format!("Failing row contains ({}).", row))
+            }
             AdapterError::Eval(e) => e.detail(),
             AdapterError::RelationOutsideTimeDomain { relations, names } => Some(format!(
                 "The following relations in the query are outside the transaction's time domain:\n{}\n{}",
@@ -194,6 +224,11 @@ impl AdapterError {
                 log_names.join("\n    "),
             )),
             AdapterError::PlanError(e) => e.detail(),
+            AdapterError::UniqueViolation { columns, key, .. } => Some(format!(
+                "Key ({})=({}) already exists.",
+                columns.join(", "),
+                key
+            )),
             _ => None,
         }
     }
@@ -202,6 +237,11 @@ impl AdapterError {
     pub fn hint(&self) -> Option<String> {
         match self {
             AdapterError::Catalog(c) => c.hint(),
+            AdapterError::ConflictAffectsRowTwice => Some(
+                "Ensure that no rows proposed for insertion within the same command have \
+                duplicate constrained values."
+                    .into(),
+            ),
             AdapterError::ConstrainedParameter {
                 valid_values: Some(valid_values),
                 ..
@@ -247,8 +287,34 @@ impl AdapterError {
 impl fmt::Display for AdapterError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            AdapterError::BadCopyFile(e) => f.write_str(e),
             AdapterError::ChangedPlan => f.write_str("cached plan must not change result type"),
+            AdapterError::ChangedTable(name) => {
+                write!(f, "table {} was altered concurrently", name.quoted())
+            }
+            AdapterError::CheckViolation {
+                relation,
+                constraint,
+                ..
+            } => write!(
+                f,
+                "new row for relation {} violates check constraint {}",
+                relation.quoted(),
+                constraint.quoted()
+            ),
+            AdapterError::CheckViolatedByExistingRows {
+                relation,
+                constraint,
+            } => write!(
+                f,
+                "check constraint {} of relation {} is violated by some row",
+                constraint.quoted(),
+                relation.quoted()
+            ),
             AdapterError::Catalog(e) => e.fmt(f),
+            AdapterError::ConflictAffectsRowTwice => {
+                f.write_str("ON CONFLICT DO UPDATE command cannot affect row a second time")
+            }
             AdapterError::ConstrainedParameter {
                 parameter, value, ..
             } => write!(
@@ -364,6 +430,11 @@ impl fmt::Display for AdapterError {
             }
             AdapterError::Unsupported(features) => write!(f, "{} are not supported", features),
             AdapterError::Unstructured(e) => write!(f, "{:#}", e),
+            AdapterError::UniqueViolation { constraint, .. } => write!(
+                f,
+                "duplicate key value violates unique constraint {}",
+                constraint.quoted()
+            ),
             AdapterError::WriteOnlyTransaction => f.write_str("transaction in write-only mode"),
             AdapterError::UnknownPreparedStatement(name) => {
                 write!(f, "prepared statement {} does not exist", name.quoted())
@@ -457,6 +528,12 @@ impl From<RecursionLimitError> for AdapterError {
     }
 }
 
+impl From<StorageError> for AdapterError {
+    fn from(e: StorageError) -> AdapterError {
+        AdapterError::Unstructured(e.into())
+    }
+}
+
 impl From<oneshot::error::RecvError> for AdapterError {
     fn from(e: oneshot::error::RecvError) -> AdapterError {
         AdapterError::Unstructured(e.into())
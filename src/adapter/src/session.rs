@@ -202,10 +202,19 @@ impl<T: CoordTimestamp> Session<T> {
                             // We should have already checked the access above, but make sure we don't miss
                             // it anyway.
                             assert!(!matches!(access, Some(TransactionAccessMode::ReadOnly)));
+                            // Truncating a table supersedes the transaction's
+                            // earlier writes to it.
+                            for write in add_writes.iter().filter(|write| write.truncate) {
+                                txn_writes.retain(|txn_write| txn_write.id != write.id);
+                            }
                             txn_writes.append(&mut add_writes);
 
+                            // The storage controller computes the retractions of
+                            // truncations itself, so any number of tables may be
+                            // truncated alongside the one table written to.
                             if txn_writes
                                 .iter()
+                                .filter(|op| !op.truncate)
                                 .map(|op| op.id)
                                 .collect::<HashSet<_>>()
                                 .len()
@@ -650,8 +659,8 @@ pub enum TransactionOps<T> {
     Peeks(T),
     /// This transaction has done a TAIL and must do nothing else.
     Tail,
-    /// This transaction has had a write (`INSERT`, `UPDATE`, `DELETE`) and must only do
-    /// other writes.
+    /// This transaction has had a write (`INSERT`, `UPDATE`, `DELETE`, `TRUNCATE`) and must
+    /// only do other writes.
     Writes(Vec<WriteOp>),
 }
 
@@ -662,6 +671,9 @@ pub struct WriteOp {
     pub id: GlobalId,
     /// The data rows.
     pub rows: Vec<(Row, Diff)>,
+    /// Whether to retract the table's committed contents before writing
+    /// `rows`.
+    pub truncate: bool,
 }
 
 /// The action to take during end_transaction.
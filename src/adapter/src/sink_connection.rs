@@ -18,7 +18,8 @@ use mz_repr::GlobalId;
 use mz_storage::types::connections::{ConnectionContext, PopulateClientConfig};
 use mz_storage::types::sinks::{
     KafkaSinkConnection, KafkaSinkConnectionBuilder, KafkaSinkConnectionRetention,
-    KafkaSinkConsistencyConnection, PublishedSchemaInfo, SinkConnection, SinkConnectionBuilder,
+    KafkaSinkConsistencyConnection, KafkaSinkConsistencyFormat, PublishedSchemaFormat,
+    PublishedSchemaInfo, SinkConnection, SinkConnectionBuilder,
 };
 
 use crate::error::AdapterError;
@@ -259,24 +260,45 @@ async fn build_kafka(
             Some(PublishedSchemaInfo {
                 key_schema_id,
                 value_schema_id,
+                format: PublishedSchemaFormat::Avro,
+            })
+        }
+        mz_storage::types::sinks::KafkaSinkFormat::Protobuf {
+            key_schema,
+            value_schema,
+            csr_connection,
+        } => {
+            let ccsr = csr_connection
+                .connect(&*connection_context.secrets_reader)
+                .await?;
+            let (key_schema_id, value_schema_id) = publish_kafka_schemas(
+                &ccsr,
+                &topic,
+                key_schema.as_deref(),
+                Some(mz_ccsr::SchemaType::Protobuf),
+                &value_schema,
+                mz_ccsr::SchemaType::Protobuf,
+            )
+            .await
+            .context("error publishing kafka schemas for sink")?;
+            Some(PublishedSchemaInfo {
+                key_schema_id,
+                value_schema_id,
+                format: PublishedSchemaFormat::Protobuf,
             })
         }
         mz_storage::types::sinks::KafkaSinkFormat::Json => None,
     };
 
     let consistency = match builder.consistency_format {
-        Some(mz_storage::types::sinks::KafkaSinkFormat::Avro {
-            value_schema,
-            csr_connection,
-            ..
-        }) => {
+        Some(consistency_format) => {
             let consistency_topic = maybe_append_nonce(
                 builder
                     .consistency_topic_prefix
                     .as_ref()
                     .expect("known to exist"),
             );
-            // create consistency topic/schema and retrieve schema id
+            // create consistency topic
             register_kafka_topic(
                 &client,
                 &consistency_topic,
@@ -288,27 +310,45 @@ async fn build_kafka(
             .await
             .context("error registering kafka consistency topic for sink")?;
 
-            let ccsr = csr_connection
-                .connect(&*connection_context.secrets_reader)
-                .await?;
-            let (_, consistency_schema_id) = publish_kafka_schemas(
-                &ccsr,
-                &consistency_topic,
-                None,
-                None,
-                &value_schema,
-                mz_ccsr::SchemaType::Avro,
-            )
-            .await
-            .context("error publishing kafka consistency schemas for sink")?;
+            let format = match consistency_format {
+                mz_storage::types::sinks::KafkaSinkFormat::Avro {
+                    value_schema,
+                    csr_connection,
+                    ..
+                } => {
+                    // publish consistency schema and retrieve schema id
+                    let ccsr = csr_connection
+                        .connect(&*connection_context.secrets_reader)
+                        .await?;
+                    let (_, schema_id) = publish_kafka_schemas(
+                        &ccsr,
+                        &consistency_topic,
+                        None,
+                        None,
+                        &value_schema,
+                        mz_ccsr::SchemaType::Avro,
+                    )
+                    .await
+                    .context("error publishing kafka consistency schemas for sink")?;
+                    KafkaSinkConsistencyFormat::Avro { schema_id }
+                }
+                mz_storage::types::sinks::KafkaSinkFormat::Json => KafkaSinkConsistencyFormat::Json,
+                // The consistency topics of Protobuf sinks are planned as Avro.
+                mz_storage::types::sinks::KafkaSinkFormat::Protobuf { .. } => {
+                    unreachable!("Protobuf consistency format")
+                }
+            };
 
             Some(KafkaSinkConsistencyConnection {
                 topic: consistency_topic,
-                schema_id: consistency_schema_id,
+                format,
+                data_collection: builder
+                    .consistency_data_collection
+                    .clone()
+                    .unwrap_or_else(|| builder.topic_prefix.clone()),
             })
         }
-        Some(other) => unreachable!("non-Avro consistency format for Kafka sink {:#?}", &other),
-        _ => None,
+        None => None,
     };
 
     Ok(SinkConnection::Kafka(KafkaSinkConnection {
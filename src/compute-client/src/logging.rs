@@ -188,6 +188,7 @@ pub enum ComputeLog {
     FrontierCurrent,
     PeekCurrent,
     PeekDuration,
+    SinkStatus,
 }
 
 impl RustType<ProtoComputeLog> for ComputeLog {
@@ -200,6 +201,7 @@ impl RustType<ProtoComputeLog> for ComputeLog {
                 ComputeLog::FrontierCurrent => FrontierCurrent(()),
                 ComputeLog::PeekCurrent => PeekCurrent(()),
                 ComputeLog::PeekDuration => PeekDuration(()),
+                ComputeLog::SinkStatus => SinkStatus(()),
             }),
         }
     }
@@ -212,6 +214,7 @@ impl RustType<ProtoComputeLog> for ComputeLog {
             Some(FrontierCurrent(())) => Ok(ComputeLog::FrontierCurrent),
             Some(PeekCurrent(())) => Ok(ComputeLog::PeekCurrent),
             Some(PeekDuration(())) => Ok(ComputeLog::PeekDuration),
+            Some(SinkStatus(())) => Ok(ComputeLog::SinkStatus),
             None => Err(TryFromProtoError::missing_field("ProtoComputeLog::kind")),
         }
     }
@@ -334,6 +337,12 @@ impl LogVariant {
                 .with_column("duration_ns", ScalarType::Int64.nullable(false))
                 .with_column("count", ScalarType::Int64.nullable(false))
                 .with_key(vec![0, 1]),
+
+            LogVariant::Compute(ComputeLog::SinkStatus) => RelationDesc::empty()
+                .with_column("sink_id", ScalarType::String.nullable(false))
+                .with_column("worker", ScalarType::Int64.nullable(false))
+                .with_column("error", ScalarType::String.nullable(false))
+                .with_key(vec![0, 1]),
         }
     }
 
@@ -381,6 +390,7 @@ impl LogVariant {
             LogVariant::Compute(ComputeLog::FrontierCurrent) => vec![],
             LogVariant::Compute(ComputeLog::PeekCurrent) => vec![],
             LogVariant::Compute(ComputeLog::PeekDuration) => vec![],
+            LogVariant::Compute(ComputeLog::SinkStatus) => vec![],
         }
     }
 }
@@ -21,6 +21,7 @@ use proptest::strategy::Strategy;
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize, Serializer};
 
+use mz_expr::visit::Visit;
 use mz_expr::{
     permutation_for_arrangement, CollectionPlan, EvalError, Id, JoinInputMapper, LocalId,
     MapFilterProject, MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, TableFunc,
@@ -213,6 +214,24 @@ pub enum Plan<T = mz_repr::Timestamp> {
         /// that reference `Id::Local(id)`.
         body: Box<Plan<T>>,
     },
+    /// Binds `value` to `id`, where `value` may itself reference `id`, and then
+    /// results in `body` with that binding.
+    ///
+    /// The binding is the fixed point of `value`, computed by iteratively
+    /// evaluating `value` starting from an empty collection for `id`. Within
+    /// `value`, all bindings (including `id`) are available only as collections.
+    LetRec {
+        /// The local identifier to be used, available to `value` and `body` as `Id::Local(id)`.
+        id: LocalId,
+        /// The bindings from outside the iteration that `value` references, and
+        /// how to form each of them as a collection to bring into the iteration.
+        imports: Vec<(Id, GetPlan)>,
+        /// The collection that should be bound to `id`, which may reference `Id::Local(id)`.
+        value: Box<Plan<T>>,
+        /// The collection that results, which is allowed to contain `Get` stages
+        /// that reference `Id::Local(id)`.
+        body: Box<Plan<T>>,
+    },
     /// Map, Filter, and Project operators.
     ///
     /// This stage contains work that we would ideally like to fuse to other plan
@@ -375,6 +394,20 @@ impl Arbitrary for Plan {
                         body: body.into(),
                     })
                     .boxed(),
+                //Plan::LetRec
+                (
+                    any::<LocalId>(),
+                    prop::collection::vec(any::<(Id, GetPlan)>(), 0..3),
+                    inner.clone(),
+                    inner.clone(),
+                )
+                    .prop_map(|(id, imports, value, body)| Plan::LetRec {
+                        id,
+                        imports,
+                        value: value.into(),
+                        body: body.into(),
+                    })
+                    .boxed(),
                 //Plan::Mfp
                 (
                     inner.clone(),
@@ -528,6 +561,26 @@ impl RustType<ProtoPlan> for Plan {
                     body: Some(body.into_proto()),
                 }
                 .into()),
+                Plan::LetRec {
+                    id,
+                    imports,
+                    value,
+                    body,
+                } => LetRec(
+                    ProtoPlanLetRec {
+                        id: Some(id.into_proto()),
+                        imports: imports
+                            .iter()
+                            .map(|(id, plan)| ProtoPlanLetRecImport {
+                                id: Some(id.into_proto()),
+                                plan: Some(plan.into_proto()),
+                            })
+                            .collect(),
+                        value: Some(value.into_proto()),
+                        body: Some(body.into_proto()),
+                    }
+                    .into(),
+                ),
                 Plan::Mfp {
                     input,
                     mfp,
@@ -659,6 +712,23 @@ impl RustType<ProtoPlan> for Plan {
                 value: proto.value.into_rust_if_some("ProtoPlanLet::value")?,
                 body: proto.body.into_rust_if_some("ProtoPlanLet::body")?,
             },
+            LetRec(proto) => Plan::LetRec {
+                id: proto.id.into_rust_if_some("ProtoPlanLetRec::id")?,
+                imports: proto
+                    .imports
+                    .into_iter()
+                    .map(|import| {
+                        Ok((
+                            import.id.into_rust_if_some("ProtoPlanLetRecImport::id")?,
+                            import
+                                .plan
+                                .into_rust_if_some("ProtoPlanLetRecImport::plan")?,
+                        ))
+                    })
+                    .collect::<Result<_, TryFromProtoError>>()?,
+                value: proto.value.into_rust_if_some("ProtoPlanLetRec::value")?,
+                body: proto.body.into_rust_if_some("ProtoPlanLetRec::body")?,
+            },
             Mfp(proto) => Plan::Mfp {
                 input: proto.input.into_rust_if_some("ProtoPlanMfp::input")?,
                 input_key_val: input_kv_try_into(proto.input_key_val)?,
@@ -1034,6 +1104,71 @@ impl<T: timely::progress::Timestamp> Plan<T> {
                     b_keys,
                 )
             }
+            MirRelationExpr::LetRec { id, value, body } => {
+                // The value is rendered in an iterative scope, into which only
+                // collections are brought. Determine how to form a collection for
+                // each binding from outside the iteration that the value references.
+                let mut imports = BTreeMap::new();
+                value.visit_pre_nolimit(&mut |e| {
+                    if let MirRelationExpr::Get { id: get_id, typ } = e {
+                        let is_outer = match get_id {
+                            Id::Local(local_id) => {
+                                local_id != id && arrangements.contains_key(get_id)
+                            }
+                            Id::Global(_) => true,
+                        };
+                        if is_outer && !imports.contains_key(get_id) {
+                            let mut mfp = MapFilterProject::new(typ.arity());
+                            let keys = arrangements
+                                .get(get_id)
+                                .cloned()
+                                .unwrap_or_else(AvailableCollections::new_raw);
+                            let plan = if keys.raw {
+                                GetPlan::Collection(mfp)
+                            } else if let Some((key, permutation, thinning)) =
+                                keys.arbitrary_arrangement().cloned()
+                            {
+                                mfp.permute(permutation, thinning.len() + key.len());
+                                GetPlan::Arrangement(key, None, mfp)
+                            } else {
+                                GetPlan::Collection(mfp)
+                            };
+                            imports.insert(*get_id, plan);
+                        }
+                    }
+                });
+                // Plan the value with all bindings, including `id` itself, available
+                // only in raw form, and ensure that the value results in a collection.
+                let mut value_arrangements = imports
+                    .keys()
+                    .map(|id| (*id, AvailableCollections::new_raw()))
+                    .collect::<BTreeMap<_, _>>();
+                value_arrangements.insert(Id::Local(*id), AvailableCollections::new_raw());
+                let (mut value_plan, v_keys) =
+                    Plan::from_mir_inner(value, &mut value_arrangements, debug_info)?;
+                if !v_keys.raw {
+                    value_plan = value_plan.arrange_by(
+                        AvailableCollections::new_raw(),
+                        &v_keys,
+                        value.arity(),
+                    );
+                }
+                // The iteration results in a collection, which is all the body may use.
+                let pre_existing =
+                    arrangements.insert(Id::Local(*id), AvailableCollections::new_raw());
+                assert!(pre_existing.is_none());
+                let (body, b_keys) = Plan::from_mir_inner(body, arrangements, debug_info)?;
+                arrangements.remove(&Id::Local(*id));
+                (
+                    Plan::LetRec {
+                        id: id.clone(),
+                        imports: imports.into_iter().collect(),
+                        value: Box::new(value_plan),
+                        body: Box::new(body),
+                    },
+                    b_keys,
+                )
+            }
             MirRelationExpr::FlatMap { input, func, exprs } => {
                 let (input, keys) = Plan::from_mir_inner(input, arrangements, debug_info)?;
                 // This stage can absorb arbitrary MFP instances.
@@ -1541,6 +1676,25 @@ This is not expected to cause incorrect results, but could indicate a performanc
                         })
                         .collect()
                 }
+                Plan::LetRec {
+                    value,
+                    body,
+                    id,
+                    imports,
+                } => {
+                    let value_parts = value.partition_among(parts);
+                    let body_parts = body.partition_among(parts);
+                    value_parts
+                        .into_iter()
+                        .zip(body_parts)
+                        .map(|(value, body)| Plan::LetRec {
+                            value: Box::new(value),
+                            body: Box::new(body),
+                            id,
+                            imports: imports.clone(),
+                        })
+                        .collect()
+                }
                 Plan::Mfp {
                     input,
                     input_key_val,
@@ -1677,7 +1831,13 @@ impl<T> CollectionPlan for Plan<T> {
                 }
                 Id::Local(_) => (),
             },
-            Plan::Let { id: _, value, body } => {
+            Plan::Let { id: _, value, body }
+            | Plan::LetRec {
+                id: _,
+                imports: _,
+                value,
+                body,
+            } => {
                 value.depends_on_into(out);
                 body.depends_on_into(out);
             }
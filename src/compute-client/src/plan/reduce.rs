@@ -874,6 +874,10 @@ fn reduction_type(func: &AggregateFunc) -> ReductionType {
         | AggregateFunc::LagLead { .. }
         | AggregateFunc::FirstValue { .. }
         | AggregateFunc::LastValue { .. } => ReductionType::Basic,
+        // Window functions re-evaluate their whole partition on each change.
+        AggregateFunc::WindowAggregate { .. } | AggregateFunc::SessionWindow { .. } => {
+            ReductionType::Basic
+        }
     }
 }
 
@@ -24,6 +24,7 @@ use mz_orchestrator_tracing::TracingCliArgs;
 use mz_ore::cli::{self, CliConfig};
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::SYSTEM_TIME;
+use mz_persist_client::PartCompression;
 use mz_pid_file::PidFile;
 use mz_service::grpc::GrpcServer;
 use mz_storage::types::connections::ConnectionContext;
@@ -77,6 +78,17 @@ struct Args {
     #[clap(env = "ADDRESSES", use_value_delimiter = true)]
     addresses: Vec<String>,
 
+    // === Persist options. ===
+    /// The codec used to compress the parts written to persist: one of
+    /// `uncompressed`, `snappy`, `lz4`, or `zstd`.
+    #[clap(
+        long,
+        env = "PERSIST_PART_COMPRESSION",
+        value_name = "CODEC",
+        default_value = "uncompressed"
+    )]
+    persist_part_compression: PartCompression,
+
     // === Cloud options. ===
     /// An external ID to be supplied to all AWS AssumeRole operations.
     ///
@@ -195,6 +207,7 @@ async fn run(args: Args) -> Result<(), anyhow::Error> {
             args.aws_external_id,
             secrets_reader,
         ),
+        persist_part_compression: args.persist_part_compression,
     };
 
     let (_server, client) = mz_compute::server::serve(config)?;
@@ -51,6 +51,13 @@ pub enum ComputeEvent {
     Peek(Peek, bool),
     /// Available frontier information for views.
     Frontier(GlobalId, Timestamp, i64),
+    /// A sink stopped writing because of an error.
+    SinkError {
+        /// Globally unique identifier for the sink.
+        sink: GlobalId,
+        /// A description of the error.
+        error: String,
+    },
 }
 
 /// A logged peek event.
@@ -107,17 +114,20 @@ pub fn construct<A: Allocate>(
         let (mut frontier_out, frontier) = demux.new_output();
         let (mut peek_out, peek) = demux.new_output();
         let (mut peek_duration_out, peek_duration) = demux.new_output();
+        let (mut sink_status_out, sink_status) = demux.new_output();
 
         let mut demux_buffer = Vec::new();
         demux.build(move |_capability| {
             let mut active_dataflows = std::collections::HashMap::new();
             let mut peek_stash = std::collections::HashMap::new();
+            let mut sink_errors = std::collections::HashMap::new();
             move |_frontiers| {
                 let mut dataflow = dataflow_out.activate();
                 let mut dependency = dependency_out.activate();
                 let mut frontier = frontier_out.activate();
                 let mut peek = peek_out.activate();
                 let mut peek_duration = peek_duration_out.activate();
+                let mut sink_status = sink_status_out.activate();
 
                 input.for_each(|time, data| {
                     data.swap(&mut demux_buffer);
@@ -127,6 +137,7 @@ pub fn construct<A: Allocate>(
                     let mut frontier_session = frontier.session(&time);
                     let mut peek_session = peek.session(&time);
                     let mut peek_duration_session = peek_duration.session(&time);
+                    let mut sink_status_session = sink_status.session(&time);
 
                     for (time, worker, datum) in demux_buffer.drain(..) {
                         let time_ms = (((time.as_millis() as Timestamp / granularity_ms) + 1)
@@ -137,6 +148,17 @@ pub fn construct<A: Allocate>(
                                 let diff = if is_create { 1 } else { -1 };
                                 dataflow_session.give(((id, worker), time_ms, diff));
 
+                                // A dropped sink no longer has a status.
+                                if !is_create {
+                                    if let Some(error) = sink_errors.remove(&(id, worker)) {
+                                        sink_status_session.give((
+                                            (id, worker, error),
+                                            time_ms,
+                                            -1,
+                                        ));
+                                    }
+                                }
+
                                 // For now we know that these always happen in
                                 // the correct order, but it may be necessary
                                 // down the line to have dataflows keep a
@@ -220,6 +242,19 @@ pub fn construct<A: Allocate>(
                                     }
                                 }
                             }
+                            ComputeEvent::SinkError { sink, error } => {
+                                // Only the first error stops a sink, so it's
+                                // the only one worth reporting.
+                                let key = (sink, worker);
+                                if !sink_errors.contains_key(&key) {
+                                    sink_status_session.give((
+                                        (sink, worker, error.clone()),
+                                        time_ms,
+                                        1,
+                                    ));
+                                    sink_errors.insert(key, error);
+                                }
+                            }
                         }
                     }
                 });
@@ -269,6 +304,16 @@ pub fn construct<A: Allocate>(
             }
         });
 
+        let sink_status_current = sink_status.as_collection().map({
+            move |(sink, worker, error)| {
+                Row::pack_slice(&[
+                    Datum::String(&sink.to_string()),
+                    Datum::Int64(worker as i64),
+                    Datum::String(&error),
+                ])
+            }
+        });
+
         let logs = vec![
             (
                 LogVariant::Compute(ComputeLog::DataflowCurrent),
@@ -284,6 +329,10 @@ pub fn construct<A: Allocate>(
             ),
             (LogVariant::Compute(ComputeLog::PeekCurrent), peek_current),
             (LogVariant::Compute(ComputeLog::PeekDuration), peek_duration),
+            (
+                LogVariant::Compute(ComputeLog::SinkStatus),
+                sink_status_current,
+            ),
         ];
 
         let mut result = std::collections::HashMap::new();
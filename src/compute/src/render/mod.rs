@@ -104,17 +104,20 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::rc::Rc;
 use std::sync::Arc;
 
+use differential_dataflow::operators::iterate::Variable;
+use differential_dataflow::operators::Threshold;
 use differential_dataflow::AsCollection;
 use timely::communication::Allocate;
 use timely::dataflow::operators::to_stream::ToStream;
 use timely::dataflow::scopes::Child;
 use timely::dataflow::Scope;
+use timely::order::Product;
 use timely::progress::Timestamp;
 use timely::worker::Worker as TimelyWorker;
 
 use mz_compute_client::command::{BuildDesc, DataflowDescription, IndexDesc};
-use mz_compute_client::plan::Plan;
-use mz_expr::Id;
+use mz_compute_client::plan::{GetPlan, Plan};
+use mz_expr::{Id, LocalId};
 use mz_ore::collections::CollectionExt as IteratorExt;
 use mz_repr::{GlobalId, Row};
 use mz_storage::controller::CollectionMetadata;
@@ -168,6 +171,11 @@ pub fn build_compute_dataflow<A: Allocate>(
                     Arc::clone(&compute_state.persist_clients),
                     source.storage_metadata.clone(),
                     dataflow.as_of.clone().unwrap(),
+                    source
+                        .arguments
+                        .operators
+                        .clone()
+                        .map(|operators| (source.typ.clone(), operators)),
                 );
 
                 // TODO(petrosagg): this is just wrapping an Arc<T> into an Rc<Arc<T>> to make the
@@ -439,6 +447,22 @@ where
                 self.remove_id(Id::Local(id));
                 body
             }
+            Plan::LetRec {
+                id,
+                imports,
+                value,
+                body,
+            } => {
+                // Render the fixed point of `value` and bind it to `id`.
+                let value =
+                    G::Timestamp::render_let_rec(self, id, imports, *value, scope, worker_index);
+                let prebound = self.insert_id(Id::Local(id), value);
+                assert!(prebound.is_none());
+
+                let body = self.render_plan(*body, scope, worker_index);
+                self.remove_id(Id::Local(id));
+                body
+            }
             Plan::Mfp {
                 input,
                 mfp,
@@ -548,6 +572,20 @@ pub trait RenderTimestamp: Timestamp + Lattice + Refines<mz_repr::Timestamp> {
     /// Steps the timestamp back so that logical compaction to the output will
     /// not conflate `self` with any historical times.
     fn step_back(&self) -> Self;
+    /// Renders the fixed point of `value`, which may reference `Id::Local(id)`.
+    ///
+    /// The bindings in `imports` are brought into the iteration as collections,
+    /// formed as described by their respective `GetPlan`s.
+    fn render_let_rec<G>(
+        context: &mut Context<G, Row>,
+        id: LocalId,
+        imports: Vec<(Id, GetPlan)>,
+        value: Plan,
+        scope: &mut G,
+        worker_index: usize,
+    ) -> CollectionBundle<G, Row>
+    where
+        G: Scope<Timestamp = Self>;
 }
 
 impl RenderTimestamp for mz_repr::Timestamp {
@@ -566,4 +604,105 @@ impl RenderTimestamp for mz_repr::Timestamp {
     fn step_back(&self) -> Self {
         self.saturating_sub(1)
     }
+    fn render_let_rec<G>(
+        context: &mut Context<G, Row>,
+        id: LocalId,
+        imports: Vec<(Id, GetPlan)>,
+        value: Plan,
+        scope: &mut G,
+        worker_index: usize,
+    ) -> CollectionBundle<G, Row>
+    where
+        G: Scope<Timestamp = Self>,
+    {
+        // Form collections for the bindings the value uses from outside the iteration.
+        let imports = imports
+            .into_iter()
+            .map(|(import_id, plan)| {
+                let bundle = context
+                    .lookup_id(import_id)
+                    .unwrap_or_else(|| panic!("Get({:?}) not found at render time", import_id));
+                let (oks, errs) = match plan {
+                    GetPlan::PassArrangements => bundle.as_specific_collection(None),
+                    GetPlan::Arrangement(key, row, mfp) => {
+                        bundle.as_collection_core(mfp, Some((key, row)))
+                    }
+                    GetPlan::Collection(mfp) => bundle.as_collection_core(mfp, None),
+                };
+                (import_id, oks, errs)
+            })
+            .collect::<Vec<_>>();
+
+        let (oks, errs) = scope.iterative::<u64, _, _>(|inner| {
+            let mut inner_context = Context {
+                debug_name: context.debug_name.clone(),
+                dataflow_id: context.dataflow_id,
+                as_of_frontier: context.as_of_frontier.clone(),
+                bindings: BTreeMap::new(),
+            };
+            for (import_id, oks, errs) in imports {
+                inner_context.insert_id(
+                    import_id,
+                    CollectionBundle::from_collections(oks.enter(inner), errs.enter(inner)),
+                );
+            }
+
+            // Each round of iteration advances the iteration coordinate by one.
+            let summary = Product::new(Default::default(), 1);
+            let oks_variable = Variable::new(inner, summary.clone());
+            let errs_variable = Variable::new(inner, summary);
+            inner_context.insert_id(
+                Id::Local(id),
+                CollectionBundle::from_collections(
+                    (*oks_variable).clone(),
+                    (*errs_variable).clone(),
+                ),
+            );
+
+            let (value_oks, value_errs) = inner_context
+                .render_plan(value, inner, worker_index)
+                .as_specific_collection(None);
+            // Errors are deduplicated so that a value that keeps producing the
+            // same errors still reaches a fixed point.
+            let value_errs = value_errs.distinct();
+            oks_variable.set(&value_oks);
+            errs_variable.set(&value_errs);
+            (value_oks.leave(), value_errs.leave())
+        });
+
+        CollectionBundle::from_collections(oks, errs)
+    }
+}
+
+impl RenderTimestamp for Product<mz_repr::Timestamp, u64> {
+    fn system_time(&mut self) -> &mut mz_repr::Timestamp {
+        &mut self.outer
+    }
+    fn system_delay(delay: mz_repr::Timestamp) -> <Self as Timestamp>::Summary {
+        Product::new(delay, 0)
+    }
+    fn event_time(&mut self) -> &mut mz_repr::Timestamp {
+        &mut self.outer
+    }
+    fn event_delay(delay: mz_repr::Timestamp) -> <Self as Timestamp>::Summary {
+        Product::new(delay, 0)
+    }
+    fn step_back(&self) -> Self {
+        Product::new(self.outer.saturating_sub(1), self.inner)
+    }
+    fn render_let_rec<G>(
+        _context: &mut Context<G, Row>,
+        _id: LocalId,
+        _imports: Vec<(Id, GetPlan)>,
+        _value: Plan,
+        _scope: &mut G,
+        _worker_index: usize,
+    ) -> CollectionBundle<G, Row>
+    where
+        G: Scope<Timestamp = Self>,
+    {
+        // Recursive bindings nested within the value of another recursive
+        // binding are rejected during planning.
+        unreachable!("nested recursive bindings are not supported")
+    }
 }
@@ -15,7 +15,9 @@ use std::collections::BTreeSet;
 use std::rc::Rc;
 
 use differential_dataflow::operators::arrange::arrangement::ArrangeByKey;
-use differential_dataflow::{Collection, Hashable};
+use differential_dataflow::operators::Consolidate;
+use differential_dataflow::{AsCollection, Collection, Hashable};
+use timely::dataflow::operators::Filter;
 use timely::dataflow::Scope;
 
 use mz_expr::{permutation_for_arrangement, MapFilterProject};
@@ -162,6 +164,8 @@ where
     //   It then renders those as Avro.
     // * Upsert" does the same, except at the last step, it renders the diff pair in upsert format.
     //   (As part of doing so, it asserts that there are not multiple conflicting values at the same timestamp)
+    // * "AppendOnly" consolidates the stream and passes inserted rows through as bare values.
+    //   Retractions are either dropped here or passed on, for the sink to reject.
     let collection = match sink.envelope {
         Some(SinkEnvelope::Debezium) => {
             let combined = combine_at_timestamp(keyed.arrange_by_key().stream);
@@ -198,6 +202,26 @@ where
             });
             collection
         }
+        Some(SinkEnvelope::AppendOnly { drop_retractions }) => {
+            let consolidated = keyed.consolidate();
+            let consolidated = if drop_retractions {
+                consolidated
+                    .inner
+                    .filter(|(_data, _time, diff)| *diff > 0)
+                    .as_collection()
+            } else {
+                consolidated
+            };
+
+            // if there is no user-specified key, remove the synthetic
+            // distribution key again
+            let user_key_indices = sink_render.get_key_indices();
+            if user_key_indices.is_some() {
+                consolidated.map(|(key, value)| (key, Some(value)))
+            } else {
+                consolidated.map(|(_key, value)| (None, Some(value)))
+            }
+        }
         None => keyed.map(|(key, value)| (key, Some(value))),
     };
 
@@ -14,7 +14,7 @@ use std::sync::{Arc, Mutex};
 
 use anyhow::anyhow;
 use crossbeam_channel::TryRecvError;
-use mz_persist_client::PersistConfig;
+use mz_persist_client::{PartCompression, PersistConfig};
 use timely::communication::initialize::WorkerGuards;
 use timely::communication::Allocate;
 use timely::execute::execute_from;
@@ -62,6 +62,8 @@ pub struct Config {
     /// Configuration for sink connections.
     // TODO: remove when sinks move to storage.
     pub connection_context: ConnectionContext,
+    /// The codec used to compress the parts written to persist.
+    pub persist_part_compression: PartCompression,
 }
 
 /// A handle to a running dataflow server.
@@ -111,10 +113,9 @@ pub fn serve(
     let (builders, other) =
         initialize_networking(config.comm_config).map_err(|e| anyhow!("{e}"))?;
 
-    let persist_clients = PersistClientCache::new(
-        PersistConfig::new(config.now.clone()),
-        &config.metrics_registry,
-    );
+    let mut persist_cfg = PersistConfig::new(config.now.clone());
+    persist_cfg.part_compression = config.persist_part_compression;
+    let persist_clients = PersistClientCache::new(persist_cfg, &config.metrics_registry);
     let persist_clients = Arc::new(tokio::sync::Mutex::new(persist_clients));
 
     let worker_guards = execute_from(
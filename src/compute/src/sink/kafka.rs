@@ -47,7 +47,10 @@ use mz_interchange::avro::{
     self, get_debezium_transaction_schema, AvroEncoder, AvroSchemaGenerator,
 };
 use mz_interchange::encode::Encode;
-use mz_interchange::json::JsonEncoder;
+use mz_interchange::json::{
+    decode_debezium_transaction_json, encode_debezium_transaction_json, JsonEncoder,
+};
+use mz_interchange::protobuf::{ProtobufEncoder, ProtobufSchemaGenerator};
 use mz_kafka_util::client::{create_new_client_config, MzClientContext};
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
@@ -59,13 +62,14 @@ use mz_storage::controller::CollectionMetadata;
 use mz_storage::types::connections::{ConnectionContext, PopulateClientConfig};
 use mz_storage::types::errors::DataflowError;
 use mz_storage::types::sinks::{
-    KafkaSinkConnection, KafkaSinkConsistencyConnection, PublishedSchemaInfo, SinkAsOf, SinkDesc,
-    SinkEnvelope,
+    KafkaSinkConnection, KafkaSinkConsistencyConnection, KafkaSinkConsistencyFormat,
+    PublishedSchemaFormat, PublishedSchemaInfo, SinkAsOf, SinkDesc, SinkEnvelope,
 };
 use mz_timely_util::async_op;
 use mz_timely_util::operators_async_ext::OperatorBuilderExt;
 
 use super::KafkaBaseMetrics;
+use crate::logging::compute::{ComputeEvent, Logger};
 use crate::render::sinks::SinkRender;
 
 impl<G> SinkRender<G> for KafkaSinkConnection
@@ -145,6 +149,7 @@ where
             Rc::clone(&shared_frontier),
             &compute_state.sink_metrics.kafka,
             &compute_state.connection_context,
+            compute_state.compute_logger.clone(),
         );
 
         compute_state
@@ -357,7 +362,8 @@ impl KafkaTxProducer {
 #[derive(Debug, Clone)]
 struct KafkaConsistencyInitState {
     topic: String,
-    schema_id: i32,
+    format: KafkaSinkConsistencyFormat,
+    data_collection: String,
     consistency_client_config: rdkafka::ClientConfig,
 }
 
@@ -365,7 +371,8 @@ impl KafkaConsistencyInitState {
     fn to_running(self, gate_ts: Rc<Cell<Option<Timestamp>>>) -> KafkaConsistencyRunningState {
         KafkaConsistencyRunningState {
             topic: self.topic,
-            schema_id: self.schema_id,
+            format: self.format,
+            data_collection: self.data_collection,
             gate_ts,
         }
     }
@@ -374,7 +381,8 @@ impl KafkaConsistencyInitState {
 #[derive(Debug, Clone)]
 struct KafkaConsistencyRunningState {
     topic: String,
-    schema_id: i32,
+    format: KafkaSinkConsistencyFormat,
+    data_collection: String,
     gate_ts: Rc<Cell<Option<Timestamp>>>,
 }
 
@@ -470,9 +478,14 @@ impl KafkaSinkState {
         };
 
         let sink_state = KafkaSinkStateEnum::Init(connection.consistency.map(
-            |KafkaSinkConsistencyConnection { topic, schema_id }| KafkaConsistencyInitState {
+            |KafkaSinkConsistencyConnection {
+                 topic,
+                 format,
+                 data_collection,
+             }| KafkaConsistencyInitState {
                 topic,
-                schema_id,
+                format,
+                data_collection,
                 consistency_client_config,
             },
         ));
@@ -732,6 +745,7 @@ impl KafkaSinkState {
         // always be called on background thread
         fn get_latest_ts(
             consistency_topic: &str,
+            format: &KafkaSinkConsistencyFormat,
             config: &ClientConfig,
             timeout: Duration,
         ) -> Result<Option<Timestamp>, anyhow::Error> {
@@ -800,7 +814,8 @@ impl KafkaSinkState {
                 debug_assert!(offset >= latest_offset.unwrap_or(0));
                 latest_offset = Some(offset);
 
-                if let Some(ts) = maybe_decode_consistency_end_record(&message, consistency_topic)?
+                if let Some(ts) =
+                    maybe_decode_consistency_end_record(&message, format, consistency_topic)?
                 {
                     if ts >= latest_ts.unwrap_or(0) {
                         latest_ts = Some(ts);
@@ -823,41 +838,55 @@ impl KafkaSinkState {
         // return an error when we know we've found an END message but cannot decode it.
         fn maybe_decode_consistency_end_record(
             bytes: &[u8],
+            format: &KafkaSinkConsistencyFormat,
             consistency_topic: &str,
         ) -> Result<Option<Timestamp>, anyhow::Error> {
-            // The first 5 bytes are reserved for the schema id/schema registry information
-            let mut bytes = bytes.get(5..).ok_or_else(|| {
-                anyhow!("Malformed consistency topic message.  Shorter than 5 bytes.")
-            })?;
-
-            let record = mz_avro::from_avro_datum(get_debezium_transaction_schema(), &mut bytes)
-                .context("Failed to decode consistency topic message")?;
-
-            if let Value::Record(ref r) = record {
-                let m: HashMap<String, Value> = r.clone().into_iter().collect();
-                let status = m.get("status");
-                let id = m.get("id");
-                match (status, id) {
-                    (Some(Value::String(status)), Some(Value::String(id))) if status == "END" => {
-                        if let Ok(ts) = id.parse::<u64>() {
-                            Ok(Some(ts))
-                        } else {
-                            bail!(
-                        "Malformed consistency record, failed to parse timestamp {} in topic {}",
-                        id,
-                        consistency_topic
-                    );
+            let status_and_id = match format {
+                KafkaSinkConsistencyFormat::Avro { .. } => {
+                    // The first 5 bytes are reserved for the schema id/schema registry information
+                    let mut bytes = bytes.get(5..).ok_or_else(|| {
+                        anyhow!("Malformed consistency topic message.  Shorter than 5 bytes.")
+                    })?;
+
+                    let record =
+                        mz_avro::from_avro_datum(get_debezium_transaction_schema(), &mut bytes)
+                            .context("Failed to decode consistency topic message")?;
+
+                    if let Value::Record(r) = record {
+                        let m: HashMap<String, Value> = r.into_iter().collect();
+                        match (m.get("status"), m.get("id")) {
+                            (Some(Value::String(status)), Some(Value::String(id))) => {
+                                Some((status.clone(), id.clone()))
+                            }
+                            _ => None,
                         }
+                    } else {
+                        None
                     }
-                    _ => Ok(None),
                 }
-            } else {
-                Ok(None)
+                KafkaSinkConsistencyFormat::Json => decode_debezium_transaction_json(bytes)
+                    .context("Failed to decode consistency topic message")?,
+            };
+
+            match status_and_id {
+                Some((status, id)) if status == "END" => {
+                    if let Ok(ts) = id.parse::<u64>() {
+                        Ok(Some(ts))
+                    } else {
+                        bail!(
+                            "Malformed consistency record, failed to parse timestamp {} in topic {}",
+                            id,
+                            consistency_topic
+                        );
+                    }
+                }
+                _ => Ok(None),
             }
         }
 
         if let KafkaSinkStateEnum::Init(Some(KafkaConsistencyInitState {
             ref topic,
+            ref format,
             ref consistency_client_config,
             ..
         })) = self.sink_state
@@ -867,12 +896,14 @@ impl KafkaSinkState {
                 .clamp_backoff(Duration::from_secs(60 * 10))
                 .retry_async(|_| async {
                     let topic = topic.clone();
+                    let format = format.clone();
                     let consistency_client_config = consistency_client_config.clone();
                     task::spawn_blocking(
                         || format!("get_latest_ts:{}", self.name),
                         move || {
                             get_latest_ts(
                                 &topic,
+                                &format,
                                 &consistency_client_config,
                                 Duration::from_secs(10),
                             )
@@ -893,13 +924,23 @@ impl KafkaSinkState {
         message_count: Option<i64>,
         consistency: &KafkaConsistencyRunningState,
     ) -> KafkaResult<()> {
-        let encoded = avro::encode_debezium_transaction_unchecked(
-            consistency.schema_id,
-            &self.topic_prefix,
-            transaction_id,
-            status,
-            message_count,
-        );
+        let encoded = match consistency.format {
+            KafkaSinkConsistencyFormat::Avro { schema_id } => {
+                avro::encode_debezium_transaction_unchecked(
+                    schema_id,
+                    &consistency.data_collection,
+                    transaction_id,
+                    status,
+                    message_count,
+                )
+            }
+            KafkaSinkConsistencyFormat::Json => encode_debezium_transaction_json(
+                &consistency.data_collection,
+                transaction_id,
+                status,
+                message_count,
+            ),
+        };
 
         let record = BaseRecord::to(&consistency.topic)
             .payload(&encoded)
@@ -1019,6 +1060,7 @@ fn kafka<G>(
     write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
     metrics: &KafkaBaseMetrics,
     connection_context: &ConnectionContext,
+    compute_logger: Option<Logger>,
 ) -> Rc<dyn Any>
 where
     G: Scope<Timestamp = Timestamp>,
@@ -1039,6 +1081,7 @@ where
         Some(PublishedSchemaInfo {
             key_schema_id,
             value_schema_id,
+            format: PublishedSchemaFormat::Avro,
         }) => {
             let schema_generator = AvroSchemaGenerator::new(
                 None,
@@ -1058,6 +1101,29 @@ where
                 name.clone(),
             )
         }
+        Some(PublishedSchemaInfo {
+            key_schema_id,
+            value_schema_id,
+            format: PublishedSchemaFormat::Protobuf,
+        }) => {
+            let schema_generator = ProtobufSchemaGenerator::new(
+                None,
+                key_desc,
+                value_desc,
+                matches!(envelope, Some(SinkEnvelope::Debezium)),
+                connection.consistency.is_some(),
+            )
+            .expect("Protobuf schema validated during planning");
+            let encoder = ProtobufEncoder::new(schema_generator, key_schema_id, value_schema_id);
+            encode_stream(
+                stream,
+                as_of.clone(),
+                Rc::clone(&shared_gate_ts),
+                encoder,
+                connection.fuel,
+                name.clone(),
+            )
+        }
         None => {
             let encoder = JsonEncoder::new(
                 key_desc,
@@ -1086,6 +1152,7 @@ where
         write_frontier,
         metrics,
         connection_context,
+        compute_logger,
     )
 }
 
@@ -1100,6 +1167,8 @@ where
 ///
 /// Updates that are not beyond the given [`SinkAsOf`] and/or the `gate_ts` in
 /// [`KafkaSinkConnection`] will be discarded without producing them.
+///
+/// Errors that stop the sink are reported to `compute_logger`, if any.
 pub fn produce_to_kafka<G>(
     stream: Stream<G, ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff)>,
     id: GlobalId,
@@ -1110,6 +1179,7 @@ pub fn produce_to_kafka<G>(
     write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
     metrics: &KafkaBaseMetrics,
     connection_context: &ConnectionContext,
+    compute_logger: Option<Logger>,
 ) -> Rc<dyn Any>
 where
     G: Scope<Timestamp = Timestamp>,
@@ -1226,7 +1296,24 @@ where
                         continue;
                     }
 
-                    assert!(diff >= 0, "can't sink negative multiplicities");
+                    if diff < 0 {
+                        // Only append-only sinks pass retractions through to
+                        // here. There is no way to represent them in the
+                        // output, so the best we can do is stop writing and
+                        // tell the user why in mz_sink_status.
+                        if !s.shutdown_flag.swap(true, Ordering::SeqCst) {
+                            let error = format!(
+                                "can't sink negative multiplicities (got {} at time {}); the \
+                                 input of an append-only sink must not contain retractions",
+                                diff, time
+                            );
+                            info!("shutting down kafka sink {}: {}", s.name, error);
+                            if let Some(logger) = &compute_logger {
+                                logger.log(ComputeEvent::SinkError { sink: id, error });
+                            }
+                        }
+                        continue;
+                    }
                     if diff == 0 {
                         // Explicitly refuse to send no-op records
                         continue;
@@ -1243,6 +1330,12 @@ where
                 }
             });
 
+            if s.shutdown_flag.load(Ordering::SeqCst) {
+                // Let the next invocation take care of the shutdown.
+                s.activator.activate();
+                return true;
+            }
+
             // Move any newly closed timestamps from pending to ready
             let mut closed_ts: Vec<u64> = s
                 .pending_rows
@@ -25,7 +25,7 @@ use mz_repr::{Diff, GlobalId, Row, Timestamp};
 use mz_storage::controller::CollectionMetadata;
 use mz_storage::types::errors::DataflowError;
 use mz_storage::types::sinks::{PersistSinkConnection, SinkDesc};
-use mz_storage::types::sources::SourceData;
+use mz_storage::types::sources::{SourceData, SourceDataSchema};
 use mz_timely_util::operators_async_ext::OperatorBuilderExt;
 
 use crate::render::sinks::SinkRender;
@@ -49,7 +49,7 @@ where
     fn render_continuous_sink(
         &self,
         compute_state: &mut crate::compute_state::ComputeState,
-        _sink: &SinkDesc<CollectionMetadata>,
+        sink: &SinkDesc<CollectionMetadata>,
         sink_id: GlobalId,
         sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
         err_collection: Collection<G, DataflowError, Diff>,
@@ -62,6 +62,7 @@ where
         let persist_clients = Arc::clone(&compute_state.persist_clients);
         let persist_location = self.storage_metadata.persist_location.clone();
         let shard_id = self.storage_metadata.data_shard;
+        let key_schema = Arc::new(SourceDataSchema::new(sink.from_desc.typ()));
 
         let operator_name = format!("persist_sink({})", shard_id);
         let mut persist_op = OperatorBuilder::new(operator_name, scope.clone());
@@ -117,7 +118,9 @@ where
                     .open(persist_location)
                     .await
                     .expect("could not open persist client")
-                    .open_writer::<SourceData, (), Timestamp, Diff>(shard_id)
+                    .open_writer_with_schema::<SourceData, (), Timestamp, Diff>(
+                        shard_id, key_schema,
+                    )
                     .await
                     .expect("could not open persist shard");
 
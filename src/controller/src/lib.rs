@@ -57,7 +57,7 @@ use mz_orchestrator::{
 };
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_persist_client::cache::PersistClientCache;
-use mz_persist_client::PersistLocation;
+use mz_persist_client::{PartCompression, PersistLocation};
 use mz_persist_types::Codec64;
 use mz_proto::RustType;
 use mz_repr::GlobalId;
@@ -87,6 +87,9 @@ pub struct ControllerConfig {
     pub storaged_image: String,
     /// The computed image to use when starting new compute processes.
     pub computed_image: String,
+    /// The codec that storage and compute processes use to compress the
+    /// parts they write to persist.
+    pub persist_part_compression: PartCompression,
 }
 
 /// Resource allocations for a replica of a compute instance.
@@ -202,6 +205,7 @@ pub struct Controller<T = mz_repr::Timestamp> {
     storage_controller: Box<dyn StorageController<Timestamp = T>>,
     compute_orchestrator: Arc<dyn NamespacedOrchestrator>,
     computed_image: String,
+    persist_part_compression: PartCompression,
     compute: BTreeMap<ComputeInstanceId, ComputeControllerState<T>>,
     readiness: Readiness,
 }
@@ -251,6 +255,7 @@ where
                 availability_zone,
             } => {
                 let service_name = generate_replica_service_name(instance_id, replica_id);
+                let persist_part_compression = self.persist_part_compression;
 
                 let service = self
                     .compute_orchestrator
@@ -269,6 +274,10 @@ where
                                         assigned.listen_host, assigned.ports["internal-http"]
                                     ),
                                     format!("--workers={}", allocation.workers),
+                                    format!(
+                                        "--persist-part-compression={}",
+                                        persist_part_compression
+                                    ),
                                     format!("--opentelemetry-resource=instance_id={}", instance_id),
                                     format!("--opentelemetry-resource=replica_id={}", replica_id),
                                 ];
@@ -510,6 +519,7 @@ where
             config.persist_clients,
             config.orchestrator.namespace("storage"),
             config.storaged_image,
+            config.persist_part_compression,
         )
         .await;
         Self {
@@ -517,6 +527,7 @@ where
             storage_controller: Box::new(storage_controller),
             compute_orchestrator: config.orchestrator.namespace("compute"),
             computed_image: config.computed_image,
+            persist_part_compression: config.persist_part_compression,
             compute: BTreeMap::default(),
             readiness: Readiness::NotReady,
         }
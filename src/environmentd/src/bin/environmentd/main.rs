@@ -52,7 +52,7 @@ use mz_ore::id_gen::PortAllocator;
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::SYSTEM_TIME;
 use mz_persist_client::cache::PersistClientCache;
-use mz_persist_client::{PersistConfig, PersistLocation};
+use mz_persist_client::{PartCompression, PersistConfig, PersistLocation};
 use mz_secrets::SecretsController;
 use mz_storage::types::connections::ConnectionContext;
 
@@ -294,6 +294,16 @@ pub struct Args {
     /// Where the persist library should perform consensus.
     #[clap(long, env = "PERSIST_CONSENSUS_URL")]
     persist_consensus_url: Url,
+    /// The codec used to compress the parts written to persist, both by this
+    /// process and by the storage and compute processes it launches: one of
+    /// `uncompressed`, `snappy`, `lz4`, or `zstd`.
+    #[clap(
+        long,
+        env = "PERSIST_PART_COMPRESSION",
+        value_name = "CODEC",
+        default_value = "uncompressed"
+    )]
+    persist_part_compression: PartCompression,
     /// The PostgreSQL URL for the storage stash.
     #[clap(long, env = "STORAGE_STASH_URL", value_name = "POSTGRES_URL")]
     storage_stash_url: String,
@@ -539,8 +549,9 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
     };
     let secrets_reader = secrets_controller.reader();
     let now = SYSTEM_TIME.clone();
-    let persist_clients =
-        PersistClientCache::new(PersistConfig::new(now.clone()), &metrics_registry);
+    let mut persist_cfg = PersistConfig::new(now.clone());
+    persist_cfg.part_compression = args.persist_part_compression;
+    let persist_clients = PersistClientCache::new(persist_cfg, &metrics_registry);
     let persist_clients = Arc::new(Mutex::new(persist_clients));
     let orchestrator = Arc::new(TracingOrchestrator::new(
         orchestrator,
@@ -558,6 +569,7 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
         storage_stash_url: args.storage_stash_url,
         storaged_image: args.storaged_image.expect("clap enforced"),
         computed_image: args.computed_image.expect("clap enforced"),
+        persist_part_compression: args.persist_part_compression,
     };
 
     // When inside a cgroup with a cpu limit,
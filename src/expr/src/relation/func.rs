@@ -9,6 +9,7 @@
 
 #![allow(missing_docs)]
 
+use std::cmp::Ordering;
 use std::fmt;
 use std::iter;
 
@@ -937,6 +938,471 @@ where
     })
 }
 
+// The expected input is in the format of [((OriginalRow, InputValue), OrderByExprs...)]
+fn window_aggregate<'a, I>(
+    datums: I,
+    temp_storage: &'a RowArena,
+    wrapped_aggregate: &AggregateFunc,
+    order_by: &[ColumnOrder],
+    window_frame: &WindowFrame,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    // Sort the datums according to the ORDER BY expressions and return the ((OriginalRow, InputValue), OrderByRow) record
+    // The OrderByRow is kept around because it is required to compute the peer groups in RANGE and GROUPS mode
+    let datums = order_aggregate_datums_with_rank(datums, order_by);
+
+    // Decode the input (OriginalRow, InputValue) into separate datums, while keeping the OrderByRow
+    let datums = datums
+        .into_iter()
+        .map(|(d, order_by_row)| {
+            let mut iter = d.unwrap_list().iter();
+            let original_row = iter.next().unwrap();
+            let input_value = iter.next().unwrap();
+
+            (input_value, original_row, order_by_row)
+        })
+        .collect_vec();
+    let length = datums.len();
+
+    // Compute the peer groups, i.e. the ranges of rows with the same ORDER BY values,
+    // and the peer group of each row
+    let mut peer_groups: Vec<(usize, usize)> = Vec::new();
+    let mut peer_group_of = Vec::with_capacity(length);
+    for (idx, (_, _, order_by_row)) in datums.iter().enumerate() {
+        if idx == 0 || *order_by_row != datums[idx - 1].2 {
+            peer_groups.push((idx, idx));
+        }
+        peer_groups.last_mut().unwrap().1 = idx + 1;
+        peer_group_of.push(peer_groups.len() - 1);
+    }
+
+    // Returns the start (inclusive) or the end (exclusive) of the rows whose ORDER BY value
+    // is within `offset` of the ORDER BY value of the row at `idx`. Only used in RANGE mode,
+    // where planning ensures that there is exactly one ORDER BY expression of a numeric type.
+    let range_bound = |idx: usize, offset: u64, following: bool, start: bool| -> usize {
+        let current = datums[idx].2.unpack_first();
+        // Rows with a null ORDER BY value are only ever in the frame of their peers
+        if current.is_null() {
+            let (group_start, group_end) = peer_groups[peer_group_of[idx]];
+            return if start { group_start } else { group_end };
+        }
+        let ColumnOrder {
+            desc, nulls_last, ..
+        } = order_by[0];
+        // Moving forward in a descending order means moving towards smaller values
+        let subtract = following == desc;
+        let cmp_to_bound = |(_, _, order_by_row): &(Datum<'a>, Datum<'a>, Row)| {
+            let value = order_by_row.unpack_first();
+            if value.is_null() {
+                if nulls_last {
+                    Ordering::Greater
+                } else {
+                    Ordering::Less
+                }
+            } else if desc {
+                cmp_with_offset(value, current, offset, subtract).reverse()
+            } else {
+                cmp_with_offset(value, current, offset, subtract)
+            }
+        };
+        if start {
+            datums.partition_point(|d| cmp_to_bound(d) == Ordering::Less)
+        } else {
+            datums.partition_point(|d| cmp_to_bound(d) != Ordering::Greater)
+        }
+    };
+
+    let frame_start = |idx: usize| -> usize {
+        match (&window_frame.start_bound, &window_frame.units) {
+            (WindowFrameBound::UnboundedPreceding, _) => 0,
+            (WindowFrameBound::OffsetPreceding(offset), WindowFrameUnits::Rows) => {
+                idx.saturating_sub(usize::cast_from(*offset))
+            }
+            (WindowFrameBound::OffsetPreceding(offset), WindowFrameUnits::Groups) => {
+                let group = peer_group_of[idx].saturating_sub(usize::cast_from(*offset));
+                peer_groups[group].0
+            }
+            (WindowFrameBound::OffsetPreceding(offset), WindowFrameUnits::Range) => {
+                range_bound(idx, *offset, false, true)
+            }
+            (WindowFrameBound::CurrentRow, WindowFrameUnits::Rows) => idx,
+            (WindowFrameBound::CurrentRow, _) => peer_groups[peer_group_of[idx]].0,
+            (WindowFrameBound::OffsetFollowing(offset), WindowFrameUnits::Rows) => {
+                std::cmp::min(idx.saturating_add(usize::cast_from(*offset)), length)
+            }
+            (WindowFrameBound::OffsetFollowing(offset), WindowFrameUnits::Groups) => {
+                let group = peer_group_of[idx].saturating_add(usize::cast_from(*offset));
+                peer_groups.get(group).map_or(length, |(start, _)| *start)
+            }
+            (WindowFrameBound::OffsetFollowing(offset), WindowFrameUnits::Range) => {
+                range_bound(idx, *offset, true, true)
+            }
+            // Forbidden during planning
+            (WindowFrameBound::UnboundedFollowing, _) => unreachable!(),
+        }
+    };
+
+    let frame_end = |idx: usize| -> usize {
+        match (&window_frame.end_bound, &window_frame.units) {
+            (WindowFrameBound::UnboundedFollowing, _) => length,
+            (WindowFrameBound::OffsetFollowing(offset), WindowFrameUnits::Rows) => std::cmp::min(
+                idx.saturating_add(usize::cast_from(*offset))
+                    .saturating_add(1),
+                length,
+            ),
+            (WindowFrameBound::OffsetFollowing(offset), WindowFrameUnits::Groups) => {
+                let group = peer_group_of[idx].saturating_add(usize::cast_from(*offset));
+                peer_groups.get(group).map_or(length, |(_, end)| *end)
+            }
+            (WindowFrameBound::OffsetFollowing(offset), WindowFrameUnits::Range) => {
+                range_bound(idx, *offset, true, false)
+            }
+            (WindowFrameBound::CurrentRow, WindowFrameUnits::Rows) => idx + 1,
+            (WindowFrameBound::CurrentRow, _) => peer_groups[peer_group_of[idx]].1,
+            (WindowFrameBound::OffsetPreceding(offset), WindowFrameUnits::Rows) => {
+                (idx + 1).saturating_sub(usize::cast_from(*offset))
+            }
+            (WindowFrameBound::OffsetPreceding(offset), WindowFrameUnits::Groups) => {
+                let offset = usize::cast_from(*offset);
+                let group = peer_group_of[idx];
+                // If the frame ends before the first peer group, it is empty
+                if group < offset {
+                    0
+                } else {
+                    peer_groups[group - offset].1
+                }
+            }
+            (WindowFrameBound::OffsetPreceding(offset), WindowFrameUnits::Range) => {
+                range_bound(idx, *offset, false, false)
+            }
+            // Forbidden during planning
+            (WindowFrameBound::UnboundedPreceding, _) => unreachable!(),
+        }
+    };
+
+    // Both ends of the frame are non-decreasing in the row index, so empty frames
+    // are represented by `start == end` and the frames slide over the partition.
+    let frames = (0..length)
+        .map(|idx| {
+            let start = frame_start(idx);
+            (start, std::cmp::max(start, frame_end(idx)))
+        })
+        .collect_vec();
+    let values = datums.iter().map(|(value, _, _)| *value).collect_vec();
+
+    let result = window_aggregate_frames(&values, &frames, wrapped_aggregate, temp_storage);
+
+    let result = result
+        .into_iter()
+        .zip(datums)
+        .map(|(value, (_, original_row, _))| {
+            temp_storage.make_datum(|packer| {
+                packer.push_list(vec![value, original_row]);
+            })
+        });
+
+    temp_storage.make_datum(|packer| {
+        packer.push_list(result);
+    })
+}
+
+/// Evaluates `wrapped_aggregate` over each of the sliding `frames` of `values`.
+///
+/// Counts and integer sums are computed from prefix sums, and minimums and maximums
+/// from a monotonic queue of candidates, so that each takes time linear in the
+/// size of the partition. Other aggregates are evaluated over each frame.
+fn window_aggregate_frames<'a>(
+    values: &[Datum<'a>],
+    frames: &[(usize, usize)],
+    wrapped_aggregate: &AggregateFunc,
+    temp_storage: &'a RowArena,
+) -> Vec<Datum<'a>> {
+    match wrapped_aggregate {
+        AggregateFunc::Count
+        | AggregateFunc::SumInt16
+        | AggregateFunc::SumInt32
+        | AggregateFunc::SumInt64 => {
+            // The number of non-null values and their sum over each prefix
+            let mut prefix = Vec::with_capacity(values.len() + 1);
+            prefix.push((0i64, 0i128));
+            for value in values {
+                let (count, sum) = *prefix.last().unwrap();
+                let value = match value {
+                    Datum::Null => None,
+                    Datum::Int16(v) => Some(i128::from(*v)),
+                    Datum::Int32(v) => Some(i128::from(*v)),
+                    Datum::Int64(v) => Some(i128::from(*v)),
+                    // `count` accepts values of any type
+                    _ => Some(0),
+                };
+                prefix.push(match value {
+                    Some(value) => (count + 1, sum + value),
+                    None => (count, sum),
+                });
+            }
+            frames
+                .iter()
+                .map(|(start, end)| {
+                    let count = prefix[*end].0 - prefix[*start].0;
+                    let sum = prefix[*end].1 - prefix[*start].1;
+                    match wrapped_aggregate {
+                        AggregateFunc::Count => Datum::Int64(count),
+                        _ if count == 0 => Datum::Null,
+                        // Matches the output types of `sum_int16` and `sum_int32`
+                        AggregateFunc::SumInt16 | AggregateFunc::SumInt32 => {
+                            Datum::Int64(i64::try_from(sum).expect("sum of 32 bit ints"))
+                        }
+                        _ => Datum::from(sum),
+                    }
+                })
+                .collect()
+        }
+        AggregateFunc::MaxNumeric
+        | AggregateFunc::MaxInt16
+        | AggregateFunc::MaxInt32
+        | AggregateFunc::MaxInt64
+        | AggregateFunc::MaxFloat32
+        | AggregateFunc::MaxFloat64
+        | AggregateFunc::MaxBool
+        | AggregateFunc::MaxString
+        | AggregateFunc::MaxDate
+        | AggregateFunc::MaxTimestamp
+        | AggregateFunc::MaxTimestampTz
+        | AggregateFunc::MinNumeric
+        | AggregateFunc::MinInt16
+        | AggregateFunc::MinInt32
+        | AggregateFunc::MinInt64
+        | AggregateFunc::MinFloat32
+        | AggregateFunc::MinFloat64
+        | AggregateFunc::MinBool
+        | AggregateFunc::MinString
+        | AggregateFunc::MinDate
+        | AggregateFunc::MinTimestamp
+        | AggregateFunc::MinTimestampTz => {
+            // The datums of each type order the same way as the aggregates compare
+            // them, so the queue only needs to keep the values that are strictly
+            // better than all later ones.
+            let is_max = matches!(
+                wrapped_aggregate,
+                AggregateFunc::MaxNumeric
+                    | AggregateFunc::MaxInt16
+                    | AggregateFunc::MaxInt32
+                    | AggregateFunc::MaxInt64
+                    | AggregateFunc::MaxFloat32
+                    | AggregateFunc::MaxFloat64
+                    | AggregateFunc::MaxBool
+                    | AggregateFunc::MaxString
+                    | AggregateFunc::MaxDate
+                    | AggregateFunc::MaxTimestamp
+                    | AggregateFunc::MaxTimestampTz
+            );
+            // Candidates are superseded by any later value that is at least as good
+            let worse = if is_max {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            };
+            let mut candidates = std::collections::VecDeque::new();
+            let mut pushed = 0;
+            frames
+                .iter()
+                .map(|(start, end)| {
+                    while pushed < *end {
+                        let value = values[pushed];
+                        if !value.is_null() {
+                            while candidates
+                                .back()
+                                .map_or(false, |idx: &usize| value.cmp(&values[*idx]) != worse)
+                            {
+                                candidates.pop_back();
+                            }
+                            candidates.push_back(pushed);
+                        }
+                        pushed += 1;
+                    }
+                    while candidates.front().map_or(false, |idx| idx < start) {
+                        candidates.pop_front();
+                    }
+                    let best = candidates.front().map(|idx| values[*idx]);
+                    wrapped_aggregate.eval(best, temp_storage)
+                })
+                .collect()
+        }
+        _ => frames
+            .iter()
+            .map(|(start, end)| {
+                // Empty frames still evaluate the aggregate, so that e.g. `sum` returns null
+                wrapped_aggregate.eval(values[*start..*end].iter().copied(), temp_storage)
+            })
+            .collect(),
+    }
+}
+
+// The expected input is in the format of [((OriginalRow, (Timestamp, Gap, Lateness)), OrderByExprs...)]
+fn session_window<'a, I>(
+    datums: I,
+    temp_storage: &'a RowArena,
+    order_by: &[ColumnOrder],
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    // Sort the datums according to the ORDER BY expressions and return the (OriginalRow, EncodedArgs) record
+    let datums = order_aggregate_datums(datums, order_by);
+
+    // Decode the input (OriginalRow, EncodedArgs) into separate datums
+    // EncodedArgs = (Timestamp, Gap, Lateness) for sessions
+    let mut datums = datums
+        .into_iter()
+        .map(|d| {
+            let mut iter = d.unwrap_list().iter();
+            let original_row = iter.next().unwrap();
+            let mut encoded_args = iter.next().unwrap().unwrap_list().iter();
+            let (ts, gap, lateness) = (
+                encoded_args.next().unwrap(),
+                encoded_args.next().unwrap(),
+                encoded_args.next().unwrap(),
+            );
+
+            (ts, gap, lateness, original_row)
+        })
+        .collect_vec();
+
+    // Sessions are formed in timestamp order
+    datums.sort_by_key(|(ts, _, _, _)| *ts);
+
+    // The start and end of each session. A session ends once no row has arrived within the gap
+    // of its latest row. An end that is out of range is null.
+    let mut sessions: Vec<(Datum, Option<NaiveDateTime>)> = vec![];
+    // The session of each row, if any, and the lateness it allows
+    let mut rows: Vec<(Option<usize>, Datum, Datum)> = Vec::with_capacity(datums.len());
+    for (ts, gap, lateness, original_row) in datums {
+        // Rows with a null timestamp or gap don't belong to any session
+        if ts.is_null() || gap.is_null() {
+            rows.push((None, lateness, original_row));
+            continue;
+        }
+
+        let time = match ts {
+            Datum::Timestamp(ts) => ts,
+            Datum::TimestampTz(ts) => ts.naive_utc(),
+            _ => unreachable!("session windows require a timestamp"),
+        };
+        let row_end = add_interval(time, gap.unwrap_interval());
+
+        match sessions.last_mut() {
+            // The row arrives before the current session ends, so it extends the session
+            Some((_, end)) if end.map_or(true, |end| time < end) => {
+                *end = end.zip(row_end).map(|(end, row_end)| end.max(row_end));
+            }
+            _ => sessions.push((ts, row_end)),
+        }
+
+        rows.push((Some(sessions.len() - 1), lateness, original_row));
+    }
+
+    // Each row is reported with the start of its session, and the time at which the session
+    // closes, i.e. its end plus the allowed lateness. The close time is null for rows that don't
+    // belong to any session, or whose session doesn't close within range, so the temporal filter
+    // on it drops them.
+    let result = rows.into_iter().map(|(session, lateness, original_row)| {
+        let (start, close) = match session {
+            Some(session) => {
+                let (start, end) = sessions[session];
+                let close = end
+                    .zip(Some(lateness).filter(|l| !l.is_null()))
+                    .and_then(|(end, lateness)| add_interval(end, lateness.unwrap_interval()));
+                let close = match (start, close) {
+                    (Datum::Timestamp(_), Some(close)) => Datum::Timestamp(close),
+                    (Datum::TimestampTz(_), Some(close)) => {
+                        Datum::TimestampTz(DateTime::<Utc>::from_utc(close, Utc))
+                    }
+                    _ => Datum::Null,
+                };
+                (start, close)
+            }
+            None => (Datum::Null, Datum::Null),
+        };
+        temp_storage.make_datum(|packer| {
+            packer.push_list_with(|packer| {
+                packer.push_list(vec![start, close]);
+                packer.push(original_row);
+            });
+        })
+    });
+
+    temp_storage.make_datum(|packer| {
+        packer.push_list(result);
+    })
+}
+
+/// Adds `interval` to `time`, or returns `None` if the result is out of range.
+fn add_interval(time: NaiveDateTime, interval: Interval) -> Option<NaiveDateTime> {
+    add_timestamp_months(time, interval.months)
+        .ok()
+        .and_then(|time| time.checked_add_signed(interval.duration_as_chrono()))
+}
+
+/// Compares `value` to `current` shifted by `offset`, where `value` and
+/// `current` are datums of the same numeric type.
+fn cmp_with_offset(value: Datum, current: Datum, offset: u64, subtract: bool) -> Ordering {
+    fn cmp_int(value: i128, current: i128, offset: u64, subtract: bool) -> Ordering {
+        let offset = i128::from(offset);
+        let bound = if subtract {
+            current - offset
+        } else {
+            current + offset
+        };
+        value.cmp(&bound)
+    }
+
+    fn cmp_float(value: f64, current: f64, offset: u64, subtract: bool) -> Ordering {
+        let offset = offset as f64;
+        let bound = if subtract {
+            current - offset
+        } else {
+            current + offset
+        };
+        OrderedFloat(value).cmp(&OrderedFloat(bound))
+    }
+
+    match (value, current) {
+        (Datum::Int16(value), Datum::Int16(current)) => {
+            cmp_int(value.into(), current.into(), offset, subtract)
+        }
+        (Datum::Int32(value), Datum::Int32(current)) => {
+            cmp_int(value.into(), current.into(), offset, subtract)
+        }
+        (Datum::Int64(value), Datum::Int64(current)) => {
+            cmp_int(value.into(), current.into(), offset, subtract)
+        }
+        (Datum::Float32(value), Datum::Float32(current)) => cmp_float(
+            value.into_inner().into(),
+            current.into_inner().into(),
+            offset,
+            subtract,
+        ),
+        (Datum::Float64(value), Datum::Float64(current)) => {
+            cmp_float(value.into_inner(), current.into_inner(), offset, subtract)
+        }
+        (Datum::Numeric(value), Datum::Numeric(current)) => {
+            let mut cx = numeric::cx_datum();
+            let mut bound = current.0;
+            let offset = numeric::Numeric::from(offset);
+            if subtract {
+                cx.sub(&mut bound, &offset);
+            } else {
+                cx.add(&mut bound, &offset);
+            }
+            value.cmp(&OrderedDecimal(bound))
+        }
+        // Forbidden during planning
+        _ => unreachable!("RANGE offsets require a numeric ORDER BY expression"),
+    }
+}
+
 /// Identify whether the given aggregate function is Lag or Lead, since they share
 /// implementations.
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect)]
@@ -1027,6 +1493,23 @@ pub enum AggregateFunc {
         order_by: Vec<ColumnOrder>,
         window_frame: WindowFrame,
     },
+    /// Evaluates `wrapped_aggregate` over the window frame of each row of a
+    /// partition, e.g. for `sum(x) OVER (ORDER BY y ROWS 2 PRECEDING)`.
+    WindowAggregate {
+        wrapped_aggregate: Box<AggregateFunc>,
+        order_by: Vec<ColumnOrder>,
+        window_frame: WindowFrame,
+    },
+    /// Assigns each row of a partition to a session, and returns the timestamp
+    /// of the first row of that session, e.g. for
+    /// `session(ts, INTERVAL '5 minutes') OVER (PARTITION BY user_id)`.
+    ///
+    /// Each row is returned along with the time at which its session closes:
+    /// once no row has arrived within the gap of the session's latest row,
+    /// plus the allowed lateness.
+    SessionWindow {
+        order_by: Vec<ColumnOrder>,
+    },
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
@@ -1115,6 +1598,24 @@ impl Arbitrary for AggregateFunc {
                     order_by,
                     window_frame,
                 }),
+            (
+                prop_oneof![
+                    Just(AggregateFunc::SumInt64),
+                    Just(AggregateFunc::MaxInt32),
+                    Just(AggregateFunc::Count),
+                ],
+                vec(proptest_any::<ColumnOrder>(), 1..4),
+                proptest_any::<WindowFrame>()
+            )
+                .prop_map(|(wrapped_aggregate, order_by, window_frame)| {
+                    AggregateFunc::WindowAggregate {
+                        wrapped_aggregate: Box::new(wrapped_aggregate),
+                        order_by,
+                        window_frame,
+                    }
+                }),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::SessionWindow { order_by }),
             Just(AggregateFunc::Dummy)
         ]
     }
@@ -1204,6 +1705,18 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                     order_by: Some(order_by.into_proto()),
                     window_frame: Some(window_frame.into_proto()),
                 }),
+                AggregateFunc::WindowAggregate {
+                    wrapped_aggregate,
+                    order_by,
+                    window_frame,
+                } => Kind::WindowAggregate(Box::new(proto_aggregate_func::ProtoWindowAggregate {
+                    wrapped_aggregate: Some(wrapped_aggregate.into_proto()),
+                    order_by: Some(order_by.into_proto()),
+                    window_frame: Some(window_frame.into_proto()),
+                })),
+                AggregateFunc::SessionWindow { order_by } => {
+                    Kind::SessionWindow(order_by.into_proto())
+                }
                 AggregateFunc::Dummy => Kind::Dummy(()),
             }),
         }
@@ -1299,6 +1812,20 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                     .window_frame
                     .into_rust_if_some("ProtoWindowFrame::window_frame")?,
             },
+            Kind::WindowAggregate(pwa) => AggregateFunc::WindowAggregate {
+                wrapped_aggregate: pwa
+                    .wrapped_aggregate
+                    .into_rust_if_some("ProtoWindowAggregate::wrapped_aggregate")?,
+                order_by: pwa
+                    .order_by
+                    .into_rust_if_some("ProtoWindowAggregate::order_by")?,
+                window_frame: pwa
+                    .window_frame
+                    .into_rust_if_some("ProtoWindowAggregate::window_frame")?,
+            },
+            Kind::SessionWindow(order_by) => AggregateFunc::SessionWindow {
+                order_by: order_by.into_rust()?,
+            },
             Kind::Dummy(()) => AggregateFunc::Dummy,
         })
     }
@@ -1362,6 +1889,20 @@ impl AggregateFunc {
                 order_by,
                 window_frame,
             } => last_value(datums, temp_storage, order_by, window_frame),
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate,
+                order_by,
+                window_frame,
+            } => window_aggregate(
+                datums,
+                temp_storage,
+                wrapped_aggregate,
+                order_by,
+                window_frame,
+            ),
+            AggregateFunc::SessionWindow { order_by } => {
+                session_window(datums, temp_storage, order_by)
+            }
             AggregateFunc::Dummy => Datum::Dummy,
         }
     }
@@ -1392,6 +1933,8 @@ impl AggregateFunc {
             AggregateFunc::LagLead { .. } => Datum::empty_list(),
             AggregateFunc::FirstValue { .. } => Datum::empty_list(),
             AggregateFunc::LastValue { .. } => Datum::empty_list(),
+            AggregateFunc::WindowAggregate { .. } => Datum::empty_list(),
+            AggregateFunc::SessionWindow { .. } => Datum::empty_list(),
             _ => Datum::Null,
         }
     }
@@ -1533,6 +2076,62 @@ impl AggregateFunc {
                     custom_id: None,
                 }
             }
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate, ..
+            } => {
+                // The input type for a window aggregate is ((OriginalRow, InputValue), OrderByExprs...)
+                let fields = input_type.scalar_type.unwrap_record_element_type();
+                let original_row_type = fields[0].unwrap_record_element_type()[0]
+                    .clone()
+                    .nullable(false);
+                let input_value_type = fields[0].unwrap_record_element_type()[1]
+                    .clone()
+                    .nullable(true);
+                // Frames can be empty, in which case most aggregates return null
+                let value_type = wrapped_aggregate.output_type(input_value_type);
+
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::Record {
+                        fields: vec![
+                            (ColumnName::from("?window_agg?"), value_type),
+                            (ColumnName::from("?record?"), original_row_type),
+                        ],
+                        custom_id: None,
+                    }),
+                    custom_id: None,
+                }
+            }
+            AggregateFunc::SessionWindow { .. } => {
+                // The input type for SessionWindow is ((OriginalRow, (Timestamp, Gap, Lateness)), OrderByExprs...)
+                let fields = input_type.scalar_type.unwrap_record_element_type();
+                let original_row_type = fields[0].unwrap_record_element_type()[0]
+                    .clone()
+                    .nullable(false);
+                let ts_type = fields[0].unwrap_record_element_type()[1]
+                    .unwrap_record_element_type()[0]
+                    .clone()
+                    .nullable(true);
+                // The start of the session, and the time at which it closes
+                let value_type = ScalarType::Record {
+                    fields: vec![
+                        (ColumnName::from("start"), ts_type.clone()),
+                        (ColumnName::from("close"), ts_type),
+                    ],
+                    custom_id: None,
+                }
+                .nullable(false);
+
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::Record {
+                        fields: vec![
+                            (ColumnName::from("?session?"), value_type),
+                            (ColumnName::from("?record?"), original_row_type),
+                        ],
+                        custom_id: None,
+                    }),
+                    custom_id: None,
+                }
+            }
             // Note AggregateFunc::MaxString, MinString rely on returning input
             // type as output type to support the proper return type for
             // character input.
@@ -1831,6 +2430,10 @@ impl fmt::Display for AggregateFunc {
             } => f.write_str("lead"),
             AggregateFunc::FirstValue { .. } => f.write_str("first_value"),
             AggregateFunc::LastValue { .. } => f.write_str("last_value"),
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate, ..
+            } => write!(f, "window_{}", wrapped_aggregate),
+            AggregateFunc::SessionWindow { .. } => f.write_str("session"),
             AggregateFunc::Dummy => f.write_str("dummy"),
         }
     }
@@ -2295,8 +2898,11 @@ impl fmt::Display for TableFunc {
 
 #[cfg(test)]
 mod tests {
-    use super::{AggregateFunc, ProtoAggregateFunc, ProtoTableFunc, TableFunc};
+    use super::{
+        window_aggregate_frames, AggregateFunc, ProtoAggregateFunc, ProtoTableFunc, TableFunc,
+    };
     use mz_proto::protobuf_roundtrip;
+    use mz_repr::{Datum, RowArena};
     use proptest::prelude::*;
 
     proptest! {
@@ -2316,4 +2922,41 @@ mod tests {
             assert_eq!(actual.unwrap(), expect);
         }
     }
+
+    proptest! {
+        #[test]
+        fn window_aggregate_frames_match_eval(
+            values in proptest::collection::vec(proptest::option::of(-100i32..100), 0..20),
+            sizes in proptest::collection::vec((0usize..4, 0usize..4), 20),
+        ) {
+            // Build sliding frames whose ends are non-decreasing, as in `window_aggregate`
+            let length = values.len();
+            let mut frames = Vec::new();
+            let (mut start, mut end) = (0, 0);
+            for (idx, (before, after)) in sizes.into_iter().take(length).enumerate() {
+                start = std::cmp::max(start, idx.saturating_sub(before));
+                end = std::cmp::max(end, std::cmp::min(idx + after, length));
+                frames.push((start, std::cmp::max(start, end)));
+            }
+            let values = values
+                .into_iter()
+                .map(|v| v.map_or(Datum::Null, Datum::Int32))
+                .collect::<Vec<_>>();
+
+            let arena = RowArena::new();
+            for func in [
+                AggregateFunc::Count,
+                AggregateFunc::SumInt32,
+                AggregateFunc::MaxInt32,
+                AggregateFunc::MinInt32,
+            ] {
+                let expected = frames
+                    .iter()
+                    .map(|(start, end)| func.eval(values[*start..*end].iter().copied(), &arena))
+                    .collect::<Vec<_>>();
+                let actual = window_aggregate_frames(&values, &frames, &func, &arena);
+                assert_eq!(actual, expected, "{}", func);
+            }
+        }
+    }
 }
@@ -104,6 +104,24 @@ pub enum MirRelationExpr {
         /// The result of the `Let`, evaluated with `id` bound to `value`.
         body: Box<MirRelationExpr>,
     },
+    /// Introduce a temporary dataflow that may refer to itself.
+    ///
+    /// The collection bound to `id` is the fixed point of `value`, which may
+    /// contain `Get` stages that reference `id`. It is computed by starting
+    /// from an empty collection and repeatedly evaluating `value` until the
+    /// collection stops changing.
+    ///
+    /// The runtime memory footprint of this operator is zero, but the
+    /// operators of `value` may need to maintain state for every iteration.
+    LetRec {
+        /// The identifier to be used in `Get` variants to retrieve `value`.
+        #[mzreflect(ignore)]
+        id: LocalId,
+        /// The collection to be bound to `id`, which may itself refer to `id`.
+        value: Box<MirRelationExpr>,
+        /// The result of the `LetRec`, evaluated with `id` bound to `value`.
+        body: Box<MirRelationExpr>,
+    },
     /// Project out some columns from a dataflow
     ///
     /// The runtime memory footprint of this operator is zero.
@@ -260,16 +278,17 @@ impl MirRelationExpr {
         #[allow(deprecated)]
         self.visit_pre_post_nolimit(
             &mut |e: &MirRelationExpr| -> Option<Vec<&MirRelationExpr>> {
-                if let MirRelationExpr::Let { body, .. } = &e {
-                    // Do not traverse the value sub-graph, since it's not relevant for
-                    // determining the relation type of Let operators.
-                    Some(vec![&*body])
-                } else {
-                    None
+                match &e {
+                    MirRelationExpr::Let { body, .. } | MirRelationExpr::LetRec { body, .. } => {
+                        // Do not traverse the value sub-graph, since it's not relevant for
+                        // determining the relation type of Let operators.
+                        Some(vec![&*body])
+                    }
+                    _ => None,
                 }
             },
             &mut |e: &MirRelationExpr| {
-                if let MirRelationExpr::Let { .. } = &e {
+                if let MirRelationExpr::Let { .. } | MirRelationExpr::LetRec { .. } = &e {
                     let body_typ = type_stack.pop().unwrap();
                     // Insert a dummy relation type for the value, since `typ_with_input_types`
                     // won't look at it, but expects the relation type of the body to be second.
@@ -345,7 +364,9 @@ impl MirRelationExpr {
                 }
             }
             MirRelationExpr::Get { typ, .. } => typ.clone(),
-            MirRelationExpr::Let { .. } => input_types.last().unwrap().clone(),
+            MirRelationExpr::Let { .. } | MirRelationExpr::LetRec { .. } => {
+                input_types.last().unwrap().clone()
+            }
             MirRelationExpr::Project { input: _, outputs } => {
                 let input_typ = &input_types[0];
                 let mut output_typ = RelationType::new(
@@ -740,7 +761,9 @@ impl MirRelationExpr {
         match self {
             MirRelationExpr::Constant { rows: _, typ } => typ.arity(),
             MirRelationExpr::Get { typ, .. } => typ.arity(),
-            MirRelationExpr::Let { body, .. } => body.arity(),
+            MirRelationExpr::Let { body, .. } | MirRelationExpr::LetRec { body, .. } => {
+                body.arity()
+            }
             MirRelationExpr::Project { input: _, outputs } => outputs.len(),
             MirRelationExpr::Map { input, scalars } => input.arity() + scalars.len(),
             MirRelationExpr::FlatMap { input, func, .. } => {
@@ -1196,6 +1219,15 @@ impl MirRelationExpr {
         contains
     }
 
+    /// True iff the expression contains a `LetRec` binding.
+    pub fn is_recursive(&self) -> bool {
+        let mut recursive = false;
+        self.visit_pre_nolimit(&mut |e| {
+            recursive = recursive || matches!(e, MirRelationExpr::LetRec { .. })
+        });
+        recursive
+    }
+
     /// Fallible visitor for the [`MirScalarExpr`]s directly owned by this relation expression.
     ///
     /// The `f` visitor should not recursively descend into owned [`MirRelationExpr`]s.
@@ -1249,6 +1281,7 @@ impl MirRelationExpr {
             Constant { .. }
             | Get { .. }
             | Let { .. }
+            | LetRec { .. }
             | Project { .. }
             | TopK { .. }
             | Negate { .. }
@@ -1304,7 +1337,7 @@ impl VisitChildren for MirRelationExpr {
         use MirRelationExpr::*;
         match self {
             Constant { .. } | Get { .. } => (),
-            Let { value, body, .. } => {
+            Let { value, body, .. } | LetRec { value, body, .. } => {
                 f(value);
                 f(body);
             }
@@ -1340,7 +1373,7 @@ impl VisitChildren for MirRelationExpr {
         use MirRelationExpr::*;
         match self {
             Constant { .. } | Get { .. } => (),
-            Let { value, body, .. } => {
+            Let { value, body, .. } | LetRec { value, body, .. } => {
                 f(value);
                 f(body);
             }
@@ -1376,7 +1409,7 @@ impl VisitChildren for MirRelationExpr {
         use MirRelationExpr::*;
         match self {
             Constant { .. } | Get { .. } => (),
-            Let { value, body, .. } => {
+            Let { value, body, .. } | LetRec { value, body, .. } => {
                 f(value)?;
                 f(body)?;
             }
@@ -1413,7 +1446,7 @@ impl VisitChildren for MirRelationExpr {
         use MirRelationExpr::*;
         match self {
             Constant { .. } | Get { .. } => (),
-            Let { value, body, .. } => {
+            Let { value, body, .. } | LetRec { value, body, .. } => {
                 f(value)?;
                 f(body)?;
             }
@@ -1866,6 +1899,131 @@ impl AggregateExpr {
                 }
             }
 
+            // The input type for a window aggregate is a ((OriginalRow, InputValue), OrderByExprs...)
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate,
+                window_frame,
+                ..
+            } => {
+                let tuple = self
+                    .expr
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Get the overall return type
+                let return_type = self
+                    .typ(input_type)
+                    .scalar_type
+                    .unwrap_list_element_type()
+                    .clone();
+                let window_agg_return_type = return_type.unwrap_record_element_type()[0].clone();
+
+                // Extract the original row
+                let original_row = tuple
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Extract the input value
+                let expr = tuple.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+
+                // If the window frame includes the current (single) row, aggregate its value,
+                // otherwise aggregate an empty frame
+                let value = if window_frame.includes_current_row() {
+                    AggregateExpr {
+                        func: (**wrapped_aggregate).clone(),
+                        expr,
+                        distinct: false,
+                    }
+                    .on_unique(input_type)
+                } else {
+                    MirScalarExpr::literal_ok(wrapped_aggregate.default(), window_agg_return_type)
+                };
+
+                MirScalarExpr::CallVariadic {
+                    func: VariadicFunc::ListCreate {
+                        elem_type: return_type,
+                    },
+                    exprs: vec![MirScalarExpr::CallVariadic {
+                        func: VariadicFunc::RecordCreate {
+                            field_names: vec![
+                                ColumnName::from("?window_agg?"),
+                                ColumnName::from("?record?"),
+                            ],
+                        },
+                        exprs: vec![value, original_row],
+                    }],
+                }
+            }
+
+            // The input type for SessionWindow is a ((OriginalRow, (Timestamp, Gap, Lateness)), OrderByExprs...)
+            AggregateFunc::SessionWindow { .. } => {
+                let tuple = self
+                    .expr
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Get the overall return type
+                let return_type = self
+                    .typ(input_type)
+                    .scalar_type
+                    .unwrap_list_element_type()
+                    .clone();
+                let session_return_type = return_type.unwrap_record_element_type()[0].clone();
+                let ts_type = session_return_type.unwrap_record_element_type()[0].clone();
+
+                // Extract the original row
+                let original_row = tuple
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+
+                // Extract the encoded args
+                let encoded_args =
+                    tuple.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+                let ts = encoded_args
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+                let gap = encoded_args
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+                let lateness =
+                    encoded_args.call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(2)));
+
+                // A single row starts its own session, unless its gap is null, and the session
+                // closes once its gap and lateness have passed.
+                let add_interval = match ts_type {
+                    ScalarType::TimestampTz => crate::BinaryFunc::AddTimestampTzInterval,
+                    _ => crate::BinaryFunc::AddTimestampInterval,
+                };
+                let close = ts
+                    .clone()
+                    .call_binary(gap.clone(), add_interval.clone())
+                    .call_binary(lateness, add_interval);
+                let start = gap
+                    .call_unary(crate::UnaryFunc::IsNull(crate::func::IsNull))
+                    .if_then_else(MirScalarExpr::literal_null(ts_type), ts);
+                let value = MirScalarExpr::CallVariadic {
+                    func: VariadicFunc::RecordCreate {
+                        field_names: vec![ColumnName::from("start"), ColumnName::from("close")],
+                    },
+                    exprs: vec![start, close],
+                };
+
+                MirScalarExpr::CallVariadic {
+                    func: VariadicFunc::ListCreate {
+                        elem_type: return_type,
+                    },
+                    exprs: vec![MirScalarExpr::CallVariadic {
+                        func: VariadicFunc::RecordCreate {
+                            field_names: vec![
+                                ColumnName::from("?session?"),
+                                ColumnName::from("?record?"),
+                            ],
+                        },
+                        exprs: vec![value, original_row],
+                    }],
+                }
+            }
+
             // All other variants should return the argument to the aggregation.
             AggregateFunc::MaxNumeric
             | AggregateFunc::MaxInt16
@@ -2261,7 +2419,8 @@ pub enum WindowFrameUnits {
     /// and offset-based bounds use the value of the ORDER BY expression
     Range,
     /// Each peer group is treated as the unit of work for bounds.
-    /// Groups is currently not supported, and it is rejected during planning.
+    /// Groups is only supported by aggregate window functions, and it is
+    /// rejected during planning otherwise.
     Groups,
 }
 
@@ -52,21 +52,111 @@ pub fn extract_avro_header(buf: &[u8]) -> Result<(i32, &[u8])> {
     extract_schema_id(buf, "avro")
 }
 
-pub fn extract_protobuf_header(buf: &[u8]) -> Result<(i32, &[u8])> {
-    let (schema_id, buf) = extract_schema_id(buf, "protobuf")?;
-
-    match buf.get(0) {
-        Some(0) => Ok((schema_id, &buf[1..])),
-        Some(message_id) => bail!(
-            "unsupported Confluent-style protobuf message descriptor id: \
-                expected 0, but found: {}. \
-                See https://github.com/MaterializeInc/materialize/issues/9250",
-            message_id
-        ),
-        None => bail!(
-            "Confluent-style protobuf datum is too few bytes: expected a message id after magic \
-            and schema id, got a buffer of length {}",
-            buf.len()
-        ),
+/// Extracts the schema ID and the message indexes placed in front of a
+/// serialized Protobuf message by the Confluent stack.
+///
+/// The message indexes identify the message in the schema that was used to
+/// serialize the datum, as a path of indexes into the message types of the
+/// file and its nested message types. They are encoded as a zigzag varint
+/// count followed by that many zigzag varints, except that the common case of
+/// the first message in the file (`[0]`) is encoded as a single `0` byte.
+///
+/// This function returns the schema ID, the message indexes and a subslice of
+/// the rest of the buffer.
+pub fn extract_protobuf_header(buf: &[u8]) -> Result<(i32, Vec<i64>, &[u8])> {
+    let (schema_id, mut buf) = extract_schema_id(buf, "protobuf")?;
+
+    let count = read_zigzag_varint(&mut buf)?;
+    if count == 0 {
+        return Ok((schema_id, vec![0], buf));
+    }
+    if count < 0 || count as usize > buf.len() {
+        bail!(
+            "invalid Confluent-style protobuf message index count {}",
+            count
+        );
+    }
+    let mut message_indexes = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        message_indexes.push(read_zigzag_varint(&mut buf)?);
+    }
+    Ok((schema_id, message_indexes, buf))
+}
+
+/// Writes the message indexes that identify a Protobuf message in its schema,
+/// in the encoding read by [`extract_protobuf_header`].
+pub fn write_protobuf_message_indexes(buf: &mut Vec<u8>, message_indexes: &[i64]) {
+    if message_indexes == [0] {
+        buf.push(0);
+        return;
+    }
+    write_zigzag_varint(
+        buf,
+        i64::try_from(message_indexes.len()).expect("message index count fits in i64"),
+    );
+    for index in message_indexes {
+        write_zigzag_varint(buf, *index);
+    }
+}
+
+/// Writes `n` to the end of `buf` as a zigzag-encoded varint.
+fn write_zigzag_varint(buf: &mut Vec<u8>, n: i64) {
+    let mut n = ((n << 1) ^ (n >> 63)) as u64;
+    while n >= 0x80 {
+        buf.push((n as u8 & 0x7f) | 0x80);
+        n >>= 7;
+    }
+    buf.push(n as u8);
+}
+
+/// Reads a zigzag-encoded varint from the front of `buf`, advancing it past
+/// the varint.
+fn read_zigzag_varint(buf: &mut &[u8]) -> Result<i64> {
+    let mut n: u64 = 0;
+    for (i, byte) in buf.iter().enumerate().take(10) {
+        n |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            *buf = &buf[i + 1..];
+            return Ok(((n >> 1) as i64) ^ -((n & 1) as i64));
+        }
+    }
+    bail!("Confluent-style protobuf datum has a malformed message index varint")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_protobuf_header() {
+        // The first message is encoded as a single zero byte.
+        let (schema_id, indexes, rest) = extract_protobuf_header(&[0, 0, 0, 0, 7, 0, 42]).unwrap();
+        assert_eq!((schema_id, indexes, rest), (7, vec![0], &[42][..]));
+
+        // The message at path [1, 2] is encoded as a count of 2 followed by
+        // the zigzag-encoded indexes.
+        let (schema_id, indexes, rest) =
+            extract_protobuf_header(&[0, 0, 0, 0, 7, 4, 2, 4, 42]).unwrap();
+        assert_eq!((schema_id, indexes, rest), (7, vec![1, 2], &[42][..]));
+
+        assert!(extract_protobuf_header(&[0, 0, 0, 0, 7]).is_err());
+        assert!(extract_protobuf_header(&[0, 0, 0, 0, 7, 4, 2]).is_err());
+        assert!(extract_protobuf_header(&[0, 0, 0, 0, 7, 0x80]).is_err());
+    }
+
+    #[test]
+    fn test_write_protobuf_message_indexes() {
+        for (indexes, encoded) in [
+            (vec![0], vec![0]),
+            (vec![1, 2], vec![4, 2, 4]),
+            (vec![1, 0], vec![4, 2, 0]),
+            (vec![100], vec![2, 0xc8, 0x01]),
+        ] {
+            let mut buf = vec![0, 0, 0, 0, 7];
+            write_protobuf_message_indexes(&mut buf, &indexes);
+            assert_eq!(buf[5..], encoded);
+            let (_, extracted, rest) = extract_protobuf_header(&buf).unwrap();
+            assert_eq!((extracted, rest), (indexes, &[][..]));
+        }
     }
 }
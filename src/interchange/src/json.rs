@@ -107,6 +107,48 @@ where
     serde_json::value::Value::Object(value_fields)
 }
 
+/// Encodes a Debezium transaction metadata record as JSON.
+///
+/// The record has the same fields as the Avro transaction record produced by
+/// [`crate::avro::encode_debezium_transaction_unchecked`].
+pub fn encode_debezium_transaction_json(
+    collection: &str,
+    id: &str,
+    status: &str,
+    message_count: Option<i64>,
+) -> Vec<u8> {
+    let data_collections = message_count.map(|count| {
+        json!([{
+            "data_collection": collection,
+            "event_count": count,
+        }])
+    });
+    let record = json!({
+        "id": id,
+        "status": status,
+        "event_count": message_count,
+        "data_collections": data_collections,
+    });
+    serde_json::to_vec(&record).expect("serializing JSON value cannot fail")
+}
+
+/// Decodes the `status` and `id` fields of a JSON-encoded Debezium transaction
+/// metadata record, as produced by [`encode_debezium_transaction_json`].
+///
+/// Returns `None` if the record is valid JSON but does not have string-valued
+/// `status` and `id` fields.
+pub fn decode_debezium_transaction_json(
+    bytes: &[u8],
+) -> Result<Option<(String, String)>, anyhow::Error> {
+    let record: serde_json::Value = serde_json::from_slice(bytes)?;
+    let status = record.get("status").and_then(|s| s.as_str());
+    let id = record.get("id").and_then(|id| id.as_str());
+    match (status, id) {
+        (Some(status), Some(id)) => Ok(Some((status.to_owned(), id.to_owned()))),
+        _ => Ok(None),
+    }
+}
+
 pub trait ToJson {
     /// Transforms this value to a JSON value.
     fn json(self) -> serde_json::value::Value;
@@ -382,3 +424,35 @@ pub fn build_row_schema_json(
         "name": name
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debezium_transaction_json_roundtrip() {
+        let end = encode_debezium_transaction_json("sink", "42", "END", Some(3));
+        let value: serde_json::Value = serde_json::from_slice(&end).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "id": "42",
+                "status": "END",
+                "event_count": 3,
+                "data_collections": [{"data_collection": "sink", "event_count": 3}],
+            })
+        );
+        assert_eq!(
+            decode_debezium_transaction_json(&end).unwrap(),
+            Some(("END".into(), "42".into()))
+        );
+
+        let begin = encode_debezium_transaction_json("sink", "42", "BEGIN", None);
+        let value: serde_json::Value = serde_json::from_slice(&begin).unwrap();
+        assert_eq!(value["event_count"], serde_json::Value::Null);
+        assert_eq!(value["data_collections"], serde_json::Value::Null);
+
+        assert_eq!(decode_debezium_transaction_json(b"{}").unwrap(), None);
+        assert!(decode_debezium_transaction_json(b"{").is_err());
+    }
+}
@@ -7,17 +7,26 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Write};
 
 use anyhow::{anyhow, bail, Context};
-
+use byteorder::{NetworkEndian, WriteBytesExt};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use prost::encoding::WireType;
 use prost_reflect::{
-    Cardinality, DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor,
+    Cardinality, DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MapKey, MessageDescriptor,
     ReflectMessage, Value,
 };
 
+use mz_ore::cast::CastFrom;
 use mz_ore::str::StrExt;
-use mz_repr::{ColumnName, ColumnType, Datum, Row, RowPacker, ScalarType};
+use mz_repr::adt::interval::Interval;
+use mz_repr::adt::jsonb::JsonbRef;
+use mz_repr::{ColumnName, ColumnType, Datum, GlobalId, RelationDesc, Row, RowPacker, ScalarType};
+
+use crate::encode::{column_names_and_types, Encode};
+use crate::envelopes::{self, ENVELOPE_CUSTOM_NAMES};
 
 /// A decoded description of the schema of a Protobuf message.
 #[derive(Debug, PartialEq)]
@@ -25,6 +34,9 @@ pub struct DecodedDescriptors {
     message_descriptor: MessageDescriptor,
     columns: Vec<(ColumnName, ColumnType)>,
     message_name: String,
+    /// The path to the message in its file, as written by Confluent-style
+    /// serializers.
+    message_indexes: Vec<i64>,
 }
 
 impl DecodedDescriptors {
@@ -32,24 +44,28 @@ impl DecodedDescriptors {
     /// the fully qualified name of a message inside that file descriptor set.
     pub fn from_bytes(bytes: &[u8], message_name: String) -> Result<Self, anyhow::Error> {
         let fds = DescriptorPool::decode(bytes).context("decoding file descriptor set")?;
-        let message_descriptor = fds.get_message_by_name(&message_name).ok_or_else(|| {
-            anyhow!(
-                "protobuf message {} not found in file descriptor set",
-                message_name.quoted(),
-            )
-        })?;
+        let message_descriptor = fds
+            .get_message_by_name(message_name.trim_start_matches('.'))
+            .ok_or_else(|| {
+                anyhow!(
+                    "protobuf message {} not found in file descriptor set",
+                    message_name.quoted(),
+                )
+            })?;
         let mut seen_messages = HashSet::new();
-        seen_messages.insert(message_descriptor.name().to_owned());
+        seen_messages.insert(message_descriptor.full_name().to_owned());
         let mut columns = vec![];
         for field in message_descriptor.fields() {
             let name = ColumnName::from(field.name());
             let ty = derive_column_type(&mut seen_messages, &field)?;
             columns.push((name, ty))
         }
+        let message_indexes = message_indexes(&message_descriptor);
         Ok(DecodedDescriptors {
             message_descriptor,
             columns,
             message_name,
+            message_indexes,
         })
     }
 
@@ -102,8 +118,22 @@ impl Decoder {
             // schema is handled gracefully (e.g., no accidentally massive
             // allocations).
             //
+            // We do check that the message was written as the message we
+            // decode, though, as decoding one message as another silently
+            // produces garbage.
+            //
             // [0]: https://developers.google.com/protocol-buffers/docs/overview
-            let (_schema_id, adjusted_bytes) = crate::confluent::extract_protobuf_header(bytes)?;
+            let (_schema_id, message_indexes, adjusted_bytes) =
+                crate::confluent::extract_protobuf_header(bytes)?;
+            if message_indexes != self.descriptors.message_indexes {
+                bail!(
+                    "protobuf message was written with message indexes {:?}, \
+                     but {} has message indexes {:?}",
+                    message_indexes,
+                    self.descriptors.message_name.quoted(),
+                    self.descriptors.message_indexes,
+                );
+            }
             bytes = adjusted_bytes;
         }
         let message = DynamicMessage::decode(self.descriptors.message_descriptor.clone(), bytes)?;
@@ -113,12 +143,78 @@ impl Decoder {
     }
 }
 
+/// Returns the path to `message` in its file, as written by Confluent-style
+/// serializers: the index of its outermost enclosing message among the file's
+/// messages, followed by the index of each nested message among its parent's
+/// nested messages.
+pub fn message_indexes(message: &MessageDescriptor) -> Vec<i64> {
+    let mut indexes = vec![];
+    let mut message = message.clone();
+    loop {
+        let parent = message.parent_message();
+        let siblings: Vec<_> = match &parent {
+            Some(parent) => parent.child_messages().collect(),
+            None => message.parent_file().messages().collect(),
+        };
+        let index = siblings
+            .iter()
+            .position(|sibling| sibling.full_name() == message.full_name())
+            .expect("message is nested in its parent");
+        indexes.push(i64::try_from(index).expect("message index fits in i64"));
+        match parent {
+            Some(parent) => message = parent,
+            None => break,
+        }
+    }
+    indexes.reverse();
+    indexes
+}
+
+/// The well-known types from the `google.protobuf` package that are decoded
+/// into a native Materialize type rather than a record.
+enum WellKnownType {
+    /// `google.protobuf.Timestamp`, decoded as a `timestamptz`.
+    Timestamp,
+    /// `google.protobuf.Duration`, decoded as an `interval`.
+    Duration,
+    /// One of the wrapper types, like `google.protobuf.Int64Value`, decoded as
+    /// a nullable value of the wrapped type.
+    Wrapper,
+}
+
+impl WellKnownType {
+    fn from_descriptor(message: &MessageDescriptor) -> Option<WellKnownType> {
+        match message.full_name() {
+            "google.protobuf.Timestamp" => Some(WellKnownType::Timestamp),
+            "google.protobuf.Duration" => Some(WellKnownType::Duration),
+            "google.protobuf.BoolValue"
+            | "google.protobuf.Int32Value"
+            | "google.protobuf.Int64Value"
+            | "google.protobuf.UInt32Value"
+            | "google.protobuf.UInt64Value"
+            | "google.protobuf.FloatValue"
+            | "google.protobuf.DoubleValue"
+            | "google.protobuf.StringValue"
+            | "google.protobuf.BytesValue" => Some(WellKnownType::Wrapper),
+            _ => None,
+        }
+    }
+}
+
 fn derive_column_type(
     seen_messages: &mut HashSet<String>,
     field: &FieldDescriptor,
 ) -> Result<ColumnType, anyhow::Error> {
     if field.is_map() {
-        bail!("Protobuf map fields are not supported");
+        // Map keys are always strings, integers or booleans, which are
+        // converted to the text keys of a Materialize map.
+        let value_field = map_value_field(field)?;
+        let value_type = derive_inner_type(seen_messages, value_field.kind())?;
+        return Ok(ScalarType::Map {
+            value_type: Box::new(value_type.scalar_type),
+            custom_id: None,
+        }
+        .nullable(false));
     }
 
     let ty = derive_inner_type(seen_messages, field.kind())?;
@@ -130,6 +226,10 @@ fn derive_column_type(
                 custom_id: None,
             },
         })
+    } else if field.containing_oneof().is_some() {
+        // Members of a `oneof`, including proto3 `optional` fields, are null
+        // when they are not set.
+        Ok(ty.nullable(true))
     } else {
         Ok(ty)
     }
@@ -152,17 +252,27 @@ fn derive_inner_type(
         Kind::Bytes => Ok(ScalarType::Bytes.nullable(false)),
         Kind::Enum(_) => Ok(ScalarType::String.nullable(false)),
         Kind::Message(m) => {
-            if seen_messages.contains(m.name()) {
-                bail!("Recursive types are not supported: {}", m.name());
+            match WellKnownType::from_descriptor(&m) {
+                Some(WellKnownType::Timestamp) => return Ok(ScalarType::TimestampTz.nullable(true)),
+                Some(WellKnownType::Duration) => return Ok(ScalarType::Interval.nullable(true)),
+                Some(WellKnownType::Wrapper) => {
+                    let value_field = wrapper_value_field(&m)?;
+                    let ty = derive_inner_type(seen_messages, value_field.kind())?;
+                    return Ok(ty.nullable(true));
+                }
+                None => (),
+            }
+            if seen_messages.contains(m.full_name()) {
+                bail!("Recursive types are not supported: {}", m.full_name());
             }
-            seen_messages.insert(m.name().to_owned());
+            seen_messages.insert(m.full_name().to_owned());
             let mut fields = Vec::with_capacity(m.fields().len());
             for field in m.fields() {
                 let column_name = ColumnName::from(field.name());
                 let column_type = derive_column_type(seen_messages, &field)?;
                 fields.push((column_name, column_type))
             }
-            seen_messages.remove(m.name());
+            seen_messages.remove(m.full_name());
             let ty = ScalarType::Record {
                 fields,
                 custom_id: None,
@@ -172,6 +282,28 @@ fn derive_inner_type(
     }
 }
 
+/// Returns the descriptor of the value field of the map entry message of a map
+/// field.
+fn map_value_field(field: &FieldDescriptor) -> Result<FieldDescriptor, anyhow::Error> {
+    match field.kind() {
+        Kind::Message(entry) => Ok(entry.map_entry_value_field()),
+        _ => bail!(
+            "internal error: protobuf map field {} is not a message",
+            field.name()
+        ),
+    }
+}
+
+/// Returns the descriptor of the `value` field of a well-known wrapper type.
+fn wrapper_value_field(message: &MessageDescriptor) -> Result<FieldDescriptor, anyhow::Error> {
+    message.get_field_by_name("value").ok_or_else(|| {
+        anyhow!(
+            "internal error: protobuf wrapper type {} missing value field",
+            message.full_name()
+        )
+    })
+}
+
 fn pack_message(packer: &mut RowPacker, message: &DynamicMessage) -> Result<(), anyhow::Error> {
     for field_desc in message.descriptor().fields() {
         if !message.has_field(&field_desc) {
@@ -181,7 +313,10 @@ fn pack_message(packer: &mut RowPacker, message: &DynamicMessage) -> Result<(),
                     field_desc.name()
                 );
             }
-            if field_desc.kind().as_message().is_some() && !field_desc.is_list() {
+            let is_singular_message = field_desc.kind().as_message().is_some()
+                && !field_desc.is_list()
+                && !field_desc.is_map();
+            if is_singular_message || field_desc.containing_oneof().is_some() {
                 packer.push(Datum::Null);
                 continue;
             }
@@ -223,7 +358,7 @@ fn pack_value(
             })?;
             packer.push(Datum::String(value.name()));
         }
-        Value::Message(m) => packer.push_list_with(|packer| pack_message(packer, m))?,
+        Value::Message(m) => pack_message_value(packer, m)?,
         Value::List(values) => {
             packer.push_list_with(|packer| {
                 for value in values {
@@ -232,10 +367,712 @@ fn pack_value(
                 Ok::<_, anyhow::Error>(())
             })?;
         }
-        Value::U32(_) | Value::U64(_) | Value::Map(_) => bail!(
+        Value::Map(entries) => {
+            let value_desc = map_value_field(field_desc)?;
+            // Materialize maps are sorted by key.
+            let mut entries: Vec<_> = entries
+                .iter()
+                .map(|(key, value)| (map_key_to_string(key), value))
+                .collect();
+            entries.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+            packer.push_dict_with(|packer| {
+                for (key, value) in &entries {
+                    packer.push(Datum::String(key));
+                    pack_value(packer, &value_desc, value)?;
+                }
+                Ok::<_, anyhow::Error>(())
+            })?;
+        }
+        Value::U32(_) | Value::U64(_) => bail!(
             "internal error: unexpected value while decoding protobuf message: {:?}",
             value
         ),
     }
     Ok(())
 }
+
+fn pack_message_value(
+    packer: &mut RowPacker,
+    message: &DynamicMessage,
+) -> Result<(), anyhow::Error> {
+    let descriptor = message.descriptor();
+    match WellKnownType::from_descriptor(&descriptor) {
+        Some(WellKnownType::Timestamp) => {
+            let (seconds, nanos) = seconds_and_nanos(message)?;
+            let nanos = u32::try_from(nanos).map_err(|_| {
+                anyhow!("error decoding protobuf: invalid Timestamp nanos {}", nanos)
+            })?;
+            // Materialize timestamps have microsecond precision.
+            let nanos = nanos / 1_000 * 1_000;
+            let ts = NaiveDateTime::from_timestamp_opt(seconds, nanos).ok_or_else(|| {
+                anyhow!(
+                    "error decoding protobuf: Timestamp out of range: {}s {}ns",
+                    seconds,
+                    nanos
+                )
+            })?;
+            packer.push(Datum::TimestampTz(DateTime::<Utc>::from_utc(ts, Utc)));
+        }
+        Some(WellKnownType::Duration) => {
+            let (seconds, nanos) = seconds_and_nanos(message)?;
+            let micros = seconds
+                .checked_mul(1_000_000)
+                .and_then(|micros| micros.checked_add(i64::from(nanos / 1_000)))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "error decoding protobuf: Duration out of range: {}s {}ns",
+                        seconds,
+                        nanos
+                    )
+                })?;
+            packer.push(Datum::Interval(Interval::new(0, 0, micros)));
+        }
+        Some(WellKnownType::Wrapper) => {
+            let value_field = wrapper_value_field(&descriptor)?;
+            let value = message.get_field(&value_field);
+            pack_value(packer, &value_field, &*value)?;
+        }
+        None => packer.push_list_with(|packer| pack_message(packer, message))?,
+    }
+    Ok(())
+}
+
+/// Extracts the `seconds` and `nanos` fields of a `google.protobuf.Timestamp`
+/// or `google.protobuf.Duration` message.
+fn seconds_and_nanos(message: &DynamicMessage) -> Result<(i64, i32), anyhow::Error> {
+    let seconds = message
+        .get_field_by_name("seconds")
+        .and_then(|v| v.as_i64())
+        .ok_or_else(|| {
+            anyhow!(
+                "internal error: protobuf {} missing seconds field",
+                message.descriptor().full_name()
+            )
+        })?;
+    let nanos = message
+        .get_field_by_name("nanos")
+        .and_then(|v| v.as_i32())
+        .ok_or_else(|| {
+            anyhow!(
+                "internal error: protobuf {} missing nanos field",
+                message.descriptor().full_name()
+            )
+        })?;
+    Ok((seconds, nanos))
+}
+
+fn map_key_to_string(key: &MapKey) -> String {
+    match key {
+        MapKey::Bool(b) => b.to_string(),
+        MapKey::I32(i) => i.to_string(),
+        MapKey::I64(i) => i.to_string(),
+        MapKey::U32(i) => i.to_string(),
+        MapKey::U64(i) => i.to_string(),
+        MapKey::String(s) => s.clone(),
+    }
+}
+
+/// Generates Protobuf schemas for the keys and values of a sink.
+///
+/// The generated schemas are `.proto` files in the `proto3` syntax. The
+/// top-level message is always the first message in the file, so that the
+/// Confluent message index array can be encoded as a single zero byte.
+pub struct ProtobufSchemaGenerator {
+    value_columns: Vec<(ColumnName, ColumnType)>,
+    value_schema: String,
+    key_info: Option<(Vec<(ColumnName, ColumnType)>, String)>,
+}
+
+impl ProtobufSchemaGenerator {
+    pub fn new(
+        value_message_name: Option<&str>,
+        key_desc: Option<RelationDesc>,
+        value_desc: RelationDesc,
+        debezium: bool,
+        include_transaction: bool,
+    ) -> Result<Self, anyhow::Error> {
+        let mut value_columns = column_names_and_types(value_desc);
+        if debezium {
+            value_columns = envelopes::dbz_envelope(value_columns);
+        }
+        if include_transaction {
+            envelopes::txn_metadata(&mut value_columns);
+        }
+        // The message name may be fully qualified, like `.pkg.Name`, in which
+        // case both schemas are generated in package `pkg`.
+        let (package, value_message_name) = match value_message_name {
+            Some(name) => split_message_name(name)?,
+            None => (None, "envelope"),
+        };
+        let value_schema = build_proto_schema(&value_columns, package, value_message_name)?;
+        let key_info = match key_desc {
+            Some(key_desc) => {
+                let key_columns = column_names_and_types(key_desc);
+                let key_schema = build_proto_schema(&key_columns, package, "row")?;
+                Some((key_columns, key_schema))
+            }
+            None => None,
+        };
+        Ok(ProtobufSchemaGenerator {
+            value_columns,
+            value_schema,
+            key_info,
+        })
+    }
+
+    pub fn value_schema(&self) -> &str {
+        &self.value_schema
+    }
+
+    pub fn key_schema(&self) -> Option<&str> {
+        self.key_info
+            .as_ref()
+            .map(|(_columns, schema)| schema.as_str())
+    }
+}
+
+impl fmt::Debug for ProtobufSchemaGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProtobufSchemaGenerator")
+            .field("value_schema", &self.value_schema)
+            .finish()
+    }
+}
+
+/// Manages encoding of Protobuf-encoded bytes in the Confluent wire format.
+pub struct ProtobufEncoder {
+    schema_generator: ProtobufSchemaGenerator,
+    key_schema_id: Option<i32>,
+    value_schema_id: i32,
+}
+
+impl fmt::Debug for ProtobufEncoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProtobufEncoder")
+            .field("value_schema", &self.schema_generator.value_schema)
+            .finish()
+    }
+}
+
+impl ProtobufEncoder {
+    pub fn new(
+        schema_generator: ProtobufSchemaGenerator,
+        key_schema_id: Option<i32>,
+        value_schema_id: i32,
+    ) -> Self {
+        ProtobufEncoder {
+            schema_generator,
+            key_schema_id,
+            value_schema_id,
+        }
+    }
+}
+
+impl Encode for ProtobufEncoder {
+    fn get_format_name(&self) -> &str {
+        "protobuf"
+    }
+
+    fn encode_key_unchecked(&self, row: Row) -> Vec<u8> {
+        let (columns, _schema) = self
+            .schema_generator
+            .key_info
+            .as_ref()
+            .expect("key schema must exist");
+        encode_message_unchecked(self.key_schema_id.unwrap(), row, columns)
+    }
+
+    fn encode_value_unchecked(&self, row: Row) -> Vec<u8> {
+        encode_message_unchecked(
+            self.value_schema_id,
+            row,
+            &self.schema_generator.value_columns,
+        )
+    }
+}
+
+/// Splits a Protobuf message name that may be fully qualified, like
+/// `.pkg.Name` or `pkg.Name`, into its package, if any, and its unqualified
+/// name, validating each component.
+fn split_message_name(name: &str) -> Result<(Option<&str>, &str), anyhow::Error> {
+    let name = name.strip_prefix('.').unwrap_or(name);
+    let (package, name) = match name.rsplit_once('.') {
+        Some((package, name)) => (Some(package), name),
+        None => (None, name),
+    };
+    for component in package.into_iter().flat_map(|package| package.split('.')) {
+        validate_proto_identifier(component, "package")?;
+    }
+    validate_proto_identifier(name, "message")?;
+    Ok((package, name))
+}
+
+/// Builds the text of a `.proto` file in `package` whose first message, named
+/// `message_name`, describes `columns`.
+fn build_proto_schema(
+    columns: &[(ColumnName, ColumnType)],
+    package: Option<&str>,
+    message_name: &str,
+) -> Result<String, anyhow::Error> {
+    let mut builder = ProtoSchemaBuilder::default();
+    builder.names_seen.insert(message_name.to_owned());
+    builder.add_message(message_name, columns)?;
+    let mut schema = String::from("syntax = \"proto3\";\n");
+    if let Some(package) = package {
+        writeln!(schema, "\npackage {};", package).expect("writing to string cannot fail");
+    }
+    for message in builder.messages {
+        schema.push('\n');
+        schema.push_str(&message);
+    }
+    Ok(schema)
+}
+
+#[derive(Default)]
+struct ProtoSchemaBuilder {
+    /// The definitions of the messages in the schema, in order.
+    messages: Vec<String>,
+    names_seen: HashSet<String>,
+    /// The names of the messages generated for records with custom names.
+    custom_messages: HashMap<GlobalId, String>,
+    name_idx: usize,
+}
+
+impl ProtoSchemaBuilder {
+    fn add_message(
+        &mut self,
+        name: &str,
+        columns: &[(ColumnName, ColumnType)],
+    ) -> Result<(), anyhow::Error> {
+        validate_proto_identifier(name, "message")?;
+        // Reserve the message's slot before generating its fields, so that
+        // a message always precedes the messages for its nested records.
+        let index = self.messages.len();
+        self.messages.push(String::new());
+        let mut message = format!("message {} {{\n", name);
+        for (i, (column_name, typ)) in columns.iter().enumerate() {
+            validate_proto_identifier(column_name.as_str(), "field")?;
+            let field_type = self.field_type(typ)?;
+            writeln!(message, "  {} {} = {};", field_type, column_name, i + 1)
+                .expect("writing to string cannot fail");
+        }
+        message.push_str("}\n");
+        self.messages[index] = message;
+        Ok(())
+    }
+
+    /// Returns the type of a field, including its label.
+    fn field_type(&mut self, typ: &ColumnType) -> Result<String, anyhow::Error> {
+        match &typ.scalar_type {
+            ty @ (ScalarType::Array(_) | ScalarType::Int2Vector | ScalarType::List { .. }) => {
+                let element_type = self.element_type(ty.unwrap_collection_element_type())?;
+                Ok(format!("repeated {}", element_type))
+            }
+            ScalarType::Map { value_type, .. } => {
+                let value_type = self.element_type(value_type)?;
+                Ok(format!("map<string, {}>", value_type))
+            }
+            // Message fields always track presence.
+            ty @ ScalarType::Record { .. } => self.element_type(ty),
+            ty if typ.nullable => Ok(format!("optional {}", self.element_type(ty)?)),
+            ty => self.element_type(ty),
+        }
+    }
+
+    /// Returns the type of a field, or of the elements of a repeated or map
+    /// field, without a label.
+    fn element_type(&mut self, typ: &ScalarType) -> Result<String, anyhow::Error> {
+        let ty = match typ {
+            ScalarType::Bool => "bool",
+            ScalarType::Int16 | ScalarType::Int32 => "int32",
+            ScalarType::Int64 => "int64",
+            ScalarType::PgLegacyChar
+            | ScalarType::Oid
+            | ScalarType::RegClass
+            | ScalarType::RegProc
+            | ScalarType::RegType => "uint32",
+            ScalarType::Float32 => "float",
+            ScalarType::Float64 => "double",
+            // Timestamps are encoded as microseconds since the Unix epoch, as
+            // in Avro sinks.
+            ScalarType::Timestamp | ScalarType::TimestampTz => "int64",
+            ScalarType::Bytes => "bytes",
+            ScalarType::Numeric { .. }
+            | ScalarType::Date
+            | ScalarType::Time
+            | ScalarType::Interval
+            | ScalarType::String
+            | ScalarType::Char { .. }
+            | ScalarType::VarChar { .. }
+            | ScalarType::Jsonb
+            | ScalarType::Uuid => "string",
+            ScalarType::Array(_)
+            | ScalarType::Int2Vector
+            | ScalarType::List { .. }
+            | ScalarType::Map { .. } => {
+                bail!("Protobuf sinks do not support nested lists, arrays or maps")
+            }
+            ScalarType::Record { fields, custom_id } => {
+                if let Some(name) = custom_id.and_then(|id| self.custom_messages.get(&id)) {
+                    return Ok(name.clone());
+                }
+                let base = match custom_id.and_then(|id| ENVELOPE_CUSTOM_NAMES.get(&id)) {
+                    Some(name) => name.clone(),
+                    None => {
+                        self.name_idx += 1;
+                        format!("record{}", self.name_idx - 1)
+                    }
+                };
+                let mut name = base.clone();
+                let mut i = 1;
+                while !self.names_seen.insert(name.clone()) {
+                    name = format!("{}{}", base, i);
+                    i += 1;
+                }
+                if let Some(id) = custom_id {
+                    self.custom_messages.insert(*id, name.clone());
+                }
+                self.add_message(&name, fields)?;
+                return Ok(name);
+            }
+        };
+        Ok(ty.into())
+    }
+}
+
+/// Validates that `name` is a valid Protobuf identifier, i.e., that it starts
+/// with a letter and contains only letters, digits and underscores.
+fn validate_proto_identifier(name: &str, what: &str) -> Result<(), anyhow::Error> {
+    let mut chars = name.chars();
+    let valid = matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        bail!(
+            "{} is not a valid Protobuf {} name; rename it to contain only letters, digits and \
+             underscores, starting with a letter",
+            name.quoted(),
+            what
+        );
+    }
+    Ok(())
+}
+
+fn encode_message_unchecked(
+    schema_id: i32,
+    row: Row,
+    columns: &[(ColumnName, ColumnType)],
+) -> Vec<u8> {
+    let mut buf = vec![];
+    // The first byte is a magic byte (0) that indicates the Confluent
+    // serialization format version, and the next four bytes are a 32-bit
+    // schema ID. They are followed by the message index array, for which a
+    // single zero byte is the shorthand for the first message in the schema.
+    //
+    // https://docs.confluent.io/platform/current/schema-registry/serdes-develop/index.html#wire-format
+    buf.write_u8(0).expect("writing to vec cannot fail");
+    buf.write_i32::<NetworkEndian>(schema_id)
+        .expect("writing to vec cannot fail");
+    buf.write_u8(0).expect("writing to vec cannot fail");
+    encode_datums_as_protobuf(&mut buf, row.iter(), columns);
+    buf
+}
+
+/// Encodes a sequence of `Datum` as the fields of a Protobuf message, using
+/// the supplied column types. The field numbers are the 1-based positions of
+/// the columns, as in the schemas produced by [`ProtobufSchemaGenerator`].
+pub fn encode_datums_as_protobuf<'a, I>(
+    buf: &mut Vec<u8>,
+    datums: I,
+    names_types: &[(ColumnName, ColumnType)],
+) where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    for (i, (datum, (_name, typ))) in datums.into_iter().zip(names_types).enumerate() {
+        let tag = u32::try_from(i + 1).expect("too many columns for a Protobuf message");
+        encode_field(buf, tag, datum, &typ.scalar_type);
+    }
+}
+
+fn encode_field(buf: &mut Vec<u8>, tag: u32, datum: Datum, typ: &ScalarType) {
+    // Null values are encoded by omitting the field. Null lists and maps are
+    // thus indistinguishable from empty ones.
+    if datum.is_null() {
+        return;
+    }
+    match typ {
+        ScalarType::Array(_) | ScalarType::Int2Vector | ScalarType::List { .. } => {
+            let elements = match typ {
+                ScalarType::List { .. } => datum.unwrap_list(),
+                _ => datum.unwrap_array().elements(),
+            };
+            let element_type = typ.unwrap_collection_element_type();
+            for element in elements.iter() {
+                encode_element(buf, tag, element, element_type);
+            }
+        }
+        ScalarType::Map { value_type, .. } => {
+            for (key, value) in datum.unwrap_map().iter() {
+                let mut entry = vec![];
+                prost::encoding::string::encode(1, &key.to_owned(), &mut entry);
+                // A missing value in a map entry decodes as the default value.
+                if !value.is_null() {
+                    encode_element(&mut entry, 2, value, value_type);
+                }
+                encode_length_delimited(buf, tag, &entry);
+            }
+        }
+        _ => encode_element(buf, tag, datum, typ),
+    }
+}
+
+/// Encodes a single value of a field, or a single element of a repeated field.
+///
+/// Null elements of repeated fields cannot be represented in Protobuf, and are
+/// encoded as the default value of the element type.
+fn encode_element(buf: &mut Vec<u8>, tag: u32, datum: Datum, typ: &ScalarType) {
+    use prost::encoding;
+
+    if datum.is_null() {
+        let wire_type = match typ {
+            ScalarType::Float32 => WireType::ThirtyTwoBit,
+            ScalarType::Float64 => WireType::SixtyFourBit,
+            ScalarType::Bool
+            | ScalarType::Int16
+            | ScalarType::Int32
+            | ScalarType::Int64
+            | ScalarType::PgLegacyChar
+            | ScalarType::Oid
+            | ScalarType::RegClass
+            | ScalarType::RegProc
+            | ScalarType::RegType
+            | ScalarType::Timestamp
+            | ScalarType::TimestampTz => WireType::Varint,
+            _ => WireType::LengthDelimited,
+        };
+        encoding::encode_key(tag, wire_type, buf);
+        match wire_type {
+            WireType::ThirtyTwoBit => buf.extend_from_slice(&[0; 4]),
+            WireType::SixtyFourBit => buf.extend_from_slice(&[0; 8]),
+            _ => encoding::encode_varint(0, buf),
+        }
+        return;
+    }
+
+    match typ {
+        ScalarType::Bool => encoding::bool::encode(tag, &datum.unwrap_bool(), buf),
+        ScalarType::Int16 => encoding::int32::encode(tag, &i32::from(datum.unwrap_int16()), buf),
+        ScalarType::Int32 => encoding::int32::encode(tag, &datum.unwrap_int32(), buf),
+        ScalarType::Int64 => encoding::int64::encode(tag, &datum.unwrap_int64(), buf),
+        ScalarType::PgLegacyChar => {
+            encoding::uint32::encode(tag, &u32::from(datum.unwrap_uint8()), buf)
+        }
+        ScalarType::Oid | ScalarType::RegClass | ScalarType::RegProc | ScalarType::RegType => {
+            encoding::uint32::encode(tag, &datum.unwrap_uint32(), buf)
+        }
+        ScalarType::Float32 => encoding::float::encode(tag, &datum.unwrap_float32(), buf),
+        ScalarType::Float64 => encoding::double::encode(tag, &datum.unwrap_float64(), buf),
+        ScalarType::Timestamp => {
+            let ts = datum.unwrap_timestamp();
+            encoding::int64::encode(tag, &timestamp_micros(&ts), buf)
+        }
+        ScalarType::TimestampTz => {
+            let ts = datum.unwrap_timestamptz().naive_utc();
+            encoding::int64::encode(tag, &timestamp_micros(&ts), buf)
+        }
+        ScalarType::Bytes => encoding::bytes::encode(tag, &datum.unwrap_bytes().to_vec(), buf),
+        ScalarType::String | ScalarType::VarChar { .. } => {
+            encoding::string::encode(tag, &datum.unwrap_str().to_owned(), buf)
+        }
+        ScalarType::Char { length } => {
+            let s = mz_repr::adt::char::format_str_pad(datum.unwrap_str(), *length);
+            encoding::string::encode(tag, &s, buf)
+        }
+        ScalarType::Numeric { .. } => {
+            let s = datum.unwrap_numeric().0.to_standard_notation_string();
+            encoding::string::encode(tag, &s, buf)
+        }
+        ScalarType::Date => encoding::string::encode(tag, &datum.unwrap_date().to_string(), buf),
+        ScalarType::Time => encoding::string::encode(tag, &datum.unwrap_time().to_string(), buf),
+        ScalarType::Interval => {
+            encoding::string::encode(tag, &datum.unwrap_interval().to_string(), buf)
+        }
+        ScalarType::Jsonb => {
+            let s = JsonbRef::from_datum(datum).to_string();
+            encoding::string::encode(tag, &s, buf)
+        }
+        ScalarType::Uuid => encoding::string::encode(tag, &datum.unwrap_uuid().to_string(), buf),
+        ScalarType::Record { fields, .. } => {
+            let mut message = vec![];
+            encode_datums_as_protobuf(&mut message, datum.unwrap_list().iter(), fields);
+            encode_length_delimited(buf, tag, &message);
+        }
+        ScalarType::Array(_)
+        | ScalarType::Int2Vector
+        | ScalarType::List { .. }
+        | ScalarType::Map { .. } => {
+            unreachable!("nested lists and maps rejected by ProtobufSchemaGenerator")
+        }
+    }
+}
+
+fn encode_length_delimited(buf: &mut Vec<u8>, tag: u32, bytes: &[u8]) {
+    prost::encoding::encode_key(tag, WireType::LengthDelimited, buf);
+    prost::encoding::encode_varint(u64::cast_from(bytes.len()), buf);
+    buf.extend_from_slice(bytes);
+}
+
+fn timestamp_micros(ts: &NaiveDateTime) -> i64 {
+    ts.timestamp() * 1_000_000 + i64::from(ts.timestamp_subsec_micros())
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+
+    static FILE_DESCRIPTOR_SET: &[u8] =
+        include_bytes!(concat!(env!("OUT_DIR"), "/file_descriptor_set.pb"));
+
+    #[test]
+    fn test_decoder_message_indexes() {
+        // `benchmark.Connector` is the second message in its file.
+        let descriptors =
+            DecodedDescriptors::from_bytes(FILE_DESCRIPTOR_SET, ".benchmark.Connector".into())
+                .unwrap();
+        assert_eq!(descriptors.message_indexes, vec![1]);
+        let mut decoder = Decoder::new(descriptors, true).unwrap();
+
+        // A count of 1 followed by the zigzag-encoded index 1.
+        assert!(block_on(decoder.decode(&[0, 0, 0, 0, 7, 2, 2])).is_ok());
+
+        // The first message is encoded as a single zero byte.
+        let err = block_on(decoder.decode(&[0, 0, 0, 0, 7, 0])).unwrap_err();
+        assert!(
+            err.to_string().contains("message indexes [0]"),
+            "unexpected error: {}",
+            err
+        );
+        // A count of 2 followed by the zigzag-encoded indexes 1 and 0.
+        assert!(block_on(decoder.decode(&[0, 0, 0, 0, 7, 4, 2, 0])).is_err());
+    }
+
+    #[test]
+    fn test_protobuf_schema_generator() {
+        let desc = RelationDesc::empty()
+            .with_column("a", ScalarType::Int32.nullable(false))
+            .with_column("b", ScalarType::String.nullable(true));
+
+        let generator =
+            ProtobufSchemaGenerator::new(None, Some(desc.clone()), desc.clone(), true, true)
+                .unwrap();
+        assert_eq!(
+            generator.value_schema(),
+            r#"syntax = "proto3";
+
+message envelope {
+  row before = 1;
+  row after = 2;
+  transaction transaction = 3;
+}
+
+message row {
+  int32 a = 1;
+  optional string b = 2;
+}
+
+message transaction {
+  string id = 1;
+}
+"#
+        );
+        assert_eq!(
+            generator.key_schema(),
+            Some(
+                r#"syntax = "proto3";
+
+message row {
+  int32 a = 1;
+  optional string b = 2;
+}
+"#
+            )
+        );
+
+        let encoder = ProtobufEncoder::new(generator, Some(6), 7);
+        let row = Row::pack_slice(&[Datum::Int32(1), Datum::String("x")]);
+        assert_eq!(
+            encoder.encode_key_unchecked(row),
+            [0, 0, 0, 0, 6, 0, 0x08, 0x01, 0x12, 0x01, b'x']
+        );
+        let row = Row::pack_slice(&[Datum::Int32(1), Datum::Null]);
+        assert_eq!(
+            encoder.encode_key_unchecked(row),
+            [0, 0, 0, 0, 6, 0, 0x08, 0x01]
+        );
+
+        let desc = RelationDesc::empty().with_column("a b", ScalarType::Int32.nullable(false));
+        assert!(ProtobufSchemaGenerator::new(None, None, desc, false, false).is_err());
+    }
+
+    #[test]
+    fn test_protobuf_schema_generator_message_name() {
+        let desc = RelationDesc::empty().with_column("a", ScalarType::Int32.nullable(false));
+
+        // Fully qualified names put both schemas in the message's package.
+        for name in [".pkg.sub.Input", "pkg.sub.Input"] {
+            let generator = ProtobufSchemaGenerator::new(
+                Some(name),
+                Some(desc.clone()),
+                desc.clone(),
+                false,
+                false,
+            )
+            .unwrap();
+            assert_eq!(
+                generator.value_schema(),
+                r#"syntax = "proto3";
+
+package pkg.sub;
+
+message Input {
+  int32 a = 1;
+}
+"#
+            );
+            assert_eq!(
+                generator.key_schema(),
+                Some(
+                    r#"syntax = "proto3";
+
+package pkg.sub;
+
+message row {
+  int32 a = 1;
+}
+"#
+                )
+            );
+        }
+
+        let generator =
+            ProtobufSchemaGenerator::new(Some(".Input"), None, desc.clone(), false, false).unwrap();
+        assert_eq!(
+            generator.value_schema(),
+            r#"syntax = "proto3";
+
+message Input {
+  int32 a = 1;
+}
+"#
+        );
+
+        for name in ["", ".", "pkg.", "pkg..Input", "1pkg.Input", "pkg.In put"] {
+            assert!(
+                ProtobufSchemaGenerator::new(Some(name), None, desc.clone(), false, false).is_err(),
+                "{:?}",
+                name
+            );
+        }
+    }
+}
@@ -22,7 +22,7 @@ use tokio::runtime::Runtime;
 use uuid::Uuid;
 
 use mz_ore::task::RuntimeExt;
-use mz_persist::indexed::encoding::BlobTraceBatchPart;
+use mz_persist::indexed::encoding::{BlobTraceBatchPart, PartCompression};
 use mz_persist::location::{Atomicity, Blob, Consensus, ExternalError, SeqNo, VersionedData};
 use mz_persist::workload::{self, DataGenerator};
 use mz_persist_client::ShardId;
@@ -203,6 +203,8 @@ pub fn bench_encode_batch(name: &str, throughput: bool, c: &mut Criterion, data:
         ),
         index: 0,
         updates: data.batches().collect::<Vec<_>>(),
+        key_columns: Vec::new(),
+        compression: PartCompression::Uncompressed,
     };
 
     g.bench_function(BenchmarkId::new("trace", data.goodput_pretty()), |b| {
@@ -21,18 +21,20 @@ use differential_dataflow::lattice::Lattice;
 use differential_dataflow::trace::Description;
 use mz_ore::cast::CastFrom;
 use mz_persist::indexed::columnar::{ColumnarRecords, ColumnarRecordsVecBuilder};
-use mz_persist::indexed::encoding::BlobTraceBatchPart;
+use mz_persist::indexed::encoding::{BlobTraceBatchPart, PartCompression};
 use mz_persist::location::{Atomicity, Blob};
+use mz_persist_types::columnar::{encode_columns, ColumnData, Schema};
 use mz_persist_types::{Codec, Codec64};
 use timely::progress::{Antichain, Timestamp};
 use timely::PartialOrder;
 use tokio::task::JoinHandle;
-use tracing::{debug_span, instrument, trace_span, warn, Instrument};
+use tracing::{debug_span, error, instrument, trace_span, warn, Instrument};
 use uuid::Uuid;
 
 use crate::error::InvalidUsage;
 use crate::r#impl::machine::retry_external;
 use crate::r#impl::metrics::{BatchWriteMetrics, Metrics};
+use crate::stats::PartStats;
 use crate::{PersistConfig, ShardId};
 
 /// A handle to a batch of updates that has been written to blob storage but
@@ -56,6 +58,9 @@ where
     /// The number of updates in this batch.
     pub(crate) num_updates: usize,
 
+    /// Statistics about the updates in each of the blobs in `blob_keys`.
+    pub(crate) stats: Vec<PartStats>,
+
     /// Handle to the [Blob] that the blobs of this batch were uploaded to.
     _blob: Arc<dyn Blob + Send + Sync>,
 
@@ -92,12 +97,14 @@ where
         desc: Description<T>,
         blob_keys: Vec<String>,
         num_updates: usize,
+        stats: Vec<PartStats>,
     ) -> Self {
         Self {
             desc,
             blob_keys,
             shard_id,
             num_updates,
+            stats,
             _blob: blob,
             _phantom: PhantomData,
         }
@@ -151,6 +158,7 @@ where
             desc: self.desc.clone(),
             keys: self.blob_keys.clone(),
             len: self.num_updates,
+            stats: self.stats.clone(),
         };
         self.mark_consumed();
         ret
@@ -198,14 +206,17 @@ where
         lower: Antichain<T>,
         blob: Arc<dyn Blob + Send + Sync>,
         shard_id: ShardId,
+        key_columns: Option<Arc<dyn PartKeyColumns>>,
     ) -> Self {
         let parts = BatchParts::new(
             cfg.batch_builder_max_outstanding_parts,
+            cfg.part_compression,
             Arc::clone(&metrics),
             shard_id,
             lower.clone(),
             Arc::clone(&blob),
             &metrics.user,
+            key_columns,
         );
         Self {
             size_hint,
@@ -256,7 +267,7 @@ where
             assert!(part.len() > 0);
             self.parts.write(part, upper.clone(), since.clone()).await;
         }
-        let keys = self.parts.finish().await;
+        let (keys, stats) = self.parts.finish().await;
 
         let desc = Description::new(self.lower, upper, since);
         let batch = Batch::new(
@@ -265,6 +276,7 @@ where
             desc,
             keys,
             self.num_updates,
+            stats,
         );
 
         Ok(batch)
@@ -326,31 +338,100 @@ where
     }
 }
 
+/// Converts between the encoded keys of a shard's updates and the typed key
+/// columns that parts store them as, without knowing the type of the keys.
+pub(crate) trait PartKeyColumns: Debug + Send + Sync {
+    /// Returns the typed columns to store the keys of `updates` as, or None if
+    /// they should be stored encoded with their [Codec].
+    fn key_columns(
+        &self,
+        updates: &ColumnarRecords,
+    ) -> Option<Result<Vec<(String, ColumnData)>, String>>;
+
+    /// Encodes the key at `idx` of the typed key `columns` of a part with its
+    /// [Codec] into `buf`.
+    fn encode_key(
+        &self,
+        columns: &[ColumnData],
+        idx: usize,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), String>;
+}
+
+/// A [PartKeyColumns] for keys of type `K`, which stores them as the columns
+/// of `schema`, if there is one.
+pub(crate) struct SchemaKeyColumns<K> {
+    schema: Option<Arc<dyn Schema<K>>>,
+}
+
+impl<K> SchemaKeyColumns<K> {
+    pub(crate) fn new(schema: Option<Arc<dyn Schema<K>>>) -> Self {
+        SchemaKeyColumns { schema }
+    }
+}
+
+impl<K> Debug for SchemaKeyColumns<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SchemaKeyColumns")
+            .field("schema", &self.schema)
+            .finish()
+    }
+}
+
+impl<K: Codec> PartKeyColumns for SchemaKeyColumns<K> {
+    fn key_columns(
+        &self,
+        updates: &ColumnarRecords,
+    ) -> Option<Result<Vec<(String, ColumnData)>, String>> {
+        let schema = self.schema.as_ref()?;
+        let keys = updates
+            .iter()
+            .map(|((k, _v), _t, _d)| K::decode(k))
+            .collect::<Result<Vec<_>, _>>();
+        Some(keys.and_then(|keys| encode_columns(schema.as_ref(), &keys)))
+    }
+
+    fn encode_key(
+        &self,
+        columns: &[ColumnData],
+        idx: usize,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), String> {
+        K::decode_columns(columns, idx)?.encode(buf);
+        Ok(())
+    }
+}
+
 // TODO: If this is dropped, cancel (and delete?) any writing parts and delete
 // any finished ones.
 #[derive(Debug)]
 pub(crate) struct BatchParts<T> {
     max_outstanding: usize,
+    compression: PartCompression,
     metrics: Arc<Metrics>,
     shard_id: ShardId,
     lower: Antichain<T>,
     blob: Arc<dyn Blob + Send + Sync>,
-    writing_parts: VecDeque<(String, JoinHandle<()>)>,
-    finished_parts: Vec<String>,
+    writing_parts: VecDeque<(String, JoinHandle<PartStats>)>,
+    finished_parts: Vec<(String, PartStats)>,
     batch_metrics: BatchWriteMetrics,
+    key_columns: Option<Arc<dyn PartKeyColumns>>,
 }
 
 impl<T: Timestamp + Codec64> BatchParts<T> {
     pub(crate) fn new(
         max_outstanding: usize,
+        compression: PartCompression,
         metrics: Arc<Metrics>,
         shard_id: ShardId,
         lower: Antichain<T>,
         blob: Arc<dyn Blob + Send + Sync>,
         batch_metrics: &BatchWriteMetrics,
+        key_columns: Option<Arc<dyn PartKeyColumns>>,
     ) -> Self {
         BatchParts {
             max_outstanding,
+            compression,
             metrics,
             shard_id,
             lower,
@@ -358,6 +439,7 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
             writing_parts: VecDeque::new(),
             finished_parts: Vec::new(),
             batch_metrics: batch_metrics.clone(),
+            key_columns,
         }
     }
 
@@ -371,6 +453,8 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
         let metrics = Arc::clone(&self.metrics);
         let blob = Arc::clone(&self.blob);
         let batch_metrics = self.batch_metrics.clone();
+        let key_columns = self.key_columns.clone();
+        let compression = self.compression;
         let key = Uuid::new_v4().to_string();
         let blob_key = key.clone();
         let index = u64::cast_from(self.finished_parts.len() + self.writing_parts.len());
@@ -405,22 +489,52 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
                 );
 
                 let goodbytes = updates.goodbytes();
-                let batch = BlobTraceBatchPart {
+                let mut batch = BlobTraceBatchPart {
                     desc: encoded_desc.clone(),
                     updates: vec![updates],
                     index,
+                    key_columns: Vec::new(),
+                    compression,
                 };
 
                 let start = Instant::now();
-                let buf = mz_ore::task::spawn_blocking(
+                let (buf, stats) = mz_ore::task::spawn_blocking(
                     || "batch::encode_part",
                     move || {
+                        let columns = key_columns.as_ref().and_then(|key_columns| {
+                            batch
+                                .updates
+                                .iter()
+                                .map(|updates| key_columns.key_columns(updates))
+                                .collect::<Option<Result<Vec<_>, _>>>()
+                        });
+                        match columns {
+                            // The typed columns replace the encoded keys.
+                            Some(Ok(columns)) => {
+                                batch.updates =
+                                    batch.updates.iter().map(|x| x.without_keys()).collect();
+                                batch.key_columns = columns;
+                            }
+                            // Keys the writer's own schema can't encode are a
+                            // bug in the writer, but the encoded keys are just
+                            // as readable, so don't lose the data over it.
+                            Some(Err(err)) => {
+                                error!("writing part with encoded keys: {}", err)
+                            }
+                            None => {}
+                        }
+
+                        let stats = match (batch.updates.as_slice(), batch.key_columns.as_slice()) {
+                            ([updates], [columns]) => PartStats::new(updates.len(), columns),
+                            _ => PartStats::default(),
+                        };
+
                         let mut buf = Vec::new();
                         batch.encode(&mut buf);
 
                         // Drop batch as soon as we can to reclaim its memory.
                         drop(batch);
-                        Bytes::from(buf)
+                        (Bytes::from(buf), stats)
                     },
                 )
                 .instrument(debug_span!("batch::encode_part"))
@@ -443,6 +557,10 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
                 .await;
                 batch_metrics.bytes.inc_by(u64::cast_from(payload_len));
                 batch_metrics.goodbytes.inc_by(u64::cast_from(goodbytes));
+                metrics
+                    .compression
+                    .observe(compression, goodbytes, payload_len);
+                stats
             }
             .instrument(write_span),
         );
@@ -453,30 +571,30 @@ impl<T: Timestamp + Codec64> BatchParts<T> {
                 .writing_parts
                 .pop_front()
                 .expect("pop failed when len was just > some usize");
-            let () = match handle
+            let stats = match handle
                 .instrument(debug_span!("batch::max_outstanding"))
                 .await
             {
-                Ok(()) => (),
-                Err(err) if err.is_cancelled() => (),
+                Ok(stats) => stats,
+                Err(err) if err.is_cancelled() => PartStats::default(),
                 Err(err) => panic!("part upload task failed: {}", err),
             };
-            self.finished_parts.push(key);
+            self.finished_parts.push((key, stats));
         }
     }
 
     #[instrument(level = "debug", name = "batch::finish_upload", skip_all, fields(shard = %self.shard_id))]
-    pub(crate) async fn finish(self) -> Vec<String> {
-        let mut keys = self.finished_parts;
+    pub(crate) async fn finish(self) -> (Vec<String>, Vec<PartStats>) {
+        let mut parts = self.finished_parts;
         for (key, handle) in self.writing_parts {
-            let () = match handle.await {
-                Ok(()) => (),
-                Err(err) if err.is_cancelled() => (),
+            let stats = match handle.await {
+                Ok(stats) => stats,
+                Err(err) if err.is_cancelled() => PartStats::default(),
                 Err(err) => panic!("part upload task failed: {}", err),
             };
-            keys.push(key);
+            parts.push((key, stats));
         }
-        keys
+        parts.into_iter().unzip()
     }
 }
 
@@ -15,8 +15,9 @@ use std::sync::Arc;
 
 use mz_ore::metrics::MetricsRegistry;
 use mz_persist::cfg::{BlobConfig, ConsensusConfig};
+use mz_persist::disk_cache::DiskCacheBlob;
 use mz_persist::location::{Blob, Consensus, ExternalError};
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::r#impl::machine::retry_external;
 use crate::r#impl::metrics::{Metrics, MetricsBlob, MetricsConsensus};
@@ -81,11 +82,27 @@ impl PersistClientCache {
                     blob.clone().open()
                 })
                 .await;
+                let blob = Arc::new(MetricsBlob::new(blob, Arc::clone(&self.metrics)))
+                    as Arc<dyn Blob + Send + Sync>;
+                let blob = match self.cfg.blob_cache.as_ref() {
+                    Some(cache_cfg) => {
+                        let cache_cfg = cache_cfg.for_location(x.key());
+                        let metrics = self.metrics.blob_cache.clone();
+                        // The cache is purely an optimization, so fall back
+                        // to the uncached blob if it can't be opened.
+                        match DiskCacheBlob::open(cache_cfg, Arc::clone(&blob), metrics).await {
+                            Ok(cache) => Arc::new(cache) as Arc<dyn Blob + Send + Sync>,
+                            Err(err) => {
+                                warn!("failed to open blob cache: {}", err);
+                                blob
+                            }
+                        }
+                    }
+                    None => blob,
+                };
                 Arc::clone(x.insert(blob))
             }
         };
-        let blob = Arc::new(MetricsBlob::new(blob, Arc::clone(&self.metrics)))
-            as Arc<dyn Blob + Send + Sync>;
         let consensus = match self.consensus_by_uri.entry(location.consensus_uri) {
             Entry::Occupied(x) => Arc::clone(x.get()),
             Entry::Vacant(x) => {
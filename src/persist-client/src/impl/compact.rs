@@ -24,7 +24,7 @@ use timely::PartialOrder;
 use tokio::runtime::Handle;
 use tracing::{debug_span, warn, Instrument, Span};
 
-use crate::batch::BatchParts;
+use crate::batch::{BatchParts, PartKeyColumns};
 use crate::r#impl::machine::Machine;
 use crate::r#impl::state::HollowBatch;
 use crate::r#impl::trace::FueledMergeRes;
@@ -64,15 +64,22 @@ pub struct Compactor {
     cfg: PersistConfig,
     blob: Arc<dyn Blob + Send + Sync>,
     metrics: Arc<Metrics>,
+    key_columns: Option<Arc<dyn PartKeyColumns>>,
 }
 
 impl Compactor {
-    pub fn new(
+    pub(crate) fn new(
         cfg: PersistConfig,
         blob: Arc<dyn Blob + Send + Sync>,
         metrics: Arc<Metrics>,
+        key_columns: Option<Arc<dyn PartKeyColumns>>,
     ) -> Self {
-        Compactor { cfg, blob, metrics }
+        Compactor {
+            cfg,
+            blob,
+            metrics,
+            key_columns,
+        }
     }
 
     pub fn compact_and_apply_background<K, V, T, D>(
@@ -103,6 +110,7 @@ impl Compactor {
         let cfg = self.cfg.clone();
         let blob = Arc::clone(&self.blob);
         let metrics = Arc::clone(&self.metrics);
+        let key_columns = self.key_columns.clone();
         let mut machine = machine.clone();
 
         // Spawn compaction in a background task, so the write that triggered it
@@ -116,9 +124,15 @@ impl Compactor {
             async move {
                 metrics.compaction.started.inc();
                 let start = Instant::now();
-                let res =
-                    Self::compact::<T, D>(cfg, Handle::current(), blob, Arc::clone(&metrics), req)
-                        .await;
+                let res = Self::compact::<T, D>(
+                    cfg,
+                    Handle::current(),
+                    blob,
+                    Arc::clone(&metrics),
+                    key_columns,
+                    req,
+                )
+                .await;
                 metrics
                     .compaction
                     .seconds
@@ -145,11 +159,12 @@ impl Compactor {
         );
     }
 
-    pub async fn compact<T, D>(
+    pub(crate) async fn compact<T, D>(
         cfg: PersistConfig,
         handle: Handle,
         blob: Arc<dyn Blob + Send + Sync>,
         metrics: Arc<Metrics>,
+        key_columns: Option<Arc<dyn PartKeyColumns>>,
         req: CompactReq<T>,
     ) -> Result<CompactRes<T>, anyhow::Error>
     where
@@ -161,11 +176,13 @@ impl Compactor {
 
             let mut parts = BatchParts::new(
                 cfg.batch_builder_max_outstanding_parts,
+                cfg.part_compression,
                 Arc::clone(&metrics),
                 req.shard_id,
                 req.desc.lower().clone(),
                 Arc::clone(&blob),
                 &metrics.compaction.batch,
+                key_columns.clone(),
             );
 
             // TODO: Do this in a bounded amount of memory. The pattern for how
@@ -180,8 +197,9 @@ impl Compactor {
                         &req.shard_id,
                         blob.as_ref(),
                         &metrics,
-                        &key,
+                        key,
                         &part.desc,
+                        key_columns.as_deref(),
                         |k, v, mut t, d| {
                             t.advance_by(req.desc.since().borrow());
                             let d = D::decode(d);
@@ -213,13 +231,14 @@ impl Compactor {
                     req.desc.since().clone(),
                 ));
             }
-            let keys = handle.block_on(parts.finish());
+            let (keys, stats) = handle.block_on(parts.finish());
 
             Ok(CompactRes {
                 output: HollowBatch {
                     desc: req.desc,
                     keys,
                     len,
+                    stats,
                 },
             })
         };
@@ -312,6 +331,7 @@ mod tests {
             Handle::current(),
             Arc::clone(&write.blob),
             Arc::clone(&write.metrics),
+            None,
             req.clone(),
         )
         .await
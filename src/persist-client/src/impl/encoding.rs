@@ -12,6 +12,7 @@ use std::marker::PhantomData;
 
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::trace::Description;
+use mz_persist_types::columnar::{ColumnBounds, ColumnStats};
 use mz_persist_types::{Codec, Codec64};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use prost::Message;
@@ -22,12 +23,15 @@ use uuid::Uuid;
 
 use crate::error::CodecMismatch;
 use crate::r#impl::state::{
-    HollowBatch, ProtoHollowBatch, ProtoHollowBatchPart, ProtoReader, ProtoSnapshotSplit,
-    ProtoStateRollup, ProtoTrace, ProtoU64Antichain, ProtoU64Description, ProtoWriter,
-    ReadCapability, State, StateCollections, WriterState,
+    proto_column_stats, HollowBatch, ProtoBoolBounds, ProtoColumnStats, ProtoF32Bounds,
+    ProtoF64Bounds, ProtoHollowBatch, ProtoHollowBatchPart, ProtoIntBounds, ProtoPartStats,
+    ProtoReader, ProtoSnapshotSplit, ProtoStateRollup, ProtoStringBounds, ProtoTrace,
+    ProtoU64Antichain, ProtoU64Description, ProtoWriter, ReadCapability, State, StateCollections,
+    WriterState,
 };
 use crate::r#impl::trace::Trace;
 use crate::read::{ReaderId, SnapshotSplit};
+use crate::stats::PartStats;
 use crate::{ShardId, WriterId};
 
 pub(crate) fn parse_id(id_prefix: char, id_type: &str, encoded: &str) -> Result<[u8; 16], String> {
@@ -275,18 +279,115 @@ impl<T: Timestamp + Codec64> RustType<ProtoHollowBatch> for HollowBatch<T> {
             desc: Some(self.desc.into_proto()),
             keys: self.keys.into_proto(),
             len: self.len.into_proto(),
+            stats: self.stats.into_proto(),
         }
     }
 
     fn from_proto(proto: ProtoHollowBatch) -> Result<Self, TryFromProtoError> {
-        Ok(HollowBatch {
+        let batch = HollowBatch {
             desc: proto.desc.into_rust_if_some("desc")?,
             keys: proto.keys.into_rust()?,
             len: proto.len.into_rust()?,
+            stats: proto.stats.into_rust()?,
+        };
+        if !batch.stats.is_empty() && batch.stats.len() != batch.keys.len() {
+            return Err(TryFromProtoError::InvalidPersistState(format!(
+                "invalid ProtoHollowBatch: {} keys but {} part stats",
+                batch.keys.len(),
+                batch.stats.len()
+            )));
+        }
+        Ok(batch)
+    }
+}
+
+impl RustType<ProtoPartStats> for PartStats {
+    fn into_proto(&self) -> ProtoPartStats {
+        ProtoPartStats {
+            len: self.len.into_proto(),
+            key: self
+                .key
+                .iter()
+                .map(|(name, stats)| column_stats_into_proto(name, stats))
+                .collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoPartStats) -> Result<Self, TryFromProtoError> {
+        let key = proto
+            .key
+            .into_iter()
+            .map(column_stats_from_proto)
+            .collect::<Result<_, _>>()?;
+        Ok(PartStats {
+            len: proto.len.into_rust()?,
+            key,
         })
     }
 }
 
+fn column_stats_into_proto(name: &str, stats: &ColumnStats) -> ProtoColumnStats {
+    use proto_column_stats::Bounds;
+    let int = |lower: i64, upper: i64| ProtoIntBounds { lower, upper };
+    let bounds = stats.bounds.as_ref().map(|bounds| match bounds {
+        ColumnBounds::Bool(lower, upper) => Bounds::Bool(ProtoBoolBounds {
+            lower: *lower,
+            upper: *upper,
+        }),
+        ColumnBounds::Int16(lower, upper) => {
+            Bounds::Int16(int(i64::from(*lower), i64::from(*upper)))
+        }
+        ColumnBounds::Int32(lower, upper) => {
+            Bounds::Int32(int(i64::from(*lower), i64::from(*upper)))
+        }
+        ColumnBounds::Int64(lower, upper) => Bounds::Int64(int(*lower, *upper)),
+        ColumnBounds::Float32(lower, upper) => Bounds::Float32(ProtoF32Bounds {
+            lower: *lower,
+            upper: *upper,
+        }),
+        ColumnBounds::Float64(lower, upper) => Bounds::Float64(ProtoF64Bounds {
+            lower: *lower,
+            upper: *upper,
+        }),
+        ColumnBounds::String(lower, upper) => Bounds::String(ProtoStringBounds {
+            lower: lower.clone(),
+            upper: upper.clone(),
+        }),
+    });
+    ProtoColumnStats {
+        name: name.to_owned(),
+        nulls: stats.nulls.into_proto(),
+        bounds,
+    }
+}
+
+fn column_stats_from_proto(
+    proto: ProtoColumnStats,
+) -> Result<(String, ColumnStats), TryFromProtoError> {
+    use proto_column_stats::Bounds;
+    let bounds = match proto.bounds {
+        None => None,
+        Some(Bounds::Bool(x)) => Some(ColumnBounds::Bool(x.lower, x.upper)),
+        Some(Bounds::Int16(x)) => Some(ColumnBounds::Int16(
+            x.lower.try_into()?,
+            x.upper.try_into()?,
+        )),
+        Some(Bounds::Int32(x)) => Some(ColumnBounds::Int32(
+            x.lower.try_into()?,
+            x.upper.try_into()?,
+        )),
+        Some(Bounds::Int64(x)) => Some(ColumnBounds::Int64(x.lower, x.upper)),
+        Some(Bounds::Float32(x)) => Some(ColumnBounds::Float32(x.lower, x.upper)),
+        Some(Bounds::Float64(x)) => Some(ColumnBounds::Float64(x.lower, x.upper)),
+        Some(Bounds::String(x)) => Some(ColumnBounds::String(x.lower, x.upper)),
+    };
+    let stats = ColumnStats {
+        nulls: proto.nulls.into_rust()?,
+        bounds,
+    };
+    Ok((proto.name, stats))
+}
+
 impl<T: Timestamp + Codec64> RustType<ProtoU64Description> for Description<T> {
     fn into_proto(&self) -> ProtoU64Description {
         ProtoU64Description {
@@ -353,9 +454,10 @@ impl<T: Timestamp + Codec64> RustType<ProtoSnapshotSplit> for SnapshotSplit<T> {
             batches: self
                 .batches
                 .iter()
-                .map(|(key, desc)| ProtoHollowBatchPart {
+                .map(|(key, desc, stats)| ProtoHollowBatchPart {
                     desc: Some(desc.into_proto()),
                     key: key.into_proto(),
+                    stats: Some(stats.into_proto()),
                 })
                 .collect(),
         }
@@ -365,7 +467,10 @@ impl<T: Timestamp + Codec64> RustType<ProtoSnapshotSplit> for SnapshotSplit<T> {
         let mut batches = Vec::new();
         for batch in proto.batches.into_iter() {
             let desc = batch.desc.into_rust_if_some("desc")?;
-            batches.push((batch.key, desc));
+            // Splits are only exchanged between processes running the same
+            // version, but tolerate missing stats anyway: they're optional.
+            let stats = batch.stats.into_rust()?.unwrap_or_default();
+            batches.push((batch.key, desc, stats));
         }
         Ok(SnapshotSplit {
             shard_id: proto.shard_id.into_rust()?,
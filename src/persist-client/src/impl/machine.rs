@@ -727,6 +727,7 @@ mod tests {
                                 Antichain::from_elem(lower),
                                 Arc::clone(&client.blob),
                                 shard_id.clone(),
+                                None,
                             );
                             for (k, t, d) in updates {
                                 builder.add(&k, &(), &t, &d).await.expect("invalid batch");
@@ -752,6 +753,7 @@ mod tests {
                                     client.metrics.as_ref(),
                                     key,
                                     &batch.desc,
+                                    None,
                                     |k, _v, t, d| {
                                         let (k, d) = (String::decode(k).unwrap(), i64::decode(d));
                                         write!(s, "{k} {t} {d}\n");
@@ -818,6 +820,7 @@ mod tests {
                                 Handle::current(),
                                 Arc::clone(&client.blob),
                                 Arc::clone(&client.metrics),
+                                None,
                                 req,
                             )
                             .await;
@@ -960,4 +963,94 @@ mod tests {
             retry = retry.sleep().await;
         }
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn retractions_compact_and_gc_to_nothing() {
+        mz_ore::test::init_logging();
+
+        let mut client = new_test_client().await;
+        // Compact every merge the spine asks for, however small.
+        client.cfg.compaction_heuristic_min_inputs = 0;
+        let (mut write, mut read) = client
+            .expect_open::<String, (), u64, i64>(ShardId::new())
+            .await;
+
+        // Write some contents, then retract all of them in a single batch
+        // built from a snapshot, the way TRUNCATE clears a table.
+        const NUM_BATCHES: u64 = 10;
+        for idx in 0..NUM_BATCHES {
+            write
+                .expect_compare_and_append(&[((idx.to_string(), ()), idx, 1)], idx, idx + 1)
+                .await;
+        }
+        let mut snapshot = read.expect_snapshot(NUM_BATCHES - 1).await;
+        let mut builder = write.builder(0, Antichain::from_elem(NUM_BATCHES));
+        while let Some(updates) = snapshot.next().await {
+            for ((k, v), _, d) in updates {
+                let (k, v) = (k.expect("invalid key"), v.expect("invalid val"));
+                builder
+                    .add(&k, &v, &NUM_BATCHES, &-d)
+                    .await
+                    .expect("invalid usage");
+            }
+        }
+        let mut batch = builder
+            .finish(Antichain::from_elem(NUM_BATCHES + 1))
+            .await
+            .expect("invalid usage");
+        write
+            .compare_and_append_batch(
+                &mut batch,
+                Antichain::from_elem(NUM_BATCHES),
+                Antichain::from_elem(NUM_BATCHES + 1),
+            )
+            .await
+            .expect("indeterminate")
+            .expect("invalid usage")
+            .expect("upper mismatch");
+
+        // Allow the retractions to consolidate with the contents, then keep
+        // writing empty batches until the spine has merged and compacted them
+        // together. Compaction is done in the background, so give up and fail
+        // the test if that takes "too much" time.
+        let since = Antichain::from_elem(NUM_BATCHES);
+        read.downgrade_since(since.clone()).await;
+        let mut upper = NUM_BATCHES + 1;
+        let mut retry = Retry::persist_defaults(SystemTime::now()).into_retry_stream();
+        loop {
+            write.expect_compare_and_append(&[], upper, upper + 1).await;
+            upper += 1;
+            write.machine.fetch_and_update_state().await;
+            let batches = write
+                .machine
+                .clone()
+                .snapshot(&since)
+                .await
+                .expect("invalid as_of");
+            if batches.iter().all(|batch| batch.len == 0) {
+                break;
+            }
+            if retry.next_sleep() > Duration::from_secs(10) {
+                panic!("did not converge in time");
+            }
+            retry = retry.sleep().await;
+        }
+
+        // Collect the garbage left behind by the compactions.
+        GarbageCollector::gc_and_truncate(
+            Arc::clone(&write.machine.consensus),
+            Arc::clone(&client.blob),
+            &client.metrics,
+            GcReq {
+                shard_id: write.machine.shard_id(),
+                old_seqno_since: SeqNo::minimum(),
+                new_seqno_since: write.machine.state.seqno_since(),
+            },
+        )
+        .await;
+
+        // Reading the shard back finds that the contents cancelled out.
+        let mut snapshot = read.expect_snapshot(NUM_BATCHES).await;
+        assert_eq!(snapshot.read_all().await, vec![]);
+    }
 }
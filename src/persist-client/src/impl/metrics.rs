@@ -17,6 +17,8 @@ use bytes::Bytes;
 use mz_ore::cast::CastFrom;
 use mz_ore::metric;
 use mz_ore::metrics::{Counter, IntCounter, MetricsRegistry};
+use mz_persist::disk_cache::DiskCacheMetrics;
+use mz_persist::indexed::encoding::PartCompression;
 use mz_persist::location::{Atomicity, Blob, Consensus, ExternalError, SeqNo, VersionedData};
 use mz_persist::retry::RetryStream;
 use prometheus::{CounterVec, IntCounterVec};
@@ -31,6 +33,8 @@ pub struct Metrics {
 
     /// Metrics for [Blob] usage.
     pub blob: BlobMetrics,
+    /// Metrics for the local disk cache of [Blob] values.
+    pub blob_cache: DiskCacheMetrics,
     /// Metrics for [Consensus] usage.
     pub consensus: ConsensusMetrics,
     /// Metrics of command evaluation.
@@ -42,8 +46,12 @@ pub struct Metrics {
     pub user: BatchWriteMetrics,
     /// Metrics for compaction.
     pub compaction: CompactionMetrics,
+    /// Metrics for the compression of written parts.
+    pub compression: CompressionMetrics,
     /// Metrics for garbage collection.
     pub gc: GcMetrics,
+    /// Metrics for snapshots and listens.
+    pub read: ReadMetrics,
     /// Metrics for various encodings and decodings.
     pub codecs: CodecsMetrics,
 }
@@ -54,13 +62,16 @@ impl Metrics {
         let vecs = MetricsVecs::new(registry);
         Metrics {
             blob: vecs.blob_metrics(),
+            blob_cache: blob_cache_metrics(registry),
             consensus: vecs.consensus_metrics(),
             cmds: vecs.cmds_metrics(),
             retries: vecs.retries_metrics(),
             codecs: vecs.codecs_metrics(),
             user: BatchWriteMetrics::new(registry, "user"),
             compaction: CompactionMetrics::new(registry),
+            compression: CompressionMetrics::new(registry),
             gc: GcMetrics::new(registry),
+            read: ReadMetrics::new(registry),
             _vecs: vecs,
         }
     }
@@ -423,6 +434,44 @@ impl BatchWriteMetrics {
     }
 }
 
+#[derive(Debug)]
+pub struct CompressionMetrics {
+    goodbytes: IntCounterVec,
+    bytes: IntCounterVec,
+}
+
+impl CompressionMetrics {
+    fn new(registry: &MetricsRegistry) -> Self {
+        CompressionMetrics {
+            goodbytes: registry.register(metric!(
+                name: "mz_persist_compression_goodbytes",
+                help: "total logical size of parts written, by compression codec",
+                var_labels: ["compression"],
+            )),
+            bytes: registry.register(metric!(
+                name: "mz_persist_compression_bytes",
+                help: "total encoded size of parts written, by compression codec",
+                var_labels: ["compression"],
+            )),
+        }
+    }
+
+    /// Records a part with the given logical size that was encoded with the
+    /// given codec into the given number of bytes.
+    ///
+    /// The compression ratio of each codec is the ratio between its goodbytes
+    /// and bytes.
+    pub(crate) fn observe(&self, compression: PartCompression, goodbytes: usize, bytes: usize) {
+        let labels = [compression.as_str()];
+        self.goodbytes
+            .with_label_values(&labels)
+            .inc_by(u64::cast_from(goodbytes));
+        self.bytes
+            .with_label_values(&labels)
+            .inc_by(u64::cast_from(bytes));
+    }
+}
+
 #[derive(Debug)]
 pub struct CompactionMetrics {
     pub(crate) skipped: IntCounter,
@@ -498,6 +547,52 @@ impl GcMetrics {
     }
 }
 
+#[derive(Debug)]
+pub struct ReadMetrics {
+    pub(crate) parts_fetched: IntCounter,
+    pub(crate) parts_skipped: IntCounter,
+}
+
+impl ReadMetrics {
+    fn new(registry: &MetricsRegistry) -> Self {
+        ReadMetrics {
+            parts_fetched: registry.register(metric!(
+                name: "mz_persist_read_parts_fetched",
+                help: "count of parts fetched by snapshots and listens",
+            )),
+            parts_skipped: registry.register(metric!(
+                name: "mz_persist_read_parts_skipped",
+                help: "count of parts skipped by snapshots and listens using part stats",
+            )),
+        }
+    }
+}
+
+fn blob_cache_metrics(registry: &MetricsRegistry) -> DiskCacheMetrics {
+    DiskCacheMetrics {
+        hits: registry.register(metric!(
+            name: "mz_persist_blob_cache_hits",
+            help: "count of blob gets served from the local disk cache",
+        )),
+        misses: registry.register(metric!(
+            name: "mz_persist_blob_cache_misses",
+            help: "count of blob gets not served from the local disk cache",
+        )),
+        evictions: registry.register(metric!(
+            name: "mz_persist_blob_cache_evictions",
+            help: "count of values evicted from the local disk cache",
+        )),
+        evicted_bytes: registry.register(metric!(
+            name: "mz_persist_blob_cache_evicted_bytes",
+            help: "total size of values evicted from the local disk cache",
+        )),
+        size_bytes: registry.register(metric!(
+            name: "mz_persist_blob_cache_size_bytes",
+            help: "total size of values in the local disk cache",
+        )),
+    }
+}
+
 struct IncOnDrop(IntCounter);
 
 impl Drop for IncOnDrop {
@@ -22,6 +22,7 @@ use timely::PartialOrder;
 use crate::error::{Determinacy, InvalidUsage};
 use crate::r#impl::trace::{FueledMergeReq, FueledMergeRes, Trace};
 use crate::read::ReaderId;
+use crate::stats::PartStats;
 use crate::write::WriterId;
 use crate::ShardId;
 
@@ -53,6 +54,11 @@ pub struct HollowBatch<T> {
     pub keys: Vec<String>,
     /// The number of updates in the batch.
     pub len: usize,
+    /// Statistics about the updates in each part.
+    ///
+    /// Either empty (for batches written before part statistics existed) or
+    /// one entry per element of `keys`.
+    pub stats: Vec<PartStats>,
 }
 
 // TODO: Document invariants.
@@ -393,6 +399,7 @@ mod tests {
             ),
             keys: keys.iter().map(|x| (*x).to_owned()).collect(),
             len,
+            stats: Vec::new(),
         }
     }
 
@@ -262,6 +262,7 @@ impl<T: Timestamp + Lattice> SpineBatch<T> {
             desc: Description::new(lower, upper, since),
             keys: vec![],
             len: 0,
+            stats: vec![],
         })
     }
 
@@ -1042,6 +1043,7 @@ mod tests {
                 ),
                 len,
                 keys: keys.iter().map(|x| (*x).to_owned()).collect(),
+                stats: Vec::new(),
             }
         }
 
@@ -1063,7 +1065,9 @@ mod tests {
                         let mut s = String::new();
                         trace.spine.map_batches(|b| {
                             let b = match b {
-                                SpineBatch::Merged(HollowBatch { desc, len, keys }) => format!(
+                                SpineBatch::Merged(HollowBatch {
+                                    desc, len, keys, ..
+                                }) => format!(
                                     "{:?}{:?}{:?} {}{}\n",
                                     desc.lower().elements(),
                                     desc.upper().elements(),
@@ -24,7 +24,10 @@ use differential_dataflow::difference::Semigroup;
 use differential_dataflow::lattice::Lattice;
 use mz_ore::now::NowFn;
 use mz_persist::cfg::{BlobConfig, ConsensusConfig};
+use mz_persist::disk_cache::DiskCacheConfig;
+use mz_persist::indexed::encoding::PartCompression;
 use mz_persist::location::{Blob, Consensus, ExternalError};
+use mz_persist_types::columnar::Schema;
 use mz_persist_types::{Codec, Codec64};
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
@@ -32,6 +35,7 @@ use timely::progress::Timestamp;
 use tracing::{debug, instrument, trace};
 use uuid::Uuid;
 
+use crate::batch::{PartKeyColumns, SchemaKeyColumns};
 use crate::error::InvalidUsage;
 use crate::r#impl::compact::Compactor;
 use crate::r#impl::encoding::parse_id;
@@ -44,9 +48,11 @@ pub mod batch;
 pub mod cache;
 pub mod error;
 pub mod read;
+pub mod stats;
 pub mod write;
 
 pub use crate::r#impl::state::{Since, Upper};
+pub use mz_persist::indexed::encoding::PartCompression;
 
 /// An implementation of the public crate interface.
 pub(crate) mod r#impl {
@@ -170,6 +176,24 @@ pub struct PersistConfig {
     /// if the number of updates is at least this many. Compaction is performed
     /// if any of the heuristic criteria are met (they are OR'd).
     pub compaction_heuristic_min_updates: usize,
+    /// If set, blob values (batch parts) fetched by this process are cached
+    /// on local disk, with a separate cache per blob location.
+    ///
+    /// Only one process at a time can cache a given blob location in a given
+    /// dir, so processes that run side by side (e.g. computed and storaged)
+    /// need to be configured with different dirs. A process that can't lock
+    /// its cache dir logs a warning and runs without the cache.
+    ///
+    /// Configured by the `MZ_PERSIST_BLOB_CACHE_DIR` env var and, optionally,
+    /// the `MZ_PERSIST_BLOB_CACHE_MAX_BYTES` env var.
+    pub blob_cache: Option<DiskCacheConfig>,
+    /// The codec used to compress the parts written to blob storage.
+    ///
+    /// Each part records the codec it was written with, so this can be
+    /// changed at any time without affecting the readability of existing
+    /// parts. Defaults to [PartCompression::Uncompressed]; callers opt in to
+    /// compression by setting this before constructing a [PersistClient].
+    pub part_compression: PartCompression,
 }
 
 // Tuning inputs:
@@ -214,12 +238,25 @@ pub struct PersistConfig {
 //   an `O(n*log(n))` upper bound on the number of unconsolidated updates that
 //   would be consolidated if we compacted as the in-mem Spine does. The initial
 //   value is a placeholder and should be revisited at some point.
+// - The blob_cache max size was picked to hold a handful of parts at the
+//   default blob_target_size without taking up an unreasonable share of a
+//   typical local disk. It's also a placeholder.
 impl PersistConfig {
     /// Returns a new instance of [PersistConfig] with default tuning.
     pub fn new(now: NowFn) -> Self {
         // Escape hatch in case we need to disable compaction.
         let compaction_disabled = mz_ore::env::is_var_truthy("MZ_PERSIST_COMPACTION_DISABLED");
         const MB: usize = 1024 * 1024;
+        let blob_cache = std::env::var_os("MZ_PERSIST_BLOB_CACHE_DIR").map(|dir| {
+            let max_bytes = std::env::var("MZ_PERSIST_BLOB_CACHE_MAX_BYTES")
+                .ok()
+                .and_then(|x| x.parse().ok())
+                .unwrap_or(4 * 1024 * MB);
+            DiskCacheConfig {
+                dir: dir.into(),
+                max_bytes,
+            }
+        });
         Self {
             now,
             blob_target_size: 128 * MB,
@@ -227,6 +264,8 @@ impl PersistConfig {
             compaction_enabled: !compaction_disabled,
             compaction_heuristic_min_inputs: 8,
             compaction_heuristic_min_updates: 1024,
+            blob_cache,
+            part_compression: PartCompression::default(),
         }
     }
 }
@@ -368,6 +407,52 @@ impl PersistClient {
         D: Semigroup + Codec64,
     {
         trace!("Client::open_writer shard_id={:?}", shard_id);
+        self.open_writer_inner(shard_id, None).await
+    }
+
+    /// [Self::open_writer], but the returned [WriteHandle] stores the keys of
+    /// the updates it writes as the typed columns described by `key_schema`
+    /// instead of encoded with their [Codec].
+    ///
+    /// This lets readers of the shard (including tools outside of Materialize)
+    /// inspect individual fields without decoding whole keys. Readers don't
+    /// need the schema to decode whole keys, which [Codec::decode_columns]
+    /// does from the columns alone. Parts written without a schema, including
+    /// all parts written before typed key columns existed, are still decoded
+    /// with [Codec::decode], so a shard may freely mix the two.
+    ///
+    /// [Codec::decode_columns] must return exactly the keys that `key_schema`
+    /// encoded, or they won't read back as they were written.
+    #[instrument(level = "debug", skip_all, fields(shard = %shard_id))]
+    pub async fn open_writer_with_schema<K, V, T, D>(
+        &self,
+        shard_id: ShardId,
+        key_schema: Arc<dyn Schema<K>>,
+    ) -> Result<WriteHandle<K, V, T, D>, InvalidUsage<T>>
+    where
+        K: Debug + Codec,
+        V: Debug + Codec,
+        T: Timestamp + Lattice + Codec64,
+        D: Semigroup + Codec64,
+    {
+        trace!("Client::open_writer_with_schema shard_id={:?}", shard_id);
+        self.open_writer_inner(shard_id, Some(key_schema)).await
+    }
+
+    async fn open_writer_inner<K, V, T, D>(
+        &self,
+        shard_id: ShardId,
+        key_schema: Option<Arc<dyn Schema<K>>>,
+    ) -> Result<WriteHandle<K, V, T, D>, InvalidUsage<T>>
+    where
+        K: Debug + Codec,
+        V: Debug + Codec,
+        T: Timestamp + Lattice + Codec64,
+        D: Semigroup + Codec64,
+    {
+        // Writers that don't store typed key columns themselves still need to
+        // be able to compact the parts of those that do.
+        let key_columns: Arc<dyn PartKeyColumns> = Arc::new(SchemaKeyColumns::new(key_schema));
         let gc = GarbageCollector::new(
             Arc::clone(&self.consensus),
             Arc::clone(&self.blob),
@@ -385,6 +470,7 @@ impl PersistClient {
                 self.cfg.clone(),
                 Arc::clone(&self.blob),
                 Arc::clone(&self.metrics),
+                Some(Arc::clone(&key_columns)),
             )
         });
         let writer_id = WriterId::new();
@@ -396,6 +482,7 @@ impl PersistClient {
             machine,
             compact,
             blob: Arc::clone(&self.blob),
+            key_columns: Some(key_columns),
             upper: shard_upper.0,
             explicitly_expired: false,
         };
@@ -438,6 +525,7 @@ mod tests {
     use futures_task::noop_waker;
     use mz_persist::indexed::encoding::BlobTraceBatchPart;
     use mz_persist::workload::DataGenerator;
+    use mz_persist_types::columnar::{ColumnData, ColumnType};
     use mz_proto::protobuf_roundtrip;
     use timely::progress::Antichain;
     use timely::PartialOrder;
@@ -612,6 +700,95 @@ mod tests {
         );
     }
 
+    // Sanity check that writers opened with a schema store keys only as typed
+    // columns, and that a shard can mix parts with and without them.
+    #[tokio::test]
+    async fn open_writer_with_schema() {
+        mz_ore::test::init_logging();
+
+        #[derive(Debug)]
+        struct StringSchema;
+
+        impl Schema<String> for StringSchema {
+            fn columns(&self) -> Vec<(String, ColumnType)> {
+                vec![("s".to_owned(), ColumnType::String)]
+            }
+
+            fn encode(&self, val: &String, columns: &mut [ColumnData]) -> Result<(), String> {
+                match columns {
+                    [ColumnData::String(s)] => {
+                        s.push(Some(val.clone()));
+                        Ok(())
+                    }
+                    _ => Err(format!("unexpected columns: {:?}", columns)),
+                }
+            }
+
+            fn decode(&self, columns: &[ColumnData], idx: usize) -> Result<String, String> {
+                match columns {
+                    [ColumnData::String(s)] => s[idx].clone().ok_or_else(|| "null".to_owned()),
+                    _ => Err(format!("unexpected columns: {:?}", columns)),
+                }
+            }
+        }
+
+        let data = vec![
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+        ];
+
+        let shard_id = ShardId::new();
+        let client = new_test_client().await;
+        let mut write1 = client
+            .open_writer_with_schema::<String, String, u64, i64>(shard_id, Arc::new(StringSchema))
+            .await
+            .expect("codec mismatch");
+        let mut write2 = client
+            .open_writer::<String, String, u64, i64>(shard_id)
+            .await
+            .expect("codec mismatch");
+        let read = client
+            .open_reader::<String, String, u64, i64>(shard_id)
+            .await
+            .expect("codec mismatch");
+
+        let batch = write1
+            .batch(&data[..1], Antichain::from_elem(0), Antichain::from_elem(2))
+            .await
+            .expect("invalid usage");
+        for key in batch.blob_keys.iter() {
+            let value = client
+                .blob
+                .get(key)
+                .await
+                .expect("failed to fetch part")
+                .expect("missing part");
+            let part = BlobTraceBatchPart::decode(&value).expect("failed to decode part");
+            assert_eq!(
+                part.key_columns,
+                vec![vec![(
+                    "s".to_owned(),
+                    ColumnData::String(vec![Some("1".to_owned())])
+                )]]
+            );
+            // The typed columns are the only copy of the keys.
+            for updates in part.updates.iter() {
+                assert!(updates.iter().all(|((k, _v), _t, _d)| k.is_empty()));
+            }
+        }
+        write1
+            .append_batch(batch, Antichain::from_elem(0), Antichain::from_elem(2))
+            .await
+            .expect("invalid usage")
+            .expect("upper mismatch");
+
+        write2.expect_compare_and_append(&data[1..], 2, 3).await;
+        assert_eq!(
+            read.expect_snapshot(2).await.read_all().await,
+            all_ok(&data, 2)
+        );
+    }
+
     #[tokio::test]
     async fn invalid_usage() {
         mz_ore::test::init_logging();
@@ -31,13 +31,16 @@ use uuid::Uuid;
 use mz_persist::indexed::encoding::BlobTraceBatchPart;
 use mz_persist::location::Blob;
 use mz_persist::retry::Retry;
+use mz_persist_types::columnar::{ColumnData, Schema};
 use mz_persist_types::{Codec, Codec64};
 
+use crate::batch::PartKeyColumns;
 use crate::error::InvalidUsage;
 use crate::r#impl::encoding::SerdeSnapshotSplit;
 use crate::r#impl::machine::{retry_external, Machine};
 use crate::r#impl::metrics::Metrics;
 use crate::r#impl::state::Since;
+use crate::stats::{PartFilter, PartStats};
 use crate::ShardId;
 
 /// An opaque identifier for a reader of a persist durable TVC (aka shard).
@@ -62,6 +65,108 @@ impl ReaderId {
     }
 }
 
+/// Restricts which parts of a shard a read fetches and how it decodes the
+/// keys of their updates.
+///
+/// The default filter fetches every part and decodes every key with its
+/// [Codec].
+pub struct ReadFilter<K> {
+    projection: Option<(Arc<dyn Schema<K>>, Vec<String>)>,
+    part_filter: Option<Arc<dyn PartFilter>>,
+}
+
+impl<K> ReadFilter<K> {
+    /// Decodes keys from the typed key columns described by `projection`
+    /// instead of with their [Codec].
+    ///
+    /// The columns of `projection` are looked up by name among the typed key
+    /// columns that the shard's writers store (see
+    /// [crate::PersistClient::open_writer_with_schema]), so it may describe any
+    /// subset of them. Only those columns are decoded, which is much cheaper
+    /// than decoding whole keys when only a few fields are needed. The value
+    /// decoded by `projection` is returned in place of the key, so it's up to
+    /// the caller to decide what it means for fields that weren't projected.
+    ///
+    /// Keys in parts that don't have all of the projected columns (e.g. parts
+    /// written without a key schema) are decoded whole instead, so callers
+    /// must be prepared to get either.
+    ///
+    /// Whole parts are still fetched from blob.
+    pub fn with_projection(mut self, projection: Arc<dyn Schema<K>>) -> Self {
+        let names = projection
+            .columns()
+            .into_iter()
+            .map(|(name, _typ)| name)
+            .collect();
+        self.projection = Some((projection, names));
+        self
+    }
+
+    /// Skips fetching parts for which `part_filter` returns false.
+    ///
+    /// Only parts with statistics can be skipped. These are recorded for parts
+    /// written with a key schema (see
+    /// [crate::PersistClient::open_writer_with_schema]).
+    pub fn with_part_filter(mut self, part_filter: Arc<dyn PartFilter>) -> Self {
+        self.part_filter = Some(part_filter);
+        self
+    }
+
+    fn should_fetch(&self, stats: Option<&PartStats>) -> bool {
+        match (&self.part_filter, stats) {
+            (Some(part_filter), Some(stats)) => part_filter.should_fetch(stats),
+            _ => true,
+        }
+    }
+
+    fn projected_columns(&self) -> Option<Vec<&str>> {
+        self.projection
+            .as_ref()
+            .map(|(_, names)| names.iter().map(|x| x.as_str()).collect())
+    }
+
+    fn decode_key(&self, key: FetchedKey<'_>) -> Result<K, String>
+    where
+        K: Codec,
+    {
+        match key {
+            FetchedKey::Encoded(buf) => K::decode(buf),
+            FetchedKey::Columns(columns, idx) => K::decode_columns(columns, idx),
+            FetchedKey::Projected(columns, idx) => match &self.projection {
+                Some((projection, _)) => projection.decode(columns, idx),
+                None => Err("internal error: projected key columns without a projection".into()),
+            },
+        }
+    }
+}
+
+impl<K> Default for ReadFilter<K> {
+    fn default() -> Self {
+        ReadFilter {
+            projection: None,
+            part_filter: None,
+        }
+    }
+}
+
+impl<K> Clone for ReadFilter<K> {
+    fn clone(&self) -> Self {
+        ReadFilter {
+            projection: self.projection.clone(),
+            part_filter: self.part_filter.clone(),
+        }
+    }
+}
+
+impl<K> Debug for ReadFilter<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadFilter")
+            .field("projection", &self.projection)
+            .field("part_filter", &self.part_filter)
+            .finish()
+    }
+}
+
 /// A token representing one split of a "snapshot" (the contents of a shard
 /// as of some frontier).
 ///
@@ -79,7 +184,7 @@ impl ReaderId {
 pub struct SnapshotSplit<T> {
     pub(crate) shard_id: ShardId,
     pub(crate) as_of: Antichain<T>,
-    pub(crate) batches: Vec<(String, Description<T>)>,
+    pub(crate) batches: Vec<(String, Description<T>, PartStats)>,
 }
 
 /// An iterator over one split of a "snapshot" (the contents of a shard as of
@@ -91,8 +196,9 @@ pub struct SnapshotIter<K, V, T, D> {
     metrics: Arc<Metrics>,
     shard_id: ShardId,
     as_of: Antichain<T>,
-    batches: Vec<(String, Description<T>)>,
+    batches: Vec<(String, Description<T>, PartStats)>,
     blob: Arc<dyn Blob + Send + Sync>,
+    filter: ReadFilter<K>,
     _phantom: PhantomData<(K, V, T, D)>,
 }
 
@@ -127,26 +233,32 @@ where
     pub async fn next(&mut self) -> Option<Vec<((Result<K, String>, Result<V, String>), T, D)>> {
         trace!("SnapshotIter::next");
         loop {
-            let (key, desc) = match self.batches.pop() {
+            let (key, desc, stats) = match self.batches.pop() {
                 Some(x) => x,
                 // All done!
                 None => return None,
             };
+            if !self.filter.should_fetch(Some(&stats)) {
+                self.metrics.read.parts_skipped.inc();
+                continue;
+            }
+            self.metrics.read.parts_fetched.inc();
 
             let mut updates = Vec::new();
-            fetch_batch_part(
+            fetch_batch_part_projected(
                 &self.shard_id,
                 self.blob.as_ref(),
                 &self.metrics,
                 &key,
                 &desc,
+                self.filter.projected_columns().as_deref(),
                 |k, v, mut t, d| {
                     // This would get covered by a listen started at the same as_of.
                     if self.as_of.less_than(&t) {
                         return;
                     }
                     t.advance_by(self.as_of.borrow());
-                    let k = self.metrics.codecs.key.decode(|| K::decode(k));
+                    let k = self.metrics.codecs.key.decode(|| self.filter.decode_key(k));
                     let v = self.metrics.codecs.val.decode(|| V::decode(v));
                     let d = D::decode(d);
                     updates.push(((k, v), t, d));
@@ -202,6 +314,7 @@ pub struct Listen<K, V, T, D> {
     frontier: Antichain<T>,
     machine: Machine<K, V, T, D>,
     blob: Arc<dyn Blob + Send + Sync>,
+    filter: ReadFilter<K>,
 }
 
 impl<K, V, T, D> Listen<K, V, T, D>
@@ -238,13 +351,19 @@ where
 
         let batch = self.machine.next_listen_batch(&self.frontier).await;
         let mut updates = Vec::new();
-        for key in batch.keys.iter() {
-            fetch_batch_part(
+        for (idx, key) in batch.keys.iter().enumerate() {
+            if !self.filter.should_fetch(batch.stats.get(idx)) {
+                self.metrics.read.parts_skipped.inc();
+                continue;
+            }
+            self.metrics.read.parts_fetched.inc();
+            fetch_batch_part_projected(
                 &self.machine.shard_id(),
                 self.blob.as_ref(),
                 &self.metrics,
                 key,
                 &batch.desc,
+                self.filter.projected_columns().as_deref(),
                 |k, v, t, d| {
                     // This would get covered by a snapshot started at the same as_of.
                     if !self.as_of.less_than(&t) {
@@ -260,7 +379,7 @@ where
                     if !self.frontier.less_equal(&t) {
                         return;
                     }
-                    let k = self.metrics.codecs.key.decode(|| K::decode(k));
+                    let k = self.metrics.codecs.key.decode(|| self.filter.decode_key(k));
                     let v = self.metrics.codecs.val.decode(|| V::decode(v));
                     let d = D::decode(d);
                     updates.push(((k, v), t, d));
@@ -388,9 +507,22 @@ where
     /// The `Since` error indicates that the requested `as_of` cannot be served
     /// (the caller has out of date information) and includes the smallest
     /// `as_of` that would have been accepted.
-    #[instrument(level = "debug", skip_all, fields(shard = %self.machine.shard_id()))]
     pub async fn listen(&self, as_of: Antichain<T>) -> Result<Listen<K, V, T, D>, Since<T>> {
-        trace!("ReadHandle::listen as_of={:?}", as_of);
+        self.listen_filtered(as_of, ReadFilter::default()).await
+    }
+
+    /// [Self::listen], but only fetching the parts and decoding the key
+    /// columns allowed by `filter`.
+    ///
+    /// Updates in skipped parts are not emitted, but progress is reported
+    /// exactly as it is by [Self::listen].
+    #[instrument(level = "debug", skip_all, fields(shard = %self.machine.shard_id()))]
+    pub async fn listen_filtered(
+        &self,
+        as_of: Antichain<T>,
+        filter: ReadFilter<K>,
+    ) -> Result<Listen<K, V, T, D>, Since<T>> {
+        trace!("ReadHandle::listen as_of={:?} filter={:?}", as_of, filter);
         let machine = self.machine.verify_listen(&as_of).await?;
         Ok(Listen {
             metrics: Arc::clone(&self.metrics),
@@ -398,6 +530,7 @@ where
             frontier: as_of,
             machine,
             blob: Arc::clone(&self.blob),
+            filter,
         })
     }
 
@@ -417,10 +550,20 @@ where
     /// immediately consuming it from a single place. If you need to parallelize
     /// snapshot iteration (potentially from multiple machines), see
     /// [Self::snapshot_splits] and [Self::snapshot_iter].
-    #[instrument(level = "trace", skip_all, fields(shard = %self.machine.shard_id()))]
     pub async fn snapshot(
         &self,
         as_of: Antichain<T>,
+    ) -> Result<SnapshotIter<K, V, T, D>, Since<T>> {
+        self.snapshot_filtered(as_of, ReadFilter::default()).await
+    }
+
+    /// [Self::snapshot], but only fetching the parts and decoding the key
+    /// columns allowed by `filter`.
+    #[instrument(level = "trace", skip_all, fields(shard = %self.machine.shard_id()))]
+    pub async fn snapshot_filtered(
+        &self,
+        as_of: Antichain<T>,
+        filter: ReadFilter<K>,
     ) -> Result<SnapshotIter<K, V, T, D>, Since<T>> {
         let mut splits = self
             .snapshot_splits(as_of, NonZeroUsize::new(1).unwrap())
@@ -428,7 +571,7 @@ where
         assert_eq!(splits.len(), 1);
         let split = splits.pop().unwrap();
         let iter = self
-            .snapshot_iter(split)
+            .snapshot_iter_filtered(split, filter)
             .await
             .expect("internal error: snapshot shard didn't match machine shard");
         Ok(iter)
@@ -474,7 +617,10 @@ where
         let batches = self.machine.clone().snapshot(&as_of).await?;
         let batches = batches.into_iter().flat_map(|b| {
             let desc = b.desc.clone();
-            b.keys.into_iter().map(move |k| (k, desc.clone()))
+            let mut stats = b.stats.into_iter();
+            b.keys
+                .into_iter()
+                .map(move |k| (k, desc.clone(), stats.next().unwrap_or_default()))
         });
         let mut splits = (0..num_splits.get())
             .map(|_| SnapshotSplit {
@@ -483,22 +629,33 @@ where
                 batches: Vec::new(),
             })
             .collect::<Vec<_>>();
-        for (idx, (batch_key, desc)) in batches.into_iter().enumerate() {
+        for (idx, (batch_key, desc, stats)) in batches.into_iter().enumerate() {
             splits[idx % num_splits.get()]
                 .batches
-                .push((batch_key, desc.clone()));
+                .push((batch_key, desc, stats));
         }
         return Ok(splits);
     }
 
     /// Trade in an exchange-able [SnapshotSplit] for an iterator over the data
     /// it represents.
-    #[instrument(level = "debug", skip_all, fields(shard = %self.machine.shard_id()))]
     pub async fn snapshot_iter(
         &self,
         split: SnapshotSplit<T>,
     ) -> Result<SnapshotIter<K, V, T, D>, InvalidUsage<T>> {
-        trace!("ReadHandle::snapshot split={:?}", split);
+        self.snapshot_iter_filtered(split, ReadFilter::default())
+            .await
+    }
+
+    /// [Self::snapshot_iter], but only fetching the parts and decoding the key
+    /// columns allowed by `filter`.
+    #[instrument(level = "debug", skip_all, fields(shard = %self.machine.shard_id()))]
+    pub async fn snapshot_iter_filtered(
+        &self,
+        split: SnapshotSplit<T>,
+        filter: ReadFilter<K>,
+    ) -> Result<SnapshotIter<K, V, T, D>, InvalidUsage<T>> {
+        trace!("ReadHandle::snapshot split={:?} filter={:?}", split, filter);
         if split.shard_id != self.machine.shard_id() {
             return Err(InvalidUsage::SnapshotNotFromThisShard {
                 snapshot_shard: split.shard_id,
@@ -511,6 +668,7 @@ where
             as_of: split.as_of,
             batches: split.batches,
             blob: Arc::clone(&self.blob),
+            filter,
             _phantom: PhantomData,
         };
         Ok(iter)
@@ -607,16 +765,75 @@ where
     }
 }
 
+/// The key of an update in a fetched part.
+pub(crate) enum FetchedKey<'a> {
+    /// The key, encoded with its [Codec].
+    Encoded(&'a [u8]),
+    /// The key at the given index of all of the part's typed key columns.
+    Columns(&'a [ColumnData], usize),
+    /// The key at the given index of the typed key columns that were asked
+    /// for, in the order they were asked for.
+    Projected(&'a [ColumnData], usize),
+}
+
+/// Fetches the updates in a part, passing each one to `update_fn` with its key
+/// encoded with its [Codec].
+///
+/// `key_columns` is used to encode the keys of parts that store them as typed
+/// columns and must be provided for shards written with a key schema.
 pub(crate) async fn fetch_batch_part<T, UpdateFn>(
-    _shard_id: &ShardId,
+    shard_id: &ShardId,
     blob: &(dyn Blob + Send + Sync),
     metrics: &Metrics,
     key: &str,
     registered_desc: &Description<T>,
+    key_columns: Option<&dyn PartKeyColumns>,
     mut update_fn: UpdateFn,
 ) where
     T: Timestamp + Lattice + Codec64,
     UpdateFn: FnMut(&[u8], &[u8], T, [u8; 8]),
+{
+    let mut key_buf = Vec::new();
+    fetch_batch_part_projected(
+        shard_id,
+        blob,
+        metrics,
+        key,
+        registered_desc,
+        None,
+        |k, v, t, d| match k {
+            FetchedKey::Encoded(k) => update_fn(k, v, t, d),
+            FetchedKey::Columns(columns, idx) => {
+                let key_columns = key_columns
+                    .expect("internal error: part with typed key columns but no key codec");
+                key_buf.clear();
+                key_columns
+                    .encode_key(columns, idx, &mut key_buf)
+                    .unwrap_or_else(|err| {
+                        panic!("internal error: invalid typed key columns: {}", err)
+                    });
+                update_fn(&key_buf, v, t, d)
+            }
+            FetchedKey::Projected(..) => unreachable!("no key columns were projected"),
+        },
+    )
+    .await
+}
+
+/// [fetch_batch_part], but passing keys to `update_fn` as they're stored in
+/// the part and only decoding the typed key columns with the given names, if
+/// the part has all of them.
+pub(crate) async fn fetch_batch_part_projected<T, UpdateFn>(
+    _shard_id: &ShardId,
+    blob: &(dyn Blob + Send + Sync),
+    metrics: &Metrics,
+    key: &str,
+    registered_desc: &Description<T>,
+    key_columns: Option<&[&str]>,
+    mut update_fn: UpdateFn,
+) where
+    T: Timestamp + Lattice + Codec64,
+    UpdateFn: FnMut(FetchedKey<'_>, &[u8], T, [u8; 8]),
 {
     let mut retry = metrics
         .retries
@@ -652,7 +869,7 @@ pub(crate) async fn fetch_batch_part<T, UpdateFn>(
         let batch = metrics
             .codecs
             .batch
-            .decode(|| BlobTraceBatchPart::decode(&value))
+            .decode(|| BlobTraceBatchPart::decode_projected(&value, key_columns))
             .map_err(|err| anyhow!("couldn't decode batch at key {}: {}", key, err))
             // We received a State that we couldn't decode. This could happen if
             // persist messes up backward/forward compatibility, if the durable
@@ -712,8 +929,19 @@ pub(crate) async fn fetch_batch_part<T, UpdateFn>(
             );
         }
 
-        for chunk in batch.updates {
-            for ((k, v), t, d) in chunk.iter() {
+        // Parts written with a key schema have typed key columns for every
+        // chunk of updates, and other parts have none.
+        let mut chunk_key_columns = batch.key_columns.into_iter();
+        for chunk in batch.updates.into_iter() {
+            let chunk_key_columns = chunk_key_columns.next().map(|columns| {
+                let projected = key_columns.map_or(false, |names| {
+                    columns.len() == names.len()
+                        && columns.iter().zip(names).all(|((x, _), name)| x == name)
+                });
+                let columns = columns.into_iter().map(|(_, x)| x).collect::<Vec<_>>();
+                (projected, columns)
+            });
+            for (idx, ((k, v), t, d)) in chunk.iter().enumerate() {
                 let t = T::decode(t);
 
                 // This filtering is really subtle, see the comment above for
@@ -727,6 +955,11 @@ pub(crate) async fn fetch_batch_part<T, UpdateFn>(
                     }
                 }
 
+                let k = match &chunk_key_columns {
+                    None => FetchedKey::Encoded(k),
+                    Some((false, columns)) => FetchedKey::Columns(columns, idx),
+                    Some((true, columns)) => FetchedKey::Projected(columns, idx),
+                };
                 update_fn(k, v, t, d);
             }
         }
@@ -758,11 +991,13 @@ mod tests {
     use mz_persist::location::Consensus;
     use mz_persist::mem::{MemBlob, MemBlobConfig, MemConsensus};
     use mz_persist::unreliable::{UnreliableConsensus, UnreliableHandle};
+    use mz_persist_types::columnar::{ColumnBounds, ColumnType};
     use timely::ExchangeData;
 
+    use crate::cache::PersistClientCache;
     use crate::r#impl::metrics::Metrics;
     use crate::tests::all_ok;
-    use crate::{PersistClient, PersistConfig};
+    use crate::{PersistClient, PersistConfig, PersistLocation};
 
     use super::*;
 
@@ -821,6 +1056,143 @@ mod tests {
         );
     }
 
+    // Verifies that filtered snapshots and listens skip the parts ruled out by
+    // their stats and decode keys with the projection when they can.
+    #[tokio::test]
+    async fn filtered_reads() {
+        mz_ore::test::init_logging();
+
+        /// Stores strings as a String column.
+        #[derive(Debug)]
+        struct StringSchema;
+
+        impl Schema<String> for StringSchema {
+            fn columns(&self) -> Vec<(String, ColumnType)> {
+                vec![("s".to_owned(), ColumnType::String)]
+            }
+
+            fn encode(&self, val: &String, columns: &mut [ColumnData]) -> Result<(), String> {
+                match columns {
+                    [ColumnData::String(s)] => {
+                        s.push(Some(val.clone()));
+                        Ok(())
+                    }
+                    _ => Err(format!("unexpected columns: {:?}", columns)),
+                }
+            }
+
+            fn decode(&self, columns: &[ColumnData], idx: usize) -> Result<String, String> {
+                String::decode_columns(columns, idx)
+            }
+        }
+
+        /// Reads the column of [StringSchema], tagging what it decodes so we
+        /// can tell which keys were decoded with it.
+        #[derive(Debug)]
+        struct TaggedSchema;
+
+        impl Schema<String> for TaggedSchema {
+            fn columns(&self) -> Vec<(String, ColumnType)> {
+                StringSchema.columns()
+            }
+
+            fn encode(&self, _val: &String, _columns: &mut [ColumnData]) -> Result<(), String> {
+                Err("read-only".to_owned())
+            }
+
+            fn decode(&self, columns: &[ColumnData], idx: usize) -> Result<String, String> {
+                StringSchema
+                    .decode(columns, idx)
+                    .map(|s| format!("tagged {}", s))
+            }
+        }
+
+        /// Skips parts where every `s` is less than the given value.
+        #[derive(Debug)]
+        struct AtLeast(&'static str);
+
+        impl PartFilter for AtLeast {
+            fn should_fetch(&self, stats: &PartStats) -> bool {
+                match stats.key_column("s").and_then(|x| x.bounds.as_ref()) {
+                    Some(ColumnBounds::String(_lower, upper)) => upper.as_str() >= self.0,
+                    _ => true,
+                }
+            }
+        }
+
+        let data = vec![
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("5".to_owned(), "five".to_owned()), 2, 1),
+            (("7".to_owned(), "seven".to_owned()), 3, 1),
+        ];
+
+        // Compaction could combine parts written with and without stats, so
+        // turn it off to keep the parts as written.
+        let mut cache = PersistClientCache::new_no_metrics();
+        cache.cfg.compaction_enabled = false;
+        let client = cache
+            .open(PersistLocation {
+                blob_uri: "mem://".to_owned(),
+                consensus_uri: "mem://".to_owned(),
+            })
+            .await
+            .expect("client construction failed");
+        let shard_id = ShardId::new();
+        let mut write1 = client
+            .open_writer_with_schema::<String, String, u64, i64>(shard_id, Arc::new(StringSchema))
+            .await
+            .expect("codec mismatch");
+        let mut write2 = client
+            .open_writer::<String, String, u64, i64>(shard_id)
+            .await
+            .expect("codec mismatch");
+        let read = client
+            .open_reader::<String, String, u64, i64>(shard_id)
+            .await
+            .expect("codec mismatch");
+
+        write1.expect_compare_and_append(&data[0..1], 0, 2).await;
+        write1.expect_compare_and_append(&data[1..2], 2, 3).await;
+        // Parts written without a schema have no stats, so they're never
+        // skipped.
+        write2.expect_compare_and_append(&data[2..3], 3, 4).await;
+
+        let filter = ReadFilter::default()
+            .with_projection(Arc::new(TaggedSchema))
+            .with_part_filter(Arc::new(AtLeast("5")));
+        let expected = |t1, t2| {
+            vec![
+                ((Ok("tagged 5".to_owned()), Ok("five".to_owned())), t1, 1),
+                ((Ok("7".to_owned()), Ok("seven".to_owned())), t2, 1),
+            ]
+        };
+
+        let skipped = client.metrics().read.parts_skipped.get();
+        let mut snapshot = read
+            .snapshot_filtered(Antichain::from_elem(3), filter.clone())
+            .await
+            .expect("cannot serve requested as_of");
+        assert_eq!(snapshot.read_all().await, expected(3, 3));
+        assert_eq!(client.metrics().read.parts_skipped.get(), skipped + 1);
+
+        let mut listen = read
+            .listen_filtered(Antichain::from_elem(0), filter)
+            .await
+            .expect("cannot serve requested as_of");
+        assert_eq!(
+            listen.read_until(&4).await,
+            (expected(2, 3), Antichain::from_elem(4))
+        );
+        assert_eq!(client.metrics().read.parts_skipped.get(), skipped + 2);
+
+        // Without a filter, everything is fetched and decoded whole, from the
+        // typed columns or with the Codec.
+        assert_eq!(
+            read.expect_snapshot(3).await.read_all().await,
+            all_ok(&data, 3)
+        );
+    }
+
     #[test]
     fn snapshot_split_exchange_data() {
         // The whole point of SnapshotSplit is that it can be exchanged between
@@ -0,0 +1,63 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Statistics about the updates in the parts of a shard
+//!
+//! Writers opened with a key schema (see
+//! [crate::PersistClient::open_writer_with_schema]) summarize the typed key
+//! columns of every part they write and record the summary in the shard's
+//! metadata. Readers can then use a [PartFilter] to skip fetching parts that
+//! can't contain anything they're interested in.
+
+use std::fmt::Debug;
+
+use mz_persist_types::columnar::{ColumnData, ColumnStats};
+
+/// Statistics about the updates in a part.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartStats {
+    /// The number of updates in the part.
+    pub len: usize,
+    /// Statistics about each of the part's typed key columns, by name.
+    ///
+    /// Empty if the part was written without typed key columns, including all
+    /// parts written before part statistics existed.
+    pub key: Vec<(String, ColumnStats)>,
+}
+
+impl PartStats {
+    pub(crate) fn new(len: usize, key_columns: &[(String, ColumnData)]) -> Self {
+        let key = key_columns
+            .iter()
+            .map(|(name, col)| (name.clone(), col.stats()))
+            .collect();
+        PartStats { len, key }
+    }
+
+    /// Returns the statistics of the typed key column with the given name, if
+    /// they're known.
+    pub fn key_column(&self, name: &str) -> Option<&ColumnStats> {
+        self.key
+            .iter()
+            .find(|(col_name, _)| col_name == name)
+            .map(|(_, stats)| stats)
+    }
+}
+
+/// A predicate on the updates in a part, evaluated using only the part's
+/// [PartStats].
+pub trait PartFilter: Debug + Send + Sync {
+    /// Returns false if no update in a part with the given statistics can be of
+    /// interest to the reader, in which case the part is not fetched.
+    ///
+    /// Statistics are best-effort: a part may have none at all, or only for
+    /// some columns. Implementations must return true unless the statistics
+    /// prove that the part can be skipped.
+    fn should_fetch(&self, stats: &PartStats) -> bool;
+}
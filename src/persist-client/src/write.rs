@@ -27,7 +27,7 @@ use tokio::runtime::Handle;
 use tracing::{debug, debug_span, info, instrument, trace, warn, Instrument};
 use uuid::Uuid;
 
-use crate::batch::{validate_truncate_batch, Batch, BatchBuilder};
+use crate::batch::{validate_truncate_batch, Batch, BatchBuilder, PartKeyColumns};
 use crate::error::InvalidUsage;
 use crate::r#impl::compact::{CompactReq, Compactor};
 use crate::r#impl::machine::{Machine, INFO_MIN_ATTEMPTS};
@@ -86,6 +86,7 @@ where
     pub(crate) machine: Machine<K, V, T, D>,
     pub(crate) compact: Option<Compactor>,
     pub(crate) blob: Arc<dyn Blob + Send + Sync>,
+    pub(crate) key_columns: Option<Arc<dyn PartKeyColumns>>,
     pub(crate) writer_id: WriterId,
     pub(crate) explicitly_expired: bool,
 
@@ -428,6 +429,7 @@ where
                     desc: desc.clone(),
                     keys: batch.blob_keys.clone(),
                     len: batch.num_updates,
+                    stats: batch.stats.clone(),
                 },
                 &self.writer_id,
             )
@@ -484,6 +486,7 @@ where
             lower,
             Arc::clone(&self.blob),
             self.machine.shard_id().clone(),
+            self.key_columns.clone(),
         )
     }
 
@@ -11,6 +11,7 @@
 
 use bytes::BufMut;
 
+use crate::columnar::ColumnData;
 use crate::{Codec, Codec64};
 
 impl Codec for () {
@@ -48,6 +49,14 @@ impl Codec for String {
     fn decode<'a>(buf: &'a [u8]) -> Result<Self, String> {
         String::from_utf8(buf.to_owned()).map_err(|err| err.to_string())
     }
+
+    /// Decodes from a single non-null [ColumnData::String] column.
+    fn decode_columns(columns: &[ColumnData], idx: usize) -> Result<Self, String> {
+        match columns {
+            [ColumnData::String(x)] => x[idx].clone().ok_or_else(|| "null String".to_owned()),
+            _ => Err(format!("expected one String column got {:?}", columns)),
+        }
+    }
 }
 
 impl Codec for Vec<u8> {
@@ -65,6 +74,14 @@ impl Codec for Vec<u8> {
     fn decode<'a>(buf: &'a [u8]) -> Result<Self, String> {
         Ok(buf.to_owned())
     }
+
+    /// Decodes from a single non-null [ColumnData::Bytes] column.
+    fn decode_columns(columns: &[ColumnData], idx: usize) -> Result<Self, String> {
+        match columns {
+            [ColumnData::Bytes(x)] => x[idx].clone().ok_or_else(|| "null Vec<u8>".to_owned()),
+            _ => Err(format!("expected one Bytes column got {:?}", columns)),
+        }
+    }
 }
 
 impl Codec64 for i64 {
@@ -0,0 +1,286 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Columnar encodings of persisted keys and values.
+//!
+//! [Codec] turns each key and value into opaque bytes, which is all persist
+//! needs to store, consolidate, and compact data. A [Schema] additionally
+//! describes how to split a type into a fixed set of typed columns, which
+//! persist stores alongside the opaque bytes so that readers can inspect
+//! individual fields without decoding whole records.
+//!
+//! [Codec]: crate::Codec
+
+use std::fmt::Debug;
+
+/// The type of a column in the columnar encoding of a [Schema].
+///
+/// Every column is nullable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnType {
+    /// A column of bools.
+    Bool,
+    /// A column of i16s.
+    Int16,
+    /// A column of i32s.
+    Int32,
+    /// A column of i64s.
+    Int64,
+    /// A column of f32s.
+    Float32,
+    /// A column of f64s.
+    Float64,
+    /// A column of utf-8 strings.
+    String,
+    /// A column of arbitrary bytes.
+    Bytes,
+}
+
+/// The contents of a column in the columnar encoding of a [Schema].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColumnData {
+    /// The contents of a [ColumnType::Bool] column.
+    Bool(Vec<Option<bool>>),
+    /// The contents of a [ColumnType::Int16] column.
+    Int16(Vec<Option<i16>>),
+    /// The contents of a [ColumnType::Int32] column.
+    Int32(Vec<Option<i32>>),
+    /// The contents of a [ColumnType::Int64] column.
+    Int64(Vec<Option<i64>>),
+    /// The contents of a [ColumnType::Float32] column.
+    Float32(Vec<Option<f32>>),
+    /// The contents of a [ColumnType::Float64] column.
+    Float64(Vec<Option<f64>>),
+    /// The contents of a [ColumnType::String] column.
+    String(Vec<Option<String>>),
+    /// The contents of a [ColumnType::Bytes] column.
+    Bytes(Vec<Option<Vec<u8>>>),
+}
+
+impl ColumnData {
+    /// Returns an empty column of the given type.
+    pub fn new(typ: ColumnType) -> Self {
+        match typ {
+            ColumnType::Bool => ColumnData::Bool(Vec::new()),
+            ColumnType::Int16 => ColumnData::Int16(Vec::new()),
+            ColumnType::Int32 => ColumnData::Int32(Vec::new()),
+            ColumnType::Int64 => ColumnData::Int64(Vec::new()),
+            ColumnType::Float32 => ColumnData::Float32(Vec::new()),
+            ColumnType::Float64 => ColumnData::Float64(Vec::new()),
+            ColumnType::String => ColumnData::String(Vec::new()),
+            ColumnType::Bytes => ColumnData::Bytes(Vec::new()),
+        }
+    }
+
+    /// The type of this column.
+    pub fn typ(&self) -> ColumnType {
+        match self {
+            ColumnData::Bool(_) => ColumnType::Bool,
+            ColumnData::Int16(_) => ColumnType::Int16,
+            ColumnData::Int32(_) => ColumnType::Int32,
+            ColumnData::Int64(_) => ColumnType::Int64,
+            ColumnData::Float32(_) => ColumnType::Float32,
+            ColumnData::Float64(_) => ColumnType::Float64,
+            ColumnData::String(_) => ColumnType::String,
+            ColumnData::Bytes(_) => ColumnType::Bytes,
+        }
+    }
+
+    /// The number of entries in this column.
+    pub fn len(&self) -> usize {
+        match self {
+            ColumnData::Bool(x) => x.len(),
+            ColumnData::Int16(x) => x.len(),
+            ColumnData::Int32(x) => x.len(),
+            ColumnData::Int64(x) => x.len(),
+            ColumnData::Float32(x) => x.len(),
+            ColumnData::Float64(x) => x.len(),
+            ColumnData::String(x) => x.len(),
+            ColumnData::Bytes(x) => x.len(),
+        }
+    }
+
+    /// Whether this column has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends a null entry to this column.
+    pub fn push_null(&mut self) {
+        match self {
+            ColumnData::Bool(x) => x.push(None),
+            ColumnData::Int16(x) => x.push(None),
+            ColumnData::Int32(x) => x.push(None),
+            ColumnData::Int64(x) => x.push(None),
+            ColumnData::Float32(x) => x.push(None),
+            ColumnData::Float64(x) => x.push(None),
+            ColumnData::String(x) => x.push(None),
+            ColumnData::Bytes(x) => x.push(None),
+        }
+    }
+
+    /// Whether the entry at `idx` is null.
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn is_null(&self, idx: usize) -> bool {
+        match self {
+            ColumnData::Bool(x) => x[idx].is_none(),
+            ColumnData::Int16(x) => x[idx].is_none(),
+            ColumnData::Int32(x) => x[idx].is_none(),
+            ColumnData::Int64(x) => x[idx].is_none(),
+            ColumnData::Float32(x) => x[idx].is_none(),
+            ColumnData::Float64(x) => x[idx].is_none(),
+            ColumnData::String(x) => x[idx].is_none(),
+            ColumnData::Bytes(x) => x[idx].is_none(),
+        }
+    }
+
+    /// Computes summary statistics of the entries in this column.
+    pub fn stats(&self) -> ColumnStats {
+        let nulls = (0..self.len()).filter(|idx| self.is_null(*idx)).count();
+        let bounds = match self {
+            ColumnData::Bool(x) => ord_bounds(x.iter().flatten())
+                .map(|(lower, upper)| ColumnBounds::Bool(*lower, *upper)),
+            ColumnData::Int16(x) => ord_bounds(x.iter().flatten())
+                .map(|(lower, upper)| ColumnBounds::Int16(*lower, *upper)),
+            ColumnData::Int32(x) => ord_bounds(x.iter().flatten())
+                .map(|(lower, upper)| ColumnBounds::Int32(*lower, *upper)),
+            ColumnData::Int64(x) => ord_bounds(x.iter().flatten())
+                .map(|(lower, upper)| ColumnBounds::Int64(*lower, *upper)),
+            ColumnData::Float32(x) => float_bounds(x.iter().flatten().copied())
+                .map(|(lower, upper)| ColumnBounds::Float32(lower, upper)),
+            ColumnData::Float64(x) => float_bounds(x.iter().flatten().copied())
+                .map(|(lower, upper)| ColumnBounds::Float64(lower, upper)),
+            ColumnData::String(x) => {
+                if x.iter().flatten().any(|x| x.len() > MAX_STRING_BOUND_LEN) {
+                    None
+                } else {
+                    ord_bounds(x.iter().flatten())
+                        .map(|(lower, upper)| ColumnBounds::String(lower.clone(), upper.clone()))
+                }
+            }
+            ColumnData::Bytes(_) => None,
+        };
+        ColumnStats { nulls, bounds }
+    }
+}
+
+/// Strings longer than this (in bytes) are not tracked in [ColumnBounds].
+///
+/// Bounds are stored in persist's metadata for every part, so they need to
+/// stay small.
+pub const MAX_STRING_BOUND_LEN: usize = 100;
+
+/// Summary statistics of the entries in a column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnStats {
+    /// The number of null entries.
+    pub nulls: usize,
+    /// The smallest and largest non-null entries, if known.
+    ///
+    /// This is None if every entry is null or if bounds are not tracked for
+    /// the column (e.g. for [ColumnType::Bytes] columns).
+    pub bounds: Option<ColumnBounds>,
+}
+
+/// The (inclusive) smallest and largest non-null entries of a column.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColumnBounds {
+    /// Bounds of a [ColumnType::Bool] column.
+    Bool(bool, bool),
+    /// Bounds of a [ColumnType::Int16] column.
+    Int16(i16, i16),
+    /// Bounds of a [ColumnType::Int32] column.
+    Int32(i32, i32),
+    /// Bounds of a [ColumnType::Int64] column.
+    Int64(i64, i64),
+    /// Bounds of a [ColumnType::Float32] column without NaNs.
+    Float32(f32, f32),
+    /// Bounds of a [ColumnType::Float64] column without NaNs.
+    Float64(f64, f64),
+    /// Bounds of a [ColumnType::String] column with no entry longer than
+    /// [MAX_STRING_BOUND_LEN].
+    String(String, String),
+}
+
+fn ord_bounds<'a, T: Ord + 'a, I: Iterator<Item = &'a T>>(mut vals: I) -> Option<(&'a T, &'a T)> {
+    let first = vals.next()?;
+    Some(vals.fold((first, first), |(lower, upper), x| {
+        (std::cmp::min(lower, x), std::cmp::max(upper, x))
+    }))
+}
+
+fn float_bounds<T: PartialOrd + Copy, I: Iterator<Item = T>>(vals: I) -> Option<(T, T)> {
+    let mut bounds: Option<(T, T)> = None;
+    for x in vals {
+        // NaN is the only value that isn't comparable to itself. It doesn't
+        // have a place in a (lower, upper) range, so give up on bounds.
+        if x.partial_cmp(&x).is_none() {
+            return None;
+        }
+        bounds = match bounds {
+            None => Some((x, x)),
+            Some((lower, upper)) => Some((
+                if x < lower { x } else { lower },
+                if x > upper { x } else { upper },
+            )),
+        };
+    }
+    bounds
+}
+
+/// A description of how to encode a type as a fixed set of typed columns.
+///
+/// Implementations must be deterministic: the same value always encodes to the
+/// same column entries, and every value of the type encodes to the columns
+/// returned by [Schema::columns].
+pub trait Schema<T>: Debug + Send + Sync {
+    /// The names and types of the columns a value is encoded into.
+    ///
+    /// Names must be unique.
+    fn columns(&self) -> Vec<(String, ColumnType)>;
+
+    /// Appends exactly one entry for `val` to each of `columns`, which are in
+    /// the order returned by [Schema::columns].
+    fn encode(&self, val: &T, columns: &mut [ColumnData]) -> Result<(), String>;
+
+    /// Decodes the value at `idx` of `columns`, which are in the order returned
+    /// by [Schema::columns].
+    fn decode(&self, columns: &[ColumnData], idx: usize) -> Result<T, String>;
+}
+
+/// Encodes `vals` into the named columns of `schema`.
+pub fn encode_columns<'a, T, I>(
+    schema: &dyn Schema<T>,
+    vals: I,
+) -> Result<Vec<(String, ColumnData)>, String>
+where
+    T: 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    let (names, mut columns): (Vec<_>, Vec<_>) = schema
+        .columns()
+        .into_iter()
+        .map(|(name, typ)| (name, ColumnData::new(typ)))
+        .unzip();
+    let mut len = 0;
+    for val in vals {
+        schema.encode(val, &mut columns)?;
+        len += 1;
+        if let Some(col) = columns.iter().find(|col| col.len() != len) {
+            return Err(format!(
+                "schema encoded {} entries for a {:?} column, expected {}",
+                col.len(),
+                col.typ(),
+                len
+            ));
+        }
+    }
+    Ok(names.into_iter().zip(columns).collect())
+}
@@ -18,7 +18,10 @@
 
 use bytes::BufMut;
 
+use crate::columnar::ColumnData;
+
 mod codec_impls;
+pub mod columnar;
 
 /// Encoding and decoding operations for a type usable as a persisted key or
 /// value.
@@ -49,6 +52,22 @@ pub trait Codec: Sized + 'static {
     // TODO: Mechanically, this could return a ref to the original bytes
     // without any copies, see if we can make the types work out for that.
     fn decode<'a>(buf: &'a [u8]) -> Result<Self, String>;
+
+    /// Decode the value at `idx` of the typed columns that a
+    /// [columnar::Schema] for this type encoded values into.
+    ///
+    /// `columns` are all of the schema's columns, in the order returned by
+    /// [columnar::Schema::columns]. Persist stores the keys of shards written
+    /// with a key schema only as typed columns, and readers don't necessarily
+    /// have the schema, so types that have one must be able to decode values
+    /// from the columns alone.
+    fn decode_columns(columns: &[ColumnData], idx: usize) -> Result<Self, String> {
+        let _ = (columns, idx);
+        Err(format!(
+            "{} can't be decoded from typed columns",
+            Self::codec_name()
+        ))
+    }
 }
 
 /// Encoding and decoding operations for a type usable as a persisted timestamp
@@ -21,6 +21,7 @@ use crate::location::{Blob, Consensus, ExternalError};
 use crate::mem::{MemBlob, MemBlobConfig, MemConsensus};
 use crate::postgres::{PostgresConsensus, PostgresConsensusConfig};
 use crate::s3::{S3Blob, S3BlobConfig};
+use crate::sqlite::{SqliteConsensus, SqliteConsensusConfig};
 
 /// Config for an implementation of [Blob].
 #[derive(Debug, Clone)]
@@ -122,6 +123,8 @@ impl BlobConfig {
 pub enum ConsensusConfig {
     /// Config for [PostgresConsensus].
     Postgres(PostgresConsensusConfig),
+    /// Config for [SqliteConsensus].
+    Sqlite(SqliteConsensusConfig),
     /// Config for [MemConsensus], only available in testing.
     Mem,
 }
@@ -133,6 +136,9 @@ impl ConsensusConfig {
             ConsensusConfig::Postgres(config) => PostgresConsensus::open(config)
                 .await
                 .map(|x| Arc::new(x) as Arc<dyn Consensus + Send + Sync>),
+            ConsensusConfig::Sqlite(config) => SqliteConsensus::open(config)
+                .await
+                .map(|x| Arc::new(x) as Arc<dyn Consensus + Send + Sync>),
             ConsensusConfig::Mem => {
                 Ok(Arc::new(MemConsensus::default()) as Arc<dyn Consensus + Send + Sync>)
             }
@@ -153,6 +159,32 @@ impl ConsensusConfig {
             "postgres" | "postgresql" => Ok(ConsensusConfig::Postgres(
                 PostgresConsensusConfig::new(value).await?,
             )),
+            // The database lives at the absolute path of the url, as in
+            // `sqlite:///path/to/consensus.db`. Anything in the host position,
+            // as in `sqlite://consensus.db`, would otherwise be dropped.
+            "sqlite" => {
+                if url.host_str().is_some() {
+                    Err(anyhow!(
+                        "sqlite consensus location must not have a host: {}",
+                        url.as_str()
+                    ))
+                } else if url.path().is_empty() || url.path().ends_with('/') {
+                    Err(anyhow!(
+                        "sqlite consensus location is missing a path: {}",
+                        url.as_str()
+                    ))
+                } else {
+                    // Also percent-decodes the path.
+                    url.to_file_path()
+                        .map(|path| ConsensusConfig::Sqlite(SqliteConsensusConfig::from(path)))
+                        .map_err(|()| {
+                            anyhow!(
+                                "sqlite consensus location must have an absolute path: {}",
+                                url.as_str()
+                            )
+                        })
+                }
+            }
             "mem" => {
                 if !cfg!(debug_assertions) {
                     warn!("persist unexpectedly using in-mem consensus in a release binary");
@@ -168,3 +200,41 @@ impl ConsensusConfig {
         Ok(config)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn sqlite_path(value: &str) -> Result<SqliteConsensusConfig, String> {
+        match ConsensusConfig::try_from(value).await {
+            Ok(ConsensusConfig::Sqlite(config)) => Ok(config),
+            Ok(config) => panic!("unexpected consensus config: {:?}", config),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn sqlite_consensus_location() {
+        assert_eq!(
+            sqlite_path("sqlite:///tmp/consensus.db").await,
+            Ok(SqliteConsensusConfig::from("/tmp/consensus.db"))
+        );
+        assert_eq!(
+            sqlite_path("sqlite:///tmp/my%20consensus.db").await,
+            Ok(SqliteConsensusConfig::from("/tmp/my consensus.db"))
+        );
+
+        // A host is not part of the path.
+        assert!(sqlite_path("sqlite://consensus.db").await.is_err());
+        assert!(sqlite_path("sqlite://localhost/tmp/consensus.db")
+            .await
+            .is_err());
+        // An empty path would open a temporary database.
+        assert!(sqlite_path("sqlite:").await.is_err());
+        assert!(sqlite_path("sqlite://").await.is_err());
+        assert!(sqlite_path("sqlite:///").await.is_err());
+        assert!(sqlite_path("sqlite:///tmp/").await.is_err());
+        // Relative paths are ambiguous.
+        assert!(sqlite_path("sqlite:consensus.db").await.is_err());
+    }
+}
@@ -0,0 +1,505 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A [Blob] wrapper that caches values on local disk.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::fs::File;
+use std::io::ErrorKind;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use bytes::Bytes;
+use md5::{Digest, Md5};
+use mz_ore::cast::CastFrom;
+use mz_ore::metrics::{IntCounter, UIntGauge};
+use tokio::fs;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::location::{Atomicity, Blob, ExternalError};
+
+/// Configuration for opening a [DiskCacheBlob].
+#[derive(Debug, Clone)]
+pub struct DiskCacheConfig {
+    /// The directory in which cached values are stored.
+    pub dir: PathBuf,
+    /// The maximum total size in bytes of the cached values.
+    pub max_bytes: usize,
+}
+
+impl DiskCacheConfig {
+    /// Returns the config for caching the values of the [Blob] at the given
+    /// location, in a subdirectory of this config's dir.
+    ///
+    /// The subdirectory is named after a hash of the location, so that the
+    /// location (which may include credentials) doesn't end up on disk.
+    pub fn for_location(&self, location: &str) -> Self {
+        let digest = Md5::digest(location.as_bytes());
+        let mut name = String::with_capacity(2 * digest.len());
+        for b in digest.iter() {
+            write!(&mut name, "{:02x}", b).expect("writing to a String cannot fail");
+        }
+        DiskCacheConfig {
+            dir: self.dir.join(name),
+            max_bytes: self.max_bytes,
+        }
+    }
+}
+
+/// Metrics for a [DiskCacheBlob].
+#[derive(Debug, Clone)]
+pub struct DiskCacheMetrics {
+    /// Count of gets served from the cache.
+    pub hits: IntCounter,
+    /// Count of gets forwarded to the wrapped [Blob].
+    pub misses: IntCounter,
+    /// Count of values evicted from the cache to make room for others.
+    pub evictions: IntCounter,
+    /// Total size in bytes of the values evicted from the cache.
+    pub evicted_bytes: IntCounter,
+    /// Total size in bytes of the values in the cache.
+    pub size_bytes: UIntGauge,
+}
+
+/// A [Blob] wrapper that caches the values it gets in files on local disk.
+///
+/// The cache is bounded by [DiskCacheConfig::max_bytes] and evicts the least
+/// recently used values first. Cached files outlive the process: they are
+/// picked up again by the next [DiskCacheBlob] opened with the same dir.
+///
+/// Values are cached by key, so this must only wrap blobs in which a key is
+/// never set to a different value, as is the case for persist's (immutable,
+/// uniquely named) batch parts.
+///
+/// Each dir is used by at most one [DiskCacheBlob] at a time, in this or any
+/// other process: it holds an exclusive lock on a file in the dir while open,
+/// and opening another one with the same dir fails.
+#[derive(Debug)]
+pub struct DiskCacheBlob {
+    blob: Arc<dyn Blob + Send + Sync>,
+    dir: PathBuf,
+    /// The locked [LOCK_FILE_NAME] file, which is unlocked when closed.
+    _lock: File,
+    max_bytes: usize,
+    lru: Mutex<Lru>,
+    metrics: DiskCacheMetrics,
+}
+
+impl DiskCacheBlob {
+    /// Opens a cache of the values of `blob` in the given dir, picking up any
+    /// values already cached there.
+    pub async fn open(
+        config: DiskCacheConfig,
+        blob: Arc<dyn Blob + Send + Sync>,
+        metrics: DiskCacheMetrics,
+    ) -> Result<Self, ExternalError> {
+        let DiskCacheConfig { dir, max_bytes } = config;
+        fs::create_dir_all(&dir).await?;
+        let lock = lock_dir(&dir)?;
+
+        let mut cached = Vec::new();
+        let mut entries = fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_name() == LOCK_FILE_NAME {
+                continue;
+            }
+            let path = entry.path();
+            let key = entry.file_name().to_str().and_then(key_from_file_name);
+            match key {
+                Some(key) => {
+                    let metadata = entry.metadata().await?;
+                    let size = usize::cast_from(metadata.len());
+                    let used = metadata.accessed().or_else(|_| metadata.modified())?;
+                    cached.push((used, key, size));
+                }
+                // Leftover temporary file from an interrupted insert.
+                None => remove_file(&path).await?,
+            }
+        }
+        // Start out with the most recently used values at the back.
+        cached.sort_by_key(|(used, _, _)| *used);
+
+        let ret = DiskCacheBlob {
+            blob,
+            dir,
+            _lock: lock,
+            max_bytes,
+            lru: Mutex::new(Lru::default()),
+            metrics,
+        };
+        let mut evicted = Vec::new();
+        {
+            let mut lru = ret.lru.lock().expect("lock poisoned");
+            for (_used, key, size) in cached {
+                ret.metrics.size_bytes.add(u64::cast_from(size));
+                evicted.extend(lru.insert(key, size, max_bytes));
+            }
+        }
+        ret.evict(evicted).await;
+        Ok(ret)
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(file_name(key))
+    }
+
+    /// Returns the cached value of the key, if any.
+    async fn cached(&self, key: &str) -> Option<Vec<u8>> {
+        if !self.lru.lock().expect("lock poisoned").touch(key) {
+            return None;
+        }
+        match fs::read(self.path(key)).await {
+            Ok(value) => Some(value),
+            Err(err) => {
+                // Most likely evicted concurrently, but in any case, the
+                // value can always be fetched again.
+                if err.kind() != ErrorKind::NotFound {
+                    warn!("failed to read cached blob value for key {}: {}", key, err);
+                }
+                self.forget(key);
+                None
+            }
+        }
+    }
+
+    /// Adds the value of the key to the cache, evicting other values if
+    /// necessary to make room for it.
+    async fn insert(&self, key: &str, value: &[u8]) -> Result<(), std::io::Error> {
+        if value.len() > self.max_bytes {
+            return Ok(());
+        }
+        // Write to a temp file and rename it into place, so that we never
+        // observe partially written values (even after a crash).
+        let path = self.path(key);
+        let tmp_path = self
+            .dir
+            .join(format!("{}.{}.tmp", file_name(key), Uuid::new_v4()));
+        fs::write(&tmp_path, value).await?;
+        fs::rename(&tmp_path, &path).await?;
+
+        let evicted = {
+            let mut lru = self.lru.lock().expect("lock poisoned");
+            if lru.contains(key) {
+                // Inserted concurrently.
+                return Ok(());
+            }
+            self.metrics.size_bytes.add(u64::cast_from(value.len()));
+            lru.insert(key.to_owned(), value.len(), self.max_bytes)
+        };
+        self.evict(evicted).await;
+        Ok(())
+    }
+
+    /// Removes the key from the cache without counting it as an eviction.
+    fn forget(&self, key: &str) {
+        if let Some(size) = self.lru.lock().expect("lock poisoned").remove(key) {
+            self.metrics.size_bytes.sub(u64::cast_from(size));
+        }
+    }
+
+    async fn evict(&self, evicted: Vec<(String, usize)>) {
+        for (key, size) in evicted {
+            self.metrics.evictions.inc();
+            self.metrics.evicted_bytes.inc_by(u64::cast_from(size));
+            self.metrics.size_bytes.sub(u64::cast_from(size));
+            if let Err(err) = remove_file(&self.path(&key)).await {
+                warn!("failed to evict cached blob value for key {}: {}", key, err);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Blob for DiskCacheBlob {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ExternalError> {
+        if let Some(value) = self.cached(key).await {
+            self.metrics.hits.inc();
+            return Ok(Some(value));
+        }
+        self.metrics.misses.inc();
+        let value = self.blob.get(key).await?;
+        if let Some(value) = value.as_ref() {
+            // The cache is purely an optimization, so don't fail the get if
+            // we can't populate it.
+            if let Err(err) = self.insert(key, value).await {
+                warn!("failed to cache blob value for key {}: {}", key, err);
+            }
+        }
+        Ok(value)
+    }
+
+    async fn list_keys(&self) -> Result<Vec<String>, ExternalError> {
+        self.blob.list_keys().await
+    }
+
+    async fn set(&self, key: &str, value: Bytes, atomic: Atomicity) -> Result<(), ExternalError> {
+        // Intentionally don't populate the cache on set: values are usually
+        // read by some other process than the one that wrote them, so it's
+        // better to leave the room for values that are actually read.
+        self.blob.set(key, value, atomic).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), ExternalError> {
+        self.blob.delete(key).await?;
+        self.forget(key);
+        if let Err(err) = remove_file(&self.path(key)).await {
+            warn!(
+                "failed to delete cached blob value for key {}: {}",
+                key, err
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The bookkeeping of which keys are cached, in order of last use.
+#[derive(Debug, Default)]
+struct Lru {
+    /// The size and last use of the value of each cached key.
+    entries: HashMap<String, (usize, u64)>,
+    /// The cached keys by last use.
+    by_use: BTreeMap<u64, String>,
+    /// The total size of the cached values.
+    bytes: usize,
+    next_use: u64,
+}
+
+impl Lru {
+    fn contains(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Marks the key as used, returning whether it's cached.
+    fn touch(&mut self, key: &str) -> bool {
+        let next_use = self.next_use;
+        match self.entries.get_mut(key) {
+            Some((_size, used)) => {
+                let key = self.by_use.remove(used).expect("entry missing from by_use");
+                *used = next_use;
+                self.by_use.insert(next_use, key);
+                self.next_use += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds the key as the most recently used one, returning the keys (and
+    /// sizes) that had to be evicted to stay within `max_bytes`.
+    fn insert(&mut self, key: String, size: usize, max_bytes: usize) -> Vec<(String, usize)> {
+        self.remove(&key);
+        self.entries.insert(key.clone(), (size, self.next_use));
+        self.by_use.insert(self.next_use, key);
+        self.next_use += 1;
+        self.bytes += size;
+
+        let mut evicted = Vec::new();
+        while self.bytes > max_bytes {
+            let used = *self
+                .by_use
+                .keys()
+                .next()
+                .expect("bytes > max_bytes implies an entry");
+            let key = self.by_use.remove(&used).expect("key exists");
+            let (size, _used) = self.entries.remove(&key).expect("entry missing");
+            self.bytes -= size;
+            evicted.push((key, size));
+        }
+        evicted
+    }
+
+    /// Removes the key, returning the size of its value if it was cached.
+    fn remove(&mut self, key: &str) -> Option<usize> {
+        let (size, used) = self.entries.remove(key)?;
+        self.by_use.remove(&used);
+        self.bytes -= size;
+        Some(size)
+    }
+}
+
+/// Returns the name of the file that caches the value of the key.
+///
+/// Every byte other than ASCII alphanumerics, `-`, and `_` is escaped as `%XX`,
+/// so the names never contain a `.` (which temp files use) and can be mapped
+/// back to the key.
+fn file_name(key: &str) -> String {
+    let mut ret = String::with_capacity(key.len());
+    for b in key.bytes() {
+        match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => ret.push(char::from(b)),
+            b => write!(&mut ret, "%{:02X}", b).expect("writing to a String cannot fail"),
+        }
+    }
+    ret
+}
+
+/// The inverse of [file_name], returning None for files that don't cache a
+/// value.
+fn key_from_file_name(name: &str) -> Option<String> {
+    let mut ret = Vec::with_capacity(name.len());
+    let mut bytes = name.bytes();
+    while let Some(b) = bytes.next() {
+        match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => ret.push(b),
+            b'%' => {
+                let hex = [bytes.next()?, bytes.next()?];
+                let hex = std::str::from_utf8(&hex).ok()?;
+                ret.push(u8::from_str_radix(hex, 16).ok()?);
+            }
+            _ => return None,
+        }
+    }
+    String::from_utf8(ret).ok()
+}
+
+/// The name of the file that a [DiskCacheBlob] locks in its dir. It contains a
+/// `.`, so it's never mistaken for a cached value.
+const LOCK_FILE_NAME: &str = "cache.lock";
+
+/// Takes an exclusive lock on the [LOCK_FILE_NAME] file in the dir, failing if
+/// it's already held by another [DiskCacheBlob].
+///
+/// The lock is held until the returned file is closed, including when the
+/// process exits.
+fn lock_dir(dir: &Path) -> Result<File, ExternalError> {
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .open(dir.join(LOCK_FILE_NAME))?;
+    // SAFETY: The fd is open for as long as `file` is alive.
+    let ret = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) };
+    if ret != 0 {
+        let err = std::io::Error::last_os_error();
+        if err.kind() == ErrorKind::WouldBlock {
+            return Err(ExternalError::from(anyhow!(
+                "blob cache dir {} is already in use",
+                dir.display()
+            )));
+        }
+        return Err(err.into());
+    }
+    Ok(file)
+}
+
+async fn remove_file(path: &Path) -> Result<(), std::io::Error> {
+    match fs::remove_file(path).await {
+        Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_ore::metric;
+    use mz_ore::metrics::MetricsRegistry;
+
+    use crate::mem::{MemBlob, MemBlobConfig};
+
+    use super::*;
+
+    fn metrics() -> DiskCacheMetrics {
+        let registry = MetricsRegistry::new();
+        DiskCacheMetrics {
+            hits: registry.register(metric!(name: "hits", help: "hits")),
+            misses: registry.register(metric!(name: "misses", help: "misses")),
+            evictions: registry.register(metric!(name: "evictions", help: "evictions")),
+            evicted_bytes: registry.register(metric!(name: "evicted_bytes", help: "evicted bytes")),
+            size_bytes: registry.register(metric!(name: "size_bytes", help: "size bytes")),
+        }
+    }
+
+    #[test]
+    fn file_name_roundtrip() {
+        for key in ["", "abc-123_XYZ", "a/b.c", "%41", "\u{1F600}"] {
+            let name = file_name(key);
+            assert!(!name.contains('.') && !name.contains('/'), "{}", name);
+            assert_eq!(key_from_file_name(&name).as_deref(), Some(key));
+        }
+        assert_eq!(key_from_file_name("abc.123.tmp"), None);
+        assert_eq!(key_from_file_name("abc%4"), None);
+    }
+
+    #[tokio::test]
+    async fn disk_cache_blob() -> Result<(), ExternalError> {
+        let temp_dir = tempfile::tempdir()?;
+        let config = DiskCacheConfig {
+            dir: temp_dir.path().join("cache"),
+            max_bytes: 10,
+        };
+        let blob: Arc<dyn Blob + Send + Sync> = Arc::new(MemBlob::open(MemBlobConfig::default()));
+        for (key, value) in [
+            ("k0", "0123"),
+            ("k1", "4567"),
+            ("k2", "89ab"),
+            ("k3", "too large!!"),
+        ] {
+            blob.set(key, Bytes::from(value), Atomicity::RequireAtomic)
+                .await?;
+        }
+
+        let metrics = metrics();
+        let cache = DiskCacheBlob::open(config.clone(), Arc::clone(&blob), metrics.clone()).await?;
+
+        // The first get of a key misses and the second hits.
+        assert_eq!(cache.get("k0").await?, Some(b"0123".to_vec()));
+        assert_eq!(cache.get("k0").await?, Some(b"0123".to_vec()));
+        assert_eq!((metrics.hits.get(), metrics.misses.get()), (1, 1));
+        // Keys that don't exist aren't cached.
+        assert_eq!(cache.get("nope").await?, None);
+        assert_eq!(cache.get("nope").await?, None);
+        assert_eq!((metrics.hits.get(), metrics.misses.get()), (1, 3));
+        // Neither are values larger than the cache.
+        assert_eq!(cache.get("k3").await?, Some(b"too large!!".to_vec()));
+        assert_eq!(metrics.size_bytes.get(), 4);
+
+        // Filling up the cache evicts the least recently used key, which is
+        // k1 because k0 was used more recently.
+        cache.get("k1").await?;
+        cache.get("k0").await?;
+        cache.get("k2").await?;
+        assert_eq!(
+            (metrics.evictions.get(), metrics.evicted_bytes.get()),
+            (1, 4)
+        );
+        assert_eq!(metrics.size_bytes.get(), 8);
+        let misses = metrics.misses.get();
+        cache.get("k0").await?;
+        cache.get("k2").await?;
+        assert_eq!(metrics.misses.get(), misses);
+        cache.get("k1").await?;
+        assert_eq!(metrics.misses.get(), misses + 1);
+
+        // Deleting a key removes it from the cache.
+        cache.delete("k1").await?;
+        assert_eq!(cache.get("k1").await?, None);
+        assert_eq!(metrics.size_bytes.get(), 4);
+
+        // The dir can't be used by another cache while this one is open.
+        assert!(
+            DiskCacheBlob::open(config.clone(), Arc::clone(&blob), metrics.clone())
+                .await
+                .is_err()
+        );
+        drop(cache);
+
+        // The cached values survive reopening the cache.
+        let metrics = metrics();
+        let cache = DiskCacheBlob::open(config, blob, metrics.clone()).await?;
+        assert_eq!(metrics.size_bytes.get(), 4);
+        assert_eq!(cache.get("k2").await?, Some(b"89ab".to_vec()));
+        assert_eq!((metrics.hits.get(), metrics.misses.get()), (1, 0));
+
+        Ok(())
+    }
+}
@@ -133,6 +133,22 @@ impl ColumnarRecords {
     pub fn iter<'a>(&'a self) -> ColumnarRecordsIter<'a> {
         self.borrow().iter()
     }
+
+    /// Returns a copy of Self with every key replaced by an empty one, for
+    /// parts that store their keys elsewhere (as typed columns).
+    pub fn without_keys(&self) -> ColumnarRecords {
+        let ret = ColumnarRecords {
+            len: self.len,
+            key_data: Buffer::from(Vec::new()),
+            key_offsets: Buffer::from(vec![0; self.len + 1]),
+            val_data: self.val_data.clone(),
+            val_offsets: self.val_offsets.clone(),
+            timestamps: self.timestamps.clone(),
+            diffs: self.diffs.clone(),
+        };
+        debug_assert_eq!(ret.borrow().validate(), Ok(()));
+        ret
+    }
 }
 
 // TODO: deduplicate this with the other FromIterator implementation.
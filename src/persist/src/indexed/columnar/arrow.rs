@@ -13,12 +13,13 @@ use std::collections::BTreeMap;
 use std::io::{Read, Seek, Write};
 use std::sync::Arc;
 
-use arrow2::array::{Array, BinaryArray, PrimitiveArray};
+use arrow2::array::{Array, BinaryArray, BooleanArray, PrimitiveArray, Utf8Array};
 use arrow2::chunk::Chunk;
 use arrow2::datatypes::{DataType, Field, Schema};
 use arrow2::io::ipc::read::{read_file_metadata, FileMetadata, FileReader};
 use arrow2::io::ipc::write::{FileWriter, WriteOptions};
 use differential_dataflow::trace::Description;
+use mz_persist_types::columnar::{ColumnData, ColumnType};
 use once_cell::sync::Lazy;
 use timely::progress::{Antichain, Timestamp};
 
@@ -26,7 +27,7 @@ use crate::error::Error;
 use crate::gen::persist::ProtoBatchFormat;
 use crate::indexed::columnar::ColumnarRecords;
 use crate::indexed::encoding::{
-    decode_trace_inline_meta, encode_trace_inline_meta, BlobTraceBatchPart,
+    decode_trace_inline_meta, encode_trace_inline_meta, BlobTraceBatchPart, PartCompression,
 };
 
 /// The Arrow schema we use to encode ((K, V), T, D) tuples.
@@ -78,12 +79,21 @@ const INLINE_METADATA_KEY: &'static str = "MZ:inline";
 /// Encodes an BlobTraceBatchPart into the Arrow file format.
 ///
 /// NB: This is currently unused, but it's here because we may want to use it
-/// for the local cache and so we can easily compare arrow vs parquet.
+/// for the local cache and so we can easily compare arrow vs parquet. It
+/// doesn't (yet) compress the batch or support typed key columns, which are
+/// the only copy of the keys of the batches that have them.
 pub fn encode_trace_arrow<W: Write>(w: &mut W, batch: &BlobTraceBatchPart) -> Result<(), Error> {
+    if !batch.key_columns.is_empty() {
+        return Err("typed key columns not supported in arrow".into());
+    }
     let mut metadata = BTreeMap::new();
     metadata.insert(
         INLINE_METADATA_KEY.into(),
-        encode_trace_inline_meta(batch, ProtoBatchFormat::ArrowKvtd),
+        encode_trace_inline_meta(
+            batch,
+            ProtoBatchFormat::ArrowKvtd,
+            PartCompression::Uncompressed,
+        ),
     );
     let schema = Schema::from(SCHEMA_ARROW_KVTD.fields.clone()).with_metadata(metadata);
     let options = WriteOptions { compression: None };
@@ -101,7 +111,7 @@ pub fn encode_trace_arrow<W: Write>(w: &mut W, batch: &BlobTraceBatchPart) -> Re
 /// for the local cache and so we can easily compare arrow vs parquet.
 pub fn decode_trace_arrow<R: Read + Seek>(r: &mut R) -> Result<BlobTraceBatchPart, Error> {
     let file_meta = read_file_metadata(r)?;
-    let (format, meta) =
+    let (format, compression, meta) =
         decode_trace_inline_meta(file_meta.schema.metadata.get(INLINE_METADATA_KEY))?;
 
     let updates = match format {
@@ -125,6 +135,8 @@ pub fn decode_trace_arrow<R: Read + Seek>(r: &mut R) -> Result<BlobTraceBatchPar
         ),
         index: meta.index,
         updates,
+        key_columns: Vec::new(),
+        compression,
     };
     ret.validate()?;
     Ok(ret)
@@ -234,3 +246,88 @@ pub fn decode_arrow_batch_kvtd(x: &Chunk<Arc<dyn Array>>) -> Result<ColumnarReco
     ret.borrow().validate()?;
     Ok(ret)
 }
+
+/// The arrow [DataType] used to store a column of the given type.
+pub fn arrow_data_type(typ: ColumnType) -> DataType {
+    match typ {
+        ColumnType::Bool => DataType::Boolean,
+        ColumnType::Int16 => DataType::Int16,
+        ColumnType::Int32 => DataType::Int32,
+        ColumnType::Int64 => DataType::Int64,
+        ColumnType::Float32 => DataType::Float32,
+        ColumnType::Float64 => DataType::Float64,
+        ColumnType::String => DataType::Utf8,
+        ColumnType::Bytes => DataType::Binary,
+    }
+}
+
+/// Converts a typed column into an arrow Array.
+pub fn encode_arrow_column(x: &ColumnData) -> Arc<dyn Array> {
+    match x {
+        ColumnData::Bool(x) => Arc::new(BooleanArray::from(x.as_slice())),
+        ColumnData::Int16(x) => Arc::new(PrimitiveArray::<i16>::from(x.as_slice())),
+        ColumnData::Int32(x) => Arc::new(PrimitiveArray::<i32>::from(x.as_slice())),
+        ColumnData::Int64(x) => Arc::new(PrimitiveArray::<i64>::from(x.as_slice())),
+        ColumnData::Float32(x) => Arc::new(PrimitiveArray::<f32>::from(x.as_slice())),
+        ColumnData::Float64(x) => Arc::new(PrimitiveArray::<f64>::from(x.as_slice())),
+        ColumnData::String(x) => Arc::new(Utf8Array::<i32>::from(x.as_slice())),
+        ColumnData::Bytes(x) => Arc::new(BinaryArray::<i32>::from(x.as_slice())),
+    }
+}
+
+/// Converts an arrow Array into a typed column.
+pub fn decode_arrow_column(x: &dyn Array) -> Result<ColumnData, String> {
+    fn downcast<'a, A: 'static>(x: &'a dyn Array) -> Result<&'a A, String> {
+        x.as_any()
+            .downcast_ref::<A>()
+            .ok_or_else(|| format!("array doesn't match data type {:?}", x.data_type()))
+    }
+
+    let ret = match x.data_type() {
+        DataType::Boolean => ColumnData::Bool(downcast::<BooleanArray>(x)?.iter().collect()),
+        DataType::Int16 => ColumnData::Int16(
+            downcast::<PrimitiveArray<i16>>(x)?
+                .iter()
+                .map(|x| x.copied())
+                .collect(),
+        ),
+        DataType::Int32 => ColumnData::Int32(
+            downcast::<PrimitiveArray<i32>>(x)?
+                .iter()
+                .map(|x| x.copied())
+                .collect(),
+        ),
+        DataType::Int64 => ColumnData::Int64(
+            downcast::<PrimitiveArray<i64>>(x)?
+                .iter()
+                .map(|x| x.copied())
+                .collect(),
+        ),
+        DataType::Float32 => ColumnData::Float32(
+            downcast::<PrimitiveArray<f32>>(x)?
+                .iter()
+                .map(|x| x.copied())
+                .collect(),
+        ),
+        DataType::Float64 => ColumnData::Float64(
+            downcast::<PrimitiveArray<f64>>(x)?
+                .iter()
+                .map(|x| x.copied())
+                .collect(),
+        ),
+        DataType::Utf8 => ColumnData::String(
+            downcast::<Utf8Array<i32>>(x)?
+                .iter()
+                .map(|x| x.map(|x| x.to_owned()))
+                .collect(),
+        ),
+        DataType::Binary => ColumnData::Bytes(
+            downcast::<BinaryArray<i32>>(x)?
+                .iter()
+                .map(|x| x.map(|x| x.to_vec()))
+                .collect(),
+        ),
+        typ => return Err(format!("unsupported data type {:?}", typ)),
+    };
+    Ok(ret)
+}
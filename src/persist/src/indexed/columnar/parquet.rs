@@ -11,51 +11,86 @@
 
 use std::io::{Read, Seek, Write};
 
-use arrow2::io::parquet::read::{read_metadata, FileReader};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::Field;
+use arrow2::io::parquet::read::{infer_schema, read_metadata, FileReader};
 use arrow2::io::parquet::write::{
     CompressionOptions, Encoding, FileWriter, KeyValue, RowGroupIterator, Version, WriteOptions,
 };
 use differential_dataflow::trace::Description;
+use mz_persist_types::columnar::ColumnData;
 use timely::progress::{Antichain, Timestamp};
 
 use crate::error::Error;
 use crate::gen::persist::ProtoBatchFormat;
 use crate::indexed::columnar::arrow::{
-    decode_arrow_batch_kvtd, encode_arrow_batch_kvtd, SCHEMA_ARROW_KVTD,
+    arrow_data_type, decode_arrow_batch_kvtd, decode_arrow_column, encode_arrow_batch_kvtd,
+    encode_arrow_column, SCHEMA_ARROW_KVTD,
 };
 use crate::indexed::columnar::ColumnarRecords;
 use crate::indexed::encoding::{
-    decode_trace_inline_meta, encode_trace_inline_meta, BlobTraceBatchPart,
+    decode_trace_inline_meta, encode_trace_inline_meta, BlobTraceBatchPart, PartCompression,
 };
 
 const INLINE_METADATA_KEY: &'static str = "MZ:inline";
 
+/// The prefix of the names of the typed key columns written after the (K, V,
+/// T, D) columns.
+const KEY_COLUMN_PREFIX: &'static str = "k.";
+
+/// The indexes of the (K, V, T, D) columns, which are always the first four.
+const KVTD_PROJECTION: [usize; 4] = [0, 1, 2, 3];
+
 /// Encodes an BlobTraceBatchPart into the Parquet format.
+///
+/// Any typed key columns in the batch are written as additional nullable
+/// columns named `k.<name>` after the (K, V, T, D) columns. All columns are
+/// compressed with the batch's codec.
 pub fn encode_trace_parquet<W: Write>(w: &mut W, batch: &BlobTraceBatchPart) -> Result<(), Error> {
     // Better to error now than write out an invalid batch.
     batch.validate()?;
     encode_parquet_kvtd(
         w,
-        encode_trace_inline_meta(batch, ProtoBatchFormat::ParquetKvtd),
+        encode_trace_inline_meta(batch, ProtoBatchFormat::ParquetKvtd, batch.compression),
         &batch.updates,
+        &batch.key_columns,
+        batch.compression,
     )
 }
 
-/// Decodes a BlobTraceBatchPart from the Parquet format.
+/// Decodes a BlobTraceBatchPart, including all of its typed key columns, from
+/// the Parquet format.
 pub fn decode_trace_parquet<R: Read + Seek>(r: &mut R) -> Result<BlobTraceBatchPart, Error> {
+    decode_trace_parquet_projected(r, None)
+}
+
+/// Decodes a BlobTraceBatchPart from the Parquet format, reading only the
+/// typed key columns with the given names if the part has all of them.
+///
+/// If `key_columns` is given and the part has every one of them, the returned
+/// batch has exactly those key columns, in the given order. Otherwise, it has
+/// all of the part's key columns, in the order they were written, so that the
+/// caller can still decode whole keys. Parts written without a key schema have
+/// none, no matter what's requested.
+pub fn decode_trace_parquet_projected<R: Read + Seek>(
+    r: &mut R,
+    key_columns: Option<&[&str]>,
+) -> Result<BlobTraceBatchPart, Error> {
     let metadata = read_metadata(r).map_err(|err| err.to_string())?;
+    let schema = infer_schema(&metadata)?;
     let metadata = metadata
         .key_value_metadata()
         .as_ref()
         .and_then(|x| x.iter().find(|x| x.key == INLINE_METADATA_KEY));
-    let (format, meta) = decode_trace_inline_meta(metadata.and_then(|x| x.value.as_ref()))?;
+    let (format, compression, meta) =
+        decode_trace_inline_meta(metadata.and_then(|x| x.value.as_ref()))?;
 
-    let updates = match format {
+    let (updates, key_columns) = match format {
         ProtoBatchFormat::Unknown => return Err("unknown format".into()),
         ProtoBatchFormat::ArrowKvtd => {
             return Err("ArrowKVTD format not supported in parquet".into())
         }
-        ProtoBatchFormat::ParquetKvtd => decode_parquet_file_kvtd(r)?,
+        ProtoBatchFormat::ParquetKvtd => decode_parquet_file_kvtd(r, &schema.fields, key_columns)?,
     };
 
     let ret = BlobTraceBatchPart {
@@ -71,6 +106,8 @@ pub fn decode_trace_parquet<R: Read + Seek>(r: &mut R) -> Result<BlobTraceBatchP
         ),
         index: meta.index,
         updates,
+        key_columns,
+        compression,
     };
     ret.validate()?;
     Ok(ret)
@@ -80,31 +117,53 @@ fn encode_parquet_kvtd<W: Write>(
     w: &mut W,
     inline_base64: String,
     iter: &[ColumnarRecords],
+    key_columns: &[Vec<(String, ColumnData)>],
+    compression: PartCompression,
 ) -> Result<(), Error> {
-    let iter = iter.into_iter().map(|x| Ok(encode_arrow_batch_kvtd(x)));
+    let mut schema = (**SCHEMA_ARROW_KVTD).clone();
+    let mut encodings = vec![
+        vec![Encoding::Plain],
+        vec![Encoding::Plain],
+        vec![Encoding::Plain],
+        vec![Encoding::Plain],
+    ];
+    // BlobTraceBatchPart::validate guarantees that every set of updates has
+    // the same key columns, if it has any.
+    if let Some(columns) = key_columns.first() {
+        for (name, col) in columns.iter() {
+            schema.fields.push(Field::new(
+                format!("{}{}", KEY_COLUMN_PREFIX, name),
+                arrow_data_type(col.typ()),
+                true,
+            ));
+            encodings.push(vec![Encoding::Plain]);
+        }
+    }
+
+    let iter = iter.into_iter().enumerate().map(|(idx, x)| {
+        let chunk = encode_arrow_batch_kvtd(x);
+        match key_columns.get(idx) {
+            None => Ok(chunk),
+            Some(columns) => {
+                let mut arrays = chunk.into_arrays();
+                arrays.extend(columns.iter().map(|(_, col)| encode_arrow_column(col)));
+                Ok(Chunk::new(arrays))
+            }
+        }
+    });
 
     let options = WriteOptions {
         write_statistics: false,
-        compression: CompressionOptions::Uncompressed,
+        compression: compression_options(compression),
         version: Version::V2,
     };
-    let row_groups = RowGroupIterator::try_new(
-        iter,
-        &SCHEMA_ARROW_KVTD,
-        options,
-        vec![
-            vec![Encoding::Plain],
-            vec![Encoding::Plain],
-            vec![Encoding::Plain],
-            vec![Encoding::Plain],
-        ],
-    )?;
+    let row_groups = RowGroupIterator::try_new(iter, &schema, options, encodings)?;
 
     let metadata = vec![KeyValue {
         key: INLINE_METADATA_KEY.into(),
         value: Some(inline_base64),
     }];
-    let mut writer = FileWriter::try_new(w, (**SCHEMA_ARROW_KVTD).clone(), options)?;
+    let mut writer = FileWriter::try_new(w, schema, options)?;
     for group in row_groups {
         writer.write(group?).map_err(|err| err.to_string())?;
     }
@@ -113,12 +172,26 @@ fn encode_parquet_kvtd<W: Write>(
     Ok(())
 }
 
-fn decode_parquet_file_kvtd<R: Read + Seek>(r: &mut R) -> Result<Vec<ColumnarRecords>, Error> {
-    let reader = FileReader::try_new(r, None, None, None, None)?;
+fn compression_options(compression: PartCompression) -> CompressionOptions {
+    // Parquet records the codec of each column chunk, which is what the reader
+    // actually uses to decompress them.
+    match compression {
+        PartCompression::Uncompressed => CompressionOptions::Uncompressed,
+        PartCompression::Snappy => CompressionOptions::Snappy,
+        PartCompression::Lz4 => CompressionOptions::Lz4Raw,
+        PartCompression::Zstd => CompressionOptions::Zstd(None),
+    }
+}
 
-    let file_schema = reader.schema().fields.as_slice();
-    // We're not trying to accept any sort of user created data, so be strict.
-    if file_schema != SCHEMA_ARROW_KVTD.fields {
+fn decode_parquet_file_kvtd<R: Read + Seek>(
+    r: &mut R,
+    file_schema: &[Field],
+    key_columns: Option<&[&str]>,
+) -> Result<(Vec<ColumnarRecords>, Vec<Vec<(String, ColumnData)>>), Error> {
+    // Parts may have typed key columns after the (K, V, T, D) ones. We're not
+    // trying to accept any sort of user created data, so otherwise be strict.
+    let kvtd_schema = file_schema.get(..KVTD_PROJECTION.len());
+    if kvtd_schema != Some(SCHEMA_ARROW_KVTD.fields.as_slice()) {
         return Err(format!(
             "expected arrow schema {:?} got: {:?}",
             SCHEMA_ARROW_KVTD.fields, file_schema
@@ -126,9 +199,64 @@ fn decode_parquet_file_kvtd<R: Read + Seek>(r: &mut R) -> Result<Vec<ColumnarRec
         .into());
     }
 
-    let mut ret = Vec::new();
-    for batch in reader {
-        ret.push(decode_arrow_batch_kvtd(&batch?)?);
+    let all_key_columns = file_schema
+        .iter()
+        .enumerate()
+        .skip(KVTD_PROJECTION.len())
+        .map(
+            |(idx, field)| match field.name.strip_prefix(KEY_COLUMN_PREFIX) {
+                Some(name) => Ok((idx, name)),
+                None => Err(format!("unexpected column {}", field.name)),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+    let projected_key_columns = key_columns.and_then(|names| {
+        names
+            .iter()
+            .map(|name| all_key_columns.iter().find(|(_, x)| x == name).copied())
+            .collect::<Option<Vec<_>>>()
+    });
+    let (reorder, key_columns) = match projected_key_columns {
+        Some(mut columns) => {
+            // Columns are read in the order they were written, so remember
+            // where each one goes in the requested order.
+            let mut reorder = (0..columns.len()).collect::<Vec<_>>();
+            reorder.sort_by_key(|x| columns[*x].0);
+            columns.sort_by_key(|(idx, _)| *idx);
+            (Some(reorder), columns)
+        }
+        None => (None, all_key_columns),
+    };
+
+    let mut projection = KVTD_PROJECTION.to_vec();
+    projection.extend(key_columns.iter().map(|(idx, _)| *idx));
+    let reader = FileReader::try_new(r, Some(&projection), None, None, None)?;
+    let mut updates = Vec::new();
+    let mut ret_key_columns = Vec::new();
+    for chunk in reader {
+        let mut arrays = chunk?.into_arrays();
+        let key_arrays = arrays.split_off(KVTD_PROJECTION.len());
+        updates.push(decode_arrow_batch_kvtd(&Chunk::new(arrays))?);
+        if key_columns.is_empty() {
+            continue;
+        }
+        let mut columns = key_columns
+            .iter()
+            .zip(key_arrays)
+            .map(|((_, name), array)| {
+                decode_arrow_column(array.as_ref())
+                    .map(|col| (name.to_string(), col))
+                    .map_err(|err| format!("invalid key column {}: {}", name, err))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some(reorder) = reorder.as_ref() {
+            let mut reordered = vec![None; columns.len()];
+            for (col, position) in columns.drain(..).zip(reorder.iter()) {
+                reordered[*position] = Some(col);
+            }
+            columns = reordered.into_iter().flatten().collect();
+        }
+        ret_key_columns.push(columns);
     }
-    Ok(ret)
+    Ok((updates, ret_key_columns))
 }
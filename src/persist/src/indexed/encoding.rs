@@ -16,10 +16,12 @@
 use std::fmt::{self, Debug};
 use std::io::Cursor;
 use std::marker::PhantomData;
+use std::str::FromStr;
 
 use bytes::BufMut;
 use differential_dataflow::trace::Description;
 use mz_ore::cast::CastFrom;
+use mz_persist_types::columnar::ColumnData;
 use mz_persist_types::{Codec, Codec64};
 use prost::Message;
 use timely::progress::{Antichain, Timestamp};
@@ -27,9 +29,12 @@ use timely::PartialOrder;
 
 use crate::error::Error;
 use crate::gen::persist::{
-    ProtoBatchFormat, ProtoBatchPartInline, ProtoU64Antichain, ProtoU64Description,
+    ProtoBatchFormat, ProtoBatchPartInline, ProtoPartCompression, ProtoU64Antichain,
+    ProtoU64Description,
+};
+use crate::indexed::columnar::parquet::{
+    decode_trace_parquet, decode_trace_parquet_projected, encode_trace_parquet,
 };
-use crate::indexed::columnar::parquet::{decode_trace_parquet, encode_trace_parquet};
 use crate::indexed::columnar::ColumnarRecords;
 use crate::location::Blob;
 
@@ -94,6 +99,90 @@ pub struct BlobTraceBatchPart {
     pub index: u64,
     /// The updates themselves.
     pub updates: Vec<ColumnarRecords>,
+    /// The keys of `updates` as typed columns, for writers that provide a
+    /// [mz_persist_types::columnar::Schema] for their keys.
+    ///
+    /// This is either empty or has one entry per element of `updates`, each
+    /// with the same named columns and one value per update in that element.
+    /// When present, these are the only copy of the keys: the encoded keys in
+    /// `updates` are empty, and readers decode keys from the columns instead
+    /// (see [mz_persist_types::Codec::decode_columns]). Parts without them,
+    /// including all parts written before typed key columns existed, store
+    /// their keys in `updates`, encoded with their [Codec].
+    pub key_columns: Vec<Vec<(String, ColumnData)>>,
+    /// The codec used to compress the part when it's encoded.
+    ///
+    /// This is recorded in the part's inline metadata, so parts written with
+    /// different codecs can be mixed freely in the same shard.
+    pub compression: PartCompression,
+}
+
+/// A codec used to compress the data in a [BlobTraceBatchPart].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PartCompression {
+    /// No compression.
+    #[default]
+    Uncompressed,
+    /// Snappy compression.
+    Snappy,
+    /// LZ4 compression.
+    Lz4,
+    /// Zstandard compression.
+    Zstd,
+}
+
+impl PartCompression {
+    /// Returns the name of the codec, as accepted by [PartCompression::from_str].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PartCompression::Uncompressed => "uncompressed",
+            PartCompression::Snappy => "snappy",
+            PartCompression::Lz4 => "lz4",
+            PartCompression::Zstd => "zstd",
+        }
+    }
+}
+
+impl fmt::Display for PartCompression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PartCompression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "uncompressed" => Ok(PartCompression::Uncompressed),
+            "snappy" => Ok(PartCompression::Snappy),
+            "lz4" => Ok(PartCompression::Lz4),
+            "zstd" => Ok(PartCompression::Zstd),
+            _ => Err(format!("unknown part compression: {}", s)),
+        }
+    }
+}
+
+impl From<PartCompression> for ProtoPartCompression {
+    fn from(x: PartCompression) -> Self {
+        match x {
+            PartCompression::Uncompressed => ProtoPartCompression::Uncompressed,
+            PartCompression::Snappy => ProtoPartCompression::Snappy,
+            PartCompression::Lz4 => ProtoPartCompression::Lz4,
+            PartCompression::Zstd => ProtoPartCompression::Zstd,
+        }
+    }
+}
+
+impl From<ProtoPartCompression> for PartCompression {
+    fn from(x: ProtoPartCompression) -> Self {
+        match x {
+            ProtoPartCompression::Uncompressed => PartCompression::Uncompressed,
+            ProtoPartCompression::Snappy => PartCompression::Snappy,
+            ProtoPartCompression::Lz4 => PartCompression::Lz4,
+            ProtoPartCompression::Zstd => PartCompression::Zstd,
+        }
+    }
 }
 
 impl TraceBatchMeta {
@@ -173,6 +262,45 @@ impl BlobTraceBatchPart {
             return Err(format!("invalid desc: {:?}", &self.desc).into());
         }
 
+        if !self.key_columns.is_empty() {
+            if self.key_columns.len() != self.updates.len() {
+                return Err(format!(
+                    "expected key columns for {} sets of updates got {}",
+                    self.updates.len(),
+                    self.key_columns.len()
+                )
+                .into());
+            }
+            let names = |columns: &[(String, ColumnData)]| {
+                columns
+                    .iter()
+                    .map(|(name, col)| (name.clone(), col.typ()))
+                    .collect::<Vec<_>>()
+            };
+            let first_names = names(&self.key_columns[0]);
+            for (updates, columns) in self.updates.iter().zip(self.key_columns.iter()) {
+                if names(columns) != first_names {
+                    return Err(format!(
+                        "inconsistent key columns: {:?} vs {:?}",
+                        first_names,
+                        names(columns)
+                    )
+                    .into());
+                }
+                for (name, col) in columns.iter() {
+                    if col.len() != updates.len() {
+                        return Err(format!(
+                            "key column {} has {} entries expected {}",
+                            name,
+                            col.len(),
+                            updates.len()
+                        )
+                        .into());
+                    }
+                }
+            }
+        }
+
         for update in self.updates.iter().flat_map(|u| u.iter()) {
             let ((_key, _val), ts, diff) = update;
             // TODO: Don't assume ts and diff are a u64 and a i64, take T and D
@@ -238,6 +366,18 @@ impl Codec for BlobTraceBatchPart {
     }
 }
 
+impl BlobTraceBatchPart {
+    /// [Codec::decode], but only reading the typed key columns with the given
+    /// names if the part has all of them.
+    ///
+    /// See [decode_trace_parquet_projected] for exactly which key columns the
+    /// returned part has.
+    pub fn decode_projected(buf: &[u8], key_columns: Option<&[&str]>) -> Result<Self, String> {
+        decode_trace_parquet_projected(&mut Cursor::new(&buf), key_columns)
+            .map_err(|err| err.to_string())
+    }
+}
+
 #[derive(PartialOrd, Ord, PartialEq, Eq)]
 struct PrettyBytes<'a>(&'a [u8]);
 
@@ -311,11 +451,16 @@ impl From<&Description<u64>> for ProtoU64Description {
 }
 
 /// Encodes the inline metadata for a trace batch into a base64 string.
-pub fn encode_trace_inline_meta(batch: &BlobTraceBatchPart, format: ProtoBatchFormat) -> String {
+pub fn encode_trace_inline_meta(
+    batch: &BlobTraceBatchPart,
+    format: ProtoBatchFormat,
+    compression: PartCompression,
+) -> String {
     let inline = ProtoBatchPartInline {
         format: format.into(),
         desc: Some((&batch.desc).into()),
         index: batch.index,
+        compression: ProtoPartCompression::from(compression).into(),
     };
     let inline_encoded = inline.encode_to_vec();
     base64::encode(inline_encoded)
@@ -324,13 +469,15 @@ pub fn encode_trace_inline_meta(batch: &BlobTraceBatchPart, format: ProtoBatchFo
 /// Decodes the inline metadata for a trace batch from a base64 string.
 pub fn decode_trace_inline_meta(
     inline_base64: Option<&String>,
-) -> Result<(ProtoBatchFormat, ProtoBatchPartInline), Error> {
+) -> Result<(ProtoBatchFormat, PartCompression, ProtoBatchPartInline), Error> {
     let inline_base64 = inline_base64.ok_or("missing batch metadata")?;
     let inline_encoded = base64::decode(&inline_base64).map_err(|err| err.to_string())?;
     let inline = ProtoBatchPartInline::decode(&*inline_encoded).map_err(|err| err.to_string())?;
     let format = ProtoBatchFormat::from_i32(inline.format)
         .ok_or_else(|| Error::from(format!("unknown format: {}", inline.format)))?;
-    Ok((format, inline))
+    let compression = ProtoPartCompression::from_i32(inline.compression)
+        .ok_or_else(|| Error::from(format!("unknown compression: {}", inline.compression)))?;
+    Ok((format, compression.into(), inline))
 }
 
 #[cfg(test)]
@@ -388,6 +535,8 @@ mod tests {
             desc: u64_desc(0, 2),
             index: 0,
             updates: columnar_records(vec![update_with_key(0, "0"), update_with_key(1, "1")]),
+            key_columns: Vec::new(),
+            compression: PartCompression::Uncompressed,
         };
         assert_eq!(b.validate(), Ok(()));
 
@@ -396,6 +545,8 @@ mod tests {
             desc: u64_desc(0, 2),
             index: 0,
             updates: columnar_records(vec![]),
+            key_columns: Vec::new(),
+            compression: PartCompression::Uncompressed,
         };
         assert_eq!(b.validate(), Ok(()));
 
@@ -404,6 +555,8 @@ mod tests {
             desc: u64_desc(2, 0),
             index: 0,
             updates: columnar_records(vec![]),
+            key_columns: Vec::new(),
+            compression: PartCompression::Uncompressed,
         };
         assert_eq!(
             b.validate(),
@@ -417,6 +570,8 @@ mod tests {
             desc: u64_desc(0, 0),
             index: 0,
             updates: columnar_records(vec![]),
+            key_columns: Vec::new(),
+            compression: PartCompression::Uncompressed,
         };
         assert_eq!(
             b.validate(),
@@ -430,6 +585,8 @@ mod tests {
             desc: u64_desc(1, 2),
             index: 0,
             updates: columnar_records(vec![update_with_key(0, "0")]),
+            key_columns: Vec::new(),
+            compression: PartCompression::Uncompressed,
         };
         assert_eq!(b.validate(), Err(Error::from("timestamp 0 is less than the batch lower: Description { lower: Antichain { elements: [1] }, upper: Antichain { elements: [2] }, since: Antichain { elements: [0] } }")));
 
@@ -438,6 +595,8 @@ mod tests {
             desc: u64_desc(1, 2),
             index: 0,
             updates: columnar_records(vec![update_with_key(2, "0")]),
+            key_columns: Vec::new(),
+            compression: PartCompression::Uncompressed,
         };
         assert_eq!(b.validate(), Err(Error::from("timestamp 2 is greater than or equal to the batch upper: Description { lower: Antichain { elements: [1] }, upper: Antichain { elements: [2] }, since: Antichain { elements: [0] } }")));
 
@@ -446,6 +605,8 @@ mod tests {
             desc: u64_desc_since(1, 2, 4),
             index: 0,
             updates: columnar_records(vec![update_with_key(2, "0")]),
+            key_columns: Vec::new(),
+            compression: PartCompression::Uncompressed,
         };
         assert_eq!(b.validate(), Ok(()));
 
@@ -454,6 +615,8 @@ mod tests {
             desc: u64_desc_since(1, 2, 4),
             index: 0,
             updates: columnar_records(vec![update_with_key(4, "0")]),
+            key_columns: Vec::new(),
+            compression: PartCompression::Uncompressed,
         };
         assert_eq!(b.validate(), Ok(()));
 
@@ -462,6 +625,8 @@ mod tests {
             desc: u64_desc_since(1, 2, 4),
             index: 0,
             updates: columnar_records(vec![update_with_key(5, "0")]),
+            key_columns: Vec::new(),
+            compression: PartCompression::Uncompressed,
         };
         assert_eq!(b.validate(), Err(Error::from("timestamp 5 is greater than the batch since: Description { lower: Antichain { elements: [1] }, upper: Antichain { elements: [2] }, since: Antichain { elements: [4] } }")));
 
@@ -470,6 +635,8 @@ mod tests {
             desc: u64_desc(0, 1),
             index: 0,
             updates: columnar_records(vec![(("0".into(), "0".into()), 0, 0)]),
+            key_columns: Vec::new(),
+            compression: PartCompression::Uncompressed,
         };
         assert_eq!(
             b.validate(),
@@ -531,6 +698,8 @@ mod tests {
             .iter()
             .collect::<ColumnarRecordsVec>()
             .into_inner(),
+            key_columns: Vec::new(),
+            compression: PartCompression::Uncompressed,
         };
         let batch1 = BlobTraceBatchPart {
             desc: batch_desc.clone(),
@@ -542,6 +711,8 @@ mod tests {
             .iter()
             .collect::<ColumnarRecordsVec>()
             .into_inner(),
+            key_columns: Vec::new(),
+            compression: PartCompression::Uncompressed,
         };
 
         let batch0_size_bytes = expect_set_trace_batch(blob.as_ref(), "b0", &batch0).await;
@@ -597,6 +768,106 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn trace_batch_key_columns() {
+        let updates = columnar_records(vec![update_with_key(0, "0"), update_with_key(1, "1")]);
+        let key_columns = vec![vec![
+            ("a".to_owned(), ColumnData::Int64(vec![Some(0), None])),
+            (
+                "b".to_owned(),
+                ColumnData::String(vec![Some("0".to_owned()), Some("1".to_owned())]),
+            ),
+        ]];
+        let b = BlobTraceBatchPart {
+            desc: u64_desc(0, 2),
+            index: 0,
+            updates: updates.clone(),
+            key_columns: key_columns.clone(),
+            compression: PartCompression::Uncompressed,
+        };
+        let mut buf = Vec::new();
+        b.encode(&mut buf);
+
+        let decoded = BlobTraceBatchPart::decode(&buf).expect("valid batch");
+        assert_eq!(decoded.updates, updates);
+        assert_eq!(decoded.key_columns, key_columns);
+
+        // Projections only read the requested columns, in the requested
+        // order...
+        let decoded =
+            BlobTraceBatchPart::decode_projected(&buf, Some(&["b", "a"])).expect("valid batch");
+        assert_eq!(
+            decoded.key_columns,
+            vec![vec![key_columns[0][1].clone(), key_columns[0][0].clone()]]
+        );
+        let decoded =
+            BlobTraceBatchPart::decode_projected(&buf, Some(&["b"])).expect("valid batch");
+        assert_eq!(decoded.key_columns, vec![vec![key_columns[0][1].clone()]]);
+        // ...unless the part is missing any of them, in which case all of them
+        // are read.
+        let decoded =
+            BlobTraceBatchPart::decode_projected(&buf, Some(&["b", "c"])).expect("valid batch");
+        assert_eq!(decoded.key_columns, key_columns);
+
+        // Parts without typed columns are still readable.
+        let b = BlobTraceBatchPart {
+            key_columns: Vec::new(),
+            ..b
+        };
+        let mut buf = Vec::new();
+        b.encode(&mut buf);
+        let decoded = BlobTraceBatchPart::decode(&buf).expect("valid batch");
+        assert_eq!(decoded.updates, updates);
+        assert_eq!(decoded.key_columns, Vec::<Vec<_>>::new());
+        let decoded =
+            BlobTraceBatchPart::decode_projected(&buf, Some(&["b"])).expect("valid batch");
+        assert_eq!(decoded.key_columns, Vec::<Vec<_>>::new());
+
+        // Typed columns must have an entry for every update.
+        let b = BlobTraceBatchPart {
+            key_columns: vec![vec![("a".to_owned(), ColumnData::Int64(vec![Some(0)]))]],
+            ..b
+        };
+        assert_eq!(
+            b.validate(),
+            Err(Error::from("key column a has 1 entries expected 2"))
+        );
+    }
+
+    #[test]
+    fn trace_batch_compression() {
+        let updates = columnar_records(vec![update_with_key(0, "0"), update_with_key(1, "1")]);
+        let mut uncompressed_buf = Vec::new();
+        for compression in [
+            PartCompression::Uncompressed,
+            PartCompression::Snappy,
+            PartCompression::Lz4,
+            PartCompression::Zstd,
+        ] {
+            assert_eq!(compression.as_str().parse(), Ok(compression));
+            let b = BlobTraceBatchPart {
+                desc: u64_desc(0, 2),
+                index: 0,
+                updates: updates.clone(),
+                key_columns: Vec::new(),
+                compression,
+            };
+            let mut buf = Vec::new();
+            b.encode(&mut buf);
+            if compression == PartCompression::Uncompressed {
+                uncompressed_buf = buf.clone();
+            } else {
+                assert_ne!(buf, uncompressed_buf);
+            }
+
+            // The codec is recorded in the part, so it can be read back
+            // without knowing how it was written.
+            let decoded = BlobTraceBatchPart::decode(&buf).expect("valid batch");
+            assert_eq!(decoded.updates, updates);
+            assert_eq!(decoded.compression, compression);
+        }
+    }
+
     #[test]
     fn encoded_batch_sizes() {
         fn sizes(data: DataGenerator) -> usize {
@@ -608,6 +879,8 @@ mod tests {
                 ),
                 index: 0,
                 updates: data.batches().collect(),
+                key_columns: Vec::new(),
+                compression: PartCompression::Uncompressed,
             };
             let mut trace_buf = Vec::new();
             trace.encode(&mut trace_buf);
@@ -18,6 +18,7 @@
 )]
 
 pub mod cfg;
+pub mod disk_cache;
 pub mod error;
 pub mod file;
 pub mod gen;
@@ -27,5 +28,6 @@ pub mod mem;
 pub mod postgres;
 pub mod retry;
 pub mod s3;
+pub mod sqlite;
 pub mod unreliable;
 pub mod workload;
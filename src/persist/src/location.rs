@@ -228,6 +228,28 @@ impl From<tokio_postgres::Error> for ExternalError {
     }
 }
 
+impl From<rusqlite::Error> for ExternalError {
+    fn from(e: rusqlite::Error) -> Self {
+        // A busy or locked database means that the statement never ran.
+        let determinate = match &e {
+            rusqlite::Error::SqliteFailure(err, _) => matches!(
+                err.code,
+                rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked
+            ),
+            _ => false,
+        };
+        if determinate {
+            ExternalError::Determinate(Determinate {
+                inner: anyhow::Error::new(e),
+            })
+        } else {
+            ExternalError::Indeterminate(Indeterminate {
+                inner: anyhow::Error::new(e),
+            })
+        }
+    }
+}
+
 impl From<tokio::task::JoinError> for ExternalError {
     fn from(x: tokio::task::JoinError) -> Self {
         ExternalError::Indeterminate(Indeterminate {
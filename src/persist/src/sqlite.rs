@@ -0,0 +1,274 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Implementation of [Consensus] backed by SQLite.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use bytes::Bytes;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSqlOutput, ValueRef};
+use rusqlite::{params, Connection, OptionalExtension, ToSql, TransactionBehavior};
+
+use mz_ore::task;
+
+use crate::error::Error;
+use crate::location::{Consensus, ExternalError, SeqNo, VersionedData};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS consensus (
+    shard text NOT NULL,
+    sequence_number integer NOT NULL,
+    data blob NOT NULL,
+    PRIMARY KEY (shard, sequence_number)
+);
+";
+
+/// How long to wait for other connections to the same database to release
+/// their locks before giving up.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+impl ToSql for SeqNo {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        // We can only represent sequence numbers in the range [0, i64::MAX].
+        let value = i64::try_from(self.0)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        Ok(ToSqlOutput::from(value))
+    }
+}
+
+impl FromSql for SeqNo {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let sequence_number = i64::column_result(value)?;
+
+        // Sanity check that the sequence number we received falls in the
+        // [0, i64::MAX] range.
+        let sequence_number =
+            u64::try_from(sequence_number).map_err(|e| FromSqlError::Other(Box::new(e)))?;
+        Ok(SeqNo(sequence_number))
+    }
+}
+
+/// Configuration for opening a [SqliteConsensus].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqliteConsensusConfig {
+    path: PathBuf,
+}
+
+impl<P: AsRef<Path>> From<P> for SqliteConsensusConfig {
+    fn from(path: P) -> Self {
+        SqliteConsensusConfig {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+/// Implementation of [Consensus] over a SQLite database stored in a single
+/// file.
+///
+/// Several instances, in the same or in different processes, may use the same
+/// file at once. SQLite's file locking serializes their writes.
+#[derive(Debug)]
+pub struct SqliteConsensus {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteConsensus {
+    /// Opens the SQLite database at the path in `config`, creating it if it
+    /// does not exist.
+    pub async fn open(config: SqliteConsensusConfig) -> Result<Self, ExternalError> {
+        let conn = task::spawn_blocking(
+            || "sqlite_consensus_open",
+            move || -> Result<Connection, ExternalError> {
+                if let Some(parent) = config.path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                let conn = Connection::open(&config.path)?;
+                conn.busy_timeout(BUSY_TIMEOUT)?;
+                // Write-ahead logging lets readers proceed while another
+                // connection is writing.
+                conn.query_row("PRAGMA journal_mode = WAL", params![], |_| Ok(()))?;
+                conn.execute_batch(SCHEMA)?;
+                Ok(conn)
+            },
+        )
+        .await??;
+        Ok(SqliteConsensus {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Runs `f` against the connection on a thread where blocking is allowed.
+    async fn run<T, F>(&self, f: F) -> Result<T, ExternalError>
+    where
+        F: FnOnce(&mut Connection) -> Result<T, ExternalError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let conn = Arc::clone(&self.conn);
+        task::spawn_blocking(
+            || "sqlite_consensus",
+            move || {
+                let mut conn = conn.lock().map_err(Error::from)?;
+                f(&mut conn)
+            },
+        )
+        .await?
+    }
+}
+
+fn head(conn: &Connection, key: &str) -> Result<Option<VersionedData>, ExternalError> {
+    let q = "SELECT sequence_number, data FROM consensus
+             WHERE shard = ?1 ORDER BY sequence_number DESC LIMIT 1";
+    let data = conn
+        .query_row(q, params![key], |row| {
+            Ok(VersionedData {
+                seqno: row.get("sequence_number")?,
+                data: Bytes::from(row.get::<_, Vec<u8>>("data")?),
+            })
+        })
+        .optional()?;
+    Ok(data)
+}
+
+#[async_trait]
+impl Consensus for SqliteConsensus {
+    async fn head(&self, key: &str) -> Result<Option<VersionedData>, ExternalError> {
+        let key = key.to_owned();
+        self.run(move |conn| head(conn, &key)).await
+    }
+
+    async fn compare_and_set(
+        &self,
+        key: &str,
+        expected: Option<SeqNo>,
+        new: VersionedData,
+    ) -> Result<Result<(), Option<VersionedData>>, ExternalError> {
+        if let Some(expected) = expected {
+            if new.seqno <= expected {
+                return Err(ExternalError::from(
+                        anyhow!("new seqno must be strictly greater than expected. Got new: {:?} expected: {:?}",
+                                 new.seqno, expected)));
+            }
+        }
+
+        let key = key.to_owned();
+        self.run(move |conn| {
+            // Take the write lock up front, so that no other connection can
+            // slip in a write between reading the head and inserting.
+            let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+            let current = head(&tx, &key)?;
+            if current.as_ref().map(|data| data.seqno) != expected {
+                return Ok(Err(current));
+            }
+            tx.execute(
+                "INSERT INTO consensus (shard, sequence_number, data) VALUES (?1, ?2, ?3)",
+                params![key, new.seqno, new.data.as_ref()],
+            )?;
+            tx.commit()?;
+            Ok(Ok(()))
+        })
+        .await
+    }
+
+    async fn scan(&self, key: &str, from: SeqNo) -> Result<Vec<VersionedData>, ExternalError> {
+        let key = key.to_owned();
+        let results = self
+            .run(move |conn| {
+                let q = "SELECT sequence_number, data FROM consensus
+                         WHERE shard = ?1 AND sequence_number >= ?2
+                         ORDER BY sequence_number";
+                let mut stmt = conn.prepare(q)?;
+                let rows = stmt.query_map(params![key, from], |row| {
+                    Ok(VersionedData {
+                        seqno: row.get("sequence_number")?,
+                        data: Bytes::from(row.get::<_, Vec<u8>>("data")?),
+                    })
+                })?;
+                let results = rows.collect::<Result<Vec<_>, _>>()?;
+                Ok(results)
+            })
+            .await?;
+
+        if results.is_empty() {
+            Err(ExternalError::from(anyhow!(
+                "sequence number lower bound too high for scan: {:?}",
+                from
+            )))
+        } else {
+            Ok(results)
+        }
+    }
+
+    async fn truncate(&self, key: &str, seqno: SeqNo) -> Result<(), ExternalError> {
+        let key = key.to_owned();
+        self.run(move |conn| {
+            let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+            let current = head(&tx, &key)?;
+            if current.map_or(true, |data| data.seqno < seqno) {
+                return Err(ExternalError::from(anyhow!(
+                    "upper bound too high for truncate: {:?}",
+                    seqno
+                )));
+            }
+            tx.execute(
+                "DELETE FROM consensus WHERE shard = ?1 AND sequence_number < ?2",
+                params![key, seqno],
+            )?;
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::location::tests::consensus_impl_test;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sqlite_consensus() -> Result<(), ExternalError> {
+        let temp_dir = tempfile::tempdir().map_err(Error::from)?;
+        let config = SqliteConsensusConfig::from(temp_dir.path().join("consensus.db"));
+
+        consensus_impl_test(|| SqliteConsensus::open(config.clone())).await?;
+
+        // Writes through one instance are visible to another instance of the
+        // same file, including after it has been reopened.
+        let consensus = SqliteConsensus::open(config.clone()).await?;
+        let other = SqliteConsensus::open(config.clone()).await?;
+        let key = Uuid::new_v4().to_string();
+        let state = VersionedData {
+            seqno: SeqNo(5),
+            data: Bytes::from("abc"),
+        };
+
+        assert_eq!(
+            consensus.compare_and_set(&key, None, state.clone()).await,
+            Ok(Ok(()))
+        );
+        assert_eq!(other.head(&key).await, Ok(Some(state.clone())));
+        assert_eq!(
+            other.compare_and_set(&key, None, state.clone()).await,
+            Ok(Err(Some(state.clone())))
+        );
+
+        drop(consensus);
+        let consensus = SqliteConsensus::open(config).await?;
+        assert_eq!(consensus.head(&key).await, Ok(Some(state)));
+
+        Ok(())
+    }
+}
@@ -8,16 +8,68 @@
 // by the Apache License, Version 2.0.
 
 use std::borrow::Cow;
-use std::io;
+use std::io::{self, BufRead, Read};
 
 use bytes::BytesMut;
 use csv::ByteRecord;
 use csv::ReaderBuilder;
 
-use mz_repr::{Datum, RelationType, Row, RowArena};
+use mz_repr::{Datum, RelationDesc, RelationType, Row, RowArena, ScalarType};
 
 static END_OF_COPY_MARKER: &[u8] = b"\\.";
 
+static BINARY_SIGNATURE: &[u8] = b"PGCOPY\n\xFF\r\n\0";
+
+/// Encodes the data that precedes the rows of a COPY TO operation, i.e., the
+/// binary file header or the CSV header line, if any.
+pub fn encode_copy_header(
+    params: &CopyFormatParams,
+    desc: &RelationDesc,
+    out: &mut Vec<u8>,
+) -> Result<(), io::Error> {
+    match params {
+        CopyFormatParams::Text(_) => Ok(()),
+        CopyFormatParams::Csv(params) => {
+            if params.header {
+                let names = Row::pack(desc.iter_names().map(|name| Datum::String(name.as_str())));
+                let typ = RelationType::new(vec![ScalarType::String.nullable(false); desc.arity()]);
+                encode_copy_row_csv(params, names, &typ, out)?;
+            }
+            Ok(())
+        }
+        CopyFormatParams::Binary => {
+            // 11-byte signature.
+            out.extend(BINARY_SIGNATURE);
+            // 32-bit flags field.
+            out.extend(&[0, 0, 0, 0]);
+            // 32-bit header extension length field.
+            out.extend(&[0, 0, 0, 0]);
+            Ok(())
+        }
+    }
+}
+
+/// Encodes the data that follows the rows of a COPY TO operation, if any.
+pub fn encode_copy_trailer(params: &CopyFormatParams, out: &mut Vec<u8>) {
+    if let CopyFormatParams::Binary = params {
+        out.extend(&(-1i16).to_be_bytes());
+    }
+}
+
+/// Encodes `row` for a COPY TO operation in the format described by `params`.
+pub fn encode_copy_format(
+    params: &CopyFormatParams,
+    row: Row,
+    typ: &RelationType,
+    out: &mut Vec<u8>,
+) -> Result<(), io::Error> {
+    match params {
+        CopyFormatParams::Text(params) => encode_copy_row_text(params, row, typ, out),
+        CopyFormatParams::Csv(params) => encode_copy_row_csv(params, row, typ, out),
+        CopyFormatParams::Binary => encode_copy_row_binary(row, typ, out),
+    }
+}
+
 pub fn encode_copy_row_binary(
     row: Row,
     typ: &RelationType,
@@ -63,16 +115,17 @@ pub fn encode_copy_row_binary(
 }
 
 pub fn encode_copy_row_text(
+    CopyTextFormatParams { null, delimiter }: &CopyTextFormatParams,
     row: Row,
     typ: &RelationType,
     out: &mut Vec<u8>,
 ) -> Result<(), io::Error> {
-    let delim = b'\t';
-    let null = b"\\N";
+    let null = null.as_bytes();
+    let delimiter = delimiter.as_bytes();
     let mut buf = BytesMut::new();
     for (idx, field) in mz_pgrepr::values_from_row(row, typ).into_iter().enumerate() {
         if idx > 0 {
-            out.push(delim);
+            out.extend(delimiter);
         }
         match field {
             None => out.extend(null),
@@ -85,6 +138,10 @@ pub fn encode_copy_row_text(
                         b'\n' => out.extend(b"\\n"),
                         b'\r' => out.extend(b"\\r"),
                         b'\t' => out.extend(b"\\t"),
+                        _ if delimiter == &[*b] => {
+                            out.push(b'\\');
+                            out.push(*b);
+                        }
                         _ => out.push(*b),
                     }
                 }
@@ -95,6 +152,57 @@ pub fn encode_copy_row_text(
     Ok(())
 }
 
+pub fn encode_copy_row_csv(
+    CopyCsvFormatParams {
+        delimiter,
+        quote,
+        escape,
+        null,
+        ..
+    }: &CopyCsvFormatParams,
+    row: Row,
+    typ: &RelationType,
+    out: &mut Vec<u8>,
+) -> Result<(), io::Error> {
+    let null = null.as_bytes();
+    let single_column = typ.column_types.len() == 1;
+    let mut buf = BytesMut::new();
+    for (idx, field) in mz_pgrepr::values_from_row(row, typ).into_iter().enumerate() {
+        if idx > 0 {
+            out.push(*delimiter);
+        }
+        match field {
+            None => out.extend(null),
+            Some(field) => {
+                buf.clear();
+                field.encode_text(&mut buf);
+                // Quote values that could otherwise be mistaken for NULL or
+                // for the end of copy marker, as well as values containing
+                // special characters.
+                let needs_quotes = buf == null
+                    || (single_column && buf == END_OF_COPY_MARKER)
+                    || buf
+                        .iter()
+                        .any(|b| [*delimiter, *quote, b'\n', b'\r'].contains(b));
+                if needs_quotes {
+                    out.push(*quote);
+                    for b in &buf {
+                        if b == quote || b == escape {
+                            out.push(*escape);
+                        }
+                        out.push(*b);
+                    }
+                    out.push(*quote);
+                } else {
+                    out.extend(&buf);
+                }
+            }
+        }
+    }
+    out.push(b'\n');
+    Ok(())
+}
+
 pub struct CopyTextFormatParser<'a> {
     data: &'a [u8],
     position: usize,
@@ -355,10 +463,21 @@ impl<'a> RawIterator<'a> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum CopyFormatParams<'a> {
     Text(CopyTextFormatParams<'a>),
     Csv(CopyCsvFormatParams<'a>),
+    Binary,
+}
+
+impl CopyFormatParams<'_> {
+    /// The format in which the individual values of a row are encoded.
+    pub fn value_format(&self) -> mz_pgrepr::Format {
+        match self {
+            CopyFormatParams::Text(_) | CopyFormatParams::Csv(_) => mz_pgrepr::Format::Text,
+            CopyFormatParams::Binary => mz_pgrepr::Format::Binary,
+        }
+    }
 }
 
 pub fn decode_copy_format<'a>(
@@ -369,10 +488,37 @@ pub fn decode_copy_format<'a>(
     match params {
         CopyFormatParams::Text(params) => decode_copy_format_text(data, column_types, params),
         CopyFormatParams::Csv(params) => decode_copy_format_csv(data, column_types, params),
+        CopyFormatParams::Binary => decode_copy_format_binary(data, column_types),
     }
 }
 
-#[derive(Debug)]
+/// Like [`decode_copy_format`], but decodes the data incrementally as it is
+/// read from `reader`, passing each row to `f` as soon as it is decoded.
+///
+/// Only one row's worth of data is held in memory at a time, so this is
+/// suitable for data that is too large to read in its entirety.
+pub fn decode_copy_format_from_reader<'a, R, F>(
+    reader: R,
+    column_types: &[mz_pgrepr::Type],
+    params: CopyFormatParams<'a>,
+    f: F,
+) -> Result<(), io::Error>
+where
+    R: BufRead,
+    F: FnMut(Row) -> Result<(), io::Error>,
+{
+    match params {
+        CopyFormatParams::Text(params) => {
+            decode_copy_format_text_from_reader(reader, column_types, params, f)
+        }
+        CopyFormatParams::Csv(params) => {
+            decode_copy_format_csv_from_reader(reader, column_types, params, f)
+        }
+        CopyFormatParams::Binary => decode_copy_format_binary_from_reader(reader, column_types, f),
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct CopyTextFormatParams<'a> {
     pub null: Cow<'a, str>,
     pub delimiter: Cow<'a, str>,
@@ -387,34 +533,74 @@ pub fn decode_copy_format_text(
 
     let mut parser = CopyTextFormatParser::new(&data, &delimiter, &null);
     while !parser.is_eof() && !parser.is_end_of_copy_marker() {
-        let mut row = Vec::new();
-        let buf = RowArena::new();
-        for (col, typ) in column_types.iter().enumerate() {
-            if col > 0 {
-                parser.expect_column_delimiter()?;
+        rows.push(decode_copy_text_row(&mut parser, column_types)?);
+    }
+    // Note that if there is any junk data after the end of copy marker, we drop
+    // it on the floor as PG does.
+    Ok(rows)
+}
+
+fn decode_copy_format_text_from_reader<R, F>(
+    mut reader: R,
+    column_types: &[mz_pgrepr::Type],
+    CopyTextFormatParams { null, delimiter }: CopyTextFormatParams,
+    mut f: F,
+) -> Result<(), io::Error>
+where
+    R: BufRead,
+    F: FnMut(Row) -> Result<(), io::Error>,
+{
+    let mut line = Vec::new();
+    loop {
+        // Each row is on a line of its own, but a newline that is escaped by
+        // a backslash is part of the value rather than the end of the row.
+        line.clear();
+        while reader.read_until(b'\n', &mut line)? > 0 {
+            let backslashes = match line.split_last() {
+                Some((&b'\n', rest)) => rest.iter().rev().take_while(|b| **b == b'\\').count(),
+                _ => 0,
+            };
+            if backslashes % 2 == 0 {
+                break;
             }
-            let raw_value = parser.consume_raw_value()?;
-            if let Some(raw_value) = raw_value {
-                match mz_pgrepr::Value::decode_text(&typ, raw_value) {
-                    Ok(value) => row.push(value.into_datum(&buf, &typ)),
-                    Err(err) => {
-                        let msg = format!("unable to decode column: {}", err);
-                        return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
-                    }
+        }
+        let mut parser = CopyTextFormatParser::new(&line, &delimiter, &null);
+        // As above, drop any data after the end of copy marker on the floor.
+        if parser.is_eof() || parser.is_end_of_copy_marker() {
+            return Ok(());
+        }
+        f(decode_copy_text_row(&mut parser, column_types)?)?;
+    }
+}
+
+fn decode_copy_text_row(
+    parser: &mut CopyTextFormatParser,
+    column_types: &[mz_pgrepr::Type],
+) -> Result<Row, io::Error> {
+    let mut row = Vec::new();
+    let buf = RowArena::new();
+    for (col, typ) in column_types.iter().enumerate() {
+        if col > 0 {
+            parser.expect_column_delimiter()?;
+        }
+        let raw_value = parser.consume_raw_value()?;
+        if let Some(raw_value) = raw_value {
+            match mz_pgrepr::Value::decode_text(&typ, raw_value) {
+                Ok(value) => row.push(value.into_datum(&buf, &typ)),
+                Err(err) => {
+                    let msg = format!("unable to decode column: {}", err);
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
                 }
-            } else {
-                row.push(Datum::Null);
             }
+        } else {
+            row.push(Datum::Null);
         }
-        parser.expect_end_of_line()?;
-        rows.push(Row::pack(row));
     }
-    // Note that if there is any junk data after the end of copy marker, we drop
-    // it on the floor as PG does.
-    Ok(rows)
+    parser.expect_end_of_line()?;
+    Ok(Row::pack(row))
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CopyCsvFormatParams<'a> {
     pub delimiter: u8,
     pub quote: u8,
@@ -426,6 +612,19 @@ pub struct CopyCsvFormatParams<'a> {
 pub fn decode_copy_format_csv(
     data: &[u8],
     column_types: &[mz_pgrepr::Type],
+    params: CopyCsvFormatParams,
+) -> Result<Vec<Row>, io::Error> {
+    let mut rows = Vec::new();
+    decode_copy_format_csv_from_reader(data, column_types, params, |row| {
+        rows.push(row);
+        Ok(())
+    })?;
+    Ok(rows)
+}
+
+fn decode_copy_format_csv_from_reader<R, F>(
+    reader: R,
+    column_types: &[mz_pgrepr::Type],
     CopyCsvFormatParams {
         delimiter,
         quote,
@@ -433,9 +632,12 @@ pub fn decode_copy_format_csv(
         null,
         header,
     }: CopyCsvFormatParams,
-) -> Result<Vec<Row>, io::Error> {
-    let mut rows = Vec::new();
-
+    mut f: F,
+) -> Result<(), io::Error>
+where
+    R: BufRead,
+    F: FnMut(Row) -> Result<(), io::Error>,
+{
     let (double_quote, escape) = if quote == escape {
         (true, None)
     } else {
@@ -451,7 +653,7 @@ pub fn decode_copy_format_csv(
         // Must be flexible to accept end of copy marker, which will always be 1
         // field.
         .flexible(true)
-        .from_reader(data);
+        .from_reader(reader);
 
     let null_as_bytes = null.as_bytes();
 
@@ -490,12 +692,142 @@ pub fn decode_copy_format_csv(
                 }
             }
         }
-        rows.push(Row::pack(row));
+        f(Row::pack(row))?;
+    }
+
+    Ok(())
+}
+
+/// A cursor over the fields of the binary COPY format.
+struct CopyBinaryFormatParser<R> {
+    reader: R,
+    buffer: Vec<u8>,
+}
+
+impl<R: BufRead> CopyBinaryFormatParser<R> {
+    fn is_eof(&mut self) -> Result<bool, io::Error> {
+        Ok(self.reader.fill_buf()?.is_empty())
     }
 
+    fn consume_bytes(&mut self, n: usize) -> Result<&[u8], io::Error> {
+        // Read through `take` rather than into a buffer of `n` bytes, so that
+        // a corrupt field size can't make us allocate more than there is data.
+        self.buffer.clear();
+        let n = u64::try_from(n).expect("usize fits in u64");
+        let read = (&mut self.reader).take(n).read_to_end(&mut self.buffer)?;
+        if u64::try_from(read).expect("usize fits in u64") == n {
+            Ok(&self.buffer)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "unexpected EOF in COPY data",
+            ))
+        }
+    }
+
+    fn consume_i16(&mut self) -> Result<i16, io::Error> {
+        let bytes = self.consume_bytes(2)?;
+        Ok(i16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn consume_i32(&mut self) -> Result<i32, io::Error> {
+        let bytes = self.consume_bytes(4)?;
+        Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn expect_header(&mut self) -> Result<(), io::Error> {
+        let invalid = |msg| Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+        if self.consume_bytes(BINARY_SIGNATURE.len()).ok() != Some(BINARY_SIGNATURE) {
+            return invalid("COPY file signature not recognized");
+        }
+        let flags = self.consume_i32()?;
+        if flags & (1 << 16) != 0 {
+            return invalid("invalid COPY file header (WITH OIDS)");
+        }
+        // The high bits of the flags are reserved for critical flags, which
+        // we must reject if we do not understand them.
+        if flags >> 16 != 0 {
+            return invalid("unrecognized critical flags in COPY file header");
+        }
+        let extension_len = self.consume_i32()?;
+        match usize::try_from(extension_len) {
+            Ok(len) => {
+                self.consume_bytes(len)?;
+                Ok(())
+            }
+            Err(_) => invalid("invalid COPY file header (negative length)"),
+        }
+    }
+}
+
+pub fn decode_copy_format_binary(
+    data: &[u8],
+    column_types: &[mz_pgrepr::Type],
+) -> Result<Vec<Row>, io::Error> {
+    let mut rows = Vec::new();
+    decode_copy_format_binary_from_reader(data, column_types, |row| {
+        rows.push(row);
+        Ok(())
+    })?;
     Ok(rows)
 }
 
+fn decode_copy_format_binary_from_reader<R, F>(
+    reader: R,
+    column_types: &[mz_pgrepr::Type],
+    mut f: F,
+) -> Result<(), io::Error>
+where
+    R: BufRead,
+    F: FnMut(Row) -> Result<(), io::Error>,
+{
+    let mut parser = CopyBinaryFormatParser {
+        reader,
+        buffer: Vec::new(),
+    };
+    parser.expect_header()?;
+    // Like PG, accept data that ends without a trailer.
+    while !parser.is_eof()? {
+        let field_count = parser.consume_i16()?;
+        // The trailer is a field count of -1. Any data after it is dropped on
+        // the floor, as PG does.
+        if field_count == -1 {
+            break;
+        }
+        if usize::try_from(field_count).ok() != Some(column_types.len()) {
+            let msg = format!(
+                "row field count is {}, expected {}",
+                field_count,
+                column_types.len()
+            );
+            return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+        }
+
+        let mut row = Vec::new();
+        let buf = RowArena::new();
+        for typ in column_types {
+            let len = parser.consume_i32()?;
+            if len == -1 {
+                row.push(Datum::Null);
+                continue;
+            }
+            let len = usize::try_from(len)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid field size"))?;
+            let raw_value = parser.consume_bytes(len)?;
+            match mz_pgrepr::Value::decode_binary(typ, raw_value) {
+                Ok(value) => row.push(value.into_datum(&buf, typ)),
+                Err(err) => {
+                    let msg = format!("unable to decode column: {}", err);
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+                }
+            }
+        }
+        f(Row::pack(row))?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -661,4 +993,150 @@ mod tests {
             assert!(parser.is_eof());
         }
     }
+    #[test]
+    fn test_copy_format_csv_encode() {
+        let typ = RelationType::new(vec![
+            ScalarType::String.nullable(true),
+            ScalarType::Int32.nullable(true),
+        ]);
+        let params = CopyCsvFormatParams {
+            delimiter: b'|',
+            quote: b'\'',
+            escape: b'\\',
+            header: false,
+            null: Cow::from("NULL"),
+        };
+        let rows = vec![
+            Row::pack_slice(&[Datum::String("plain"), Datum::Int32(1)]),
+            Row::pack_slice(&[Datum::String("a|b"), Datum::Null]),
+            Row::pack_slice(&[Datum::String("it's"), Datum::Int32(2)]),
+            Row::pack_slice(&[Datum::String("NULL"), Datum::Int32(3)]),
+            Row::pack_slice(&[Datum::String("back\\slash\n"), Datum::Int32(4)]),
+        ];
+        let mut out = Vec::new();
+        for row in rows {
+            encode_copy_row_csv(&params, row, &typ, &mut out).expect("encoding failed");
+        }
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "plain|1\n'a|b'|NULL\n'it\\'s'|2\n'NULL'|3\n'back\\\\slash\n'|4\n"
+        );
+    }
+
+    #[test]
+    fn test_copy_format_binary_roundtrip() {
+        let typ = RelationType::new(vec![
+            ScalarType::Int64.nullable(true),
+            ScalarType::String.nullable(true),
+        ]);
+        let rows = vec![
+            Row::pack_slice(&[Datum::Int64(1), Datum::String("one")]),
+            Row::pack_slice(&[Datum::Int64(-2), Datum::Null]),
+        ];
+        let desc = RelationDesc::new(typ.clone(), ["a", "b"]);
+        let params = CopyFormatParams::Binary;
+        let mut out = Vec::new();
+        encode_copy_header(&params, &desc, &mut out).expect("encoding failed");
+        for row in rows.clone() {
+            encode_copy_format(&params, row, &typ, &mut out).expect("encoding failed");
+        }
+        encode_copy_trailer(&params, &mut out);
+
+        let column_types = typ
+            .column_types
+            .iter()
+            .map(|typ| mz_pgrepr::Type::from(&typ.scalar_type))
+            .collect::<Vec<_>>();
+        let decoded = decode_copy_format(&out, &column_types, params).expect("decoding failed");
+        assert_eq!(decoded, rows);
+
+        // Bad signature.
+        let err = decode_copy_format_binary(b"PGCOPY\n", &column_types).unwrap_err();
+        assert_eq!(err.to_string(), "COPY file signature not recognized");
+
+        // Wrong number of fields.
+        let err = decode_copy_format_binary(&out, &column_types[..1]).unwrap_err();
+        assert_eq!(err.to_string(), "row field count is 2, expected 1");
+
+        // Truncated data.
+        let err = decode_copy_format_binary(&out[..out.len() - 4], &column_types).unwrap_err();
+        assert_eq!(err.to_string(), "unexpected EOF in COPY data");
+    }
+
+    #[test]
+    fn test_copy_format_from_reader() {
+        let column_types = vec![mz_pgrepr::Type::Int4, mz_pgrepr::Type::Text];
+        let decode = |data: &[u8], params: CopyFormatParams| {
+            // Read a byte at a time to make sure that no row relies on being
+            // read all at once.
+            let reader = io::BufReader::with_capacity(1, data);
+            let mut rows = Vec::new();
+            decode_copy_format_from_reader(reader, &column_types, params.clone(), |row| {
+                rows.push(row);
+                Ok(())
+            })
+            .expect("decoding failed");
+            assert_eq!(
+                rows,
+                decode_copy_format(data, &column_types, params).expect("decoding failed")
+            );
+            rows
+        };
+
+        let text = CopyFormatParams::Text(CopyTextFormatParams {
+            null: Cow::from("\\N"),
+            delimiter: Cow::from("\t"),
+        });
+        assert_eq!(
+            decode(
+                b"1\tone\n2\t\\N\n3\tthree\\\nlines\n\\.\n4\tjunk\n",
+                text.clone()
+            ),
+            vec![
+                Row::pack_slice(&[Datum::Int32(1), Datum::String("one")]),
+                Row::pack_slice(&[Datum::Int32(2), Datum::Null]),
+                Row::pack_slice(&[Datum::Int32(3), Datum::String("three\nlines")]),
+            ]
+        );
+        assert_eq!(
+            decode(b"1\tback\\\\\n2\tslash", text),
+            vec![
+                Row::pack_slice(&[Datum::Int32(1), Datum::String("back\\")]),
+                Row::pack_slice(&[Datum::Int32(2), Datum::String("slash")]),
+            ]
+        );
+
+        let csv = CopyFormatParams::Csv(CopyCsvFormatParams {
+            delimiter: b',',
+            quote: b'"',
+            escape: b'"',
+            header: true,
+            null: Cow::from(""),
+        });
+        assert_eq!(
+            decode(b"a,b\n1,\"multi\nline\"\n2,\n", csv),
+            vec![
+                Row::pack_slice(&[Datum::Int32(1), Datum::String("multi\nline")]),
+                Row::pack_slice(&[Datum::Int32(2), Datum::Null]),
+            ]
+        );
+
+        let typ = RelationType::new(vec![
+            ScalarType::Int32.nullable(true),
+            ScalarType::String.nullable(true),
+        ]);
+        let desc = RelationDesc::new(typ.clone(), ["a", "b"]);
+        let rows = vec![
+            Row::pack_slice(&[Datum::Int32(1), Datum::String("one")]),
+            Row::pack_slice(&[Datum::Null, Datum::String("two")]),
+        ];
+        let mut out = Vec::new();
+        encode_copy_header(&CopyFormatParams::Binary, &desc, &mut out).expect("encoding failed");
+        for row in rows.clone() {
+            encode_copy_format(&CopyFormatParams::Binary, row, &typ, &mut out)
+                .expect("encoding failed");
+        }
+        encode_copy_trailer(&CopyFormatParams::Binary, &mut out);
+        assert_eq!(decode(&out, CopyFormatParams::Binary), rows);
+    }
 }
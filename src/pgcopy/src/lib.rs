@@ -15,5 +15,8 @@
 
 mod copy;
 
-pub use copy::{decode_copy_format, encode_copy_row_binary, encode_copy_row_text};
+pub use copy::{
+    decode_copy_format, decode_copy_format_from_reader, encode_copy_format, encode_copy_header,
+    encode_copy_row_binary, encode_copy_row_csv, encode_copy_row_text, encode_copy_trailer,
+};
 pub use copy::{CopyCsvFormatParams, CopyFormatParams, CopyTextFormatParams, CopyTextFormatParser};
@@ -101,3 +101,15 @@ pub const TYPE_ANYCOMPATIBLEMAP_OID: u32 = 16_455;
 pub const FUNC_MAP_LENGTH_OID: u32 = 16_456;
 pub const FUNC_MZ_PANIC_OID: u32 = 16_457;
 pub const FUNC_MZ_VERSION_NUM_OID: u32 = 16_458;
+pub const FUNC_MZ_TUMBLE_TS_OID: u32 = 16_459;
+pub const FUNC_MZ_TUMBLE_TSTZ_OID: u32 = 16_460;
+pub const FUNC_MZ_TUMBLE_LATENESS_TS_OID: u32 = 16_461;
+pub const FUNC_MZ_TUMBLE_LATENESS_TSTZ_OID: u32 = 16_462;
+pub const FUNC_MZ_HOP_TS_OID: u32 = 16_463;
+pub const FUNC_MZ_HOP_TSTZ_OID: u32 = 16_464;
+pub const FUNC_MZ_HOP_LATENESS_TS_OID: u32 = 16_465;
+pub const FUNC_MZ_HOP_LATENESS_TSTZ_OID: u32 = 16_466;
+pub const FUNC_MZ_SESSION_TS_OID: u32 = 16_467;
+pub const FUNC_MZ_SESSION_TSTZ_OID: u32 = 16_468;
+pub const FUNC_MZ_SESSION_LATENESS_TS_OID: u32 = 16_469;
+pub const FUNC_MZ_SESSION_LATENESS_TSTZ_OID: u32 = 16_470;
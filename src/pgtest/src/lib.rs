@@ -30,6 +30,8 @@
 //! - [`Bind`](struct.Bind.html)
 //! - [`Execute`](struct.Execute.html)
 //! - `Sync`
+//! - `CopyData`, whose argument is a JSON string or, for data that is not
+//! valid UTF-8, a JSON array of bytes.
 //!
 //! Supported `until` arguments:
 //! - `no_error_fields` causes `ErrorResponse` messages to have empty
@@ -524,8 +526,11 @@ pub fn run_test(tf: &mut datadriven::TestFile, addr: String, user: String, timeo
                             .unwrap();
                         }
                         "CopyData" => {
-                            let v: String = serde_json::from_str(args).unwrap();
-                            frontend::CopyData::new(v.as_bytes()).unwrap().write(buf);
+                            let v: Vec<u8> = match serde_json::from_str::<String>(args) {
+                                Ok(v) => v.into_bytes(),
+                                Err(_) => serde_json::from_str(args).unwrap(),
+                            };
+                            frontend::CopyData::new(&v[..]).unwrap().write(buf);
                         }
                         "CopyDone" => {
                             frontend::copy_done(buf);
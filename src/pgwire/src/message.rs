@@ -362,8 +362,13 @@ impl ErrorResponse {
         // a various classes of uncategorized errors that use this error code
         // inappropriately.
         let code = match e {
+            AdapterError::BadCopyFile(_) => SqlState::BAD_COPY_FILE_FORMAT,
             AdapterError::Catalog(_) => SqlState::INTERNAL_ERROR,
             AdapterError::ChangedPlan => SqlState::FEATURE_NOT_SUPPORTED,
+            AdapterError::ChangedTable(_) => SqlState::T_R_SERIALIZATION_FAILURE,
+            AdapterError::CheckViolation { .. } => SqlState::CHECK_VIOLATION,
+            AdapterError::CheckViolatedByExistingRows { .. } => SqlState::CHECK_VIOLATION,
+            AdapterError::ConflictAffectsRowTwice => SqlState::CARDINALITY_VIOLATION,
             AdapterError::ConstrainedParameter { .. } => SqlState::INVALID_PARAMETER_VALUE,
             AdapterError::DuplicateCursor(_) => SqlState::DUPLICATE_CURSOR,
             AdapterError::Eval(EvalError::CharacterNotValidForEncoding(_)) => {
@@ -413,6 +418,7 @@ impl ErrorResponse {
             AdapterError::Unsupported(..) => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::Unstructured(_) => SqlState::INTERNAL_ERROR,
             AdapterError::UntargetedLogRead { .. } => SqlState::FEATURE_NOT_SUPPORTED,
+            AdapterError::UniqueViolation { .. } => SqlState::UNIQUE_VIOLATION,
             // It's not immediately clear which error code to use here because a
             // "write-only transaction" and "single table write transaction" are
             // not things in Postgres. This error code is the generic "bad txn thing"
@@ -35,10 +35,10 @@ use mz_ore::cast::CastFrom;
 use mz_ore::netio::AsyncReady;
 use mz_ore::str::StrExt;
 use mz_pgcopy::CopyFormatParams;
-use mz_repr::{Datum, RelationDesc, RelationType, Row, RowArena, ScalarType};
+use mz_repr::{Datum, RelationDesc, Row, RowArena, ScalarType};
 use mz_sql::ast::display::AstDisplay;
 use mz_sql::ast::{FetchDirection, Ident, NoticeSeverity, Raw, Statement};
-use mz_sql::plan::{CopyFormat, ExecuteTimeout, StatementDesc};
+use mz_sql::plan::{ExecuteTimeout, StatementDesc};
 
 use crate::codec::FramedConn;
 use crate::message::{
@@ -1297,6 +1297,8 @@ where
                     row_desc.expect("missing row description for ExecuteResponse::CopyFrom");
                 self.copy_from(id, columns, params, row_desc).await
             }
+            ExecuteResponse::Copied(n) => command_complete!("COPY {}", n),
+            ExecuteResponse::Truncated => command_complete!("TRUNCATE TABLE"),
             ExecuteResponse::Updated(n) => command_complete!("UPDATE {}", n),
             ExecuteResponse::AlteredObject(o) => command_complete!("ALTER {}", o),
             ExecuteResponse::AlteredIndexLogicalCompaction => command_complete!("ALTER INDEX"),
@@ -1507,27 +1509,12 @@ where
     #[tracing::instrument(level = "debug", skip(self))]
     async fn copy_rows(
         &mut self,
-        format: CopyFormat,
+        format: CopyFormatParams<'_>,
         row_desc: RelationDesc,
         mut stream: RowBatchStream,
     ) -> Result<State, io::Error> {
-        let (encode_fn, encode_format): (
-            fn(Row, &RelationType, &mut Vec<u8>) -> Result<(), std::io::Error>,
-            mz_pgrepr::Format,
-        ) = match format {
-            CopyFormat::Text => (mz_pgcopy::encode_copy_row_text, mz_pgrepr::Format::Text),
-            CopyFormat::Binary => (mz_pgcopy::encode_copy_row_binary, mz_pgrepr::Format::Binary),
-            _ => {
-                return self
-                    .error(ErrorResponse::error(
-                        SqlState::FEATURE_NOT_SUPPORTED,
-                        format!("COPY TO format {:?} not supported", format),
-                    ))
-                    .await
-            }
-        };
-
         let typ = row_desc.typ();
+        let encode_format = format.value_format();
         let column_formats = iter::repeat(encode_format)
             .take(typ.column_types.len())
             .collect();
@@ -1542,14 +1529,13 @@ where
         // common vec that we can extend one time now and then fill up with the encode
         // functions.
         let mut out = Vec::new();
-
-        if let CopyFormat::Binary = format {
-            // 11-byte signature.
-            out.extend(b"PGCOPY\n\xFF\r\n\0");
-            // 32-bit flags field.
-            out.extend(&[0, 0, 0, 0]);
-            // 32-bit header extension length field.
-            out.extend(&[0, 0, 0, 0]);
+        mz_pgcopy::encode_copy_header(&format, &row_desc, &mut out)?;
+        // The CSV header line, on the other hand, is sent on its own.
+        if let CopyFormatParams::Csv(_) = format {
+            if !out.is_empty() {
+                self.send(BackendMessage::CopyData(mem::take(&mut out)))
+                    .await?;
+            }
         }
 
         let mut count = 0;
@@ -1602,7 +1588,7 @@ where
                     Some(PeekResponseUnary::Rows(rows)) => {
                         count += rows.len();
                         for row in rows {
-                            encode_fn(row, typ, &mut out)?;
+                            mz_pgcopy::encode_copy_format(&format, row, typ, &mut out)?;
                             self.send(BackendMessage::CopyData(mem::take(&mut out)))
                                 .await?;
                         }
@@ -1613,9 +1599,8 @@ where
             self.conn.flush().await?;
         }
         // Send required trailers.
-        if let CopyFormat::Binary = format {
-            let trailer: i16 = -1;
-            out.extend(&trailer.to_be_bytes());
+        mz_pgcopy::encode_copy_trailer(&format, &mut out);
+        if !out.is_empty() {
             self.send(BackendMessage::CopyData(mem::take(&mut out)))
                 .await?;
         }
@@ -1636,9 +1621,9 @@ where
         row_desc: RelationDesc,
     ) -> Result<State, io::Error> {
         let typ = row_desc.typ();
-        let column_formats = vec![mz_pgrepr::Format::Text; typ.column_types.len()];
+        let column_formats = vec![params.value_format(); typ.column_types.len()];
         self.send(BackendMessage::CopyInResponse {
-            overall_format: mz_pgrepr::Format::Text,
+            overall_format: params.value_format(),
             column_formats,
         })
         .await?;
@@ -11,6 +11,8 @@
 //!
 //! See row.proto for details.
 
+use std::collections::HashSet;
+
 use bytes::BufMut;
 use chrono::{Datelike, Timelike};
 use dec::Decimal;
@@ -18,6 +20,7 @@ use prost::Message;
 use uuid::Uuid;
 
 use mz_ore::cast::CastFrom;
+use mz_persist_types::columnar::{ColumnData, ColumnType, Schema};
 use mz_persist_types::Codec;
 use mz_proto::{ProtoType, RustType, TryFromProtoError};
 
@@ -29,7 +32,7 @@ use crate::row::{
     ProtoArray, ProtoArrayDimension, ProtoDatum, ProtoDatumOther, ProtoDict, ProtoDictElement,
     ProtoNumeric, ProtoRow,
 };
-use crate::{Datum, Row, RowPacker};
+use crate::{Datum, RelationDesc, Row, RowPacker, ScalarType};
 
 impl Codec for Row {
     fn codec_name() -> String {
@@ -59,6 +62,35 @@ impl Codec for Row {
         let proto_row = ProtoRow::decode(buf).map_err(|err| err.to_string())?;
         Row::try_from(&proto_row)
     }
+
+    /// Decodes the row at `idx` of the typed columns that the [Schema] impl
+    /// for [RelationDesc] encoded it into.
+    ///
+    /// Every column is self-describing, so this doesn't need the
+    /// [RelationDesc].
+    fn decode_columns(columns: &[ColumnData], idx: usize) -> Result<Row, String> {
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        for col in columns.iter() {
+            match col {
+                ColumnData::Bool(x) => packer.push(Datum::from(x[idx])),
+                ColumnData::Int16(x) => packer.push(Datum::from(x[idx])),
+                ColumnData::Int32(x) => packer.push(Datum::from(x[idx])),
+                ColumnData::Int64(x) => packer.push(Datum::from(x[idx])),
+                ColumnData::Float32(x) => packer.push(Datum::from(x[idx])),
+                ColumnData::Float64(x) => packer.push(Datum::from(x[idx])),
+                ColumnData::String(x) => packer.push(Datum::from(x[idx].as_deref())),
+                ColumnData::Bytes(x) => match x[idx].as_deref() {
+                    None => packer.push(Datum::Null),
+                    Some(buf) => {
+                        let proto = ProtoDatum::decode(buf).map_err(|err| err.to_string())?;
+                        packer.try_push_proto(&proto)?;
+                    }
+                },
+            }
+        }
+        Ok(row)
+    }
 }
 
 impl<'a> From<Datum<'a>> for ProtoDatum {
@@ -246,6 +278,85 @@ impl RowPacker<'_> {
     }
 }
 
+/// Stores each column of a [Row] as a typed column.
+///
+/// Bools, integers, floats, and strings are stored natively. Every other type
+/// is stored as the protobuf encoding of the datum (the same one used by the
+/// [Codec] impl for [Row]) in a bytes column. Columns are named after the
+/// columns of the relation, disambiguated with their position if necessary.
+impl Schema<Row> for RelationDesc {
+    fn columns(&self) -> Vec<(String, ColumnType)> {
+        let mut names = HashSet::new();
+        self.iter()
+            .enumerate()
+            .map(|(idx, (name, typ))| {
+                let mut name = name.as_str().to_owned();
+                while !names.insert(name.clone()) {
+                    name = format!("{}_{}", name, idx);
+                }
+                let typ = match typ.scalar_type {
+                    ScalarType::Bool => ColumnType::Bool,
+                    ScalarType::Int16 => ColumnType::Int16,
+                    ScalarType::Int32 => ColumnType::Int32,
+                    ScalarType::Int64 => ColumnType::Int64,
+                    ScalarType::Float32 => ColumnType::Float32,
+                    ScalarType::Float64 => ColumnType::Float64,
+                    ScalarType::String | ScalarType::Char { .. } | ScalarType::VarChar { .. } => {
+                        ColumnType::String
+                    }
+                    _ => ColumnType::Bytes,
+                };
+                (name, typ)
+            })
+            .collect()
+    }
+
+    fn encode(&self, val: &Row, columns: &mut [ColumnData]) -> Result<(), String> {
+        let mut datums = val.iter();
+        for col in columns.iter_mut() {
+            let datum = datums
+                .next()
+                .ok_or_else(|| format!("row {:?} has fewer columns than {:?}", val, self))?;
+            match (datum, col) {
+                (Datum::Null, col) => col.push_null(),
+                (Datum::False, ColumnData::Bool(x)) => x.push(Some(false)),
+                (Datum::True, ColumnData::Bool(x)) => x.push(Some(true)),
+                (Datum::Int16(d), ColumnData::Int16(x)) => x.push(Some(d)),
+                (Datum::Int32(d), ColumnData::Int32(x)) => x.push(Some(d)),
+                (Datum::Int64(d), ColumnData::Int64(x)) => x.push(Some(d)),
+                (Datum::Float32(d), ColumnData::Float32(x)) => x.push(Some(d.into_inner())),
+                (Datum::Float64(d), ColumnData::Float64(x)) => x.push(Some(d.into_inner())),
+                (Datum::String(d), ColumnData::String(x)) => x.push(Some(d.to_owned())),
+                (datum, ColumnData::Bytes(x)) => {
+                    x.push(Some(ProtoDatum::from(datum).encode_to_vec()))
+                }
+                (datum, col) => {
+                    return Err(format!(
+                        "datum {:?} doesn't match column type {:?}",
+                        datum,
+                        col.typ()
+                    ))
+                }
+            }
+        }
+        if datums.next().is_some() {
+            return Err(format!("row {:?} has more columns than {:?}", val, self));
+        }
+        Ok(())
+    }
+
+    fn decode(&self, columns: &[ColumnData], idx: usize) -> Result<Row, String> {
+        if columns.len() != self.arity() {
+            return Err(format!(
+                "expected {} columns got {}",
+                self.arity(),
+                columns.len()
+            ));
+        }
+        <Row as Codec>::decode_columns(columns, idx)
+    }
+}
+
 /// TODO: remove this in favor of [`RustType::from_proto`].
 impl TryFrom<&ProtoRow> for Row {
     type Error = String;
@@ -285,13 +396,14 @@ impl RustType<ProtoRow> for Row {
 #[cfg(test)]
 mod tests {
     use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+    use mz_persist_types::columnar::{encode_columns, ColumnData, ColumnType, Schema};
     use mz_persist_types::Codec;
     use uuid::Uuid;
 
     use crate::adt::array::ArrayDimension;
     use crate::adt::interval::Interval;
     use crate::adt::numeric::Numeric;
-    use crate::{Datum, Row};
+    use crate::{Datum, RelationDesc, Row, ScalarType};
 
     // TODO: datadriven golden tests for various interesting Datums and Rows to
     // catch any changes in the encoding.
@@ -366,4 +478,59 @@ mod tests {
         row.encode(&mut encoded);
         assert_eq!(Row::decode(&encoded), Ok(row));
     }
+
+    #[test]
+    fn schema_roundtrip() {
+        let desc = RelationDesc::empty()
+            .with_column("a", ScalarType::Bool.nullable(true))
+            .with_column("b", ScalarType::Int64.nullable(false))
+            .with_column("a", ScalarType::String.nullable(true))
+            .with_column("c", ScalarType::Numeric { max_scale: None }.nullable(true));
+        assert_eq!(
+            desc.columns(),
+            vec![
+                ("a".to_owned(), ColumnType::Bool),
+                ("b".to_owned(), ColumnType::Int64),
+                ("a_2".to_owned(), ColumnType::String),
+                ("c".to_owned(), ColumnType::Bytes),
+            ]
+        );
+
+        let rows = vec![
+            Row::pack_slice(&[
+                Datum::True,
+                Datum::Int64(1),
+                Datum::String("one"),
+                Datum::from(Numeric::from(1)),
+            ]),
+            Row::pack_slice(&[Datum::Null, Datum::Int64(2), Datum::Null, Datum::Null]),
+            Row::pack_slice(&[
+                Datum::False,
+                Datum::Int64(3),
+                Datum::String(""),
+                Datum::from(Numeric::nan()),
+            ]),
+        ];
+        let columns = encode_columns(&desc, &rows).expect("rows match desc");
+        assert_eq!(
+            columns[1],
+            (
+                "b".to_owned(),
+                ColumnData::Int64(vec![Some(1), Some(2), Some(3)])
+            )
+        );
+        let columns = columns.into_iter().map(|(_, col)| col).collect::<Vec<_>>();
+        for (idx, row) in rows.iter().enumerate() {
+            assert_eq!(desc.decode(&columns, idx).as_ref(), Ok(row));
+            // The columns are self-describing, so rows can also be decoded
+            // without the desc.
+            assert_eq!(Row::decode_columns(&columns, idx).as_ref(), Ok(row));
+        }
+
+        // Rows that don't match the desc are rejected.
+        let row = Row::pack_slice(&[Datum::Int64(1), Datum::Int64(1), Datum::Null, Datum::Null]);
+        assert!(encode_columns(&desc, &[row]).is_err());
+        let row = Row::pack_slice(&[Datum::True]);
+        assert!(encode_columns(&desc, &[row]).is_err());
+    }
 }
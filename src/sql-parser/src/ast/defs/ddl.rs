@@ -25,7 +25,7 @@ use std::fmt;
 use std::path::PathBuf;
 
 use crate::ast::display::{self, AstDisplay, AstFormatter};
-use crate::ast::{AstInfo, Expr, Ident, UnresolvedObjectName, WithOption, WithOptionValue};
+use crate::ast::{AstInfo, Expr, Ident, UnresolvedObjectName, Value, WithOption, WithOptionValue};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Schema {
@@ -120,6 +120,9 @@ impl_display_t!(AvroSchema);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ProtobufSchema<T: AstInfo> {
     Csr {
+        /// The fully-qualified name of the message to decode, if the schema
+        /// in the registry defines more than one message.
+        message_name: Option<String>,
         csr_connection: CsrConnectionProtobuf<T>,
     },
     InlineSchema {
@@ -131,7 +134,15 @@ pub enum ProtobufSchema<T: AstInfo> {
 impl<T: AstInfo> AstDisplay for ProtobufSchema<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
-            Self::Csr { csr_connection } => {
+            Self::Csr {
+                message_name,
+                csr_connection,
+            } => {
+                if let Some(message_name) = message_name {
+                    f.write_str("MESSAGE '");
+                    f.write_node(&display::escape_single_quote_string(message_name));
+                    f.write_str("' ");
+                }
                 f.write_node(csr_connection);
             }
             Self::InlineSchema {
@@ -710,6 +721,50 @@ impl<T: AstInfo> AstDisplay for SshConnectionOption<T> {
 }
 impl_display_t!(SshConnectionOption);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AwsConnectionOptionName {
+    AccessKeyId,
+    Endpoint,
+    Profile,
+    Region,
+    RoleArn,
+    SecretAccessKey,
+    Token,
+}
+
+impl AstDisplay for AwsConnectionOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            AwsConnectionOptionName::AccessKeyId => "ACCESS KEY ID",
+            AwsConnectionOptionName::Endpoint => "ENDPOINT",
+            AwsConnectionOptionName::Profile => "PROFILE",
+            AwsConnectionOptionName::Region => "REGION",
+            AwsConnectionOptionName::RoleArn => "ROLE ARN",
+            AwsConnectionOptionName::SecretAccessKey => "SECRET ACCESS KEY",
+            AwsConnectionOptionName::Token => "TOKEN",
+        })
+    }
+}
+impl_display!(AwsConnectionOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE CONNECTION...AWS`.
+pub struct AwsConnectionOption<T: AstInfo> {
+    pub name: AwsConnectionOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for AwsConnectionOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(AwsConnectionOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CreateConnection<T: AstInfo> {
     Kafka {
@@ -724,6 +779,9 @@ pub enum CreateConnection<T: AstInfo> {
     Ssh {
         with_options: Vec<SshConnectionOption<T>>,
     },
+    Aws {
+        with_options: Vec<AwsConnectionOption<T>>,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for CreateConnection<T> {
@@ -745,6 +803,10 @@ impl<T: AstInfo> AstDisplay for CreateConnection<T> {
                 f.write_str("SSH TUNNEL ");
                 f.write_node(&display::comma_separated(&with_options));
             }
+            Self::Aws { with_options } => {
+                f.write_str("AWS ");
+                f.write_node(&display::comma_separated(&with_options));
+            }
         }
     }
 }
@@ -778,6 +840,9 @@ pub struct KafkaSourceConnection<T: AstInfo> {
     pub connection: KafkaConnection<T>,
     pub topic: String,
     pub key: Option<Vec<Ident>>,
+    /// The argument to the `START TIMESTAMP` clause: milliseconds since the
+    /// Unix epoch, or relative to now if negative or an interval.
+    pub start_timestamp: Option<Value>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -816,6 +881,7 @@ impl<T: AstInfo> AstDisplay for CreateSourceConnection<T> {
                 connection,
                 topic,
                 key,
+                start_timestamp,
             }) => {
                 f.write_str("KAFKA ");
                 f.write_node(connection);
@@ -827,6 +893,10 @@ impl<T: AstInfo> AstDisplay for CreateSourceConnection<T> {
                     f.write_node(&display::comma_separated(&key));
                     f.write_str(")");
                 }
+                if let Some(start_timestamp) = start_timestamp {
+                    f.write_str(" START TIMESTAMP ");
+                    f.write_node(start_timestamp);
+                }
             }
             CreateSourceConnection::Kinesis { arn } => {
                 f.write_str("KINESIS ARN '");
@@ -31,6 +31,8 @@ use crate::ast::{AstInfo, Expr, FunctionArgs, Ident, UnresolvedObjectName, WithO
 pub struct Query<T: AstInfo> {
     /// WITH (common table expressions, or CTEs)
     pub ctes: Vec<Cte<T>>,
+    /// Whether the WITH clause is `WITH RECURSIVE`
+    pub recursive: bool,
     /// SELECT or UNION / EXCEPT / INTERSECT
     pub body: SetExpr<T>,
     /// ORDER BY
@@ -46,6 +48,9 @@ impl<T: AstInfo> AstDisplay for Query<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         if !self.ctes.is_empty() {
             f.write_str("WITH ");
+            if self.recursive {
+                f.write_str("RECURSIVE ");
+            }
             f.write_node(&display::comma_separated(&self.ctes));
             f.write_str(" ");
         }
@@ -84,6 +89,7 @@ impl<T: AstInfo> Query<T> {
     pub fn select(select: Select<T>) -> Query<T> {
         Query {
             ctes: vec![],
+            recursive: false,
             body: SetExpr::Select(Box::new(select)),
             order_by: vec![],
             limit: None,
@@ -94,6 +100,7 @@ impl<T: AstInfo> Query<T> {
     pub fn query(query: Query<T>) -> Query<T> {
         Query {
             ctes: vec![],
+            recursive: false,
             body: SetExpr::Query(Box::new(query)),
             order_by: vec![],
             limit: None,
@@ -106,6 +113,7 @@ impl<T: AstInfo> Query<T> {
             self,
             Query::<T> {
                 ctes: vec![],
+                recursive: false,
                 order_by: vec![],
                 body: SetExpr::Values(Values(vec![])),
                 limit: None,
@@ -196,7 +204,7 @@ pub struct Select<T: AstInfo> {
     /// WHERE
     pub selection: Option<Expr<T>>,
     /// GROUP BY
-    pub group_by: Vec<Expr<T>>,
+    pub group_by: Vec<GroupByItem<T>>,
     /// HAVING
     pub having: Option<Expr<T>>,
     /// OPTION
@@ -297,6 +305,64 @@ impl<T: AstInfo> AstDisplay for Cte<T> {
 }
 impl_display_t!(Cte);
 
+/// One item of the comma-separated list following `GROUP BY`, or of the list
+/// inside `GROUPING SETS (...)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GroupByItem<T: AstInfo> {
+    /// A grouping expression, e.g. `a`.
+    Expr(Expr<T>),
+    /// A parenthesized list of expressions that form a single grouping set,
+    /// e.g. `(a, b)` within `GROUPING SETS`, or the empty grouping set `()`.
+    Set(Vec<Expr<T>>),
+    /// `ROLLUP (a, (b, c), ...)`, where each element is a list of expressions
+    /// that are treated as a unit.
+    Rollup(Vec<Vec<Expr<T>>>),
+    /// `CUBE (a, (b, c), ...)`, where each element is a list of expressions
+    /// that are treated as a unit.
+    Cube(Vec<Vec<Expr<T>>>),
+    /// `GROUPING SETS (...)`.
+    GroupingSets(Vec<GroupByItem<T>>),
+}
+
+impl<T: AstInfo> AstDisplay for GroupByItem<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            GroupByItem::Expr(expr) => f.write_node(expr),
+            GroupByItem::Set(exprs) => {
+                f.write_str("(");
+                f.write_node(&display::comma_separated(exprs));
+                f.write_str(")");
+            }
+            GroupByItem::Rollup(elems) | GroupByItem::Cube(elems) => {
+                if let GroupByItem::Rollup(_) = self {
+                    f.write_str("ROLLUP (");
+                } else {
+                    f.write_str("CUBE (");
+                }
+                let mut delim = "";
+                for elem in elems {
+                    f.write_str(delim);
+                    delim = ", ";
+                    if elem.len() == 1 {
+                        f.write_node(&elem[0]);
+                    } else {
+                        f.write_str("(");
+                        f.write_node(&display::comma_separated(elem));
+                        f.write_str(")");
+                    }
+                }
+                f.write_str(")");
+            }
+            GroupByItem::GroupingSets(items) => {
+                f.write_str("GROUPING SETS (");
+                f.write_node(&display::comma_separated(items));
+                f.write_str(")");
+            }
+        }
+    }
+}
+impl_display_t!(GroupByItem);
+
 /// One item of the comma-separated list following `SELECT`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SelectItem<T: AstInfo> {
@@ -37,6 +37,7 @@ pub enum Statement<T: AstInfo> {
     Copy(CopyStatement<T>),
     Update(UpdateStatement<T>),
     Delete(DeleteStatement<T>),
+    Truncate(TruncateStatement<T>),
     CreateConnection(CreateConnectionStatement<T>),
     CreateDatabase(CreateDatabaseStatement),
     CreateSchema(CreateSchemaStatement),
@@ -55,6 +56,7 @@ pub enum Statement<T: AstInfo> {
     AlterObjectRename(AlterObjectRenameStatement),
     AlterIndex(AlterIndexStatement<T>),
     AlterSecret(AlterSecretStatement<T>),
+    AlterTable(AlterTableStatement<T>),
     Discard(DiscardStatement),
     DropDatabase(DropDatabaseStatement),
     DropSchema(DropSchemaStatement),
@@ -100,6 +102,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::Copy(stmt) => f.write_node(stmt),
             Statement::Update(stmt) => f.write_node(stmt),
             Statement::Delete(stmt) => f.write_node(stmt),
+            Statement::Truncate(stmt) => f.write_node(stmt),
             Statement::CreateConnection(stmt) => f.write_node(stmt),
             Statement::CreateDatabase(stmt) => f.write_node(stmt),
             Statement::CreateSchema(stmt) => f.write_node(stmt),
@@ -118,6 +121,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::AlterObjectRename(stmt) => f.write_node(stmt),
             Statement::AlterIndex(stmt) => f.write_node(stmt),
             Statement::AlterSecret(stmt) => f.write_node(stmt),
+            Statement::AlterTable(stmt) => f.write_node(stmt),
             Statement::Discard(stmt) => f.write_node(stmt),
             Statement::DropDatabase(stmt) => f.write_node(stmt),
             Statement::DropSchema(stmt) => f.write_node(stmt),
@@ -185,6 +189,8 @@ pub struct InsertStatement<T: AstInfo> {
     pub columns: Vec<Ident>,
     /// A SQL query that specifies what to insert.
     pub source: InsertSource<T>,
+    /// ON CONFLICT
+    pub on_conflict: Option<OnConflict<T>>,
     /// RETURNING
     pub returning: Vec<SelectItem<T>>,
 }
@@ -200,6 +206,10 @@ impl<T: AstInfo> AstDisplay for InsertStatement<T> {
         }
         f.write_str(" ");
         f.write_node(&self.source);
+        if let Some(on_conflict) = &self.on_conflict {
+            f.write_str(" ");
+            f.write_node(on_conflict);
+        }
         if !self.returning.is_empty() {
             f.write_str(" RETURNING ");
             f.write_node(&display::comma_separated(&self.returning));
@@ -208,6 +218,60 @@ impl<T: AstInfo> AstDisplay for InsertStatement<T> {
 }
 impl_display_t!(InsertStatement);
 
+/// The `ON CONFLICT` clause of an `INSERT` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnConflict<T: AstInfo> {
+    /// The columns of the unique constraint that is checked for conflicts.
+    /// Empty if no conflict target was specified.
+    pub target: Vec<Ident>,
+    /// What to do with rows that conflict with an existing row.
+    pub action: OnConflictAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for OnConflict<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ON CONFLICT ");
+        if !self.target.is_empty() {
+            f.write_str("(");
+            f.write_node(&display::comma_separated(&self.target));
+            f.write_str(") ");
+        }
+        f.write_node(&self.action);
+    }
+}
+impl_display_t!(OnConflict);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OnConflictAction<T: AstInfo> {
+    /// `DO NOTHING`
+    DoNothing,
+    /// `DO UPDATE SET ... [WHERE ...]`
+    DoUpdate {
+        assignments: Vec<Assignment<T>>,
+        selection: Option<Expr<T>>,
+    },
+}
+
+impl<T: AstInfo> AstDisplay for OnConflictAction<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            OnConflictAction::DoNothing => f.write_str("DO NOTHING"),
+            OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            } => {
+                f.write_str("DO UPDATE SET ");
+                f.write_node(&display::comma_separated(assignments));
+                if let Some(selection) = selection {
+                    f.write_str(" WHERE ");
+                    f.write_node(selection);
+                }
+            }
+        }
+    }
+}
+impl_display_t!(OnConflictAction);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CopyRelation<T: AstInfo> {
     Table {
@@ -238,14 +302,21 @@ impl_display!(CopyDirection);
 pub enum CopyTarget {
     Stdin,
     Stdout,
+    /// A location outside of Materialize, like `'s3://bucket/key'`.
+    Location(String),
 }
 
 impl AstDisplay for CopyTarget {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
-        f.write_str(match self {
-            CopyTarget::Stdin => "STDIN",
-            CopyTarget::Stdout => "STDOUT",
-        })
+        match self {
+            CopyTarget::Stdin => f.write_str("STDIN"),
+            CopyTarget::Stdout => f.write_str("STDOUT"),
+            CopyTarget::Location(location) => {
+                f.write_str("'");
+                f.write_node(&display::escape_single_quote_string(location));
+                f.write_str("'");
+            }
+        }
     }
 }
 impl_display!(CopyTarget);
@@ -258,6 +329,7 @@ pub enum CopyOptionName {
     Escape,
     Quote,
     Header,
+    Connection,
 }
 
 impl AstDisplay for CopyOptionName {
@@ -269,6 +341,7 @@ impl AstDisplay for CopyOptionName {
             CopyOptionName::Escape => "ESCAPE",
             CopyOptionName::Quote => "QUOTE",
             CopyOptionName::Header => "HEADER",
+            CopyOptionName::Connection => "CONNECTION",
         })
     }
 }
@@ -398,6 +471,21 @@ impl<T: AstInfo> AstDisplay for DeleteStatement<T> {
 }
 impl_display_t!(DeleteStatement);
 
+/// `TRUNCATE`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TruncateStatement<T: AstInfo> {
+    /// The tables to truncate.
+    pub table_names: Vec<T::ObjectName>,
+}
+
+impl<T: AstInfo> AstDisplay for TruncateStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("TRUNCATE ");
+        f.write_node(&display::comma_separated(&self.table_names));
+    }
+}
+impl_display_t!(TruncateStatement);
+
 /// `CREATE DATABASE`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateDatabaseStatement {
@@ -1210,6 +1298,59 @@ impl<T: AstInfo> AstDisplay for AlterSecretStatement<T> {
 
 impl_display_t!(AlterSecretStatement);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlterTableAction<T: AstInfo> {
+    AddColumn {
+        if_not_exists: bool,
+        column: ColumnDef<T>,
+    },
+    DropColumn {
+        if_exists: bool,
+        name: Ident,
+    },
+}
+
+/// `ALTER TABLE ... {ADD, DROP} COLUMN`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterTableStatement<T: AstInfo> {
+    pub name: UnresolvedObjectName,
+    pub if_exists: bool,
+    pub action: AlterTableAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for AlterTableStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER TABLE ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.name);
+        f.write_str(" ");
+
+        match &self.action {
+            AlterTableAction::AddColumn {
+                if_not_exists,
+                column,
+            } => {
+                f.write_str("ADD COLUMN ");
+                if *if_not_exists {
+                    f.write_str("IF NOT EXISTS ");
+                }
+                f.write_node(column);
+            }
+            AlterTableAction::DropColumn { if_exists, name } => {
+                f.write_str("DROP COLUMN ");
+                if *if_exists {
+                    f.write_str("IF EXISTS ");
+                }
+                f.write_node(name);
+            }
+        }
+    }
+}
+
+impl_display_t!(AlterTableStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DiscardStatement {
     pub target: DiscardTarget,
@@ -2030,6 +2171,9 @@ pub enum WithOptionValue<T: AstInfo> {
     // Temporary variant until we have support for connections, which will use
     // explicit fields for each secret reference.
     Secret(T::ObjectName),
+    /// A reference to a catalog object, like the connection in
+    /// `COPY ... WITH (CONNECTION conn)`.
+    Object(T::ObjectName),
 }
 
 impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
@@ -2042,6 +2186,7 @@ impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
                 f.write_str("SECRET ");
                 f.write_node(name)
             }
+            WithOptionValue::Object(name) => f.write_node(name),
         }
     }
 }
@@ -241,6 +241,7 @@ impl<'a> Parser<'a> {
                 Token::Keyword(DELETE) => Ok(self.parse_delete()?),
                 Token::Keyword(INSERT) => Ok(self.parse_insert()?),
                 Token::Keyword(UPDATE) => Ok(self.parse_update()?),
+                Token::Keyword(TRUNCATE) => Ok(self.parse_truncate()?),
                 Token::Keyword(ALTER) => Ok(self.parse_alter()?),
                 Token::Keyword(COPY) => Ok(self.parse_copy()?),
                 Token::Keyword(SET) => Ok(self.parse_set()?),
@@ -535,7 +536,7 @@ impl<'a> Parser<'a> {
                         let query = SetExpr::Query(Box::new(query));
                         let ctes = vec![];
                         let body = parser.parse_query_body_seeded(SetPrecedence::Zero, query)?;
-                        Ok(Either::Query(parser.parse_query_tail(ctes, body)?))
+                        Ok(Either::Query(parser.parse_query_tail(ctes, false, body)?))
                     }
 
                     // The next token is something else. That implies we have a
@@ -1723,9 +1724,19 @@ impl<'a> Parser<'a> {
     fn parse_protobuf_schema(&mut self) -> Result<ProtobufSchema<Raw>, ParserError> {
         if self.parse_keywords(&[USING, CONFLUENT, SCHEMA, REGISTRY]) {
             let csr_connection = self.parse_csr_connection_proto()?;
-            Ok(ProtobufSchema::Csr { csr_connection })
+            Ok(ProtobufSchema::Csr {
+                message_name: None,
+                csr_connection,
+            })
         } else if self.parse_keyword(MESSAGE) {
             let message_name = self.parse_literal_string()?;
+            if self.parse_keywords(&[USING, CONFLUENT, SCHEMA, REGISTRY]) {
+                let csr_connection = self.parse_csr_connection_proto()?;
+                return Ok(ProtobufSchema::Csr {
+                    message_name: Some(message_name),
+                    csr_connection,
+                });
+            }
             self.expect_keyword(USING)?;
             let schema = self.parse_schema()?;
             Ok(ProtobufSchema::InlineSchema {
@@ -1940,36 +1951,72 @@ impl<'a> Parser<'a> {
         let if_not_exists = self.parse_if_not_exists()?;
         let name = self.parse_object_name()?;
         self.expect_keyword(FOR)?;
-        let connection = match self.expect_one_of_keywords(&[KAFKA, CONFLUENT, POSTGRES, SSH])? {
-            KAFKA => {
-                let with_options =
-                    self.parse_comma_separated(Parser::parse_kafka_connection_options)?;
-                CreateConnection::Kafka { with_options }
-            }
-            CONFLUENT => {
-                self.expect_keywords(&[SCHEMA, REGISTRY])?;
-                let with_options =
-                    self.parse_comma_separated(Parser::parse_csr_connection_options)?;
-                CreateConnection::Csr { with_options }
+        let connection =
+            match self.expect_one_of_keywords(&[AWS, KAFKA, CONFLUENT, POSTGRES, SSH])? {
+                AWS => {
+                    let with_options =
+                        self.parse_comma_separated(Parser::parse_aws_connection_options)?;
+                    CreateConnection::Aws { with_options }
+                }
+                KAFKA => {
+                    let with_options =
+                        self.parse_comma_separated(Parser::parse_kafka_connection_options)?;
+                    CreateConnection::Kafka { with_options }
+                }
+                CONFLUENT => {
+                    self.expect_keywords(&[SCHEMA, REGISTRY])?;
+                    let with_options =
+                        self.parse_comma_separated(Parser::parse_csr_connection_options)?;
+                    CreateConnection::Csr { with_options }
+                }
+                POSTGRES => {
+                    let with_options =
+                        self.parse_comma_separated(Parser::parse_postgres_connection_options)?;
+                    CreateConnection::Postgres { with_options }
+                }
+                SSH => {
+                    self.expect_keyword(TUNNEL)?;
+                    let with_options =
+                        self.parse_comma_separated(Parser::parse_ssh_connection_options)?;
+                    CreateConnection::Ssh { with_options }
+                }
+                _ => unreachable!(),
+            };
+        Ok(Statement::CreateConnection(CreateConnectionStatement {
+            name,
+            connection,
+            if_not_exists,
+        }))
+    }
+
+    fn parse_aws_connection_options(&mut self) -> Result<AwsConnectionOption<Raw>, ParserError> {
+        let name = match self
+            .expect_one_of_keywords(&[ACCESS, ENDPOINT, PROFILE, REGION, ROLE, SECRET, TOKEN])?
+        {
+            ACCESS => {
+                self.expect_keywords(&[KEY, ID])?;
+                AwsConnectionOptionName::AccessKeyId
             }
-            POSTGRES => {
-                let with_options =
-                    self.parse_comma_separated(Parser::parse_postgres_connection_options)?;
-                CreateConnection::Postgres { with_options }
+            ENDPOINT => AwsConnectionOptionName::Endpoint,
+            PROFILE => AwsConnectionOptionName::Profile,
+            REGION => AwsConnectionOptionName::Region,
+            ROLE => {
+                self.expect_keyword(ARN)?;
+                AwsConnectionOptionName::RoleArn
             }
-            SSH => {
-                self.expect_keyword(TUNNEL)?;
-                let with_options =
-                    self.parse_comma_separated(Parser::parse_ssh_connection_options)?;
-                CreateConnection::Ssh { with_options }
+            SECRET => {
+                self.expect_keywords(&[ACCESS, KEY])?;
+                AwsConnectionOptionName::SecretAccessKey
             }
+            TOKEN => AwsConnectionOptionName::Token,
             _ => unreachable!(),
         };
-        Ok(Statement::CreateConnection(CreateConnectionStatement {
+
+        let _ = self.consume_token(&Token::Eq);
+        Ok(AwsConnectionOption {
             name,
-            connection,
-            if_not_exists,
-        }))
+            value: self.parse_opt_with_option_value(false)?,
+        })
     }
 
     fn parse_kafka_connection_options(
@@ -2283,10 +2330,17 @@ impl<'a> Parser<'a> {
                 } else {
                     None
                 };
+                let start_timestamp = if self.parse_keywords(&[START, TIMESTAMP]) {
+                    let _ = self.consume_token(&Token::Eq);
+                    Some(self.parse_value()?)
+                } else {
+                    None
+                };
                 Ok(CreateSourceConnection::Kafka(KafkaSourceConnection {
                     connection,
                     topic,
                     key,
+                    start_timestamp,
                 }))
             }
             KINESIS => {
@@ -2982,26 +3036,7 @@ impl<'a> Parser<'a> {
             if let Some(constraint) = self.parse_optional_table_constraint()? {
                 constraints.push(constraint);
             } else if let Some(column_name) = self.consume_identifier() {
-                let data_type = self.parse_data_type()?;
-                let collation = if self.parse_keyword(COLLATE) {
-                    Some(self.parse_object_name()?)
-                } else {
-                    None
-                };
-                let mut options = vec![];
-                loop {
-                    match self.peek_token() {
-                        None | Some(Token::Comma) | Some(Token::RParen) => break,
-                        _ => options.push(self.parse_column_option_def()?),
-                    }
-                }
-
-                columns.push(ColumnDef {
-                    name: column_name,
-                    data_type,
-                    collation,
-                    options,
-                });
+                columns.push(self.parse_column_def(column_name)?);
             } else {
                 return self.expected(
                     self.peek_pos(),
@@ -3025,6 +3060,31 @@ impl<'a> Parser<'a> {
         Ok((columns, constraints))
     }
 
+    /// Parses the remainder of a column definition whose name has already
+    /// been consumed.
+    fn parse_column_def(&mut self, name: Ident) -> Result<ColumnDef<Raw>, ParserError> {
+        let data_type = self.parse_data_type()?;
+        let collation = if self.parse_keyword(COLLATE) {
+            Some(self.parse_object_name()?)
+        } else {
+            None
+        };
+        let mut options = vec![];
+        loop {
+            match self.peek_token() {
+                None | Some(Token::Comma) | Some(Token::RParen) | Some(Token::Semicolon) => break,
+                _ => options.push(self.parse_column_option_def()?),
+            }
+        }
+
+        Ok(ColumnDef {
+            name,
+            data_type,
+            collation,
+            options,
+        })
+    }
+
     fn parse_column_option_def(&mut self) -> Result<ColumnOptionDef<Raw>, ParserError> {
         let name = if self.parse_keyword(CONSTRAINT) {
             Some(self.parse_identifier()?)
@@ -3215,7 +3275,7 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(VIEW)?;
                 ObjectType::RecordedView
             }
-            TABLE => ObjectType::Table,
+            TABLE => return self.parse_alter_table(),
             INDEX => return self.parse_alter_index(),
             SECRET => return self.parse_alter_secret(),
             _ => unreachable!(),
@@ -3276,6 +3336,53 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_alter_table(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let if_exists = self.parse_if_exists()?;
+        let name = self.parse_object_name()?;
+
+        Ok(match self.expect_one_of_keywords(&[ADD, DROP, RENAME])? {
+            ADD => {
+                let _ = self.parse_keyword(COLUMN);
+                let if_not_exists = self.parse_if_not_exists()?;
+                let column_name = self.parse_identifier()?;
+                let column = self.parse_column_def(column_name)?;
+                Statement::AlterTable(AlterTableStatement {
+                    name,
+                    if_exists,
+                    action: AlterTableAction::AddColumn {
+                        if_not_exists,
+                        column,
+                    },
+                })
+            }
+            DROP => {
+                let _ = self.parse_keyword(COLUMN);
+                let column_if_exists = self.parse_if_exists()?;
+                let column_name = self.parse_identifier()?;
+                Statement::AlterTable(AlterTableStatement {
+                    name,
+                    if_exists,
+                    action: AlterTableAction::DropColumn {
+                        if_exists: column_if_exists,
+                        name: column_name,
+                    },
+                })
+            }
+            RENAME => {
+                self.expect_keyword(TO)?;
+                let to_item_name = self.parse_identifier()?;
+
+                Statement::AlterObjectRename(AlterObjectRenameStatement {
+                    object_type: ObjectType::Table,
+                    if_exists,
+                    name,
+                    to_item_name,
+                })
+            }
+            _ => unreachable!(),
+        })
+    }
+
     fn parse_alter_secret(&mut self) -> Result<Statement<Raw>, ParserError> {
         let if_exists = self.parse_if_exists()?;
         let name = self.parse_object_name()?;
@@ -3330,12 +3437,20 @@ impl<'a> Parser<'a> {
                         "queries not allowed in COPY FROM"
                     );
                 }
-                self.expect_keyword(STDIN)?;
-                (CopyDirection::From, CopyTarget::Stdin)
+                let target = if self.parse_keyword(STDIN) {
+                    CopyTarget::Stdin
+                } else {
+                    CopyTarget::Location(self.parse_copy_location(STDIN)?)
+                };
+                (CopyDirection::From, target)
             }
             TO => {
-                self.expect_keyword(STDOUT)?;
-                (CopyDirection::To, CopyTarget::Stdout)
+                let target = if self.parse_keyword(STDOUT) {
+                    CopyTarget::Stdout
+                } else {
+                    CopyTarget::Location(self.parse_copy_location(STDOUT)?)
+                };
+                (CopyDirection::To, target)
             }
             _ => unreachable!(),
         };
@@ -3363,17 +3478,37 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_copy_location(&mut self, stdio: Keyword) -> Result<String, ParserError> {
+        match self.next_token() {
+            Some(Token::String(s)) => Ok(s),
+            other => self.expected(
+                self.peek_prev_pos(),
+                format!("{} or literal string", stdio),
+                other,
+            ),
+        }
+    }
+
     fn parse_copy_options(&mut self) -> Result<CopyOption<Raw>, ParserError> {
-        let name =
-            match self.expect_one_of_keywords(&[FORMAT, DELIMITER, NULL, ESCAPE, QUOTE, HEADER])? {
-                FORMAT => CopyOptionName::Format,
-                DELIMITER => CopyOptionName::Delimiter,
-                NULL => CopyOptionName::Null,
-                ESCAPE => CopyOptionName::Escape,
-                QUOTE => CopyOptionName::Quote,
-                HEADER => CopyOptionName::Header,
-                _ => unreachable!(),
-            };
+        let name = match self
+            .expect_one_of_keywords(&[FORMAT, DELIMITER, NULL, ESCAPE, QUOTE, HEADER, CONNECTION])?
+        {
+            FORMAT => CopyOptionName::Format,
+            DELIMITER => CopyOptionName::Delimiter,
+            NULL => CopyOptionName::Null,
+            ESCAPE => CopyOptionName::Escape,
+            QUOTE => CopyOptionName::Quote,
+            HEADER => CopyOptionName::Header,
+            CONNECTION => {
+                let _ = self.consume_token(&Token::Eq);
+                let connection = self.parse_raw_name()?;
+                return Ok(CopyOption {
+                    name: CopyOptionName::Connection,
+                    value: Some(WithOptionValue::Object(connection)),
+                });
+            }
+            _ => unreachable!(),
+        };
         let _ = self.consume_token(&Token::Eq);
         let value = self.parse_opt_with_option_value(false)?;
         Ok(CopyOption { name, value })
@@ -3912,44 +4047,47 @@ impl<'a> Parser<'a> {
     /// expect the initial keyword to be already consumed
     fn parse_query(&mut self) -> Result<Query<Raw>, ParserError> {
         self.checked_recur_mut(|parser| {
-            let ctes = if parser.parse_keyword(WITH) {
-                // TODO: optional RECURSIVE
-                parser.parse_comma_separated(Parser::parse_cte)?
+            let (ctes, recursive) = if parser.parse_keyword(WITH) {
+                let recursive = parser.parse_keyword(RECURSIVE);
+                (parser.parse_comma_separated(Parser::parse_cte)?, recursive)
             } else {
-                vec![]
+                (vec![], false)
             };
 
             let body = parser.parse_query_body(SetPrecedence::Zero)?;
 
-            parser.parse_query_tail(ctes, body)
+            parser.parse_query_tail(ctes, recursive, body)
         })
     }
 
     fn parse_query_tail(
         &mut self,
         ctes: Vec<Cte<Raw>>,
+        recursive: bool,
         body: SetExpr<Raw>,
     ) -> Result<Query<Raw>, ParserError> {
-        let (inner_ctes, inner_order_by, inner_limit, inner_offset, body) = match body {
-            SetExpr::Query(query) => {
-                let Query {
-                    ctes,
-                    body,
-                    order_by,
-                    limit,
-                    offset,
-                } = *query;
-                (ctes, order_by, limit, offset, body)
-            }
-            _ => (vec![], vec![], None, None, body),
-        };
+        let (inner_ctes, inner_recursive, inner_order_by, inner_limit, inner_offset, body) =
+            match body {
+                SetExpr::Query(query) => {
+                    let Query {
+                        ctes,
+                        recursive,
+                        body,
+                        order_by,
+                        limit,
+                        offset,
+                    } = *query;
+                    (ctes, recursive, order_by, limit, offset, body)
+                }
+                _ => (vec![], false, vec![], None, None, body),
+            };
 
-        let ctes = if ctes.is_empty() {
-            inner_ctes
+        let (ctes, recursive) = if ctes.is_empty() {
+            (inner_ctes, inner_recursive)
         } else if !inner_ctes.is_empty() {
             return parser_err!(self, self.peek_pos(), "multiple WITH clauses not allowed");
         } else {
-            ctes
+            (ctes, recursive)
         };
 
         let order_by = if self.parse_keywords(&[ORDER, BY]) {
@@ -4028,6 +4166,7 @@ impl<'a> Parser<'a> {
 
         Ok(Query {
             ctes,
+            recursive,
             body,
             order_by,
             limit,
@@ -4184,7 +4323,7 @@ impl<'a> Parser<'a> {
         };
 
         let group_by = if self.parse_keywords(&[GROUP, BY]) {
-            self.parse_comma_separated(Parser::parse_expr)?
+            self.parse_comma_separated(|parser| parser.parse_group_by_item(false))?
         } else {
             vec![]
         };
@@ -4212,6 +4351,64 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parses one item of a `GROUP BY` clause.
+    ///
+    /// Within `GROUPING SETS`, a parenthesized list of expressions denotes a
+    /// single grouping set rather than a row constructor.
+    fn parse_group_by_item(
+        &mut self,
+        in_grouping_sets: bool,
+    ) -> Result<GroupByItem<Raw>, ParserError> {
+        if self.parse_keywords(&[GROUPING, SETS]) {
+            self.expect_token(&Token::LParen)?;
+            let items = self.checked_recur_mut(|parser| {
+                parser.parse_comma_separated(|parser| parser.parse_group_by_item(true))
+            })?;
+            self.expect_token(&Token::RParen)?;
+            Ok(GroupByItem::GroupingSets(items))
+        } else if self.peek_nth_token(1) == Some(Token::LParen)
+            && (self.peek_keyword(ROLLUP) || self.peek_keyword(CUBE))
+        {
+            let rollup = self.parse_keyword(ROLLUP);
+            if !rollup {
+                self.expect_keyword(CUBE)?;
+            }
+            self.expect_token(&Token::LParen)?;
+            let elems = self.parse_comma_separated(Parser::parse_grouping_element)?;
+            self.expect_token(&Token::RParen)?;
+            if rollup {
+                Ok(GroupByItem::Rollup(elems))
+            } else {
+                Ok(GroupByItem::Cube(elems))
+            }
+        } else if self.peek_token() == Some(Token::LParen)
+            && self.peek_nth_token(1) == Some(Token::RParen)
+        {
+            self.next_token();
+            self.next_token();
+            Ok(GroupByItem::Set(vec![]))
+        } else if in_grouping_sets {
+            let mut exprs = self.parse_grouping_element()?;
+            if exprs.len() == 1 {
+                Ok(GroupByItem::Expr(exprs.remove(0)))
+            } else {
+                Ok(GroupByItem::Set(exprs))
+            }
+        } else {
+            Ok(GroupByItem::Expr(self.parse_expr()?))
+        }
+    }
+
+    /// Parses an element of `ROLLUP`, `CUBE`, or `GROUPING SETS`, which is
+    /// either an expression or a parenthesized list of expressions.
+    fn parse_grouping_element(&mut self) -> Result<Vec<Expr<Raw>>, ParserError> {
+        let parenthesized = self.peek_token() == Some(Token::LParen);
+        match self.parse_expr()? {
+            Expr::Row { exprs } if parenthesized => Ok(exprs),
+            expr => Ok(vec![expr]),
+        }
+    }
+
     fn parse_set(&mut self) -> Result<Statement<Raw>, ParserError> {
         let modifier = self.parse_one_of_keywords(&[SESSION, LOCAL]);
         let mut variable = self.parse_identifier()?;
@@ -4751,15 +4948,44 @@ impl<'a> Parser<'a> {
         } else {
             InsertSource::Query(self.parse_query()?)
         };
+        let on_conflict = if self.parse_keywords(&[ON, CONFLICT]) {
+            Some(self.parse_on_conflict()?)
+        } else {
+            None
+        };
         let returning = self.parse_returning()?;
         Ok(Statement::Insert(InsertStatement {
             table_name,
             columns,
             source,
+            on_conflict,
             returning,
         }))
     }
 
+    fn parse_on_conflict(&mut self) -> Result<OnConflict<Raw>, ParserError> {
+        let target = self.parse_parenthesized_column_list(Optional)?;
+        self.expect_keyword(DO)?;
+        let action = match self.expect_one_of_keywords(&[NOTHING, UPDATE])? {
+            NOTHING => OnConflictAction::DoNothing,
+            UPDATE => {
+                self.expect_keyword(SET)?;
+                let assignments = self.parse_comma_separated(Parser::parse_assignment)?;
+                let selection = if self.parse_keyword(WHERE) {
+                    Some(self.parse_expr()?)
+                } else {
+                    None
+                };
+                OnConflictAction::DoUpdate {
+                    assignments,
+                    selection,
+                }
+            }
+            _ => unreachable!(),
+        };
+        Ok(OnConflict { target, action })
+    }
+
     fn parse_returning(&mut self) -> Result<Vec<SelectItem<Raw>>, ParserError> {
         Ok(if self.parse_keyword(RETURNING) {
             self.parse_comma_separated(Parser::parse_select_item)?
@@ -4786,7 +5012,15 @@ impl<'a> Parser<'a> {
         }))
     }
 
-    /// Parse a `var = expr` assignment, used in an UPDATE statement
+    fn parse_truncate(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let _ = self.parse_keyword(TABLE);
+        let table_names = self
+            .parse_comma_separated(|parser| Ok(RawObjectName::Name(parser.parse_object_name()?)))?;
+        Ok(Statement::Truncate(TruncateStatement { table_names }))
+    }
+
+    /// Parse a `var = expr` assignment, used in UPDATE statements and
+    /// `ON CONFLICT DO UPDATE` clauses
     fn parse_assignment(&mut self) -> Result<Assignment<Raw>, ParserError> {
         let id = self.parse_identifier()?;
         self.expect_token(&Token::Eq)?;
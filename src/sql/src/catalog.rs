@@ -38,6 +38,7 @@ use crate::names::{
     ResolvedDatabaseSpecifier, SchemaSpecifier,
 };
 use crate::plan::statement::StatementDesc;
+use crate::plan::UniqueConstraint;
 
 /// A catalog keeps track of SQL objects and session state available to the
 /// planner.
@@ -331,6 +332,10 @@ pub trait CatalogItem {
     /// catalog item is a table.
     fn table_details(&self) -> Option<&[Expr<Aug>]>;
 
+    /// Returns the unique constraints declared on the catalog item, if the
+    /// catalog item is a table.
+    fn table_unique_constraints(&self) -> Option<&[UniqueConstraint]>;
+
     /// Returns the type information associated with the catalog item, if the
     /// catalog item is a type.
     fn type_details(&self) -> Option<&CatalogTypeDetails<IdReference>>;
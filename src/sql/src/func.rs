@@ -2789,6 +2789,42 @@ pub static MZ_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "current_timestamp" => Scalar {
             params!() => UnmaterializableFunc::CurrentTimestamp, oid::FUNC_CURRENT_TIMESTAMP_OID;
         },
+        // The windows that `hop` and `tumble` produce are aligned to the Unix
+        // epoch. Each window carries a temporal filter that retracts it once
+        // `mz_logical_timestamp()` passes its end, plus the allowed lateness if
+        // one is given.
+        "hop" => Table {
+            // (timestamp, size, slide)
+            params!(Timestamp, Interval, Interval) => sql_impl_table_func("
+                    SELECT window_start, window_start + $2 AS window_end
+                    FROM pg_catalog.generate_series(
+                        pg_catalog.date_bin($3, $1 - $2, '1970-01-01') + $3, $1, $3
+                    ) AS w(window_start)
+                    WHERE mz_catalog.mz_logical_timestamp() < pg_catalog.extract('epoch', window_start + $2) * 1000
+                ") => ReturnType::set_of(RecordAny), oid::FUNC_MZ_HOP_TS_OID;
+            params!(TimestampTz, Interval, Interval) => sql_impl_table_func("
+                    SELECT window_start, window_start + $2 AS window_end
+                    FROM pg_catalog.generate_series(
+                        pg_catalog.date_bin($3, $1 - $2, '1970-01-01') + $3, $1, $3
+                    ) AS w(window_start)
+                    WHERE mz_catalog.mz_logical_timestamp() < pg_catalog.extract('epoch', window_start + $2) * 1000
+                ") => ReturnType::set_of(RecordAny), oid::FUNC_MZ_HOP_TSTZ_OID;
+            // (timestamp, size, slide, lateness)
+            params!(Timestamp, Interval, Interval, Interval) => sql_impl_table_func("
+                    SELECT window_start, window_start + $2 AS window_end
+                    FROM pg_catalog.generate_series(
+                        pg_catalog.date_bin($3, $1 - $2, '1970-01-01') + $3, $1, $3
+                    ) AS w(window_start)
+                    WHERE mz_catalog.mz_logical_timestamp() < pg_catalog.extract('epoch', window_start + $2 + $4) * 1000
+                ") => ReturnType::set_of(RecordAny), oid::FUNC_MZ_HOP_LATENESS_TS_OID;
+            params!(TimestampTz, Interval, Interval, Interval) => sql_impl_table_func("
+                    SELECT window_start, window_start + $2 AS window_end
+                    FROM pg_catalog.generate_series(
+                        pg_catalog.date_bin($3, $1 - $2, '1970-01-01') + $3, $1, $3
+                    ) AS w(window_start)
+                    WHERE mz_catalog.mz_logical_timestamp() < pg_catalog.extract('epoch', window_start + $2 + $4) * 1000
+                ") => ReturnType::set_of(RecordAny), oid::FUNC_MZ_HOP_LATENESS_TSTZ_OID;
+        },
         "list_agg" => Aggregate {
             params!(Any) => Operation::unary_ordered(|ecx, e, order_by| {
                 if let ScalarType::Char {.. }  = ecx.scalar_type(&e) {
@@ -2886,6 +2922,76 @@ pub static MZ_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
                 })
             }), oid::FUNC_REPEAT_OID;
         },
+        // Unlike `tumble` and `hop` windows, a row's session depends on the
+        // other rows in its partition, so sessions are computed by a window
+        // function rather than a table function. Each row carries a temporal
+        // filter that retracts it once `mz_logical_timestamp()` passes the end
+        // of its session, plus the allowed lateness if one is given.
+        "session" => ValueWindow {
+            // All args are encoded into a single record to be handled later
+            // (timestamp, gap)
+            params!(Timestamp, Interval) => Operation::binary(|_ecx, ts, gap| {
+                let e = HirScalarExpr::CallVariadic {
+                    func: VariadicFunc::RecordCreate {
+                        field_names: vec![ColumnName::from("ts"), ColumnName::from("gap"), ColumnName::from("lateness")]
+                    },
+                    exprs: vec![ts, gap, HirScalarExpr::literal(Datum::Interval(mz_repr::adt::interval::Interval::default()), ScalarType::Interval)],
+                };
+                Ok((e, ValueWindowFunc::Session))
+            }) => Timestamp, oid::FUNC_MZ_SESSION_TS_OID;
+            params!(TimestampTz, Interval) => Operation::binary(|_ecx, ts, gap| {
+                let e = HirScalarExpr::CallVariadic {
+                    func: VariadicFunc::RecordCreate {
+                        field_names: vec![ColumnName::from("ts"), ColumnName::from("gap"), ColumnName::from("lateness")]
+                    },
+                    exprs: vec![ts, gap, HirScalarExpr::literal(Datum::Interval(mz_repr::adt::interval::Interval::default()), ScalarType::Interval)],
+                };
+                Ok((e, ValueWindowFunc::Session))
+            }) => TimestampTz, oid::FUNC_MZ_SESSION_TSTZ_OID;
+            // (timestamp, gap, lateness)
+            params!(Timestamp, Interval, Interval) => Operation::variadic(|_ecx, exprs| {
+                let e = HirScalarExpr::CallVariadic {
+                    func: VariadicFunc::RecordCreate {
+                        field_names: vec![ColumnName::from("ts"), ColumnName::from("gap"), ColumnName::from("lateness")]
+                    },
+                    exprs,
+                };
+                Ok((e, ValueWindowFunc::Session))
+            }) => Timestamp, oid::FUNC_MZ_SESSION_LATENESS_TS_OID;
+            params!(TimestampTz, Interval, Interval) => Operation::variadic(|_ecx, exprs| {
+                let e = HirScalarExpr::CallVariadic {
+                    func: VariadicFunc::RecordCreate {
+                        field_names: vec![ColumnName::from("ts"), ColumnName::from("gap"), ColumnName::from("lateness")]
+                    },
+                    exprs,
+                };
+                Ok((e, ValueWindowFunc::Session))
+            }) => TimestampTz, oid::FUNC_MZ_SESSION_LATENESS_TSTZ_OID;
+        },
+        "tumble" => Table {
+            // (timestamp, size)
+            params!(Timestamp, Interval) => sql_impl_table_func("
+                    SELECT window_start, window_start + $2 AS window_end
+                    FROM (SELECT pg_catalog.date_bin($2, $1, '1970-01-01') AS window_start) AS w
+                    WHERE mz_catalog.mz_logical_timestamp() < pg_catalog.extract('epoch', window_start + $2) * 1000
+                ") => ReturnType::set_of(RecordAny), oid::FUNC_MZ_TUMBLE_TS_OID;
+            params!(TimestampTz, Interval) => sql_impl_table_func("
+                    SELECT window_start, window_start + $2 AS window_end
+                    FROM (SELECT pg_catalog.date_bin($2, $1, '1970-01-01') AS window_start) AS w
+                    WHERE mz_catalog.mz_logical_timestamp() < pg_catalog.extract('epoch', window_start + $2) * 1000
+                ") => ReturnType::set_of(RecordAny), oid::FUNC_MZ_TUMBLE_TSTZ_OID;
+            // (timestamp, size, lateness)
+            params!(Timestamp, Interval, Interval) => sql_impl_table_func("
+                    SELECT window_start, window_start + $2 AS window_end
+                    FROM (SELECT pg_catalog.date_bin($2, $1, '1970-01-01') AS window_start) AS w
+                    WHERE mz_catalog.mz_logical_timestamp() < pg_catalog.extract('epoch', window_start + $2 + $3) * 1000
+                ") => ReturnType::set_of(RecordAny), oid::FUNC_MZ_TUMBLE_LATENESS_TS_OID;
+            params!(TimestampTz, Interval, Interval) => sql_impl_table_func("
+                    SELECT window_start, window_start + $2 AS window_end
+                    FROM (SELECT pg_catalog.date_bin($2, $1, '1970-01-01') AS window_start) AS w
+                    WHERE mz_catalog.mz_logical_timestamp() < pg_catalog.extract('epoch', window_start + $2 + $3) * 1000
+                ") => ReturnType::set_of(RecordAny), oid::FUNC_MZ_TUMBLE_LATENESS_TSTZ_OID;
+        },
         "unnest" => Table {
             vec![ArrayAny] => Operation::unary(move |ecx, e| {
                 let el_typ = ecx.scalar_type(&e).unwrap_array_element_type().clone();
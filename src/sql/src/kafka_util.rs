@@ -21,8 +21,9 @@ use tokio::time::Duration;
 
 use mz_kafka_util::client::{create_new_client_config, MzClientContext};
 use mz_ore::task;
+use mz_repr::strconv;
 use mz_secrets::SecretsReader;
-use mz_sql_parser::ast::Value;
+use mz_sql_parser::ast::{IntervalValue, Value};
 use mz_storage::types::connections::{
     CsrConnection, CsrConnectionHttpAuth, KafkaConnection, StringOrSecret, TlsIdentity,
 };
@@ -266,52 +267,33 @@ pub async fn create_consumer(
 }
 
 /// Returns start offsets for the partitions of `topic` and the provided
-/// `kafka_time_offset` option.
+/// `START TIMESTAMP` clause or `kafka_time_offset` option.
 ///
 /// For each partition, the returned offset is the earliest offset whose
 /// timestamp is greater than or equal to the given timestamp for the
 /// partition. If no such message exists (or the Kafka broker is before
 /// 0.10.0), the current end offset is returned for the partition.
 ///
-/// The provided `kafka_time_offset` option must be a non-zero number:
-/// * Non-Negative numbers will used as is (e.g. `1622659034343`)
-/// * Negative numbers will be translated to a timestamp in millis
-///   before now (e.g. `-10` means 10 millis ago)
-///
-/// If `kafka_time_offset` has not been configured, an empty Option is
-/// returned.
+/// The provided `time_offset` is resolved as described in
+/// [`resolve_start_timestamp`]. `name` is used to refer to it in error
+/// messages.
 pub async fn lookup_start_offsets(
     consumer: Arc<BaseConsumer<KafkaErrCheckContext>>,
     topic: &str,
+    name: &str,
+    time_offset: Value,
     with_options: &BTreeMap<String, SqlValueOrSecret>,
     now: u64,
-) -> Result<Option<Vec<i64>>, PlanError> {
-    let time_offset = match with_options.get("kafka_time_offset").cloned() {
-        None => return Ok(None),
-        Some(_) if with_options.contains_key("start_offset") => {
-            sql_bail!("`start_offset` and `kafka_time_offset` cannot be set at the same time.")
-        }
-        Some(offset) => offset,
-    };
+) -> Result<Vec<i64>, PlanError> {
+    if with_options.contains_key("start_offset") {
+        sql_bail!(
+            "`start_offset` and `{}` cannot be set at the same time.",
+            name
+        )
+    }
 
-    // Validate and resolve `kafka_time_offset`.
-    let time_offset = match time_offset.into() {
-        Some(Value::Number(s)) => match s.parse::<i64>() {
-            // Timestamp in millis *before* now (e.g. -10 means 10 millis ago)
-            Ok(ts) if ts < 0 => {
-                let now: i64 = now.try_into()?;
-                let ts = now - ts.abs();
-                if ts <= 0 {
-                    sql_bail!("Relative `kafka_time_offset` must be smaller than current system timestamp")
-                }
-                ts
-            }
-            // Timestamp in millis (e.g. 1622659034343)
-            Ok(ts) => ts,
-            _ => sql_bail!("`kafka_time_offset` must be a number"),
-        },
-        _ => sql_bail!("`kafka_time_offset` must be a number"),
-    };
+    // Validate and resolve the timestamp.
+    let time_offset = resolve_start_timestamp(name, time_offset, now)?;
 
     // Lookup offsets
     // TODO(guswynn): see if we can add broker to this name
@@ -359,13 +341,52 @@ pub async fn lookup_start_offsets(
                 );
             }
 
-            Ok(Some(start_offsets))
+            Ok(start_offsets)
         }
     })
     .await
     .map_err(|e| sql_err!("{}", e))?
 }
 
+/// Resolves a Kafka start timestamp, as given to the `START TIMESTAMP` clause
+/// or the `kafka_time_offset` option, to milliseconds since the Unix epoch.
+///
+/// The provided `value` must be either a number or an interval:
+/// * Non-Negative numbers will used as is (e.g. `1622659034343`)
+/// * Negative numbers will be translated to a timestamp in millis
+///   before now (e.g. `-10` means 10 millis ago)
+/// * Intervals will be added to now (e.g. `'-30 days'` means 30 days ago)
+///
+/// `name` is used to refer to the option in error messages.
+fn resolve_start_timestamp(name: &str, value: Value, now: u64) -> Result<i64, PlanError> {
+    let now: i64 = now.try_into()?;
+    let ts = match value {
+        Value::Number(s) => match s.parse::<i64>() {
+            // Timestamp in millis *before* now (e.g. -10 means 10 millis ago)
+            Ok(ts) if ts < 0 => now - ts.abs(),
+            // Timestamp in millis (e.g. 1622659034343)
+            Ok(ts) => return Ok(ts),
+            _ => sql_bail!("`{}` must be a number or an interval", name),
+        },
+        // Interval relative to now (e.g. '-1 day' means one day ago)
+        Value::Interval(IntervalValue { value, .. }) | Value::String(value) => {
+            let interval = match strconv::parse_interval(&value) {
+                Ok(interval) => interval,
+                Err(_) => sql_bail!("`{}` must be a number or an interval", name),
+            };
+            now + i64::try_from(interval.as_microseconds() / 1_000)?
+        }
+        _ => sql_bail!("`{}` must be a number or an interval", name),
+    };
+    if ts <= 0 {
+        sql_bail!(
+            "Relative `{}` must be smaller than current system timestamp",
+            name
+        )
+    }
+    Ok(ts)
+}
+
 // Kafka supports bulk lookup of watermarks, but it is not exposed in rdkafka.
 // If that ever changes, we will want to first collect all pids that have no
 // offset for a given timestamp and then do a single request (instead of doing
@@ -789,16 +789,27 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
             used_names.insert(cte_name.clone());
 
             let id = LocalId::new(self.ctes.len() as u64);
+            // The CTEs of a `WITH RECURSIVE` block are in scope in their own
+            // definitions, rather than only in those that follow.
+            let query = if q.recursive {
+                let old_val = self.ctes.insert(cte_name.clone(), id);
+                old_cte_values.push((cte_name, old_val));
+                self.fold_query(cte.query)
+            } else {
+                let query = self.fold_query(cte.query);
+                let old_val = self.ctes.insert(cte_name.clone(), id);
+                old_cte_values.push((cte_name, old_val));
+                query
+            };
             ctes.push(Cte {
                 alias: cte.alias,
                 id,
-                query: self.fold_query(cte.query),
+                query,
             });
-            let old_val = self.ctes.insert(cte_name.clone(), id);
-            old_cte_values.push((cte_name, old_val));
         }
         let result = Query {
             ctes,
+            recursive: q.recursive,
             body: self.fold_set_expr(q.body),
             limit: q.limit.map(|l| self.fold_limit(l)),
             offset: q.offset.map(|l| self.fold_expr(l)),
@@ -1053,6 +1064,7 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
                 }
                 Secret(object_name)
             }
+            Object(name) => Object(self.fold_object_name(name)),
         }
     }
 }
@@ -157,6 +157,9 @@ pub fn options(
             Some(WithOptionValue::Secret(_)) => {
                 panic!("SECRET option {} must be Object", option.key)
             }
+            Some(WithOptionValue::Object(_)) => {
+                sql_bail!("option {} cannot refer to an object", option.key)
+            }
             None => {
                 sql_bail!("option {} requires a value", option.key);
             }
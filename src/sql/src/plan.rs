@@ -39,6 +39,7 @@ use mz_expr::{MirRelationExpr, MirScalarExpr, RowSetFinishing};
 use mz_ore::now::{self, NOW_ZERO};
 use mz_pgcopy::CopyFormatParams;
 use mz_repr::{ColumnName, Diff, GlobalId, RelationDesc, Row, ScalarType};
+use mz_storage::copy::CopyLocation;
 use mz_storage::types::sinks::{SinkConnectionBuilder, SinkEnvelope};
 use mz_storage::types::sources::{SourceDesc, Timeline};
 
@@ -110,6 +111,7 @@ pub enum Plan {
     Tail(TailPlan),
     SendRows(SendRowsPlan),
     CopyFrom(CopyFromPlan),
+    CopyTo(CopyToPlan),
     Explain(ExplainPlan),
     SendDiffs(SendDiffsPlan),
     Insert(InsertPlan),
@@ -118,10 +120,12 @@ pub enum Plan {
     AlterIndexResetOptions(AlterIndexResetOptionsPlan),
     AlterItemRename(AlterItemRenamePlan),
     AlterSecret(AlterSecretPlan),
+    AlterTable(AlterTablePlan),
     Declare(DeclarePlan),
     Fetch(FetchPlan),
     Close(ClosePlan),
     ReadThenWrite(ReadThenWritePlan),
+    Truncate(TruncatePlan),
     Prepare(PreparePlan),
     Execute(ExecutePlan),
     Deallocate(DeallocatePlan),
@@ -319,7 +323,7 @@ pub struct PeekPlan {
     pub source: MirRelationExpr,
     pub when: QueryWhen,
     pub finishing: RowSetFinishing,
-    pub copy_to: Option<CopyFormat>,
+    pub copy_to: Option<CopyFormatParams<'static>>,
 }
 
 #[derive(Debug)]
@@ -327,7 +331,7 @@ pub struct TailPlan {
     pub from: TailFrom,
     pub with_snapshot: bool,
     pub when: QueryWhen,
-    pub copy_to: Option<CopyFormat>,
+    pub copy_to: Option<CopyFormatParams<'static>>,
     pub emit_progress: bool,
 }
 
@@ -350,6 +354,21 @@ pub struct CopyFromPlan {
     pub id: GlobalId,
     pub columns: Vec<usize>,
     pub params: CopyFormatParams<'static>,
+    /// The location to read the data from, or `None` to read it from the
+    /// client.
+    pub location: Option<CopyLocation>,
+}
+
+/// A `COPY ... TO` a location outside of Materialize.
+///
+/// Copying to the client is planned as a [`PeekPlan`] or [`TailPlan`]
+/// instead.
+#[derive(Debug)]
+pub struct CopyToPlan {
+    pub peek: PeekPlan,
+    pub desc: RelationDesc,
+    pub params: CopyFormatParams<'static>,
+    pub location: CopyLocation,
 }
 
 #[derive(Debug)]
@@ -387,9 +406,30 @@ pub struct SendDiffsPlan {
 pub struct InsertPlan {
     pub id: GlobalId,
     pub values: mz_expr::MirRelationExpr,
+    pub on_conflict: Option<OnConflictPlan>,
     pub returning: Vec<mz_expr::MirScalarExpr>,
 }
 
+/// Describes how an `INSERT` handles rows that conflict with existing rows.
+#[derive(Debug)]
+pub struct OnConflictPlan {
+    /// The unique constraints whose keys are checked for conflicts.
+    pub constraints: Vec<UniqueConstraint>,
+    /// How to update an existing row that conflicts with an inserted row, or
+    /// `None` to skip inserting the row instead.
+    pub update: Option<OnConflictUpdate>,
+}
+
+/// The `DO UPDATE` action of an `ON CONFLICT` clause.
+///
+/// The expressions are evaluated over the columns of the existing row followed
+/// by the columns of the row proposed for insertion.
+#[derive(Debug)]
+pub struct OnConflictUpdate {
+    pub assignments: HashMap<usize, mz_expr::MirScalarExpr>,
+    pub selection: Option<mz_expr::MirScalarExpr>,
+}
+
 #[derive(Debug)]
 pub struct ReadThenWritePlan {
     pub id: GlobalId,
@@ -397,9 +437,15 @@ pub struct ReadThenWritePlan {
     pub finishing: RowSetFinishing,
     pub assignments: HashMap<usize, mz_expr::MirScalarExpr>,
     pub kind: MutationKind,
+    pub on_conflict: Option<OnConflictPlan>,
     pub returning: Vec<mz_expr::MirScalarExpr>,
 }
 
+#[derive(Debug)]
+pub struct TruncatePlan {
+    pub ids: Vec<GlobalId>,
+}
+
 /// Generated by `ALTER ... IF EXISTS` if the named object did not exist.
 #[derive(Debug)]
 pub struct AlterNoopPlan {
@@ -432,6 +478,26 @@ pub struct AlterSecretPlan {
     pub secret_as: MirScalarExpr,
 }
 
+#[derive(Debug)]
+pub struct AlterTablePlan {
+    pub id: GlobalId,
+    /// The definition of the table that the alteration was planned against.
+    pub old_create_sql: String,
+    /// The definition of the table after the alteration.
+    pub table: Table,
+    pub column: AlterTableColumn,
+    pub depends_on: Vec<GlobalId>,
+}
+
+/// How the existing rows of a table change when the table is altered.
+#[derive(Debug)]
+pub enum AlterTableColumn {
+    /// A column is appended, whose value in existing rows is `default`.
+    Add { default: MirScalarExpr },
+    /// The column at `index` is removed.
+    Drop { index: usize },
+}
+
 #[derive(Debug)]
 pub struct DeclarePlan {
     pub name: String,
@@ -478,9 +544,30 @@ pub struct Table {
     pub create_sql: String,
     pub desc: RelationDesc,
     pub defaults: Vec<Expr<Aug>>,
+    pub checks: Vec<CheckConstraint>,
+    pub uniques: Vec<UniqueConstraint>,
     pub temporary: bool,
 }
 
+/// A `CHECK` constraint on a table.
+#[derive(Clone, Debug, Serialize)]
+pub struct CheckConstraint {
+    pub name: String,
+    /// A predicate over the columns of the table that no row of the table may
+    /// falsify.
+    pub expr: MirScalarExpr,
+}
+
+/// A `UNIQUE` or `PRIMARY KEY` constraint on a table.
+#[derive(Clone, Debug, Serialize)]
+pub struct UniqueConstraint {
+    pub name: String,
+    /// The indices of the constrained columns. No two rows of the table may
+    /// agree on the values of these columns, unless one of the values is
+    /// `NULL`.
+    pub columns: Vec<usize>,
+}
+
 #[derive(Clone, Debug)]
 pub struct Source {
     pub create_sql: String,
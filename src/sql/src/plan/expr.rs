@@ -60,6 +60,18 @@ pub enum HirRelationExpr {
         /// The result of the `Let`, evaluated with `name` bound to `value`.
         body: Box<HirRelationExpr>,
     },
+    /// Recursive CTE
+    LetRec {
+        name: String,
+        /// The identifier to be used in `Get` variants to retrieve `value`.
+        id: mz_expr::LocalId,
+        /// The collection to be bound to `name`, which may itself contain
+        /// `Get` variants that reference `id`.
+        value: Box<HirRelationExpr>,
+        /// The result of the `LetRec`, evaluated with `name` bound to the
+        /// fixed point of `value`.
+        body: Box<HirRelationExpr>,
+    },
     Project {
         input: Box<HirRelationExpr>,
         outputs: Vec<usize>,
@@ -222,6 +234,7 @@ impl WindowExpr {
 pub enum WindowExprType {
     Scalar(ScalarWindowExpr),
     Value(ValueWindowExpr),
+    Aggregate(AggregateWindowExpr),
 }
 
 impl WindowExprType {
@@ -232,6 +245,7 @@ impl WindowExprType {
         match self {
             Self::Scalar(expr) => expr.visit_expressions(f),
             Self::Value(expr) => expr.visit_expressions(f),
+            Self::Aggregate(expr) => expr.visit_expressions(f),
         }
     }
 
@@ -242,6 +256,7 @@ impl WindowExprType {
         match self {
             Self::Scalar(expr) => expr.visit_expressions_mut(f),
             Self::Value(expr) => expr.visit_expressions_mut(f),
+            Self::Aggregate(expr) => expr.visit_expressions_mut(f),
         }
     }
 
@@ -254,6 +269,7 @@ impl WindowExprType {
         match self {
             Self::Scalar(expr) => expr.typ(outers, inner, params),
             Self::Value(expr) => expr.typ(outers, inner, params),
+            Self::Aggregate(expr) => expr.typ(outers, inner, params),
         }
     }
 }
@@ -375,6 +391,10 @@ impl ValueWindowExpr {
                 order_by: self.order_by,
                 window_frame: self.window_frame,
             },
+            // Sessions span the whole partition, so they ignore the window frame
+            ValueWindowFunc::Session => mz_expr::AggregateFunc::SessionWindow {
+                order_by: self.order_by,
+            },
         }
     }
 }
@@ -386,13 +406,15 @@ pub enum ValueWindowFunc {
     Lead,
     FirstValue,
     LastValue,
+    Session,
 }
 
 impl ValueWindowFunc {
     pub fn output_type(&self, input_type: ColumnType) -> ColumnType {
         match self {
-            ValueWindowFunc::Lag | ValueWindowFunc::Lead => {
-                // The input is a (value, offset, default) record, so extract the type of the first arg
+            ValueWindowFunc::Lag | ValueWindowFunc::Lead | ValueWindowFunc::Session => {
+                // The input is a (value, offset, default) or (timestamp, gap, lateness) record,
+                // so extract the type of the first arg
                 input_type.scalar_type.unwrap_record_element_type()[0]
                     .clone()
                     .nullable(true)
@@ -404,6 +426,46 @@ impl ValueWindowFunc {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AggregateWindowExpr {
+    pub aggregate_expr: AggregateExpr,
+    pub order_by: Vec<ColumnOrder>,
+    pub window_frame: WindowFrame,
+}
+
+impl AggregateWindowExpr {
+    pub fn visit_expressions<'a, F, E>(&'a self, f: &mut F) -> Result<(), E>
+    where
+        F: FnMut(&'a HirScalarExpr) -> Result<(), E>,
+    {
+        f(&self.aggregate_expr.expr)
+    }
+
+    pub fn visit_expressions_mut<'a, F, E>(&'a mut self, f: &mut F) -> Result<(), E>
+    where
+        F: FnMut(&'a mut HirScalarExpr) -> Result<(), E>,
+    {
+        f(&mut self.aggregate_expr.expr)
+    }
+
+    fn typ(
+        &self,
+        outers: &[RelationType],
+        inner: &RelationType,
+        params: &BTreeMap<usize, ScalarType>,
+    ) -> ColumnType {
+        self.aggregate_expr.typ(outers, inner, params)
+    }
+
+    pub fn into_expr(self) -> mz_expr::AggregateFunc {
+        mz_expr::AggregateFunc::WindowAggregate {
+            wrapped_aggregate: Box::new(self.aggregate_expr.func.into_expr()),
+            order_by: self.order_by,
+            window_frame: self.window_frame,
+        }
+    }
+}
+
 /// A `CoercibleScalarExpr` is a [`HirScalarExpr`] whose type is not fully
 /// determined. Several SQL expressions can be freely coerced based upon where
 /// in the expression tree they appear. For example, the string literal '42'
@@ -799,7 +861,9 @@ impl HirRelationExpr {
         stack::maybe_grow(|| match self {
             HirRelationExpr::Constant { typ, .. } => typ.clone(),
             HirRelationExpr::Get { typ, .. } => typ.clone(),
-            HirRelationExpr::Let { body, .. } => body.typ(outers, params),
+            HirRelationExpr::Let { body, .. } | HirRelationExpr::LetRec { body, .. } => {
+                body.typ(outers, params)
+            }
             HirRelationExpr::Project { input, outputs } => {
                 let input_typ = input.typ(outers, params);
                 RelationType::new(
@@ -882,7 +946,9 @@ impl HirRelationExpr {
         match self {
             HirRelationExpr::Constant { typ, .. } => typ.column_types.len(),
             HirRelationExpr::Get { typ, .. } => typ.column_types.len(),
-            HirRelationExpr::Let { body, .. } => body.arity(),
+            HirRelationExpr::Let { body, .. } | HirRelationExpr::LetRec { body, .. } => {
+                body.arity()
+            }
             HirRelationExpr::Project { outputs, .. } => outputs.len(),
             HirRelationExpr::Map { input, scalars } => input.arity() + scalars.len(),
             HirRelationExpr::CallTable { func, .. } => func.output_arity(),
@@ -1097,7 +1163,8 @@ impl HirRelationExpr {
             HirRelationExpr::Constant { .. }
             | HirRelationExpr::Get { .. }
             | HirRelationExpr::CallTable { .. } => (),
-            HirRelationExpr::Let { body, value, .. } => {
+            HirRelationExpr::Let { body, value, .. }
+            | HirRelationExpr::LetRec { body, value, .. } => {
                 f(value, depth)?;
                 f(body, depth)?;
             }
@@ -1169,7 +1236,8 @@ impl HirRelationExpr {
             HirRelationExpr::Constant { .. }
             | HirRelationExpr::Get { .. }
             | HirRelationExpr::CallTable { .. } => (),
-            HirRelationExpr::Let { body, value, .. } => {
+            HirRelationExpr::Let { body, value, .. }
+            | HirRelationExpr::LetRec { body, value, .. } => {
                 f(value, depth)?;
                 f(body, depth)?;
             }
@@ -1249,6 +1317,7 @@ impl HirRelationExpr {
                 }
                 HirRelationExpr::Union { .. }
                 | HirRelationExpr::Let { .. }
+                | HirRelationExpr::LetRec { .. }
                 | HirRelationExpr::Project { .. }
                 | HirRelationExpr::Distinct { .. }
                 | HirRelationExpr::TopK { .. }
@@ -1295,6 +1364,7 @@ impl HirRelationExpr {
                 }
                 HirRelationExpr::Union { .. }
                 | HirRelationExpr::Let { .. }
+                | HirRelationExpr::LetRec { .. }
                 | HirRelationExpr::Project { .. }
                 | HirRelationExpr::Distinct { .. }
                 | HirRelationExpr::TopK { .. }
@@ -294,6 +294,82 @@ impl HirRelationExpr {
                         body
                     })
                 }
+                LetRec {
+                    name: _,
+                    id,
+                    value,
+                    body,
+                } => {
+                    // References to `id` within `value` must be resolvable before
+                    // `value` is lowered, so we derive the type of the binding
+                    // from the non-recursive branch of `value`, which the planner
+                    // makes the base of its union. The recursive branch may
+                    // produce nulls from it, so we widen the type until lowering
+                    // `value` against it produces no new nulls. Keys are not
+                    // carried over, as the recursive branch may violate them.
+                    let mut non_recursive = &*value;
+                    loop {
+                        match non_recursive {
+                            Distinct { input } => non_recursive = &**input,
+                            Union { base, .. } => {
+                                non_recursive = &**base;
+                                break;
+                            }
+                            _ => break,
+                        }
+                    }
+                    let non_recursive = non_recursive.clone().applied_to(
+                        id_gen,
+                        get_outer.clone(),
+                        col_map,
+                        cte_map,
+                    );
+                    let new_id = mz_expr::LocalId::new(id_gen.allocate_id());
+                    // Add the information about the CTE to the map, for use in
+                    // both `value` and `body`, and remove it when it goes out of scope.
+                    let old_value = cte_map.insert(
+                        id.clone(),
+                        CteDesc {
+                            new_id,
+                            relation_type: RelationType::new(non_recursive.typ().column_types),
+                            outer_relation: get_outer.clone(),
+                        },
+                    );
+                    let value = loop {
+                        let lowered = (*value).clone().applied_to(
+                            id_gen,
+                            get_outer.clone(),
+                            col_map,
+                            cte_map,
+                        );
+                        let relation_type = &mut cte_map.get_mut(&id).unwrap().relation_type;
+                        let mut widened = false;
+                        for (column, value_column) in relation_type
+                            .column_types
+                            .iter_mut()
+                            .zip(lowered.typ().column_types)
+                        {
+                            if value_column.nullable && !column.nullable {
+                                column.nullable = true;
+                                widened = true;
+                            }
+                        }
+                        if !widened {
+                            break lowered;
+                        }
+                    };
+                    let body = body.applied_to(id_gen, get_outer, col_map, cte_map);
+                    if let Some(old_value) = old_value {
+                        cte_map.insert(id, old_value);
+                    } else {
+                        cte_map.remove(&id);
+                    }
+                    SR::LetRec {
+                        id: new_id,
+                        value: Box::new(value),
+                        body: Box::new(body),
+                    }
+                }
                 Project { input, outputs } => {
                     // Projections should be applied to the decorrelated `inner`, and to its columns,
                     // which means rebasing `outputs` to start `get_outer.arity()` columns later.
@@ -1043,8 +1119,18 @@ impl HirScalarExpr {
                                     });
                             SS::Column(inner.arity() - 1)
                         }
-                        WindowExprType::Value(func) => {
-                            let hir_scalar_input = func.expr.clone();
+                        // Value window functions and aggregate window functions are both computed
+                        // from the window frame of each row, so they share the same lowering.
+                        WindowExprType::Value(_) | WindowExprType::Aggregate(_) => {
+                            let (hir_scalar_input, func) = match expr.func {
+                                WindowExprType::Value(func) => {
+                                    (func.expr.clone(), func.into_expr())
+                                }
+                                WindowExprType::Aggregate(func) => {
+                                    (func.aggregate_expr.expr.clone(), func.into_expr())
+                                }
+                                WindowExprType::Scalar(_) => unreachable!(),
+                            };
                             *inner =
                                 inner
                                     .take_dangerous()
@@ -1186,7 +1272,6 @@ impl HirScalarExpr {
                                             }
                                             .nullable(false);
 
-                                            let func = func.into_expr();
                                             let aggregate = mz_expr::AggregateExpr {
                                                 func,
                                                 expr: agg_input,
@@ -1255,9 +1340,67 @@ impl HirScalarExpr {
                                             );
 
                                             let agg_col = record_col + 1 + input_arity;
-                                            reduce.project(
-                                                (record_col + 1..agg_col + 1).collect_vec(),
-                                            )
+
+                                            // Like `tumble` and `hop` windows, each row of a
+                                            // session carries a temporal filter that retracts
+                                            // it once the session closes. The session window
+                                            // returns the start and close time of each row's
+                                            // session, of which only the start is exposed.
+                                            if let mz_expr::AggregateFunc::SessionWindow { .. } =
+                                                aggregate.func
+                                            {
+                                                let session = mz_expr::MirScalarExpr::Column(agg_col);
+                                                let close = session.clone().call_unary(
+                                                    mz_expr::UnaryFunc::RecordGet(
+                                                        mz_expr::func::RecordGet(1),
+                                                    ),
+                                                );
+                                                let extract_epoch = match reduce.typ().column_types
+                                                    [agg_col]
+                                                    .scalar_type
+                                                    .unwrap_record_element_type()[1]
+                                                {
+                                                    ScalarType::TimestampTz => {
+                                                        mz_expr::BinaryFunc::ExtractTimestampTz
+                                                    }
+                                                    _ => mz_expr::BinaryFunc::ExtractTimestamp,
+                                                };
+                                                let close_ms = mz_expr::MirScalarExpr::literal_ok(
+                                                    Datum::String("epoch"),
+                                                    ScalarType::String,
+                                                )
+                                                .call_binary(close, extract_epoch)
+                                                .call_binary(
+                                                    mz_expr::MirScalarExpr::literal_ok(
+                                                        Datum::from(adt::numeric::Numeric::from(
+                                                            1000,
+                                                        )),
+                                                        ScalarType::Numeric { max_scale: None },
+                                                    ),
+                                                    mz_expr::BinaryFunc::MulNumeric,
+                                                );
+                                                let predicate =
+                                                    mz_expr::MirScalarExpr::CallUnmaterializable(
+                                                        mz_expr::UnmaterializableFunc::MzLogicalTimestamp,
+                                                    )
+                                                    .call_binary(close_ms, mz_expr::BinaryFunc::Lt);
+                                                reduce
+                                                    .filter(vec![predicate])
+                                                    .map_one(session.call_unary(
+                                                        mz_expr::UnaryFunc::RecordGet(
+                                                            mz_expr::func::RecordGet(0),
+                                                        ),
+                                                    ))
+                                                    .project(
+                                                        (record_col + 1..agg_col)
+                                                            .chain(std::iter::once(agg_col + 1))
+                                                            .collect_vec(),
+                                                    )
+                                            } else {
+                                                reduce.project(
+                                                    (record_col + 1..agg_col + 1).collect_vec(),
+                                                )
+                                            }
                                         })
                                     });
                             SS::Column(inner.arity() - 1)
@@ -1547,7 +1690,7 @@ where
                 );
             }
         }
-        HirRelationExpr::Let { id, .. } => {
+        HirRelationExpr::Let { id, .. } | HirRelationExpr::LetRec { id, .. } => {
             // Note: if ID uniqueness is not guaranteed, we can't use `visit` since
             // we would need to remove the old CTE with the same ID temporarily while
             // traversing the definition of the new CTE under the same ID.
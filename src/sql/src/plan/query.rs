@@ -54,13 +54,15 @@ use mz_repr::{
     ScalarType,
 };
 
+use mz_sql_parser::ast::visit::{self, Visit};
 use mz_sql_parser::ast::visit_mut::{self, VisitMut};
 use mz_sql_parser::ast::{
-    AsOf, Assignment, AstInfo, DeleteStatement, Distinct, Expr, Function, FunctionArgs,
-    HomogenizingFunction, Ident, InsertSource, IsExprConstruct, Join, JoinConstraint, JoinOperator,
-    Limit, OrderByExpr, Query, Select, SelectItem, SetExpr, SetOperator, SubscriptPosition,
-    TableAlias, TableFactor, TableFunction, TableWithJoins, UnresolvedObjectName, UpdateStatement,
-    Value, Values, WindowFrame, WindowFrameBound, WindowFrameUnits, WindowSpec,
+    AsOf, Assignment, AstInfo, Cte, DeleteStatement, Distinct, Expr, Function, FunctionArgs,
+    GroupByItem, HomogenizingFunction, Ident, InsertSource, IsExprConstruct, Join, JoinConstraint,
+    JoinOperator, Limit, OnConflict, OnConflictAction, OrderByExpr, Query, Select, SelectItem,
+    SetExpr, SetOperator, SubscriptPosition, TableAlias, TableFactor, TableFunction,
+    TableWithJoins, UnresolvedObjectName, UpdateStatement, Value, Values, WindowFrame,
+    WindowFrameBound, WindowFrameUnits, WindowSpec,
 };
 
 use crate::catalog::{CatalogItemType, CatalogType, SessionCatalog};
@@ -69,17 +71,17 @@ use crate::names::{Aug, PartialObjectName, ResolvedDataType, ResolvedObjectName}
 use crate::normalize::{self, SqlValueOrSecret};
 use crate::plan::error::PlanError;
 use crate::plan::expr::{
-    AbstractColumnType, AbstractExpr, AggregateExpr, AggregateFunc, BinaryFunc,
-    CoercibleScalarExpr, ColumnOrder, ColumnRef, HirRelationExpr, HirScalarExpr, JoinKind,
-    ScalarWindowExpr, ScalarWindowFunc, UnaryFunc, ValueWindowExpr, VariadicFunc, WindowExpr,
-    WindowExprType,
+    AbstractColumnType, AbstractExpr, AggregateExpr, AggregateFunc, AggregateWindowExpr,
+    BinaryFunc, CoercibleScalarExpr, ColumnOrder, ColumnRef, HirRelationExpr, HirScalarExpr,
+    JoinKind, ScalarWindowExpr, ScalarWindowFunc, UnaryFunc, ValueWindowExpr, ValueWindowFunc,
+    VariadicFunc, WindowExpr, WindowExprType,
 };
 use crate::plan::plan_utils::{self, JoinSide};
 use crate::plan::scope::{Scope, ScopeItem};
 use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::typeconv::{self, CastContext};
 use crate::plan::{transform_ast, PlanContext};
-use crate::plan::{Params, QueryWhen};
+use crate::plan::{Params, QueryWhen, UniqueConstraint};
 
 #[derive(Debug)]
 pub struct PlannedQuery<E> {
@@ -169,8 +171,17 @@ pub fn plan_insert_query(
     table_name: ResolvedObjectName,
     columns: Vec<Ident>,
     source: InsertSource<Aug>,
+    on_conflict: Option<OnConflict<Aug>>,
     returning: Vec<SelectItem<Aug>>,
-) -> Result<(GlobalId, HirRelationExpr, PlannedQuery<Vec<HirScalarExpr>>), PlanError> {
+) -> Result<
+    (
+        GlobalId,
+        HirRelationExpr,
+        Option<OnConflictPlan>,
+        PlannedQuery<Vec<HirScalarExpr>>,
+    ),
+    PlanError,
+> {
     let mut qcx = QueryContext::root(scx, QueryLifetime::OneShot(scx.pcx()?));
     let table = scx.get_item_by_resolved_name(&table_name)?;
 
@@ -239,6 +250,7 @@ pub fn plan_insert_query(
                 Query {
                     body: SetExpr::Values(Values(values)),
                     ctes,
+                    recursive: _,
                     order_by,
                     limit: None,
                     offset: None,
@@ -307,6 +319,23 @@ pub fn plan_insert_query(
         }
     }
 
+    let on_conflict = match on_conflict {
+        Some(on_conflict) => {
+            let uniques = table
+                .table_unique_constraints()
+                .expect("attempted to insert into non-table");
+            let full_name = scx.catalog.resolve_full_name(table.name());
+            Some(plan_on_conflict(
+                &qcx,
+                full_name.into(),
+                &desc,
+                uniques,
+                on_conflict,
+            )?)
+        }
+        None => None,
+    };
+
     let returning = {
         let (scope, typ) = if let ResolvedObjectName::Object { full_name, .. } = table_name {
             let desc = table.desc(&full_name)?;
@@ -358,10 +387,140 @@ pub fn plan_insert_query(
     Ok((
         table.id(),
         expr.map(map_exprs).project(project_key),
+        on_conflict,
         returning,
     ))
 }
 
+/// The `ON CONFLICT` clause of an `INSERT` statement.
+pub struct OnConflictPlan {
+    /// The unique constraints whose keys are checked for conflicts.
+    pub constraints: Vec<UniqueConstraint>,
+    /// Map from column index to SET expression, and the WHERE filter, of a
+    /// `DO UPDATE` action. `None` for `DO NOTHING`.
+    pub update: Option<(HashMap<usize, HirScalarExpr>, Option<HirScalarExpr>)>,
+}
+
+fn plan_on_conflict(
+    qcx: &QueryContext,
+    table_name: PartialObjectName,
+    desc: &RelationDesc,
+    uniques: &[UniqueConstraint],
+    OnConflict { target, action }: OnConflict<Aug>,
+) -> Result<OnConflictPlan, PlanError> {
+    // As in PostgreSQL, the conflict target must name exactly the columns of
+    // one of the table's unique constraints, though in any order. Without a
+    // target, a conflict on any unique constraint causes the row to be
+    // skipped.
+    let constraints = if target.is_empty() {
+        if let OnConflictAction::DoUpdate { .. } = action {
+            sql_bail!("ON CONFLICT DO UPDATE requires inference specification or constraint name");
+        }
+        uniques.to_vec()
+    } else {
+        let mut columns = HashSet::new();
+        for name in target {
+            let name = normalize::column_name(name);
+            match desc.get_by_name(&name) {
+                Some((idx, _)) => {
+                    columns.insert(idx);
+                }
+                None => sql_bail!("column {} does not exist", name.as_str().quoted()),
+            }
+        }
+        let constraints: Vec<_> = uniques
+            .iter()
+            .filter(|unique| unique.columns.iter().copied().collect::<HashSet<_>>() == columns)
+            .cloned()
+            .collect();
+        if constraints.is_empty() {
+            sql_bail!(
+                "there is no unique or exclusion constraint matching the ON CONFLICT specification"
+            );
+        }
+        constraints
+    };
+
+    let update = match action {
+        OnConflictAction::DoNothing => None,
+        OnConflictAction::DoUpdate {
+            assignments,
+            selection,
+        } => {
+            // The existing row is referred to by the table's name, while the
+            // row proposed for insertion is only accessible as `excluded`.
+            let mut excluded = Scope::from_source(
+                Some(PartialObjectName {
+                    database: None,
+                    schema: None,
+                    item: "excluded".into(),
+                }),
+                desc.iter_names(),
+            );
+            for item in &mut excluded.items {
+                item.allow_unqualified_references = false;
+            }
+            let scope =
+                Scope::from_source(Some(table_name), desc.iter_names()).product(excluded)?;
+            let relation_type = RelationType::new(
+                desc.iter_types()
+                    .chain(desc.iter_types())
+                    .cloned()
+                    .collect(),
+            );
+
+            let selection = match selection {
+                Some(expr) => {
+                    let ecx = &ExprContext {
+                        qcx,
+                        name: "WHERE clause",
+                        scope: &scope,
+                        relation_type: &relation_type,
+                        allow_aggregates: false,
+                        allow_subqueries: false,
+                        allow_windows: false,
+                    };
+                    Some(plan_expr(ecx, &expr)?.type_as(ecx, &ScalarType::Bool)?)
+                }
+                None => None,
+            };
+
+            let mut sets = HashMap::new();
+            for Assignment { id, value } in assignments {
+                let name = normalize::column_name(id);
+                match desc.get_by_name(&name) {
+                    Some((idx, typ)) => {
+                        let ecx = &ExprContext {
+                            qcx,
+                            name: "SET clause",
+                            scope: &scope,
+                            relation_type: &relation_type,
+                            allow_aggregates: false,
+                            allow_subqueries: false,
+                            allow_windows: false,
+                        };
+                        let expr = plan_expr(ecx, &value)?.cast_to(
+                            ecx,
+                            CastContext::Assignment,
+                            &typ.scalar_type,
+                        )?;
+                        if sets.insert(idx, expr).is_some() {
+                            sql_bail!("column {} set twice", name)
+                        }
+                    }
+                    None => sql_bail!("unknown column {}", name),
+                };
+            }
+            Some((sets, selection))
+        }
+    };
+
+    Ok(OnConflictPlan {
+        constraints,
+        update,
+    })
+}
+
 pub fn plan_copy_from(
     scx: &StatementContext,
     table_name: ResolvedObjectName,
@@ -956,6 +1115,37 @@ pub fn plan_index_exprs<'a>(
     Ok(out)
 }
 
+/// Plans the expression of a `CHECK` constraint on a table with the given
+/// description.
+pub fn plan_check_expr(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    expr: &Expr<Aug>,
+) -> Result<MirScalarExpr, PlanError> {
+    let scope = Scope::from_source(None, desc.iter_names());
+    let qcx = QueryContext::root(scx, QueryLifetime::Static);
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "CHECK constraint",
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_windows: false,
+    };
+    let mut expr = expr.clone();
+    transform_ast::transform_expr(scx, &mut expr)?;
+    let expr = plan_expr(ecx, &expr)?
+        .type_as(ecx, &ScalarType::Bool)?
+        .lower_uncorrelated()?;
+    // The constraint is checked whenever a row is written, so its outcome must
+    // depend only on the row itself.
+    if expr.contains_unmaterializable() {
+        sql_bail!("CHECK constraints cannot call unmaterializable functions");
+    }
+    Ok(expr)
+}
+
 fn plan_expr_or_col_index(ecx: &ExprContext, e: &Expr<Aug>) -> Result<HirScalarExpr, PlanError> {
     match check_col_index(&ecx.name, e, ecx.relation_type.column_types.len())? {
         Some(column) => Ok(HirScalarExpr::column(column)),
@@ -1011,14 +1201,24 @@ fn plan_query_inner(
         used_names.insert(cte_name.clone());
 
         // Plan CTE.
-        let (val, scope) = plan_nested_query(qcx, &cte.query)?;
-        let typ = qcx.relation_type(&val);
-        let mut val_desc = RelationDesc::new(typ, scope.column_names());
-        plan_utils::maybe_rename_columns(
-            format!("CTE {}", cte.alias.name),
-            &mut val_desc,
-            &cte.alias.columns,
-        )?;
+        let recursive = q.recursive && {
+            let mut finder = CteReferenceFinder::new(cte.id);
+            finder.visit_query(&cte.query);
+            finder.found
+        };
+        let (val, val_desc) = if recursive {
+            plan_recursive_cte(qcx, cte, &cte_name)?
+        } else {
+            let (val, scope) = plan_nested_query(qcx, &cte.query)?;
+            let typ = qcx.relation_type(&val);
+            let mut val_desc = RelationDesc::new(typ, scope.column_names());
+            plan_utils::maybe_rename_columns(
+                format!("CTE {}", cte.alias.name),
+                &mut val_desc,
+                &cte.alias.columns,
+            )?;
+            (val, val_desc)
+        };
 
         let old_val = qcx.ctes.insert(
             cte.id,
@@ -1026,6 +1226,7 @@ fn plan_query_inner(
                 val,
                 name: cte_name,
                 val_desc,
+                recursive,
             },
         );
         old_cte_values.push((cte.id, old_val));
@@ -1087,11 +1288,20 @@ fn plan_query_inner(
 
     for (id, old_val) in old_cte_values.into_iter().rev() {
         if let Some(cte) = qcx.ctes.remove(&id) {
-            result = HirRelationExpr::Let {
-                name: cte.name,
-                id: id.clone(),
-                value: Box::new(cte.val),
-                body: Box::new(result),
+            result = if cte.recursive {
+                HirRelationExpr::LetRec {
+                    name: cte.name,
+                    id: id.clone(),
+                    value: Box::new(cte.val),
+                    body: Box::new(result),
+                }
+            } else {
+                HirRelationExpr::Let {
+                    name: cte.name,
+                    id: id.clone(),
+                    value: Box::new(cte.val),
+                    body: Box::new(result),
+                }
             };
         }
         if let Some(old_val) = old_val {
@@ -1102,6 +1312,250 @@ fn plan_query_inner(
     Ok((result, scope, finishing))
 }
 
+/// Plans the CTE `cte` of a `WITH RECURSIVE` block, whose query references the
+/// CTE itself.
+///
+/// The query must have the form `non_recursive_term UNION [ALL] recursive_term`,
+/// where only the recursive term references the CTE, and does so at most once
+/// and in a way that makes it linear in the CTE. The CTE is then the fixed point
+/// of the query, as computed by iteration starting from an empty CTE.
+fn plan_recursive_cte(
+    qcx: &mut QueryContext,
+    cte: &Cte<Aug>,
+    cte_name: &str,
+) -> Result<(HirRelationExpr, RelationDesc), PlanError> {
+    let (left, right, all) = match &cte.query {
+        Query {
+            ctes,
+            recursive: _,
+            body:
+                SetExpr::SetOperation {
+                    op: SetOperator::Union,
+                    all,
+                    left,
+                    right,
+                },
+            order_by,
+            limit: None,
+            offset: None,
+        } if ctes.is_empty() && order_by.is_empty() => (left, right, *all),
+        _ => sql_bail!(
+            "recursive query {} does not have the form non-recursive-term UNION [ALL] recursive-term",
+            cte_name.quoted()
+        ),
+    };
+    let mut finder = CteReferenceFinder::new(cte.id);
+    finder.visit_set_expr(left);
+    if finder.found {
+        sql_bail!(
+            "recursive reference to query {} must not appear within its non-recursive term",
+            cte_name.quoted()
+        );
+    }
+
+    let (base, base_scope) = plan_set_expr(qcx, left)?;
+    let base_type = qcx.relation_type(&base);
+    // References to the CTE within the recursive term observe the columns of
+    // the non-recursive term, but we cannot yet know whether they are nullable.
+    let mut val_desc = RelationDesc::new(
+        RelationType::new(
+            base_type
+                .column_types
+                .iter()
+                .map(|typ| typ.scalar_type.clone().nullable(true))
+                .collect(),
+        ),
+        base_scope.column_names(),
+    );
+    plan_utils::maybe_rename_columns(
+        format!("CTE {}", cte.alias.name),
+        &mut val_desc,
+        &cte.alias.columns,
+    )?;
+
+    // Plan the recursive term with the CTE in scope.
+    let prior = qcx.ctes.insert(
+        cte.id,
+        CteDesc {
+            val: HirRelationExpr::constant(vec![], val_desc.typ().clone()),
+            name: cte_name.to_string(),
+            val_desc: val_desc.clone(),
+            recursive: true,
+        },
+    );
+    let step = plan_set_expr(qcx, right);
+    qcx.ctes.remove(&cte.id);
+    if let Some(prior) = prior {
+        qcx.ctes.insert(cte.id, prior);
+    }
+    let (step, _step_scope) = step?;
+
+    let step_type = qcx.relation_type(&step);
+    if base_type.arity() != step_type.arity() {
+        sql_bail!(
+            "each UNION query must have the same number of columns: {} vs {}",
+            base_type.arity(),
+            step_type.arity(),
+        );
+    }
+    for (i, (base_col, step_col)) in base_type
+        .column_types
+        .iter()
+        .zip(step_type.column_types.iter())
+        .enumerate()
+    {
+        if !base_col.scalar_type.base_eq(&step_col.scalar_type) {
+            sql_bail!(
+                "recursive query {} column {} has type {} in non-recursive term but type {} overall",
+                cte_name.quoted(),
+                i + 1,
+                qcx.humanize_scalar_type(&base_col.scalar_type),
+                qcx.humanize_scalar_type(&step_col.scalar_type),
+            );
+        }
+    }
+    check_recursive_term(cte_name, cte.id, &step)?;
+
+    let mut val = base.union(step);
+    if !all {
+        val = val.distinct();
+    }
+
+    let mut nested = false;
+    visit_with_subqueries(&val, &mut |e| {
+        nested |= matches!(e, HirRelationExpr::LetRec { .. });
+    });
+    if nested {
+        bail_unsupported!("WITH RECURSIVE within the definition of a recursive query");
+    }
+
+    let typ = qcx.relation_type(&val);
+    let val_desc = RelationDesc::new(typ, val_desc.iter_names().cloned());
+    Ok((val, val_desc))
+}
+
+/// Checks that the recursive term `expr` of the recursive CTE `name` references
+/// the CTE, bound to `id`, in a way that makes the term linear in the CTE.
+fn check_recursive_term(name: &str, id: LocalId, expr: &HirRelationExpr) -> Result<(), PlanError> {
+    let is_reference = |e: &HirRelationExpr| matches!(e, HirRelationExpr::Get { id: Id::Local(get_id), .. } if *get_id == id);
+    let count_references = |e: &HirRelationExpr| {
+        let mut count = 0;
+        visit_with_subqueries(e, &mut |e| {
+            if is_reference(e) {
+                count += 1;
+            }
+        });
+        count
+    };
+
+    match count_references(expr) {
+        0 => sql_bail!(
+            "recursive query {} does not reference itself in its recursive term",
+            name.quoted()
+        ),
+        1 => (),
+        _ => sql_bail!(
+            "recursive reference to query {} must not appear more than once",
+            name.quoted()
+        ),
+    }
+
+    let mut direct_references = 0;
+    let mut context = None;
+    expr.visit(0, &mut |e, _| {
+        if is_reference(e) {
+            direct_references += 1;
+        }
+        let violation = match e {
+            HirRelationExpr::Reduce { input, .. } if count_references(input) > 0 => {
+                Some("an aggregate")
+            }
+            HirRelationExpr::Distinct { input } if count_references(input) > 0 => Some("DISTINCT"),
+            HirRelationExpr::TopK { input, .. } if count_references(input) > 0 => {
+                Some("ORDER BY, LIMIT, or OFFSET")
+            }
+            HirRelationExpr::Negate { input } | HirRelationExpr::Threshold { input }
+                if count_references(input) > 0 =>
+            {
+                Some("EXCEPT or INTERSECT")
+            }
+            HirRelationExpr::Join {
+                left, right, kind, ..
+            } => {
+                let outer = match kind {
+                    JoinKind::Inner => false,
+                    JoinKind::LeftOuter => count_references(right) > 0,
+                    JoinKind::RightOuter => count_references(left) > 0,
+                    JoinKind::FullOuter => count_references(e) > 0,
+                };
+                outer.then(|| "an outer join")
+            }
+            HirRelationExpr::Map { input, scalars } if count_references(input) > 0 => {
+                let mut windowing = false;
+                for scalar in scalars {
+                    scalar.visit(&mut |s| {
+                        windowing |= matches!(s, HirScalarExpr::Windowing(_));
+                    });
+                }
+                windowing.then(|| "a window function")
+            }
+            _ => None,
+        };
+        if context.is_none() {
+            context = violation;
+        }
+    });
+    if direct_references == 0 {
+        context = Some("a subquery");
+    }
+
+    if let Some(context) = context {
+        sql_bail!(
+            "recursive reference to query {} must not appear within {}",
+            name.quoted(),
+            context
+        );
+    }
+    Ok(())
+}
+
+/// Calls `f` on `expr` and each of its descendants, including the relation
+/// expressions within subqueries.
+fn visit_with_subqueries<F>(expr: &HirRelationExpr, f: &mut F)
+where
+    F: FnMut(&HirRelationExpr),
+{
+    expr.visit(0, &mut |e, _| f(e));
+    let _ = expr.visit_scalar_expressions(0, &mut |s, _| -> Result<(), ()> {
+        s.visit(&mut |s| {
+            if let HirScalarExpr::Exists(subquery) | HirScalarExpr::Select(subquery) = s {
+                visit_with_subqueries(subquery, f);
+            }
+        });
+        Ok(())
+    });
+}
+
+/// Finds references to the CTE bound to `id`.
+struct CteReferenceFinder {
+    id: LocalId,
+    found: bool,
+}
+
+impl CteReferenceFinder {
+    fn new(id: LocalId) -> CteReferenceFinder {
+        CteReferenceFinder { id, found: false }
+    }
+}
+
+impl<'ast> Visit<'ast, Aug> for CteReferenceFinder {
+    fn visit_object_name(&mut self, name: &'ast ResolvedObjectName) {
+        if let ResolvedObjectName::Cte { id, .. } = name {
+            self.found |= *id == self.id;
+        }
+    }
+}
+
 pub fn plan_nested_query(
     qcx: &mut QueryContext,
     q: &Query<Aug>,
@@ -1520,6 +1974,19 @@ fn plan_view_select(
         }
         visitor.into_result()?
     };
+    let grouping_funcs = {
+        let mut visitor = GroupingFuncVisitor::new();
+        for si in &s.projection {
+            visitor.visit_select_item(si);
+        }
+        if let Some(having) = &s.having {
+            visitor.visit_expr(having);
+        }
+        for o in &order_by_exprs {
+            visitor.visit_order_by_expr(o);
+        }
+        visitor.funcs
+    };
     let mut table_func_names: HashMap<String, Ident> = HashMap::new();
     if !table_funcs.is_empty() {
         let (expr, scope) = plan_scalar_table_funcs(
@@ -1572,7 +2039,11 @@ fn plan_view_select(
         let mut group_scope = Scope::empty();
         let mut select_all_mapping = BTreeMap::new();
 
-        for group_expr in &s.group_by {
+        let (ast_group_exprs, grouping_sets) = expand_grouping_sets(&s.group_by)?;
+        // The position in `group_hir_exprs` of each of `ast_group_exprs`.
+        let mut key_positions = vec![];
+
+        for group_expr in ast_group_exprs {
             let (group_expr, expr) = plan_group_by_expr(ecx, group_expr, &projection)?;
             let new_column = group_key.len();
 
@@ -1582,6 +2053,12 @@ fn plan_view_select(
                 // next AST expression to its set
                 if let Some(existing_scope_item) = group_exprs.get_mut(&expr) {
                     existing_scope_item.exprs.insert(group_expr.clone());
+                    key_positions.push(
+                        group_hir_exprs
+                            .iter()
+                            .position(|e| *e == expr)
+                            .expect("group expression must be present"),
+                    );
                     continue;
                 }
             }
@@ -1607,6 +2084,7 @@ fn plan_view_select(
                 scope_item.exprs.insert(group_expr);
             }
 
+            key_positions.push(group_hir_exprs.len());
             group_key.push(from_scope.len() + group_exprs.len());
             group_hir_exprs.push(expr.clone());
             group_exprs.insert(expr, scope_item);
@@ -1619,6 +2097,31 @@ fn plan_view_select(
             }
         }
 
+        // With grouping sets, or when asked for `GROUPING` operations, each
+        // group is additionally keyed by the ID of its grouping set and by the
+        // values of the `GROUPING` operations, which are constant per set.
+        let grouping_sets = match grouping_sets {
+            Some(sets) => Some(
+                sets.into_iter()
+                    .map(|set| set.into_iter().map(|i| key_positions[i]).collect())
+                    .collect::<Vec<Vec<_>>>(),
+            ),
+            None if !grouping_funcs.is_empty() => Some(vec![(0..group_hir_exprs.len()).collect()]),
+            None => None,
+        };
+        let mut grouping_func_args = vec![];
+        if grouping_sets.is_some() {
+            for func in &grouping_funcs {
+                grouping_func_args.push(plan_grouping_func_args(ecx, func, &group_hir_exprs)?);
+            }
+            group_scope.items.push(ScopeItem::empty());
+            for func in grouping_funcs {
+                group_scope
+                    .items
+                    .push(ScopeItem::from_expr(Expr::Function(func)));
+            }
+        }
+
         // Plan aggregates.
         let ecx = &ExprContext {
             qcx,
@@ -1636,7 +2139,19 @@ fn plan_view_select(
                 .items
                 .push(ScopeItem::from_expr(Expr::Function(sql_function.clone())));
         }
-        if !agg_exprs.is_empty() || !group_key.is_empty() || s.having.is_some() {
+        if let Some(grouping_sets) = grouping_sets {
+            // apply GROUPING SETS / aggregates
+            relation_expr = plan_grouping_sets(
+                qcx,
+                relation_expr.map(group_hir_exprs),
+                from_scope.len(),
+                grouping_sets,
+                &grouping_func_args,
+                agg_exprs,
+                expected_group_size,
+            );
+            (group_scope, select_all_mapping)
+        } else if !agg_exprs.is_empty() || !group_key.is_empty() || s.having.is_some() {
             // apply GROUP BY / aggregates
             relation_expr = relation_expr.map(group_hir_exprs).reduce(
                 group_key,
@@ -1931,6 +2446,317 @@ fn plan_scalar_table_funcs(
     Ok((expr, scope))
 }
 
+/// The maximum number of grouping sets in a `GROUP BY` clause. PostgreSQL
+/// imposes the same limit.
+const MAX_GROUPING_SETS: usize = 4096;
+
+/// Expands the items of a `GROUP BY` clause into the distinct grouping
+/// expressions they mention and the grouping sets they denote, as lists of
+/// indexes into the former.
+///
+/// If the clause is a plain list of expressions, the expressions are returned
+/// as is and the grouping sets are `None`.
+fn expand_grouping_sets(
+    items: &[GroupByItem<Aug>],
+) -> Result<(Vec<&Expr<Aug>>, Option<Vec<Vec<usize>>>), PlanError> {
+    if let Some(exprs) = items
+        .iter()
+        .map(|item| match item {
+            GroupByItem::Expr(expr) => Some(expr),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+    {
+        return Ok((exprs, None));
+    }
+
+    fn position<'a>(exprs: &mut Vec<&'a Expr<Aug>>, expr: &'a Expr<Aug>) -> usize {
+        match exprs.iter().position(|e| *e == expr) {
+            Some(i) => i,
+            None => {
+                exprs.push(expr);
+                exprs.len() - 1
+            }
+        }
+    }
+
+    fn positions<'a>(exprs: &mut Vec<&'a Expr<Aug>>, elems: &'a [Expr<Aug>]) -> Vec<usize> {
+        let mut positions = vec![];
+        for expr in elems {
+            positions.push(position(exprs, expr));
+        }
+        positions
+    }
+
+    fn expand<'a>(
+        item: &'a GroupByItem<Aug>,
+        exprs: &mut Vec<&'a Expr<Aug>>,
+    ) -> Result<Vec<Vec<usize>>, PlanError> {
+        Ok(match item {
+            GroupByItem::Expr(expr) => vec![vec![position(exprs, expr)]],
+            GroupByItem::Set(set) => vec![positions(exprs, set)],
+            // `ROLLUP (a, b)` is `GROUPING SETS ((a, b), (a), ())`.
+            GroupByItem::Rollup(elems) => {
+                let elems: Vec<_> = elems.iter().map(|elem| positions(exprs, elem)).collect();
+                (0..=elems.len())
+                    .rev()
+                    .map(|n| elems[..n].concat())
+                    .collect()
+            }
+            // `CUBE (a, b)` is `GROUPING SETS ((a, b), (a), (b), ())`.
+            GroupByItem::Cube(elems) => {
+                // A `CUBE` of `n` elements has `2^n` grouping sets.
+                if elems.len() > MAX_GROUPING_SETS.trailing_zeros() as usize {
+                    sql_bail!(
+                        "too many grouping sets present (maximum {})",
+                        MAX_GROUPING_SETS
+                    );
+                }
+                let elems: Vec<_> = elems.iter().map(|elem| positions(exprs, elem)).collect();
+                let n = elems.len();
+                (0..1 << n)
+                    .rev()
+                    .map(|mask: usize| {
+                        (0..n)
+                            .filter(|i| mask & (1 << (n - 1 - i)) != 0)
+                            .flat_map(|i| elems[i].iter().copied())
+                            .collect()
+                    })
+                    .collect()
+            }
+            GroupByItem::GroupingSets(items) => {
+                let mut sets = vec![];
+                for item in items {
+                    sets.extend(expand(item, exprs)?);
+                    if sets.len() > MAX_GROUPING_SETS {
+                        sql_bail!(
+                            "too many grouping sets present (maximum {})",
+                            MAX_GROUPING_SETS
+                        );
+                    }
+                }
+                sets
+            }
+        })
+    }
+
+    // The items of the clause combine by cross product, so that e.g.
+    // `GROUP BY a, ROLLUP (b)` is `GROUPING SETS ((a, b), (a))`.
+    let mut exprs = vec![];
+    let mut sets = vec![vec![]];
+    for item in items {
+        let item_sets = expand(item, &mut exprs)?;
+        if sets.len() * item_sets.len() > MAX_GROUPING_SETS {
+            sql_bail!(
+                "too many grouping sets present (maximum {})",
+                MAX_GROUPING_SETS
+            );
+        }
+        sets = sets
+            .iter()
+            .cartesian_product(&item_sets)
+            .map(|(l, r)| [l.as_slice(), r.as_slice()].concat())
+            .collect();
+    }
+    Ok((exprs, Some(sets)))
+}
+
+/// Reports whether `func` is a `GROUPING` operation.
+fn is_grouping_func(func: &Function<Aug>) -> bool {
+    matches!(func.name.0.as_slice(), [name] if name.as_str() == "grouping")
+}
+
+/// Plans the arguments of the `GROUPING` operation `func`, returning the
+/// positions in `group_exprs` of the grouping expressions they refer to.
+fn plan_grouping_func_args(
+    ecx: &ExprContext,
+    func: &Function<Aug>,
+    group_exprs: &[HirScalarExpr],
+) -> Result<Vec<usize>, PlanError> {
+    let args = match func {
+        Function {
+            args: FunctionArgs::Args { args, order_by },
+            filter: None,
+            over: None,
+            distinct: false,
+            ..
+        } if !args.is_empty() && order_by.is_empty() => args,
+        _ => sql_bail!("GROUPING must be called with a list of grouping expressions"),
+    };
+    if args.len() > 31 {
+        sql_bail!("GROUPING must have fewer than 32 arguments");
+    }
+    args.iter()
+        .map(|arg| {
+            let expr = plan_expr(ecx, arg)?.type_as_any(ecx)?;
+            group_exprs.iter().position(|e| *e == expr).ok_or_else(|| {
+                sql_err!(
+                    "arguments to GROUPING must be grouping expressions of the associated query level"
+                )
+            })
+        })
+        .collect()
+}
+
+/// Plans the aggregation of `input` over each of `grouping_sets`.
+///
+/// The columns of `input` after the first `num_inputs` are the grouping
+/// expressions, and the grouping sets are given as positions among them. The result has one column
+/// for each grouping expression, which is null for groups of grouping sets
+/// that don't contain it, followed by the ID of the grouping set, the value of
+/// each `GROUPING` operation, and the aggregates.
+///
+/// Grouping sets that contain at least one expression are evaluated by a
+/// single reduction over the input, crossed with a constant collection that
+/// describes each grouping set. Empty grouping sets instead become global
+/// aggregations, so that they produce a row even when the input is empty.
+fn plan_grouping_sets(
+    qcx: &QueryContext,
+    input: HirRelationExpr,
+    num_inputs: usize,
+    grouping_sets: Vec<Vec<usize>>,
+    grouping_func_args: &[Vec<usize>],
+    agg_exprs: Vec<AggregateExpr>,
+    expected_group_size: Option<usize>,
+) -> HirRelationExpr {
+    let key_types: Vec<_> = qcx.relation_type(&input).column_types[num_inputs..]
+        .iter()
+        .map(|typ| typ.scalar_type.clone())
+        .collect();
+    let num_keys = key_types.len();
+    let num_grouping_funcs = grouping_func_args.len();
+    let num_aggs = agg_exprs.len();
+    let grouping_func_values = |set: &[usize]| -> Vec<Datum<'static>> {
+        grouping_func_args
+            .iter()
+            .map(|args| {
+                // Each argument contributes a bit, set when the argument is
+                // not in the grouping set, with the last argument in the
+                // least significant bit.
+                let value = args
+                    .iter()
+                    .fold(0, |value, arg| (value << 1) | i32::from(!set.contains(arg)));
+                Datum::Int32(value)
+            })
+            .collect()
+    };
+
+    let (empty_sets, sets): (Vec<_>, Vec<_>) = grouping_sets
+        .into_iter()
+        .enumerate()
+        .partition(|(_id, set)| set.is_empty());
+
+    let mut branches = vec![];
+    if !sets.is_empty() {
+        let rows = sets
+            .iter()
+            .map(|(id, set)| {
+                let mut row = vec![Datum::Int64(
+                    i64::try_from(*id).expect("too many grouping sets"),
+                )];
+                row.extend((0..num_keys).map(|key| Datum::from(set.contains(&key))));
+                row.extend(grouping_func_values(set));
+                row
+            })
+            .collect();
+        let typ = RelationType::new(
+            iter::once(ScalarType::Int64.nullable(false))
+                .chain(iter::repeat(ScalarType::Bool.nullable(false)).take(num_keys))
+                .chain(iter::repeat(ScalarType::Int32.nullable(false)).take(num_grouping_funcs))
+                .collect(),
+        );
+
+        // Columns of the input crossed with the constant collection.
+        let id_column = num_inputs + num_keys;
+        let contains_start = id_column + 1;
+        let grouping_funcs_start = contains_start + num_keys;
+        let keys_start = grouping_funcs_start + num_grouping_funcs;
+
+        let keys = key_types
+            .iter()
+            .enumerate()
+            .map(|(key, typ)| HirScalarExpr::If {
+                cond: Box::new(HirScalarExpr::column(contains_start + key)),
+                then: Box::new(HirScalarExpr::column(num_inputs + key)),
+                els: Box::new(HirScalarExpr::literal_null(typ.clone())),
+            })
+            .collect();
+        let group_key = (keys_start..keys_start + num_keys)
+            .chain(iter::once(id_column))
+            .chain(grouping_funcs_start..grouping_funcs_start + num_grouping_funcs)
+            .collect();
+        branches.push(
+            input
+                .clone()
+                .join(
+                    HirRelationExpr::constant(rows, typ),
+                    HirScalarExpr::literal_true(),
+                    JoinKind::Inner,
+                )
+                .map(keys)
+                .reduce(group_key, agg_exprs.clone(), expected_group_size),
+        );
+    }
+    for (id, set) in empty_sets {
+        let mut scalars: Vec<_> = key_types
+            .iter()
+            .map(|typ| HirScalarExpr::literal_null(typ.clone()))
+            .collect();
+        scalars.push(HirScalarExpr::literal(
+            Datum::Int64(i64::try_from(id).expect("too many grouping sets")),
+            ScalarType::Int64,
+        ));
+        scalars.extend(
+            grouping_func_values(&set)
+                .into_iter()
+                .map(|value| HirScalarExpr::literal(value, ScalarType::Int32)),
+        );
+        let num_scalars = scalars.len();
+        branches.push(
+            input
+                .clone()
+                .reduce(vec![], agg_exprs.clone(), expected_group_size)
+                .map(scalars)
+                .project(
+                    (num_aggs..num_aggs + num_scalars)
+                        .chain(0..num_aggs)
+                        .collect(),
+                ),
+        );
+    }
+    branches
+        .into_iter()
+        .reduce(|left, right| left.union(right))
+        .expect("GROUP BY has at least one grouping set")
+}
+
+/// Collects the `GROUPING` operations of a `SELECT`, outside of subqueries.
+struct GroupingFuncVisitor {
+    funcs: Vec<Function<Aug>>,
+}
+
+impl GroupingFuncVisitor {
+    fn new() -> GroupingFuncVisitor {
+        GroupingFuncVisitor { funcs: vec![] }
+    }
+}
+
+impl<'ast> Visit<'ast, Aug> for GroupingFuncVisitor {
+    fn visit_function(&mut self, func: &'ast Function<Aug>) {
+        if is_grouping_func(func) {
+            if !self.funcs.contains(func) {
+                self.funcs.push(func.clone());
+            }
+        } else {
+            visit::visit_function(self, func);
+        }
+    }
+
+    fn visit_query(&mut self, _query: &'ast Query<Aug>) {
+        // Don't go into subqueries.
+    }
+}
+
 /// Plans an expression in a `GROUP BY` clause.
 ///
 /// For historical reasons, PostgreSQL allows `GROUP BY` expressions to refer to
@@ -3834,7 +4660,7 @@ fn plan_aggregate(
         name,
         args,
         filter,
-        over,
+        over: _,
         distinct,
     }: &Function<Aug>,
 ) -> Result<AggregateExpr, PlanError> {
@@ -3856,10 +4682,6 @@ fn plan_aggregate(
         _ => unreachable!("plan_aggregate called on non-aggregate function,"),
     };
 
-    if over.is_some() {
-        bail_unsupported!("aggregate window functions");
-    }
-
     let name = normalize::unresolved_object_name(name.clone())?;
 
     // We follow PostgreSQL's rule here for mapping `count(*)` into the
@@ -4050,9 +4872,16 @@ fn plan_function<'a>(
         distinct,
     }: &'a Function<Aug>,
 ) -> Result<HirScalarExpr, PlanError> {
+    if is_grouping_func(f) {
+        // Should already have been caught by `scope.resolve_expr` in
+        // `plan_expr` in contexts that allow them.
+        sql_bail!("grouping operations are not allowed in {}", ecx.name);
+    }
+
     let unresolved_name = normalize::unresolved_object_name(name.clone())?;
 
     let impls = match resolve_func(ecx, name, args)? {
+        Func::Aggregate(_) if over.is_some() => return plan_aggregate_window(ecx, f),
         Func::Aggregate(_) if ecx.allow_aggregates => {
             // should already have been caught by `scope.resolve_expr` in `plan_expr`
             sql_bail!(
@@ -4101,6 +4930,19 @@ fn plan_function<'a>(
                 vec![],
             )?;
 
+            // Sessions are always formed in timestamp order over the whole partition
+            if let ValueWindowFunc::Session = func {
+                if !window_spec.order_by.is_empty() {
+                    sql_bail!(
+                        "window function {} does not accept an ORDER BY clause",
+                        name
+                    );
+                }
+                if window_spec.window_frame.is_some() {
+                    sql_bail!("window function {} does not accept a window frame", name);
+                }
+            }
+
             let (order_by, col_orders) = plan_function_order_by(ecx, &window_spec.order_by)?;
 
             return Ok(HirScalarExpr::Windowing(WindowExpr {
@@ -4324,6 +5166,88 @@ fn plan_literal<'a>(l: &'a Value) -> Result<CoercibleScalarExpr, PlanError> {
     Ok(expr.into())
 }
 
+/// Plans an aggregate function that is invoked as a window function, like
+/// `sum(x) OVER (PARTITION BY y ORDER BY z ROWS 2 PRECEDING)`.
+fn plan_aggregate_window(ecx: &ExprContext, f: &Function<Aug>) -> Result<HirScalarExpr, PlanError> {
+    if !ecx.allow_windows {
+        sql_bail!("window functions are not allowed in {}", ecx.name);
+    }
+
+    if f.distinct {
+        bail_unsupported!("DISTINCT in aggregate window functions");
+    }
+
+    let window_spec = f
+        .over
+        .as_ref()
+        .expect("plan_aggregate_window called without an OVER clause");
+
+    // The aggregate's arguments are planned in the same scope as the window
+    // function itself, but must not contain other window functions.
+    let mut args_ecx = ecx.with_name("window function arguments");
+    args_ecx.allow_windows = false;
+    let aggregate_expr = plan_aggregate(&args_ecx, f)?;
+
+    let mut partition = Vec::new();
+    for expr in &window_spec.partition_by {
+        partition.push(plan_expr(ecx, expr)?.type_as_any(ecx)?);
+    }
+
+    let (order_by, col_orders) = plan_function_order_by(ecx, &window_spec.order_by)?;
+
+    let window_frame = match window_spec.window_frame.as_ref() {
+        Some(frame) => plan_window_frame(frame)?,
+        None => mz_expr::WindowFrame::default(),
+    };
+    match window_frame.units {
+        mz_expr::WindowFrameUnits::Rows => (),
+        mz_expr::WindowFrameUnits::Groups => {
+            if order_by.is_empty() {
+                sql_bail!("GROUPS mode requires an ORDER BY clause");
+            }
+        }
+        mz_expr::WindowFrameUnits::Range => {
+            let has_offset = |bound: &mz_expr::WindowFrameBound| {
+                matches!(
+                    bound,
+                    mz_expr::WindowFrameBound::OffsetPreceding(_)
+                        | mz_expr::WindowFrameBound::OffsetFollowing(_)
+                )
+            };
+            if has_offset(&window_frame.start_bound) || has_offset(&window_frame.end_bound) {
+                if order_by.len() != 1 {
+                    sql_bail!(
+                        "RANGE with offset PRECEDING/FOLLOWING requires exactly one ORDER BY column"
+                    );
+                }
+                let typ = ecx.scalar_type(&order_by[0]);
+                match typ {
+                    ScalarType::Int16
+                    | ScalarType::Int32
+                    | ScalarType::Int64
+                    | ScalarType::Float32
+                    | ScalarType::Float64
+                    | ScalarType::Numeric { .. } => (),
+                    _ => sql_bail!(
+                        "RANGE with offset PRECEDING/FOLLOWING is not supported for column type {}",
+                        ecx.humanize_scalar_type(&typ)
+                    ),
+                }
+            }
+        }
+    }
+
+    Ok(HirScalarExpr::Windowing(WindowExpr {
+        func: WindowExprType::Aggregate(AggregateWindowExpr {
+            aggregate_expr,
+            order_by: col_orders,
+            window_frame,
+        }),
+        partition,
+        order_by,
+    }))
+}
+
 fn validate_window_function_plan<'a>(
     ecx: &ExprContext,
     Function {
@@ -4367,6 +5291,17 @@ fn validate_window_function_plan<'a>(
         Some(frame) => plan_window_frame(frame)?,
         None => mz_expr::WindowFrame::default(),
     };
+    // Only aggregate window functions support GROUPS, and RANGE outside of the
+    // default frame
+    match window_frame.units {
+        mz_expr::WindowFrameUnits::Rows => (),
+        mz_expr::WindowFrameUnits::Range => {
+            if window_frame != mz_expr::WindowFrame::default() {
+                bail_unsupported!("RANGE in non-default window frames")
+            }
+        }
+        mz_expr::WindowFrameUnits::Groups => bail_unsupported!("GROUPS in window frames"),
+    }
     let mut partition = Vec::new();
     for expr in &window_spec.partition_by {
         partition.push(plan_expr(ecx, expr)?.type_as_any(ecx)?);
@@ -4398,7 +5333,7 @@ fn plan_window_frame(
     }: &WindowFrame,
 ) -> Result<mz_expr::WindowFrame, PlanError> {
     use mz_expr::WindowFrameBound::*;
-    let units = window_frame_unit_ast_to_expr(units)?;
+    let units = window_frame_unit_ast_to_expr(units);
     let start_bound = window_frame_bound_ast_to_expr(start_bound);
     let end_bound = end_bound
         .as_ref()
@@ -4426,13 +5361,6 @@ fn plan_window_frame(
         (_, _) => (),
     }
 
-    // RANGE is only supported in the default frame
-    if units == mz_expr::WindowFrameUnits::Range
-        && (start_bound != UnboundedPreceding || end_bound != CurrentRow)
-    {
-        bail_unsupported!("RANGE in non-default window frames")
-    }
-
     let frame = mz_expr::WindowFrame {
         units,
         start_bound,
@@ -4441,13 +5369,11 @@ fn plan_window_frame(
     Ok(frame)
 }
 
-fn window_frame_unit_ast_to_expr(
-    unit: &WindowFrameUnits,
-) -> Result<mz_expr::WindowFrameUnits, PlanError> {
+fn window_frame_unit_ast_to_expr(unit: &WindowFrameUnits) -> mz_expr::WindowFrameUnits {
     match unit {
-        WindowFrameUnits::Rows => Ok(mz_expr::WindowFrameUnits::Rows),
-        WindowFrameUnits::Range => Ok(mz_expr::WindowFrameUnits::Range),
-        WindowFrameUnits::Groups => bail_unsupported!("GROUPS in window frames"),
+        WindowFrameUnits::Rows => mz_expr::WindowFrameUnits::Rows,
+        WindowFrameUnits::Range => mz_expr::WindowFrameUnits::Range,
+        WindowFrameUnits::Groups => mz_expr::WindowFrameUnits::Groups,
     }
 }
 
@@ -4731,6 +5657,11 @@ impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
         };
 
         match item.func() {
+            // Aggregate window functions are planned like other window
+            // functions, but their arguments may contain regular aggregates.
+            Ok(Func::Aggregate { .. }) if func.over.is_some() => {
+                visit_mut::visit_function_mut(self, func)
+            }
             Ok(Func::Aggregate { .. }) => {
                 if self.within_aggregate {
                     self.err = Some(sql_err!("nested aggregate functions are not allowed",));
@@ -4855,6 +5786,8 @@ pub struct CteDesc {
     val: HirRelationExpr,
     name: String,
     val_desc: RelationDesc,
+    /// Whether `val` references the CTE itself.
+    recursive: bool,
 }
 
 /// The state required when planning a `Query`.
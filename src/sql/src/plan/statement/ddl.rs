@@ -28,6 +28,7 @@ use tracing::{debug, warn};
 
 use mz_expr::CollectionPlan;
 use mz_interchange::avro::{self, AvroSchemaGenerator};
+use mz_interchange::protobuf::ProtobufSchemaGenerator;
 use mz_ore::collections::CollectionExt;
 use mz_ore::str::StrExt;
 use mz_postgres_util::desc::PostgresTableDesc;
@@ -35,6 +36,7 @@ use mz_proto::RustType;
 use mz_repr::adt::interval::Interval;
 use mz_repr::strconv;
 use mz_repr::{ColumnName, GlobalId, RelationDesc, RelationType, ScalarType};
+use mz_storage::types::connections::aws::{AwsAssumeRole, AwsConfig, AwsCredentials, SerdeUri};
 use mz_storage::types::connections::{
     Connection, CsrConnectionHttpAuth, KafkaConnection, KafkaSecurity, KafkaTlsConfig, SaslConfig,
     StringOrSecret, TlsIdentity,
@@ -58,7 +60,8 @@ use mz_storage::types::sources::{
 use crate::ast::display::AstDisplay;
 use crate::ast::{
     AlterIndexAction, AlterIndexStatement, AlterObjectRenameStatement, AlterSecretStatement,
-    AvroSchema, AvroSchemaOption, AvroSchemaOptionName, ClusterOption, ColumnOption, Compression,
+    AlterTableAction, AlterTableStatement, AvroSchema, AvroSchemaOption, AvroSchemaOptionName,
+    AwsConnectionOption, AwsConnectionOptionName, ClusterOption, ColumnOption, Compression,
     CreateClusterReplicaStatement, CreateClusterStatement, CreateConnection,
     CreateConnectionStatement, CreateDatabaseStatement, CreateIndexStatement,
     CreateRecordedViewStatement, CreateRoleOption, CreateRoleStatement, CreateSchemaStatement,
@@ -91,14 +94,14 @@ use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::with_options::{self, OptionalInterval, TryFromValue};
 use crate::plan::{
     plan_utils, query, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan,
-    AlterNoopPlan, AlterSecretPlan, ComputeInstanceIntrospectionConfig,
-    ComputeInstanceReplicaConfig, CreateComputeInstancePlan, CreateComputeInstanceReplicaPlan,
-    CreateConnectionPlan, CreateDatabasePlan, CreateIndexPlan, CreateRecordedViewPlan,
-    CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
-    CreateTablePlan, CreateTypePlan, CreateViewPlan, CreateViewsPlan,
+    AlterNoopPlan, AlterSecretPlan, AlterTableColumn, AlterTablePlan, CheckConstraint,
+    ComputeInstanceIntrospectionConfig, ComputeInstanceReplicaConfig, CreateComputeInstancePlan,
+    CreateComputeInstanceReplicaPlan, CreateConnectionPlan, CreateDatabasePlan, CreateIndexPlan,
+    CreateRecordedViewPlan, CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan,
+    CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, CreateViewsPlan,
     DropComputeInstanceReplicaPlan, DropComputeInstancesPlan, DropDatabasePlan, DropItemsPlan,
     DropRolesPlan, DropSchemaPlan, Index, Params, Plan, RecordedView, Secret, Sink, Source, Table,
-    Type, View,
+    Type, UniqueConstraint, View,
 };
 
 pub fn describe_create_database(
@@ -171,12 +174,26 @@ pub fn plan_create_table(
     scx: &StatementContext,
     stmt: CreateTableStatement<Aug>,
 ) -> Result<Plan, PlanError> {
+    let if_not_exists = stmt.if_not_exists;
+    let (name, table) = plan_table(scx, stmt)?;
+    Ok(Plan::CreateTable(CreateTablePlan {
+        name,
+        table,
+        if_not_exists,
+    }))
+}
+
+/// Plans the table described by a `CREATE TABLE` statement.
+fn plan_table(
+    scx: &StatementContext,
+    stmt: CreateTableStatement<Aug>,
+) -> Result<(QualifiedObjectName, Table), PlanError> {
     let CreateTableStatement {
         name,
         columns,
         constraints,
         with_options,
-        if_not_exists,
+        if_not_exists: _,
         temporary,
     } = &stmt;
 
@@ -193,11 +210,16 @@ pub fn plan_create_table(
         sql_bail!("column {} specified more than once", dup.as_str().quoted());
     }
 
+    let temporary = *temporary;
+    let partial_name = normalize::unresolved_object_name(name.to_owned())?;
+    let mut constraint_names = ConstraintNames::new(&partial_name.item);
+
     // Build initial relation type that handles declared data types
     // and NOT NULL constraints.
     let mut column_types = Vec::with_capacity(columns.len());
     let mut defaults = Vec::with_capacity(columns.len());
-    let mut keys = Vec::new();
+    let mut uniques = Vec::new();
+    let mut checks = Vec::new();
 
     for (i, c) in columns.into_iter().enumerate() {
         let aug_data_type = &c.data_type;
@@ -214,11 +236,21 @@ pub fn plan_create_table(
                     default = expr.clone();
                 }
                 ColumnOption::Unique { is_primary } => {
-                    keys.push(vec![i]);
+                    let name = if *is_primary {
+                        constraint_names.choose(option.name.as_ref(), &[], "pkey")?
+                    } else {
+                        constraint_names.choose(option.name.as_ref(), &[&names[i]], "key")?
+                    };
+                    uniques.push((name, vec![i], *is_primary));
                     if *is_primary {
                         nullable = false;
                     }
                 }
+                ColumnOption::Check(expr) => {
+                    let name =
+                        constraint_names.choose(option.name.as_ref(), &[&names[i]], "check")?;
+                    checks.push((name, expr));
+                }
                 other => {
                     bail_unsupported!(format!("CREATE TABLE with column constraint: {}", other))
                 }
@@ -231,7 +263,7 @@ pub fn plan_create_table(
     for constraint in constraints {
         match constraint {
             TableConstraint::Unique {
-                name: _,
+                name,
                 columns,
                 is_primary,
             } => {
@@ -248,49 +280,132 @@ pub fn plan_create_table(
                         }
                     }
                 }
-                keys.push(key);
+                let name = if *is_primary {
+                    constraint_names.choose(name.as_ref(), &[], "pkey")?
+                } else {
+                    let key_names: Vec<_> = key.iter().map(|i| &names[*i]).collect();
+                    constraint_names.choose(name.as_ref(), &key_names, "key")?
+                };
+                uniques.push((name, key, *is_primary));
             }
             TableConstraint::ForeignKey { .. } => {
                 // Foreign key constraints are not presently enforced. We allow
                 // them in unsafe mode for sqllogictest's sake.
                 scx.require_unsafe_mode("CREATE TABLE with a foreign key")?
             }
-            TableConstraint::Check { .. } => {
-                // Check constraints are not presently enforced. We allow them
-                // in unsafe mode for sqllogictest's sake.
-                scx.require_unsafe_mode("CREATE TABLE with a check constraint")?
+            TableConstraint::Check { name, expr } => {
+                let name = constraint_names.choose(name.as_ref(), &[], "check")?;
+                checks.push((name, expr));
             }
         }
     }
 
-    if !keys.is_empty() {
-        // Unique constraints are not presently enforced. We allow them in
-        // unsafe mode for sqllogictest's sake.
-        scx.require_unsafe_mode("CREATE TABLE with a primary key or unique constraint")?;
+    if uniques
+        .iter()
+        .filter(|(_, _, is_primary)| *is_primary)
+        .count()
+        > 1
+    {
+        sql_bail!(
+            "multiple primary keys for table {} are not allowed",
+            partial_name.item.quoted()
+        );
     }
 
+    // Unique constraints are enforced whenever the table is written to, but
+    // permit any number of rows with a `NULL` in the constrained columns. Only
+    // constraints whose columns are all non-nullable are therefore keys in the
+    // sense of the relation type.
+    let keys = uniques
+        .iter()
+        .map(|(_, columns, _)| columns)
+        .filter(|columns| columns.iter().all(|i| !column_types[*i].nullable))
+        .cloned()
+        .collect();
+    let uniques = uniques
+        .into_iter()
+        .map(|(name, columns, _)| UniqueConstraint { name, columns })
+        .collect();
+
     let typ = RelationType::new(column_types).with_keys(keys);
 
-    let temporary = *temporary;
     let name = if temporary {
-        scx.allocate_temporary_qualified_name(normalize::unresolved_object_name(name.to_owned())?)?
+        scx.allocate_temporary_qualified_name(partial_name)?
     } else {
-        scx.allocate_qualified_name(normalize::unresolved_object_name(name.to_owned())?)?
+        scx.allocate_qualified_name(partial_name)?
     };
     let desc = RelationDesc::new(typ, names);
 
+    let checks = checks
+        .into_iter()
+        .map(|(name, expr)| {
+            Ok(CheckConstraint {
+                name,
+                expr: query::plan_check_expr(scx, &desc, expr)?,
+            })
+        })
+        .collect::<Result<_, PlanError>>()?;
+
     let create_sql = normalize::create_statement(&scx, Statement::CreateTable(stmt.clone()))?;
     let table = Table {
         create_sql,
         desc,
         defaults,
+        checks,
+        uniques,
         temporary,
     };
-    Ok(Plan::CreateTable(CreateTablePlan {
-        name,
-        table,
-        if_not_exists: *if_not_exists,
-    }))
+    Ok((name, table))
+}
+
+/// Chooses names for the constraints on a table, following PostgreSQL's
+/// conventions for constraints that are not explicitly named.
+struct ConstraintNames<'a> {
+    table: &'a str,
+    taken: HashSet<String>,
+}
+
+impl<'a> ConstraintNames<'a> {
+    fn new(table: &'a str) -> ConstraintNames<'a> {
+        ConstraintNames {
+            table,
+            taken: HashSet::new(),
+        }
+    }
+
+    /// Returns `name`, if the constraint was explicitly named, or otherwise a
+    /// name of the form `<table>_<columns>_<label>` that is not yet taken.
+    fn choose(
+        &mut self,
+        name: Option<&Ident>,
+        columns: &[&ColumnName],
+        label: &str,
+    ) -> Result<String, PlanError> {
+        if let Some(name) = name {
+            let name = normalize::ident(name.clone());
+            if !self.taken.insert(name.clone()) {
+                sql_bail!(
+                    "constraint {} for relation {} already exists",
+                    name.quoted(),
+                    self.table.quoted()
+                );
+            }
+            return Ok(name);
+        }
+        let mut base = self.table.to_string();
+        for column in columns {
+            write!(base, "_{}", column).expect("writing to string cannot fail");
+        }
+        write!(base, "_{}", label).expect("writing to string cannot fail");
+        let mut name = base.clone();
+        let mut i = 0;
+        while self.taken.contains(&name) {
+            i += 1;
+            name = format!("{}{}", base, i);
+        }
+        self.taken.insert(name.clone());
+        Ok(name)
+    }
 }
 
 pub fn describe_create_source(
@@ -406,6 +521,11 @@ pub fn plan_create_source(
                 _ => sql_bail!("start_offset must be a nonnegative integer"),
             };
 
+            // Purification translates `START TIMESTAMP` to `start_offset`.
+            if kafka.start_timestamp.is_some() {
+                sql_bail!("[internal error] START TIMESTAMP should be resolved in purification");
+            }
+
             let mut start_offsets = HashMap::new();
             match with_options.remove("start_offset") {
                 None => {
@@ -649,10 +769,6 @@ pub fn plan_create_source(
                     let mut tx_metadata_source = None;
                     let mut tx_metadata_collection = None;
 
-                    if !tx_metadata.is_empty() {
-                        scx.require_unsafe_mode("ENVELOPE DEBEZIUM ... TRANSACTION METADATA")?;
-                    }
-
                     for option in tx_metadata {
                         match option {
                             DbzTxMetadataOption::Source(source) => {
@@ -1041,7 +1157,7 @@ fn typecheck_debezium_transaction_metadata(
     let tx_data_collections_event_count_idx = match tx_data_collections_event_count {
         Some((idx, (_, ty))) => match ty.scalar_type {
             ScalarType::Int16 | ScalarType::Int32 | ScalarType::Int64 => idx,
-            _ => sql_bail!("'data_collections.event_count' must be of type string"),
+            _ => sql_bail!("'data_collections.event_count' must be of type integer"),
         },
         _ => sql_bail!("'data_collections.event_count' missing from debezium transaction metadata"),
     };
@@ -1232,6 +1348,7 @@ fn get_encoding_inner(
         }
         Format::Protobuf(schema) => match schema {
             ProtobufSchema::Csr {
+                message_name: _,
                 csr_connection:
                     CsrConnectionProtobuf {
                         connection,
@@ -1328,7 +1445,7 @@ fn get_encoding_inner(
                 },
             })
         }
-        Format::Json => bail_unsupported!("JSON sources"),
+        Format::Json => DataEncodingInner::Json,
         Format::Text => DataEncodingInner::Text,
     }))
 }
@@ -1378,7 +1495,7 @@ fn get_unnamed_key_envelope(key: &DataEncoding) -> Result<KeyEnvelope, PlanError
         DataEncodingInner::Postgres | DataEncodingInner::RowCodec(_) => {
             sql_bail!("{} sources cannot use INCLUDE KEY", key.op_name())
         }
-        DataEncodingInner::Bytes | DataEncodingInner::Text => false,
+        DataEncodingInner::Bytes | DataEncodingInner::Text | DataEncodingInner::Json => false,
         DataEncodingInner::Avro(_)
         | DataEncodingInner::Csv(_)
         | DataEncodingInner::Protobuf(_)
@@ -1608,6 +1725,7 @@ pub fn plan_create_views(
                 }
                 let query = Query {
                     ctes: vec![],
+                    recursive: false,
                     body: SetExpr::Select(Box::new(Select {
                         distinct: None,
                         projection,
@@ -1742,6 +1860,7 @@ fn kafka_sink_builder(
     key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
     value_desc: RelationDesc,
     envelope: SinkEnvelope,
+    tx_metadata_collection: Option<String>,
     topic_suffix_nonce: String,
     root_dependencies: &[&dyn CatalogItem],
 ) -> Result<SinkConnectionBuilder, PlanError> {
@@ -1821,8 +1940,10 @@ fn kafka_sink_builder(
             )?;
             normalize::ensure_empty_options(&normalized_with_options, "CONFLUENT SCHEMA REGISTRY")?;
 
-            let include_transaction =
-                reuse_topic || consistency_topic.is_some() || consistency.is_some();
+            let include_transaction = reuse_topic
+                || consistency_topic.is_some()
+                || consistency.is_some()
+                || tx_metadata_collection.is_some();
             let schema_generator = AvroSchemaGenerator::new(
                 avro_key_fullname.as_deref(),
                 avro_value_fullname.as_deref(),
@@ -1844,6 +1965,51 @@ fn kafka_sink_builder(
                 csr_connection,
             }
         }
+        Some(Format::Protobuf(ProtobufSchema::Csr {
+            message_name,
+            csr_connection:
+                CsrConnectionProtobuf {
+                    connection: CsrConnection::Inline { url },
+                    seed,
+                    with_options,
+                },
+        })) => {
+            if seed.is_some() {
+                sql_bail!("SEED option does not make sense with sinks");
+            }
+
+            let mut normalized_with_options = normalize::options(&with_options)?;
+            let csr_connection = kafka_util::generate_ccsr_connection(
+                url.parse()
+                    .map_err(|e| sql_err!("parsing schema registry url: {e}"))?,
+                &mut normalized_with_options,
+            )?;
+            normalize::ensure_empty_options(&normalized_with_options, "CONFLUENT SCHEMA REGISTRY")?;
+
+            let include_transaction = reuse_topic
+                || consistency_topic.is_some()
+                || consistency.is_some()
+                || tx_metadata_collection.is_some();
+            let schema_generator = ProtobufSchemaGenerator::new(
+                message_name.as_deref(),
+                key_desc_and_indices
+                    .as_ref()
+                    .map(|(desc, _indices)| desc.clone()),
+                value_desc.clone(),
+                matches!(envelope, SinkEnvelope::Debezium),
+                include_transaction,
+            )
+            .map_err(|e| sql_err!("{:#}", e))?;
+
+            KafkaSinkFormat::Protobuf {
+                key_schema: schema_generator.key_schema().map(|s| s.to_owned()),
+                value_schema: schema_generator.value_schema().to_owned(),
+                csr_connection,
+            }
+        }
+        Some(Format::Protobuf(ProtobufSchema::InlineSchema { .. })) => {
+            bail_unsupported!("FORMAT PROTOBUF USING SCHEMA FILE for sinks")
+        }
         Some(Format::Json) => KafkaSinkFormat::Json,
         Some(format) => bail_unsupported!(format!("sink format {:?}", format)),
         None => bail_unsupported!("sink without format"),
@@ -1853,6 +2019,7 @@ fn kafka_sink_builder(
         &topic_prefix,
         &format,
         reuse_topic,
+        tx_metadata_collection.is_some(),
         consistency,
         consistency_topic,
     )?;
@@ -1939,6 +2106,7 @@ fn kafka_sink_builder(
         topic_prefix,
         consistency_topic_prefix: consistency_topic,
         consistency_format,
+        consistency_data_collection: tx_metadata_collection,
         topic_suffix_nonce,
         partition_count,
         replication_factor,
@@ -1958,11 +2126,13 @@ fn kafka_sink_builder(
 /// This is slightly complicated because of a desire to maintain backwards compatibility with
 /// previous ways of specifying consistency configuration. [`KafkaConsistency`] is the new way of
 /// doing things, we support specifying just a topic name (via `consistency_topic`) for backwards
-/// compatibility.
+/// compatibility. Requesting transaction metadata implies a consistency topic, since that is
+/// where the transaction metadata is written.
 fn get_kafka_sink_consistency_config(
     topic_prefix: &str,
     sink_format: &KafkaSinkFormat,
     reuse_topic: bool,
+    transaction_metadata: bool,
     consistency: Option<KafkaConsistency<Aug>>,
     consistency_topic: Option<String>,
 ) -> Result<Option<(String, KafkaSinkFormat)>, PlanError> {
@@ -2006,48 +2176,51 @@ fn get_kafka_sink_consistency_config(
                     },
                 ))
             }
+            Some(Format::Json) => Some((topic, KafkaSinkFormat::Json)),
             None => {
                 // If a CONSISTENCY FORMAT is not provided, default to the FORMAT of the sink.
+                // Protobuf sinks write Avro transaction records to the same schema registry.
                 match sink_format {
-                    format @ KafkaSinkFormat::Avro { .. } => Some((topic, format.clone())),
-                    KafkaSinkFormat::Json => bail_unsupported!("CONSISTENCY FORMAT JSON"),
+                    KafkaSinkFormat::Avro { csr_connection, .. }
+                    | KafkaSinkFormat::Protobuf { csr_connection, .. } => Some((
+                        topic,
+                        KafkaSinkFormat::Avro {
+                            key_schema: None,
+                            value_schema: avro::get_debezium_transaction_schema().canonical_form(),
+                            csr_connection: csr_connection.clone(),
+                        },
+                    )),
+                    KafkaSinkFormat::Json => Some((topic, KafkaSinkFormat::Json)),
                 }
             }
             Some(other) => bail_unsupported!(format!("CONSISTENCY FORMAT {}", &other)),
         },
         None => {
-            // Support use of `consistency_topic` with option if the sink is Avro-formatted
-            // for backwards compatibility.
-            if reuse_topic | consistency_topic.is_some() {
-                match sink_format {
-                    KafkaSinkFormat::Avro {
-                        csr_connection,
-                        ..
-                    } => {
-                        let consistency_topic = match consistency_topic {
-                            Some(topic) => topic,
-                            None => {
-                                let default_consistency_topic =
-                                    format!("{}-consistency", topic_prefix);
-                                debug!(
-                                    "Using default consistency topic '{}' for topic '{}'",
-                                    default_consistency_topic, topic_prefix
-                                );
-                                default_consistency_topic
-                            }
-                        };
-                        Some((
-                            consistency_topic,
-                            KafkaSinkFormat::Avro {
-                                key_schema: None,
-                                value_schema: avro::get_debezium_transaction_schema()
-                                    .canonical_form(),
-                                csr_connection: csr_connection.clone(),
-                            },
-                        ))
+            // Support use of the `consistency_topic` option for backwards compatibility. The
+            // consistency topic uses the same format as the sink, except that Protobuf sinks
+            // use Avro.
+            if reuse_topic | transaction_metadata | consistency_topic.is_some() {
+                let consistency_topic = match consistency_topic {
+                    Some(topic) => topic,
+                    None => {
+                        let default_consistency_topic = format!("{}-consistency", topic_prefix);
+                        debug!(
+                            "Using default consistency topic '{}' for topic '{}'",
+                            default_consistency_topic, topic_prefix
+                        );
+                        default_consistency_topic
                     }
-                    KafkaSinkFormat::Json => sql_bail!("For FORMAT JSON, you need to manually specify an Avro consistency topic using 'CONSISTENCY TOPIC consistency_topic CONSISTENCY FORMAT AVRO USING CONFLUENT SCHEMA REGISTRY url'. The default of using a JSON consistency topic is not supported."),
-                }
+                };
+                let consistency_format = match sink_format {
+                    KafkaSinkFormat::Avro { csr_connection, .. }
+                    | KafkaSinkFormat::Protobuf { csr_connection, .. } => KafkaSinkFormat::Avro {
+                        key_schema: None,
+                        value_schema: avro::get_debezium_transaction_schema().canonical_form(),
+                        csr_connection: csr_connection.clone(),
+                    },
+                    KafkaSinkFormat::Json => KafkaSinkFormat::Json,
+                };
+                Some((consistency_topic, consistency_format))
             } else {
                 None
             }
@@ -2093,21 +2266,49 @@ pub fn plan_create_sink(
         if_not_exists,
     } = stmt;
 
+    let mut with_options = normalize::options(&with_options)?;
+
+    let drop_retractions = match with_options.remove("drop_retractions") {
+        Some(SqlValueOrSecret::Value(Value::Boolean(b))) => b,
+        None => false,
+        Some(_) => sql_bail!("drop_retractions must be a boolean"),
+    };
+    if drop_retractions && !matches!(envelope, Some(Envelope::None)) {
+        sql_bail!("drop_retractions is only supported for sinks with ENVELOPE NONE");
+    }
+
+    let mut tx_metadata_collection = None;
     let envelope = match envelope {
         // Sinks default to ENVELOPE DEBEZIUM. Not sure that's good, though...
         None => SinkEnvelope::Debezium,
         Some(Envelope::Debezium(mz_sql_parser::ast::DbzMode::Plain { tx_metadata })) => {
-            if !tx_metadata.is_empty() {
-                bail_unsupported!("ENVELOPE DEBEZIUM ... TRANSACTION METADATA");
+            // Transaction metadata for sinks is written to the consistency
+            // topic, so the only thing to configure is the collection name.
+            for option in tx_metadata {
+                match option {
+                    DbzTxMetadataOption::Source(_) => sql_bail!(
+                        "TRANSACTION METADATA SOURCE is not supported for sinks; \
+                         transaction metadata is written to the sink's consistency topic"
+                    ),
+                    DbzTxMetadataOption::Collection(data_collection) => {
+                        if tx_metadata_collection.is_some() {
+                            sql_bail!("TRANSACTION METADATA COLLECTION specified more than once");
+                        }
+                        tx_metadata_collection = Some(String::try_from_value(data_collection)?);
+                    }
+                }
             }
             SinkEnvelope::Debezium
         }
         Some(Envelope::Upsert) => SinkEnvelope::Upsert,
         Some(Envelope::CdcV2) => bail_unsupported!("CDCv2 sinks"),
         Some(Envelope::Debezium(mz_sql_parser::ast::DbzMode::Upsert)) => {
-            bail_unsupported!("UPSERT doesn't make sense for sinks")
+            sql_bail!(
+                "ENVELOPE DEBEZIUM UPSERT is not supported for sinks; \
+                 use ENVELOPE UPSERT to write the latest value for each KEY"
+            )
         }
-        Some(Envelope::None) => bail_unsupported!("\"ENVELOPE NONE\" sinks"),
+        Some(Envelope::None) => SinkEnvelope::AppendOnly { drop_retractions },
     };
     let name = scx.allocate_qualified_name(normalize::unresolved_object_name(name)?)?;
     let from = scx.get_item_by_resolved_name(&from)?;
@@ -2117,8 +2318,6 @@ pub fn plan_create_sink(
         scx.catalog.config().nonce
     );
 
-    let mut with_options = normalize::options(&with_options)?;
-
     let desc = from.desc(&scx.catalog.resolve_full_name(from.name()))?;
     let key_indices = match &connection {
         CreateSinkConnection::Kafka { key, .. } => {
@@ -2209,6 +2408,7 @@ pub fn plan_create_sink(
             key_desc_and_indices,
             desc.into_owned(),
             envelope,
+            tx_metadata_collection,
             suffix_nonce,
             &root_user_dependencies,
         )?,
@@ -3064,6 +3264,64 @@ impl TryFrom<SshConnectionOptionExtracted> for mz_storage::types::connections::S
     }
 }
 
+generate_extracted_config!(
+    AwsConnectionOption,
+    (AccessKeyId, String),
+    (Endpoint, String),
+    (Profile, String),
+    (Region, String),
+    (RoleArn, String),
+    (SecretAccessKey, String),
+    (Token, String)
+);
+
+impl TryFrom<AwsConnectionOptionExtracted> for AwsConfig {
+    type Error = PlanError;
+
+    fn try_from(options: AwsConnectionOptionExtracted) -> Result<Self, Self::Error> {
+        let credentials = match (
+            options.profile,
+            options.access_key_id,
+            options.secret_access_key,
+            options.token,
+        ) {
+            (Some(profile_name), None, None, None) => AwsCredentials::Profile { profile_name },
+            (Some(_), _, _, _) => sql_bail!(
+                "invalid CONNECTION: PROFILE cannot be specified with \
+                 ACCESS KEY ID, SECRET ACCESS KEY, or TOKEN"
+            ),
+            (None, None, None, None) => AwsCredentials::Default,
+            (None, Some(access_key_id), Some(secret_access_key), session_token) => {
+                AwsCredentials::Static {
+                    access_key_id,
+                    secret_access_key,
+                    session_token,
+                }
+            }
+            (None, Some(_), None, _) => sql_bail!(
+                "invalid CONNECTION: SECRET ACCESS KEY must be specified with ACCESS KEY ID"
+            ),
+            (None, None, _, _) => sql_bail!(
+                "invalid CONNECTION: ACCESS KEY ID must be specified with SECRET ACCESS KEY or TOKEN"
+            ),
+        };
+        let endpoint = match options.endpoint {
+            None => None,
+            Some(endpoint) => Some(SerdeUri(
+                endpoint
+                    .parse()
+                    .map_err(|e| sql_err!("parsing AWS endpoint: {e}"))?,
+            )),
+        };
+        Ok(AwsConfig {
+            credentials,
+            region: options.region,
+            role: options.role_arn.map(|arn| AwsAssumeRole { arn }),
+            endpoint,
+        })
+    }
+}
+
 pub fn plan_create_connection(
     scx: &StatementContext,
     stmt: CreateConnectionStatement<Aug>,
@@ -3095,6 +3353,10 @@ pub fn plan_create_connection(
             let connection = mz_storage::types::connections::SshConnection::try_from(c)?;
             Connection::Ssh(connection)
         }
+        CreateConnection::Aws { with_options } => {
+            let c = AwsConnectionOptionExtracted::try_from(with_options)?;
+            Connection::Aws(AwsConfig::try_from(c)?)
+        }
     };
     let name = scx.allocate_qualified_name(normalize::unresolved_object_name(name)?)?;
     let plan = CreateConnectionPlan {
@@ -3618,3 +3880,175 @@ pub fn plan_alter_secret(
 
     Ok(Plan::AlterSecret(AlterSecretPlan { id, secret_as }))
 }
+
+pub fn describe_alter_table(
+    _: &StatementContext,
+    _: AlterTableStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_table(
+    scx: &StatementContext,
+    stmt: AlterTableStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let AlterTableStatement {
+        name,
+        if_exists,
+        action,
+    } = stmt;
+    let noop = Plan::AlterNoop(AlterNoopPlan {
+        object_type: ObjectType::Table,
+    });
+    let name = normalize::unresolved_object_name(name)?;
+    let entry = match scx.catalog.resolve_item(&name) {
+        Ok(table) => table,
+        Err(_) if if_exists => {
+            // TODO: generate a notice indicating this table does not
+            // exist.
+            return Ok(noop);
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let full_name = scx.catalog.resolve_full_name(entry.name());
+    if entry.item_type() != CatalogItemType::Table {
+        sql_bail!("{} is a {} not a table", full_name, entry.item_type())
+    }
+    if entry.id().is_system() {
+        sql_bail!("cannot alter system table {}", full_name)
+    }
+
+    let parsed = match crate::parse::parse(entry.create_sql()) {
+        Ok(parsed) => parsed,
+        Err(e) => sql_bail!(
+            "[internal error] sql for existing table {} should parse: {}",
+            full_name,
+            e
+        ),
+    };
+    let create_stmt = match parsed.into_last() {
+        Statement::CreateTable(stmt) => stmt,
+        _ => sql_bail!(
+            "[internal error] sql for existing table {} should parse as a table",
+            full_name
+        ),
+    };
+    let (mut create_stmt, _) = names::resolve(scx.catalog, create_stmt)?;
+    let column_names: Vec<_> = create_stmt
+        .columns
+        .iter()
+        .map(|c| normalize::column_name(c.name.clone()))
+        .collect();
+
+    let column = match action {
+        AlterTableAction::AddColumn {
+            if_not_exists,
+            column,
+        } => {
+            let column_name = normalize::column_name(column.name.clone());
+            if column_names.contains(&column_name) {
+                if if_not_exists {
+                    // TODO: generate a notice indicating this column
+                    // already exists.
+                    return Ok(noop);
+                }
+                sql_bail!(
+                    "column {} of relation {} already exists",
+                    column_name.as_str().quoted(),
+                    full_name
+                )
+            }
+            create_stmt.columns.push(column);
+            None
+        }
+        AlterTableAction::DropColumn { if_exists, name } => {
+            let column_name = normalize::column_name(name);
+            let index = match column_names.iter().position(|c| *c == column_name) {
+                Some(index) => index,
+                None if if_exists => return Ok(noop),
+                None => sql_bail!(
+                    "column {} of relation {} does not exist",
+                    column_name.as_str().quoted(),
+                    full_name
+                ),
+            };
+            // Constraints declared as part of the column's definition are
+            // dropped along with it. Any other constraint that involves the
+            // column prevents it from being dropped.
+            let desc = entry.desc(&full_name)?;
+            let mut checks = vec![];
+            let mut depended_on = false;
+            for constraint in &create_stmt.constraints {
+                match constraint {
+                    TableConstraint::Unique { columns, .. } => {
+                        depended_on |= columns
+                            .iter()
+                            .any(|c| normalize::column_name(c.clone()) == column_name);
+                    }
+                    TableConstraint::Check { expr, .. } => checks.push(&**expr),
+                    TableConstraint::ForeignKey { .. } => (),
+                }
+            }
+            for (i, column) in create_stmt.columns.iter().enumerate() {
+                for option in &column.options {
+                    if let ColumnOption::Check(expr) = &option.option {
+                        if i != index {
+                            checks.push(expr);
+                        }
+                    }
+                }
+            }
+            for expr in checks {
+                let expr = query::plan_check_expr(scx, &desc, expr)?;
+                depended_on |= expr.support().contains(&index);
+            }
+            if depended_on {
+                sql_bail!(
+                    "cannot drop column {} of relation {} because a constraint depends on it",
+                    column_name.as_str().quoted(),
+                    full_name
+                )
+            }
+            create_stmt.columns.remove(index);
+            Some(index)
+        }
+    };
+
+    let (_, table) = plan_table(scx, create_stmt)?;
+
+    let column = match column {
+        None => {
+            // The existing rows of the table take the same value in the new
+            // column, so its constraints are checked against that value alone.
+            let index = table.desc.arity() - 1;
+            for check in &table.checks {
+                let support = check.expr.support();
+                if support.contains(&index) && support.len() > 1 {
+                    bail_unsupported!(
+                        "CHECK constraints on added columns that refer to other columns"
+                    )
+                }
+            }
+            let typ = table.desc.typ().column_types.last().expect("column added");
+            let default = table.defaults.last().expect("column added");
+            let default =
+                query::plan_default_expr(scx, default, &typ.scalar_type)?.lower_uncorrelated()?;
+            AlterTableColumn::Add { default }
+        }
+        Some(index) => AlterTableColumn::Drop { index },
+    };
+
+    // The dependencies of the table change if the added or dropped column
+    // has a user-defined type.
+    let parsed =
+        crate::parse::parse(&table.create_sql).expect("Sql for altered table should be valid sql");
+    let (_, depends_on) = names::resolve(scx.catalog, parsed.into_last())?;
+
+    Ok(Plan::AlterTable(AlterTablePlan {
+        id: entry.id(),
+        old_create_sql: entry.create_sql().into(),
+        table,
+        column,
+        depends_on: depends_on.into_iter().collect(),
+    }))
+}
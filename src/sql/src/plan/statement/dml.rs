@@ -14,13 +14,17 @@
 
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
 use mz_expr::MirRelationExpr;
 use mz_ore::collections::CollectionExt;
+use mz_ore::str::StrExt;
 use mz_pgcopy::{CopyCsvFormatParams, CopyFormatParams, CopyTextFormatParams};
 use mz_repr::adt::numeric::NumericMaxScale;
 use mz_repr::explain_new::{ExplainConfig, ExplainFormat};
-use mz_repr::{RelationDesc, ScalarType};
+use mz_repr::{GlobalId, RelationDesc, ScalarType};
+use mz_storage::copy::CopyLocation;
+use mz_storage::types::connections::Connection;
 
 use crate::ast::display::AstDisplay;
 use crate::ast::{
@@ -28,17 +32,17 @@ use crate::ast::{
     CreateRecordedViewStatement, CreateViewStatement, DeleteStatement, ExplainStageNew,
     ExplainStageOld, ExplainStatement, ExplainStatementNew, ExplainStatementOld, Explainee, Ident,
     InsertStatement, Query, SelectStatement, Statement, TailOption, TailOptionName, TailRelation,
-    TailStatement, UpdateStatement, ViewDefinition,
+    TailStatement, TruncateStatement, UpdateStatement, ViewDefinition,
 };
 use crate::catalog::CatalogItemType;
 use crate::names::{self, Aug, ResolvedObjectName};
 use crate::plan::query::QueryLifetime;
 use crate::plan::statement::{StatementContext, StatementDesc};
-use crate::plan::with_options::TryFromValue;
+use crate::plan::with_options::{self, TryFromValue};
 use crate::plan::{
-    query, CopyFormat, CopyFromPlan, ExplainPlan, ExplainPlanNew, ExplainPlanOld, InsertPlan,
-    MutationKind, Params, PeekPlan, Plan, PlanError, QueryContext, ReadThenWritePlan, TailFrom,
-    TailPlan,
+    query, CopyFormat, CopyFromPlan, CopyToPlan, ExplainPlan, ExplainPlanNew, ExplainPlanOld,
+    InsertPlan, MutationKind, OnConflictPlan, OnConflictUpdate, Params, PeekPlan, Plan, PlanError,
+    QueryContext, ReadThenWritePlan, TailFrom, TailPlan, TruncatePlan,
 };
 
 // TODO(benesch): currently, describing a `SELECT` or `INSERT` query
@@ -53,10 +57,12 @@ pub fn describe_insert(
         table_name,
         columns,
         source,
+        on_conflict,
         returning,
     }: InsertStatement<Aug>,
 ) -> Result<StatementDesc, PlanError> {
-    let (_, _, returning) = query::plan_insert_query(scx, table_name, columns, source, returning)?;
+    let (_, _, _, returning) =
+        query::plan_insert_query(scx, table_name, columns, source, on_conflict, returning)?;
     let desc = if returning.expr.is_empty() {
         None
     } else {
@@ -71,14 +77,48 @@ pub fn plan_insert(
         table_name,
         columns,
         source,
+        on_conflict,
         returning,
     }: InsertStatement<Aug>,
     params: &Params,
 ) -> Result<Plan, PlanError> {
-    let (id, mut expr, returning) =
-        query::plan_insert_query(scx, table_name, columns, source, returning)?;
+    let (id, mut expr, on_conflict, returning) =
+        query::plan_insert_query(scx, table_name, columns, source, on_conflict, returning)?;
     expr.bind_parameters(&params)?;
     let expr = expr.optimize_and_lower(&scx.into())?;
+    let on_conflict = match on_conflict {
+        Some(query::OnConflictPlan {
+            constraints,
+            update,
+        }) => {
+            let update = match update {
+                Some((assignments, selection)) => {
+                    let mut assignments_outer = HashMap::new();
+                    for (idx, mut set) in assignments {
+                        set.bind_parameters(&params)?;
+                        assignments_outer.insert(idx, set.lower_uncorrelated()?);
+                    }
+                    let selection = match selection {
+                        Some(mut selection) => {
+                            selection.bind_parameters(&params)?;
+                            Some(selection.lower_uncorrelated()?)
+                        }
+                        None => None,
+                    };
+                    Some(OnConflictUpdate {
+                        assignments: assignments_outer,
+                        selection,
+                    })
+                }
+                None => None,
+            };
+            Some(OnConflictPlan {
+                constraints,
+                update,
+            })
+        }
+        None => None,
+    };
     let returning = returning
         .expr
         .into_iter()
@@ -88,6 +128,7 @@ pub fn plan_insert(
     Ok(Plan::Insert(InsertPlan {
         id,
         values: expr,
+        on_conflict,
         returning,
     }))
 }
@@ -126,6 +167,40 @@ pub fn plan_update(
     plan_read_then_write(MutationKind::Update, scx, params, rtw_plan)
 }
 
+pub fn describe_truncate(
+    _: &StatementContext,
+    _: TruncateStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_truncate(
+    scx: &StatementContext,
+    TruncateStatement { table_names }: TruncateStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let mut ids = vec![];
+    for table_name in table_names {
+        let item = scx.get_item_by_resolved_name(&table_name)?;
+        if item.item_type() != CatalogItemType::Table {
+            sql_bail!(
+                "cannot truncate {} '{}'",
+                item.item_type(),
+                table_name.full_name_str()
+            );
+        }
+        if item.id().is_system() {
+            sql_bail!(
+                "cannot truncate system table '{}'",
+                table_name.full_name_str()
+            );
+        }
+        if !ids.contains(&item.id()) {
+            ids.push(item.id());
+        }
+    }
+    Ok(Plan::Truncate(TruncatePlan { ids }))
+}
+
 pub fn plan_read_then_write(
     kind: MutationKind,
     scx: &StatementContext,
@@ -152,6 +227,7 @@ pub fn plan_read_then_write(
         finishing,
         assignments: assignments_outer,
         kind,
+        on_conflict: None,
         returning: Vec::new(),
     }))
 }
@@ -169,7 +245,7 @@ pub fn plan_select(
     scx: &StatementContext,
     SelectStatement { query, as_of }: SelectStatement<Aug>,
     params: &Params,
-    copy_to: Option<CopyFormat>,
+    copy_to: Option<CopyFormatParams<'static>>,
 ) -> Result<Plan, PlanError> {
     let query::PlannedQuery {
         expr, finishing, ..
@@ -517,7 +593,7 @@ pub fn plan_tail(
         options,
         as_of,
     }: TailStatement<Aug>,
-    copy_to: Option<CopyFormat>,
+    copy_to: Option<CopyFormatParams<'static>>,
 ) -> Result<Plan, PlanError> {
     let from = match relation {
         TailRelation::Name(name) => {
@@ -597,9 +673,110 @@ fn plan_copy_from(
     scx: &StatementContext,
     table_name: ResolvedObjectName,
     columns: Vec<Ident>,
+    params: CopyFormatParams<'static>,
+    location: Option<CopyLocation>,
+) -> Result<Plan, PlanError> {
+    let (id, _, columns) = query::plan_copy_from(scx, table_name, columns)?;
+    Ok(Plan::CopyFrom(CopyFromPlan {
+        id,
+        columns,
+        params,
+        location,
+    }))
+}
+
+fn plan_copy_to(
+    scx: &StatementContext,
+    SelectStatement { query, as_of }: SelectStatement<Aug>,
+    params: CopyFormatParams<'static>,
+    location: CopyLocation,
+) -> Result<Plan, PlanError> {
+    let query::PlannedQuery {
+        expr,
+        desc,
+        finishing,
+        ..
+    } = plan_query(
+        scx,
+        query,
+        &Params::empty(),
+        QueryLifetime::OneShot(scx.pcx()?),
+    )?;
+    let when = query::plan_as_of(scx, as_of)?;
+    Ok(Plan::CopyTo(CopyToPlan {
+        peek: PeekPlan {
+            source: expr,
+            when,
+            finishing,
+            copy_to: None,
+        },
+        desc,
+        params,
+        location,
+    }))
+}
+
+/// Plans the location of a `COPY` that reads from or writes to somewhere other
+/// than the client.
+///
+/// Locations starting with `s3://` refer to objects in S3 and must be
+/// accompanied by an AWS connection. All other locations are absolute paths on
+/// the filesystem of the machine running Materialize.
+fn plan_copy_location(
+    scx: &StatementContext,
+    direction: &CopyDirection,
+    location: &str,
+    connection: Option<with_options::Object>,
+) -> Result<CopyLocation, PlanError> {
+    match location.strip_prefix("s3://") {
+        Some(path) => {
+            let (bucket, key) = path.split_once('/').unwrap_or((path, ""));
+            if bucket.is_empty() {
+                sql_bail!("invalid S3 location {}: missing bucket", location.quoted());
+            }
+            if *direction == CopyDirection::To && (key.is_empty() || key.ends_with('/')) {
+                sql_bail!(
+                    "invalid S3 location {}: COPY TO requires an object key",
+                    location.quoted()
+                );
+            }
+            let item = match connection {
+                Some(connection) => scx.get_item(&GlobalId::from(connection)),
+                None => sql_bail!("COPY to or from S3 requires a CONNECTION"),
+            };
+            let aws = match item.connection()? {
+                Connection::Aws(aws) => aws.clone(),
+                _ => sql_bail!(
+                    "{} is not an AWS connection",
+                    scx.catalog.resolve_full_name(item.name())
+                ),
+            };
+            Ok(CopyLocation::S3 {
+                bucket: bucket.into(),
+                key: key.into(),
+                aws,
+            })
+        }
+        None => {
+            scx.require_unsafe_mode("COPY to or from a file")?;
+            if connection.is_some() {
+                sql_bail!("COPY to or from a file does not support CONNECTION");
+            }
+            let path = PathBuf::from(location);
+            if !path.is_absolute() {
+                sql_bail!("relative path not allowed for COPY to or from a file");
+            }
+            Ok(CopyLocation::File(path))
+        }
+    }
+}
+
+/// Validates the options of a `COPY` statement against `format`, following
+/// the rules PostgreSQL applies to both directions.
+fn plan_copy_params(
     format: CopyFormat,
     options: CopyOptionExtracted,
-) -> Result<Plan, PlanError> {
+) -> Result<CopyFormatParams<'static>, PlanError> {
     fn only_available_with_csv<T>(option: Option<T>, param: &str) -> Result<(), PlanError> {
         match option {
             Some(_) => sql_bail!("COPY {} available only in CSV mode", param),
@@ -619,23 +796,36 @@ fn plan_copy_from(
         }
     }
 
+    fn check_delimiter_and_null(delimiter: u8, null: &str) -> Result<(), PlanError> {
+        if delimiter == b'\n' || delimiter == b'\r' {
+            sql_bail!("COPY delimiter cannot be newline or carriage return");
+        }
+        if null.contains(&['\n', '\r'][..]) {
+            sql_bail!("COPY null representation cannot use newline or carriage return");
+        }
+        if null.as_bytes().contains(&delimiter) {
+            sql_bail!("COPY delimiter must not appear in the NULL specification");
+        }
+        Ok(())
+    }
+
     let params = match format {
         CopyFormat::Text => {
             only_available_with_csv(options.quote, "quote")?;
             only_available_with_csv(options.escape, "escape")?;
             only_available_with_csv(options.header, "HEADER")?;
-            let delimiter = match options.delimiter {
-                Some(delimiter) if delimiter.len() > 1 => {
-                    sql_bail!("COPY delimiter must be a single one-byte character");
-                }
-                Some(delimiter) => Cow::from(delimiter),
-                None => Cow::from("\t"),
-            };
-            let null = match options.null {
-                Some(null) => Cow::from(null),
-                None => Cow::from("\\N"),
-            };
-            CopyFormatParams::Text(CopyTextFormatParams { null, delimiter })
+            let delimiter = extract_byte_param_value(options.delimiter, b'\t', "delimiter")?;
+            let null = options.null.unwrap_or_else(|| "\\N".into());
+            check_delimiter_and_null(delimiter, &null)?;
+            // Backslashes, lowercase letters and digits have special meanings
+            // in the text format.
+            if b"\\.abcdefghijklmnopqrstuvwxyz0123456789".contains(&delimiter) {
+                sql_bail!("COPY delimiter cannot be \"{}\"", char::from(delimiter));
+            }
+            CopyFormatParams::Text(CopyTextFormatParams {
+                null: Cow::from(null),
+                delimiter: Cow::from(char::from(delimiter).to_string()),
+            })
         }
         CopyFormat::Csv => {
             let quote = extract_byte_param_value(options.quote, b'"', "quote")?;
@@ -645,27 +835,33 @@ fn plan_copy_from(
             if delimiter == quote {
                 sql_bail!("COPY delimiter and quote must be different");
             }
-            let null = match options.null {
-                Some(null) => Cow::from(null),
-                None => Cow::from(""),
-            };
+            let null = options.null.unwrap_or_default();
+            check_delimiter_and_null(delimiter, &null)?;
+            if null.as_bytes().contains(&quote) {
+                sql_bail!("CSV quote character must not appear in the NULL specification");
+            }
             CopyFormatParams::Csv(CopyCsvFormatParams {
                 delimiter,
                 quote,
                 escape,
-                null,
+                null: Cow::from(null),
                 header,
             })
         }
-        CopyFormat::Binary => bail_unsupported!("FORMAT BINARY"),
+        CopyFormat::Binary => {
+            if options.delimiter.is_some() {
+                sql_bail!("cannot specify DELIMITER in BINARY mode");
+            }
+            if options.null.is_some() {
+                sql_bail!("cannot specify NULL in BINARY mode");
+            }
+            only_available_with_csv(options.quote, "quote")?;
+            only_available_with_csv(options.escape, "escape")?;
+            only_available_with_csv(options.header, "HEADER")?;
+            CopyFormatParams::Binary
+        }
     };
-
-    let (id, _, columns) = query::plan_copy_from(scx, table_name, columns)?;
-    Ok(Plan::CopyFrom(CopyFromPlan {
-        id,
-        columns,
-        params,
-    }))
+    Ok(params)
 }
 
 generate_extracted_config!(
@@ -675,7 +871,8 @@ generate_extracted_config!(
     (Null, String),
     (Escape, String),
     (Quote, String),
-    (Header, bool)
+    (Header, bool),
+    (Connection, with_options::Object)
 );
 
 pub fn plan_copy(
@@ -687,32 +884,44 @@ pub fn plan_copy(
         options,
     }: CopyStatement<Aug>,
 ) -> Result<Plan, PlanError> {
-    let options = CopyOptionExtracted::try_from(options)?;
+    let mut options = CopyOptionExtracted::try_from(options)?;
+    let connection = options.connection.take();
     let format = match options.format.to_lowercase().as_str() {
         "text" => CopyFormat::Text,
         "csv" => CopyFormat::Csv,
         "binary" => CopyFormat::Binary,
         _ => sql_bail!("unknown FORMAT: {}", options.format),
     };
-    if let CopyDirection::To = direction {
-        if options.delimiter.is_some() {
-            sql_bail!("COPY TO does not support DELIMITER option yet");
-        }
-        if options.null.is_some() {
-            sql_bail!("COPY TO does not support NULL option yet");
-        }
+    let params = plan_copy_params(format, options)?;
+    if connection.is_some() && !matches!(target, CopyTarget::Location(_)) {
+        sql_bail!("COPY {} {} does not support CONNECTION", direction, target);
     }
     match (&direction, &target) {
         (CopyDirection::To, CopyTarget::Stdout) => match relation {
             CopyRelation::Table { .. } => sql_bail!("table with COPY TO unsupported"),
             CopyRelation::Select(stmt) => {
-                Ok(plan_select(scx, stmt, &Params::empty(), Some(format))?)
+                Ok(plan_select(scx, stmt, &Params::empty(), Some(params))?)
             }
-            CopyRelation::Tail(stmt) => Ok(plan_tail(scx, stmt, Some(format))?),
+            CopyRelation::Tail(stmt) => Ok(plan_tail(scx, stmt, Some(params))?),
+        },
+        (CopyDirection::To, CopyTarget::Location(location)) => match relation {
+            CopyRelation::Table { .. } => sql_bail!("table with COPY TO unsupported"),
+            CopyRelation::Select(stmt) => {
+                let location = plan_copy_location(scx, &direction, location, connection)?;
+                plan_copy_to(scx, stmt, params, location)
+            }
+            CopyRelation::Tail(_) => sql_bail!("COPY (TAIL ...) TO {} not supported", target),
         },
         (CopyDirection::From, CopyTarget::Stdin) => match relation {
             CopyRelation::Table { name, columns } => {
-                plan_copy_from(scx, name, columns, format, options)
+                plan_copy_from(scx, name, columns, params, None)
+            }
+            _ => sql_bail!("COPY FROM {} not supported", target),
+        },
+        (CopyDirection::From, CopyTarget::Location(location)) => match relation {
+            CopyRelation::Table { name, columns } => {
+                let location = plan_copy_location(scx, &direction, location, connection)?;
+                plan_copy_from(scx, name, columns, params, Some(location))
             }
             _ => sql_bail!("COPY FROM {} not supported", target),
         },
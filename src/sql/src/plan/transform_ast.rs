@@ -20,7 +20,7 @@ use mz_ore::stack::{CheckedRecursion, RecursionGuard};
 use mz_sql_parser::ast::visit_mut::{self, VisitMut};
 use mz_sql_parser::ast::{
     Expr, Function, FunctionArgs, Ident, Op, OrderByExpr, Query, Select, SelectItem, TableAlias,
-    TableFactor, TableFunction, TableWithJoins, UnresolvedObjectName, Value,
+    TableFactor, TableFunction, TableWithJoins, UnresolvedObjectName, Value, WindowSpec,
 };
 
 use crate::normalize;
@@ -73,6 +73,9 @@ where
 //   * Rewrites the suite of standard deviation and variance functions in a
 //     manner similar to `avg`.
 //
+// The aggregate rewrites preserve any `OVER` clause, so that e.g.
+// `avg(col) OVER (...)` becomes `sum(col) OVER (...) / count(col) OVER (...)`.
+//
 // TODO(sploiselle): rewrite these in terms of func::sql_op!
 struct FuncRewriter<'a> {
     scx: &'a StatementContext<'a>,
@@ -104,6 +107,7 @@ impl<'a> FuncRewriter<'a> {
         order_by: Vec<OrderByExpr<Aug>>,
         filter: Option<Box<Expr<Aug>>>,
         distinct: bool,
+        over: Option<WindowSpec<Aug>>,
     ) -> Expr<Aug> {
         Expr::Function(Function {
             name,
@@ -112,18 +116,24 @@ impl<'a> FuncRewriter<'a> {
                 order_by,
             },
             filter,
-            over: None,
+            over,
             distinct,
         })
     }
 
-    fn plan_avg(expr: Expr<Aug>, filter: Option<Box<Expr<Aug>>>, distinct: bool) -> Expr<Aug> {
+    fn plan_avg(
+        expr: Expr<Aug>,
+        filter: Option<Box<Expr<Aug>>>,
+        distinct: bool,
+        over: Option<WindowSpec<Aug>>,
+    ) -> Expr<Aug> {
         let sum = Self::plan_agg(
             UnresolvedObjectName::qualified(&["pg_catalog", "sum"]),
             expr.clone(),
             vec![],
             filter.clone(),
             distinct,
+            over.clone(),
         )
         .call_unary(vec!["mz_internal", "mz_avg_promotion"]);
         let count = Self::plan_agg(
@@ -132,6 +142,7 @@ impl<'a> FuncRewriter<'a> {
             vec![],
             filter,
             distinct,
+            over,
         );
         Self::plan_divide(sum, count)
     }
@@ -140,6 +151,7 @@ impl<'a> FuncRewriter<'a> {
         expr: Expr<Aug>,
         filter: Option<Box<Expr<Aug>>>,
         distinct: bool,
+        over: Option<WindowSpec<Aug>>,
         sample: bool,
     ) -> Expr<Aug> {
         // N.B. this variance calculation uses the "textbook" algorithm, which
@@ -164,6 +176,7 @@ impl<'a> FuncRewriter<'a> {
             vec![],
             filter.clone(),
             distinct,
+            over.clone(),
         );
         let sum = Self::plan_agg(
             UnresolvedObjectName::qualified(&["pg_catalog", "sum"]),
@@ -171,6 +184,7 @@ impl<'a> FuncRewriter<'a> {
             vec![],
             filter.clone(),
             distinct,
+            over.clone(),
         );
         let sum_squared = sum.clone().multiply(sum);
         let count = Self::plan_agg(
@@ -179,6 +193,7 @@ impl<'a> FuncRewriter<'a> {
             vec![],
             filter,
             distinct,
+            over,
         );
         Self::plan_divide(
             sum_squares.minus(Self::plan_divide(sum_squared, count.clone())),
@@ -194,9 +209,10 @@ impl<'a> FuncRewriter<'a> {
         expr: Expr<Aug>,
         filter: Option<Box<Expr<Aug>>>,
         distinct: bool,
+        over: Option<WindowSpec<Aug>>,
         sample: bool,
     ) -> Expr<Aug> {
-        Self::plan_variance(expr, filter, distinct, sample).call_unary(vec!["sqrt"])
+        Self::plan_variance(expr, filter, distinct, over, sample).call_unary(vec!["sqrt"])
     }
 
     fn rewrite_expr(&mut self, expr: &Expr<Aug>) -> Option<(Ident, Expr<Aug>)> {
@@ -206,7 +222,7 @@ impl<'a> FuncRewriter<'a> {
                 args: FunctionArgs::Args { args, order_by: _ },
                 filter,
                 distinct,
-                over: None,
+                over,
             }) => {
                 let name = normalize::unresolved_object_name(name.clone()).ok()?;
                 if let Some(database) = &name.database {
@@ -222,17 +238,22 @@ impl<'a> FuncRewriter<'a> {
                 }
                 let filter = filter.clone();
                 let distinct = *distinct;
+                let over = over.clone();
                 let expr = if args.len() == 1 {
                     let arg = args[0].clone();
                     match name.item.as_str() {
-                        "avg" => Self::plan_avg(arg, filter, distinct),
-                        "variance" | "var_samp" => Self::plan_variance(arg, filter, distinct, true),
-                        "var_pop" => Self::plan_variance(arg, filter, distinct, false),
-                        "stddev" | "stddev_samp" => Self::plan_stddev(arg, filter, distinct, true),
-                        "stddev_pop" => Self::plan_stddev(arg, filter, distinct, false),
+                        "avg" => Self::plan_avg(arg, filter, distinct, over),
+                        "variance" | "var_samp" => {
+                            Self::plan_variance(arg, filter, distinct, over, true)
+                        }
+                        "var_pop" => Self::plan_variance(arg, filter, distinct, over, false),
+                        "stddev" | "stddev_samp" => {
+                            Self::plan_stddev(arg, filter, distinct, over, true)
+                        }
+                        "stddev_pop" => Self::plan_stddev(arg, filter, distinct, over, false),
                         _ => return None,
                     }
-                } else if args.len() == 2 {
+                } else if args.len() == 2 && over.is_none() {
                     let (lhs, rhs) = (args[0].clone(), args[1].clone());
                     match name.item.as_str() {
                         "mod" => lhs.modulo(rhs),
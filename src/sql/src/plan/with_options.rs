@@ -52,6 +52,32 @@ impl ImpliedValue for Secret {
     }
 }
 
+/// A reference to a catalog object, like the connection in
+/// `COPY ... WITH (CONNECTION conn)`.
+#[derive(Copy, Clone, Debug)]
+pub struct Object(GlobalId);
+
+impl From<Object> for GlobalId {
+    fn from(object: Object) -> Self {
+        object.0
+    }
+}
+
+impl TryFromValue<WithOptionValue<Aug>> for Object {
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        match v {
+            WithOptionValue::Object(ResolvedObjectName::Object { id, .. }) => Ok(Object(id)),
+            _ => sql_bail!("must provide an object name"),
+        }
+    }
+}
+
+impl ImpliedValue for Object {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide an object name")
+    }
+}
+
 impl TryFromValue<WithOptionValue<Aug>> for StringOrSecret {
     fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
         Ok(match v {
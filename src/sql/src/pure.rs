@@ -79,7 +79,10 @@ pub async fn purify_create_source(
 
     match connection {
         CreateSourceConnection::Kafka(KafkaSourceConnection {
-            connection, topic, ..
+            connection,
+            topic,
+            start_timestamp,
+            ..
         }) => {
             // Extract any/all configuration options
             let mut connection_options = kafka_util::extract_config(&mut with_options_map)?;
@@ -116,33 +119,50 @@ pub async fn purify_create_source(
             .await
             .map_err(|e| anyhow!("Failed to create and connect Kafka consumer: {}", e))?;
 
-            // Translate `kafka_time_offset` to `start_offset`.
-            match kafka_util::lookup_start_offsets(
-                Arc::clone(&consumer),
-                &topic,
-                &with_options_map,
-                now,
-            )
-            .await?
-            {
-                Some(start_offsets) => {
-                    // Drop `kafka_time_offset`
-                    with_options.retain(|val| match val {
-                        WithOption { key, .. } => key.as_str() != "kafka_time_offset",
-                    });
-                    info!("add start_offset {:?}", start_offsets);
-                    // Add `start_offset`
-                    with_options.push(WithOption {
-                        key: Ident::new("start_offset"),
-                        value: Some(WithOptionValue::Value(Value::Array(
-                            start_offsets
-                                .iter()
-                                .map(|offset| Value::Number(offset.to_string()))
-                                .collect(),
-                        ))),
-                    });
+            // Translate `START TIMESTAMP` or `kafka_time_offset` to
+            // `start_offset`, so that the catalog records the offsets of each
+            // partition and restarts start from the same ones.
+            let time_offset = match (
+                start_timestamp.take(),
+                with_options_map.get("kafka_time_offset").cloned(),
+            ) {
+                (None, None) => None,
+                (Some(_), Some(_)) => {
+                    bail!(
+                        "`START TIMESTAMP` and `kafka_time_offset` cannot be set at the same time."
+                    )
                 }
-                None => {}
+                (Some(value), None) => Some(("START TIMESTAMP", value)),
+                (None, Some(value)) => match value.into() {
+                    Some(value) => Some(("kafka_time_offset", value)),
+                    None => bail!("`kafka_time_offset` must be a number or an interval"),
+                },
+            };
+            if let Some((name, time_offset)) = time_offset {
+                let start_offsets = kafka_util::lookup_start_offsets(
+                    Arc::clone(&consumer),
+                    &topic,
+                    name,
+                    time_offset,
+                    &with_options_map,
+                    now,
+                )
+                .await?;
+                // Drop `kafka_time_offset`
+                with_options.retain(|val| match val {
+                    WithOption { key, .. } => key.as_str() != "kafka_time_offset",
+                });
+                info!("add start_offset {:?}", start_offsets);
+                // Add `start_offset`
+                with_options.push(WithOption {
+                    key: Ident::new("start_offset"),
+                    value: Some(WithOptionValue::Value(Value::Array(
+                        start_offsets
+                            .iter()
+                            .map(|offset| Value::Number(offset.to_string()))
+                            .collect(),
+                    ))),
+                });
             }
         }
         CreateSourceConnection::S3 { .. } => {
@@ -267,10 +287,14 @@ async fn purify_source_format_single(
             }
         },
         Format::Protobuf(schema) => match schema {
-            ProtobufSchema::Csr { csr_connection } => {
+            ProtobufSchema::Csr {
+                message_name,
+                csr_connection,
+            } => {
                 purify_csr_connection_proto(
                     catalog,
                     connection,
+                    message_name.as_deref(),
                     csr_connection,
                     envelope,
                     connection_context,
@@ -312,6 +336,7 @@ async fn purify_source_format_single(
 async fn purify_csr_connection_proto(
     catalog: &dyn SessionCatalog,
     connection: &mut CreateSourceConnection<Aug>,
+    message_name: Option<&str>,
     csr_connection: &mut CsrConnectionProtobuf<Aug>,
     envelope: &Option<Envelope<Aug>>,
     connection_context: &ConnectionContext,
@@ -349,8 +374,9 @@ async fn purify_csr_connection_proto(
                 .connect(&*connection_context.secrets_reader)
                 .await?;
 
-            let value = compile_proto(&format!("{}-value", topic), &ccsr_client).await?;
-            let key = compile_proto(&format!("{}-key", topic), &ccsr_client)
+            let value =
+                compile_proto(&format!("{}-value", topic), &ccsr_client, message_name).await?;
+            let key = compile_proto(&format!("{}-key", topic), &ccsr_client, None)
                 .await
                 .ok();
 
@@ -520,6 +546,7 @@ async fn get_remote_csr_schema(
 async fn compile_proto(
     subject_name: &String,
     ccsr_client: &Client,
+    message_name: Option<&str>,
 ) -> Result<CsrSeedProtobufSchema, anyhow::Error> {
     let (primary_subject, dependency_subjects) =
         ccsr_client.get_subject_and_references(subject_name).await?;
@@ -537,12 +564,24 @@ async fn compile_proto(
         .as_mut()
         .build_file_descriptor_set(&[Path::new(&primary_subject.name)])?;
 
-    // Ensure there is exactly one message in the file.
+    // Select the message to decode. Unless the user explicitly named a
+    // message, we follow the Confluent serializers and use the first message
+    // in the file.
     let primary_fd = fds.file(0);
-    let message_name = match primary_fd.message_type_size() {
-        1 => String::from_utf8_lossy(primary_fd.message_type(0).name()).into_owned(),
-        0 => bail_unsupported!(9598, "Protobuf schemas with no messages"),
-        _ => bail_unsupported!(9598, "Protobuf schemas with multiple messages"),
+    let message_name = match message_name {
+        Some(message_name) => message_name.to_owned(),
+        None => {
+            if primary_fd.message_type_size() == 0 {
+                bail_unsupported!(9598, "Protobuf schemas with no messages");
+            }
+            let package = String::from_utf8_lossy(primary_fd.package());
+            let name = String::from_utf8_lossy(primary_fd.message_type(0).name());
+            if package.is_empty() {
+                format!(".{}", name)
+            } else {
+                format!(".{}.{}", package, name)
+            }
+        }
     };
 
     // Encode the file descriptor set into a SQL byte string.
@@ -36,7 +36,7 @@ use crate::names::{
     Aug, DatabaseId, FullObjectName, ObjectQualifiers, PartialObjectName, QualifiedObjectName,
     RawDatabaseSpecifier, ResolvedDatabaseSpecifier, SchemaId, SchemaSpecifier,
 };
-use crate::plan::StatementDesc;
+use crate::plan::{StatementDesc, UniqueConstraint};
 use crate::DEFAULT_SCHEMA;
 
 static DUMMY_CONFIG: Lazy<CatalogConfig> = Lazy::new(|| CatalogConfig {
@@ -136,6 +136,10 @@ impl CatalogItem for TestCatalogItem {
         unimplemented!()
     }
 
+    fn table_unique_constraints(&self) -> Option<&[UniqueConstraint]> {
+        unimplemented!()
+    }
+
     fn type_details(&self) -> Option<&CatalogTypeDetails<IdReference>> {
         unimplemented!()
     }
@@ -20,13 +20,14 @@
 //! Eventually, the source is dropped with either `drop_sources()` or by allowing compaction to the
 //! empty frontier.
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error;
 use std::fmt;
 use std::fmt::Debug;
 use std::str::FromStr;
 use std::sync::Arc;
 
+use anyhow::anyhow;
 use async_trait::async_trait;
 use bytes::BufMut;
 use derivative::Derivative;
@@ -48,11 +49,13 @@ use mz_expr::PartitionId;
 use mz_orchestrator::NamespacedOrchestrator;
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::{
-    read::ReadHandle, write::WriteHandle, PersistClient, PersistLocation, ShardId,
+    read::{ReadFilter, ReadHandle},
+    write::WriteHandle,
+    PartCompression, PersistClient, PersistLocation, ShardId,
 };
 use mz_persist_types::{Codec, Codec64};
-use mz_proto::{ProtoType, RustType, TryFromProtoError};
-use mz_repr::{Diff, GlobalId, RelationDesc, Row};
+use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use mz_repr::{ColumnType, Datum, Diff, GlobalId, RelationDesc, RelationType, Row};
 use mz_stash::{self, StashError, TypedCollection};
 
 use crate::controller::hosts::{StorageHosts, StorageHostsConfig};
@@ -60,8 +63,11 @@ use crate::protocol::client::{
     IngestSourceCommand, ProtoStorageCommand, ProtoStorageResponse, StorageCommand,
     StorageResponse, Update,
 };
+use crate::source::persist_source::KeyPartFilter;
 use crate::types::errors::DataflowError;
-use crate::types::sources::{IngestionDescription, MzOffset, SourceData, SourceEnvelope};
+use crate::types::sources::{
+    IngestionDescription, MzOffset, SourceData, SourceDataSchema, SourceEnvelope,
+};
 
 mod hosts;
 mod rehydration;
@@ -83,6 +89,9 @@ pub struct CollectionDescription {
     /// If `None`, the controller manages the lifetime of the `storaged`
     /// process.
     pub remote_addr: Option<String>,
+    /// How the rows of this collection are stored in its shard, if they are
+    /// not stored as is. See [`ColumnLayout`].
+    pub layout: Option<ColumnLayout>,
 }
 
 #[async_trait(?Send)]
@@ -122,6 +131,41 @@ pub trait StorageController: Debug + Send {
         commands: Vec<(GlobalId, Vec<Update<Self::Timestamp>>, Self::Timestamp)>,
     ) -> Result<(), StorageError>;
 
+    /// Like [`StorageController::append`], but additionally retracts the entire
+    /// contents of each local input named in `truncations` at the associated
+    /// time.
+    ///
+    /// The retractions are computed from the contents of the local input just
+    /// before its current upper and appended in the same batch as the updates
+    /// in `commands`, which must name every local input in `truncations`. The
+    /// contents are streamed into the batch, so they need not fit in memory.
+    async fn append_with_truncations(
+        &mut self,
+        commands: Vec<(GlobalId, Vec<Update<Self::Timestamp>>, Self::Timestamp)>,
+        truncations: Vec<(GlobalId, Self::Timestamp)>,
+    ) -> Result<(), StorageError>;
+
+    /// Replaces the schema of the local input named `id` with `desc`, whose
+    /// rows are stored in its existing shard according to `layout`.
+    ///
+    /// The contents of the shard are not rewritten. Appends and snapshots
+    /// issued from now on, as well as the metadata handed out for new
+    /// dataflows, use `layout`, while dataflows that are already reading the
+    /// local input continue to read it with its previous layout. Local inputs
+    /// that may be altered must therefore be created with a layout, even if
+    /// they store their rows as is, as readers without one would see the
+    /// stored rows, including the columns added by later alterations.
+    ///
+    /// This only updates the controller's in-memory state. The caller is
+    /// responsible for durably recording `desc` and `layout` and for passing
+    /// them to [`StorageController::create_collections`] after a restart.
+    async fn alter_collection(
+        &mut self,
+        id: GlobalId,
+        desc: RelationDesc,
+        layout: ColumnLayout,
+    ) -> Result<(), StorageError>;
+
     /// Returns the snapshot of the contents of the local input named `id` at `as_of`.
     async fn snapshot(
         &mut self,
@@ -129,6 +173,31 @@ pub trait StorageController: Debug + Send {
         as_of: Self::Timestamp,
     ) -> Result<Vec<(Row, Diff)>, StorageError>;
 
+    /// Returns the number of rows in the local input named `id` at `as_of`.
+    ///
+    /// Unlike [`StorageController::snapshot`], this does not hold the contents
+    /// of the input in memory.
+    async fn snapshot_count(
+        &mut self,
+        id: GlobalId,
+        as_of: Self::Timestamp,
+    ) -> Result<Diff, StorageError>;
+
+    /// Returns the rows of the local input named `id` at `as_of` that match one
+    /// of `lookups`.
+    ///
+    /// Each lookup is a list of columns of the input and a list of keys, and a
+    /// row matches it if its values in those columns are one of the keys. Keys
+    /// with nulls match no rows. Unlike [`StorageController::snapshot`], this
+    /// skips the parts of the input that cannot contain matching rows, where
+    /// their statistics tell, and does not hold other rows in memory.
+    async fn lookup(
+        &mut self,
+        id: GlobalId,
+        as_of: Self::Timestamp,
+        lookups: &[(Vec<usize>, Vec<Row>)],
+    ) -> Result<Vec<(Row, Diff)>, StorageError>;
+
     /// Assigns a read policy to specific identifiers.
     ///
     /// The policies are assigned in the order presented, and repeated identifiers should
@@ -239,7 +308,7 @@ impl<T: Timestamp> ReadPolicy<T> {
 }
 
 /// Metadata required by a storage instance to read a storage collection
-#[derive(Arbitrary, Clone, Debug, PartialEq, PartialOrd, Ord, Eq, Serialize, Deserialize)]
+#[derive(Arbitrary, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CollectionMetadata {
     /// The persist location where the shards are located
     pub persist_location: PersistLocation,
@@ -247,6 +316,9 @@ pub struct CollectionMetadata {
     pub remap_shard: ShardId,
     /// The persist shard containing the contents of this storage collection
     pub data_shard: ShardId,
+    /// How the rows of this storage collection are stored in `data_shard`, if
+    /// they are not stored as is
+    pub layout: Option<ColumnLayout>,
 }
 
 impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
@@ -256,6 +328,7 @@ impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
             consensus_uri: self.persist_location.consensus_uri.clone(),
             data_shard: self.data_shard.to_string(),
             remap_shard: self.remap_shard.to_string(),
+            layout: self.layout.into_proto(),
         }
     }
 
@@ -273,6 +346,7 @@ impl RustType<ProtoCollectionMetadata> for CollectionMetadata {
                 .data_shard
                 .parse()
                 .map_err(TryFromProtoError::InvalidShardId)?,
+            layout: value.layout.into_rust()?,
         })
     }
 }
@@ -294,6 +368,114 @@ impl Codec for CollectionMetadata {
     }
 }
 
+/// How the rows of a collection are stored in its persist shard, if not as is.
+///
+/// Altering a table leaves the rows in its shard untouched. Instead, the
+/// columns of the shard are only ever appended to: adding a column to the
+/// table adds one to the end of the shard's columns, and dropping a column
+/// from the table leaves it in place in the shard, where rows written from
+/// then on store null. Rows written before a column was added don't store it
+/// at all, and read as the column's entry in [`ColumnLayout::missing`].
+#[derive(Arbitrary, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnLayout {
+    /// The type of the columns of the shard.
+    pub typ: RelationType,
+    /// The column of the shard that stores each column of the collection.
+    pub columns: Vec<usize>,
+    /// The value of each column of the shard in rows that were written before
+    /// the column was added.
+    pub missing: Row,
+}
+
+impl ColumnLayout {
+    /// Returns the layout of a collection of the given type whose rows are
+    /// stored as is.
+    pub fn identity(typ: &RelationType) -> Self {
+        ColumnLayout {
+            typ: typ.clone(),
+            columns: (0..typ.arity()).collect(),
+            missing: Row::pack(std::iter::repeat(Datum::Null).take(typ.arity())),
+        }
+    }
+
+    /// Returns the layout after a column of type `typ` is appended to the
+    /// collection, whose value in the rows written so far is `default`.
+    pub fn add_column(&self, typ: ColumnType, default: Datum) -> Self {
+        let mut layout = self.clone();
+        layout.columns.push(layout.typ.arity());
+        layout.typ.column_types.push(typ);
+        layout.missing = Row::pack(self.missing.iter().chain(std::iter::once(default)));
+        layout
+    }
+
+    /// Returns the layout after the column at `index` is removed from the
+    /// collection.
+    pub fn drop_column(&self, index: usize) -> Self {
+        let mut layout = self.clone();
+        let column = layout.columns.remove(index);
+        // From now on, rows store null in the column.
+        layout.typ.column_types[column].nullable = true;
+        layout.typ.keys.retain(|key| !key.contains(&column));
+        layout
+    }
+
+    /// Converts a row of the collection into the row to store in the shard.
+    pub fn to_stored(&self, row: &Row) -> Row {
+        let mut datums = vec![Datum::Null; self.typ.arity()];
+        for (column, datum) in self.columns.iter().zip(row.iter()) {
+            datums[*column] = datum;
+        }
+        Row::pack_slice(&datums)
+    }
+
+    /// Converts a row stored in the shard into a row of the collection.
+    pub fn from_stored(&self, row: &Row) -> Row {
+        let stored = row.unpack();
+        let missing = self.missing.unpack();
+        Row::pack(self.columns.iter().map(|column| match stored.get(*column) {
+            Some(datum) => *datum,
+            None => missing[*column],
+        }))
+    }
+}
+
+impl RustType<ProtoColumnLayout> for ColumnLayout {
+    fn into_proto(&self) -> ProtoColumnLayout {
+        ProtoColumnLayout {
+            typ: Some(self.typ.into_proto()),
+            columns: self.columns.into_proto(),
+            missing: Some(self.missing.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoColumnLayout) -> Result<Self, TryFromProtoError> {
+        Ok(ColumnLayout {
+            typ: proto.typ.into_rust_if_some("ProtoColumnLayout::typ")?,
+            columns: proto.columns.into_rust()?,
+            missing: proto
+                .missing
+                .into_rust_if_some("ProtoColumnLayout::missing")?,
+        })
+    }
+}
+
+impl Codec for ColumnLayout {
+    fn codec_name() -> String {
+        "protobuf[ColumnLayout]".into()
+    }
+
+    fn encode<B: BufMut>(&self, buf: &mut B) {
+        self.into_proto()
+            .encode(buf)
+            .expect("no required fields means no initialization errors");
+    }
+
+    fn decode(buf: &[u8]) -> Result<Self, String> {
+        let proto = ProtoColumnLayout::decode(buf).map_err(|err| err.to_string())?;
+        proto.into_rust().map_err(|err| err.to_string())
+    }
+}
+
 /// Controller state maintained for each storage instance.
 #[derive(Debug)]
 pub struct StorageControllerState<
@@ -483,14 +665,30 @@ where
                 persist_location: self.persist_location.clone(),
                 data_shard: ShardId::new(),
                 remap_shard: ShardId::new(),
+                layout: None,
             };
             let metadata = METADATA_COLLECTION
                 .insert_without_overwrite(&mut self.state.stash, &id, metadata)
                 .await?;
+            // The layout is owned by whoever describes the collection, and may
+            // have changed since the metadata was stashed.
+            let metadata = CollectionMetadata {
+                layout: description.layout.clone(),
+                ..metadata
+            };
 
-            let (write, read) = self
+            let key_schema = Arc::new(match &description.layout {
+                Some(layout) => SourceDataSchema::new(&layout.typ),
+                None => SourceDataSchema::new(description.desc.typ()),
+            });
+            let write = self
                 .persist_client
-                .open(metadata.data_shard)
+                .open_writer_with_schema::<SourceData, (), T, Diff>(metadata.data_shard, key_schema)
+                .await
+                .expect("invalid persist usage");
+            let read = self
+                .persist_client
+                .open_reader(metadata.data_shard)
                 .await
                 .expect("invalid persist usage");
 
@@ -583,6 +781,22 @@ where
         &mut self,
         commands: Vec<(GlobalId, Vec<Update<Self::Timestamp>>, Self::Timestamp)>,
     ) -> Result<(), StorageError> {
+        self.append_with_truncations(commands, vec![]).await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn append_with_truncations(
+        &mut self,
+        commands: Vec<(GlobalId, Vec<Update<Self::Timestamp>>, Self::Timestamp)>,
+        truncations: Vec<(GlobalId, Self::Timestamp)>,
+    ) -> Result<(), StorageError> {
+        let truncations: HashMap<_, _> = truncations.into_iter().collect();
+        for id in truncations.keys() {
+            if !commands.iter().any(|(command_id, _, _)| command_id == id) {
+                return Err(StorageError::IdentifierMissing(*id));
+            }
+        }
+
         // TODO(petrosagg): validate appends against the expected RelationDesc of the collection
         let mut updates_by_id = HashMap::new();
 
@@ -623,19 +837,82 @@ where
 
             let new_upper = Antichain::from_elem(new_upper);
 
-            let updates = updates
-                .into_iter()
-                .map(|u| ((SourceData(Ok(u.row)), ()), u.timestamp, u.diff));
+            let layout = self.state.collections[id]
+                .collection_metadata
+                .layout
+                .clone();
+            let updates = updates.into_iter().map(move |u| {
+                let row = match &layout {
+                    Some(layout) => layout.to_stored(&u.row),
+                    None => u.row,
+                };
+                ((SourceData(Ok(row)), ()), u.timestamp, u.diff)
+            });
 
-            let write = &mut persist_handle.write;
+            let PersistHandles { read, write } = persist_handle;
+            let truncate_at = truncations.get(id).cloned();
 
             futs.push(async move {
-                write
-                    .compare_and_append(updates, upper.clone(), new_upper.clone())
-                    .await
-                    .expect("cannot append updates")
-                    .expect("cannot append updates")
-                    .or(Err(StorageError::InvalidUpper(*id)))?;
+                match truncate_at {
+                    Some(truncate_at) => {
+                        // Retract the contents of the local input as of just
+                        // before its upper, in the same batch as the updates.
+                        // The contents are read one part at a time and the
+                        // builder flushes them to blob storage as it goes, so
+                        // they never have to fit in memory. They need not be
+                        // consolidated, as the retractions of all their
+                        // updates sum to the retraction of the contents.
+                        let mut builder = write.builder(0, upper.clone());
+                        // An upper of the minimum timestamp means that
+                        // nothing has been written yet.
+                        if let Some(u) = upper.as_option().filter(|u| **u != T::minimum()) {
+                            let u: i64 = u.clone().try_into().expect("must fit");
+                            let as_of = T::try_from(u - 1).expect("must fit");
+                            let mut snapshot = read
+                                .snapshot(Antichain::from_elem(as_of))
+                                .await
+                                .map_err(|_| StorageError::ReadBeforeSince(*id))?;
+                            while let Some(contents) = snapshot.next().await {
+                                for ((source_data, _), _, diff) in contents {
+                                    let source_data = source_data.map_err(|e| {
+                                        anyhow!("cannot read snapshot of {}: {}", id, e)
+                                    })?;
+                                    builder
+                                        .add(&source_data, &(), &truncate_at, &-diff)
+                                        .await
+                                        .expect("invalid usage");
+                                }
+                            }
+                        }
+                        for ((source_data, ()), ts, diff) in updates {
+                            builder
+                                .add(&source_data, &(), &ts, &diff)
+                                .await
+                                .expect("invalid usage");
+                        }
+                        let mut batch = builder
+                            .finish(new_upper.clone())
+                            .await
+                            .expect("invalid usage");
+                        let res = write
+                            .compare_and_append_batch(&mut batch, upper.clone(), new_upper.clone())
+                            .await
+                            .expect("cannot append updates")
+                            .expect("cannot append updates");
+                        if res.is_err() {
+                            batch.delete().await;
+                            return Err(StorageError::InvalidUpper(*id));
+                        }
+                    }
+                    None => {
+                        write
+                            .compare_and_append(updates, upper.clone(), new_upper.clone())
+                            .await
+                            .expect("cannot append updates")
+                            .expect("cannot append updates")
+                            .or(Err(StorageError::InvalidUpper(*id)))?;
+                    }
+                }
 
                 let mut change_batch = ChangeBatch::new();
                 change_batch.extend(new_upper.iter().cloned().map(|t| (t, 1)));
@@ -652,6 +929,36 @@ where
         Ok(())
     }
 
+    async fn alter_collection(
+        &mut self,
+        id: GlobalId,
+        desc: RelationDesc,
+        layout: ColumnLayout,
+    ) -> Result<(), StorageError> {
+        let data_shard = self.collection(id)?.collection_metadata.data_shard;
+
+        // Typed columns are written according to the new layout from now on.
+        let key_schema = Arc::new(SourceDataSchema::new(&layout.typ));
+        let write = self
+            .persist_client
+            .open_writer_with_schema::<SourceData, (), T, Diff>(data_shard, key_schema)
+            .await
+            .expect("invalid persist usage");
+        let handles = self
+            .state
+            .persist_handles
+            .get_mut(&id)
+            .ok_or(StorageError::IdentifierMissing(id))?;
+        handles.write = write;
+
+        let collection = self.collection_mut(id)?;
+        collection.description.desc = desc;
+        collection.description.layout = Some(layout.clone());
+        collection.collection_metadata.layout = Some(layout);
+
+        Ok(())
+    }
+
     async fn snapshot(
         &mut self,
         id: GlobalId,
@@ -666,9 +973,16 @@ where
 
         let mut contents = Vec::new();
 
+        let layout = &self.state.collections[&id].collection_metadata.layout;
         while let Some(updates) = snapshot.next().await {
             for ((source_data, _pid), _ts, diff) in updates {
-                let row = source_data.expect("cannot read snapshot").0?;
+                let row = source_data
+                    .map_err(|e| anyhow!("cannot read snapshot of {}: {}", id, e))?
+                    .0?;
+                let row = match layout {
+                    Some(layout) => layout.from_stored(&row),
+                    None => row,
+                };
                 contents.push((row, diff));
             }
         }
@@ -676,6 +990,93 @@ where
         Ok(contents)
     }
 
+    async fn snapshot_count(
+        &mut self,
+        id: GlobalId,
+        as_of: Self::Timestamp,
+    ) -> Result<Diff, StorageError> {
+        let as_of = Antichain::from_elem(as_of);
+        let mut snapshot = self.state.persist_handles[&id]
+            .read
+            .snapshot(as_of)
+            .await
+            .map_err(|_| StorageError::ReadBeforeSince(id))?;
+
+        let mut count = 0;
+        while let Some(updates) = snapshot.next().await {
+            count += updates.into_iter().map(|(_, _ts, diff)| diff).sum::<Diff>();
+        }
+
+        Ok(count)
+    }
+
+    async fn lookup(
+        &mut self,
+        id: GlobalId,
+        as_of: Self::Timestamp,
+        lookups: &[(Vec<usize>, Vec<Row>)],
+    ) -> Result<Vec<(Row, Diff)>, StorageError> {
+        let layout = self.collection(id)?.collection_metadata.layout.clone();
+        let lookups = lookups
+            .iter()
+            .filter_map(|(columns, keys)| {
+                let keys: HashSet<Row> = keys
+                    .iter()
+                    .filter(|key| key.iter().all(|datum| !datum.is_null()))
+                    .cloned()
+                    .collect();
+                (!keys.is_empty()).then(|| (columns.clone(), keys))
+            })
+            .collect::<Vec<_>>();
+        if lookups.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // Part statistics describe the columns of the shard.
+        let stored_lookups = lookups
+            .iter()
+            .map(|(columns, keys)| {
+                let columns = match &layout {
+                    Some(layout) => columns.iter().map(|c| layout.columns[*c]).collect(),
+                    None => columns.clone(),
+                };
+                (columns, keys.iter().cloned().collect())
+            })
+            .collect::<Vec<_>>();
+        let filter =
+            ReadFilter::default().with_part_filter(Arc::new(KeyPartFilter::new(&stored_lookups)));
+
+        let as_of = Antichain::from_elem(as_of);
+        let mut snapshot = self.state.persist_handles[&id]
+            .read
+            .snapshot_filtered(as_of, filter)
+            .await
+            .map_err(|_| StorageError::ReadBeforeSince(id))?;
+
+        let mut contents = Vec::new();
+        while let Some(updates) = snapshot.next().await {
+            for ((source_data, _pid), _ts, diff) in updates {
+                let row = source_data
+                    .map_err(|e| anyhow!("cannot read snapshot of {}: {}", id, e))?
+                    .0?;
+                let row = match &layout {
+                    Some(layout) => layout.from_stored(&row),
+                    None => row,
+                };
+                let datums = row.unpack();
+                let matches = lookups.iter().any(|(columns, keys)| {
+                    keys.contains(&Row::pack(columns.iter().map(|c| datums[*c])))
+                });
+                if matches {
+                    contents.push((row, diff));
+                }
+            }
+        }
+        differential_dataflow::consolidation::consolidate(&mut contents);
+
+        Ok(contents)
+    }
+
     async fn set_read_policy(
         &mut self,
         policies: Vec<(GlobalId, ReadPolicy<T>)>,
@@ -862,6 +1263,7 @@ where
         persist_clients: Arc<Mutex<PersistClientCache>>,
         orchestrator: Arc<dyn NamespacedOrchestrator>,
         storaged_image: String,
+        persist_part_compression: PartCompression,
     ) -> Self {
         let persist_client = persist_clients
             .lock()
@@ -876,6 +1278,7 @@ where
                 build_info,
                 orchestrator,
                 storaged_image,
+                persist_part_compression,
             }),
             persist_location,
             persist_client,
@@ -31,6 +31,7 @@ use tracing::info;
 use mz_build_info::BuildInfo;
 use mz_orchestrator::{NamespacedOrchestrator, ServiceConfig, ServicePort};
 use mz_ore::collections::CollectionExt;
+use mz_persist_client::PartCompression;
 use mz_proto::RustType;
 use mz_repr::GlobalId;
 
@@ -50,6 +51,9 @@ pub struct StorageHostsConfig {
     pub orchestrator: Arc<dyn NamespacedOrchestrator>,
     /// The storaged image to use when starting new storage hosts.
     pub storaged_image: String,
+    /// The codec that storage hosts use to compress the parts they write to
+    /// persist.
+    pub persist_part_compression: PartCompression,
 }
 
 /// Manages provisioning of storage hosts and assignment of storage objects
@@ -64,6 +68,9 @@ pub struct StorageHosts<T> {
     orchestrator: Arc<dyn NamespacedOrchestrator>,
     /// The storaged image to use when starting new storage hosts.
     storaged_image: String,
+    /// The codec that storage hosts use to compress the parts they write to
+    /// persist.
+    persist_part_compression: PartCompression,
     /// The known storage hosts, identified by network address.
     hosts: HashMap<StorageHostAddr, StorageHost<T>>,
     /// The assignment of storage objects to storage hosts.
@@ -88,6 +95,7 @@ impl<T> StorageHosts<T> {
             build_info: config.build_info,
             orchestrator: config.orchestrator,
             storaged_image: config.storaged_image,
+            persist_part_compression: config.persist_part_compression,
             objects: HashMap::new(),
             hosts: HashMap::new(),
         }
@@ -204,6 +212,7 @@ impl<T> StorageHosts<T> {
 
     /// Starts a orchestrated storage host for the specified ID.
     async fn start_storage_host(&self, id: GlobalId) -> Result<StorageHostAddr, anyhow::Error> {
+        let persist_part_compression = self.persist_part_compression;
         let storage_service = self
             .orchestrator
             .ensure_service(
@@ -221,6 +230,7 @@ impl<T> StorageHosts<T> {
                                 "--internal-http-listen-addr={}:{}",
                                 assigned.listen_host, assigned.ports["internal-http"]
                             ),
+                            format!("--persist-part-compression={}", persist_part_compression),
                             format!("--opentelemetry-resource=storage_id={}", id),
                         ]
                     },
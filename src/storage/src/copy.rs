@@ -0,0 +1,184 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Access to the locations outside of Materialize that `COPY` statements can
+//! read from and write to, like S3 buckets and local files.
+
+use std::fmt;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use aws_sdk_s3::types::ByteStream;
+use aws_sdk_s3::Client as S3Client;
+use tokio::runtime::Handle;
+use tokio_util::io::SyncIoBridge;
+
+use mz_ore::task;
+
+use crate::source::s3::object_reader;
+use crate::types::connections::aws::AwsConfig;
+use crate::types::connections::ConnectionContext;
+use crate::types::sources::Compression;
+
+/// A location outside of Materialize that a `COPY` statement reads from or
+/// writes to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CopyLocation {
+    /// Objects in an S3 bucket.
+    S3 {
+        /// The name of the bucket.
+        bucket: String,
+        /// When reading, the prefix of the keys of the objects to read. When
+        /// writing, the key of the object to write.
+        key: String,
+        /// The configuration used to connect to S3.
+        aws: AwsConfig,
+    },
+    /// A file on the filesystem of the machine running Materialize.
+    File(PathBuf),
+}
+
+impl CopyLocation {
+    /// Reads the data stored at the location, passing a reader over each
+    /// object or file to `f` in turn, so that the caller can decode them
+    /// independently of one another. Objects in S3 are read in the
+    /// lexicographic order of their keys.
+    ///
+    /// The data is streamed from the location as `f` reads it rather than
+    /// read into memory up front. `f` is called on a thread where blocking is
+    /// allowed, so it may block on the reader.
+    pub async fn read<F, E>(
+        &self,
+        connection_context: &ConnectionContext,
+        mut f: F,
+    ) -> Result<(), E>
+    where
+        F: FnMut(&mut dyn BufRead) -> Result<(), E> + Send + 'static,
+        E: From<anyhow::Error> + Send + 'static,
+    {
+        match self {
+            CopyLocation::S3 { bucket, key, aws } => {
+                let client = s3_client(aws, connection_context).await;
+                let mut keys = vec![];
+                let mut continuation_token = None;
+                loop {
+                    let response = client
+                        .list_objects_v2()
+                        .bucket(bucket)
+                        .prefix(key)
+                        .set_continuation_token(continuation_token)
+                        .send()
+                        .await
+                        .map_err(|e| anyhow!("listing objects in {}: {}", self, e))?;
+                    // Keys that end in a slash are conventionally used to
+                    // represent directories and never contain data.
+                    keys.extend(
+                        response
+                            .contents
+                            .unwrap_or_default()
+                            .into_iter()
+                            .filter_map(|object| object.key)
+                            .filter(|key| !key.ends_with('/')),
+                    );
+                    continuation_token = response.next_continuation_token;
+                    if continuation_token.is_none() {
+                        break;
+                    }
+                }
+                if keys.is_empty() {
+                    return Err(anyhow!("no objects found in {}", self).into());
+                }
+
+                let bucket = bucket.clone();
+                let handle = Handle::current();
+                task::spawn_blocking(
+                    || "copy_read_s3",
+                    move || {
+                        for key in &keys {
+                            let reader = object_reader(&client, &bucket, key, Compression::None);
+                            let reader =
+                                SyncIoBridge::new_with_handle(Box::pin(reader), handle.clone());
+                            f(&mut BufReader::new(reader))?;
+                        }
+                        Ok(())
+                    },
+                )
+                .await
+                .map_err(anyhow::Error::from)?
+            }
+            CopyLocation::File(path) => {
+                let path = path.clone();
+                task::spawn_blocking(
+                    || "copy_read_file",
+                    move || {
+                        let file = std::fs::File::open(&path).map_err(|e| {
+                            anyhow!(
+                                "could not open file \"{}\" for reading: {}",
+                                path.display(),
+                                e
+                            )
+                        })?;
+                        f(&mut BufReader::new(file))
+                    },
+                )
+                .await
+                .map_err(anyhow::Error::from)?
+            }
+        }
+    }
+
+    /// Writes `data` to the location, replacing any existing object or file.
+    pub async fn write(
+        &self,
+        data: Vec<u8>,
+        connection_context: &ConnectionContext,
+    ) -> Result<(), anyhow::Error> {
+        match self {
+            CopyLocation::S3 { bucket, key, aws } => {
+                let client = s3_client(aws, connection_context).await;
+                client
+                    .put_object()
+                    .bucket(bucket)
+                    .key(key)
+                    .body(ByteStream::from(data))
+                    .send()
+                    .await
+                    .map_err(|e| anyhow!("uploading {}: {}", self, e))?;
+                Ok(())
+            }
+            CopyLocation::File(path) => {
+                tokio::fs::write(path, data).await.map_err(|e| {
+                    anyhow!(
+                        "could not open file \"{}\" for writing: {}",
+                        path.display(),
+                        e
+                    )
+                })?;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for CopyLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CopyLocation::S3 { bucket, key, .. } => write!(f, "s3://{}/{}", bucket, key),
+            CopyLocation::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+async fn s3_client(aws: &AwsConfig, connection_context: &ConnectionContext) -> S3Client {
+    let config = aws
+        .load(connection_context.aws_external_id_prefix.as_ref(), None)
+        .await;
+    S3Client::new(&config)
+}
@@ -0,0 +1,40 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::Row;
+
+use crate::types::errors::DecodeError;
+
+/// Decodes JSON-formatted records into a single `jsonb` column.
+#[derive(Debug, Default)]
+pub struct JsonDecoderState {
+    row_buf: Row,
+    events_success: i64,
+    events_error: i64,
+}
+
+impl JsonDecoderState {
+    pub fn get_value(&mut self, bytes: &[u8]) -> Option<Result<Row, DecodeError>> {
+        let mut packer = self.row_buf.packer();
+        match JsonbPacker::new(&mut packer).pack_slice(bytes) {
+            Ok(()) => {
+                self.events_success += 1;
+                Some(Ok(self.row_buf.clone()))
+            }
+            Err(err) => {
+                self.events_error += 1;
+                Some(Err(DecodeError::Text(format!(
+                    "JSON deserialization error: {:#}",
+                    err
+                ))))
+            }
+        }
+    }
+}
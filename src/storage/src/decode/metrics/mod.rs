@@ -43,6 +43,7 @@ impl DecodeMetrics {
                 PreDelimitedFormat::Text => "text",
                 PreDelimitedFormat::Regex(..) => "regex",
                 PreDelimitedFormat::Protobuf(..) => "protobuf",
+                PreDelimitedFormat::Json(..) => "json",
             },
         };
         let success_label = if success { "success" } else { "error" };
@@ -42,6 +42,7 @@ use tracing::error;
 
 use self::avro::AvroDecoderState;
 use self::csv::CsvDecoderState;
+use self::json::JsonDecoderState;
 use self::metrics::DecodeMetrics;
 use self::protobuf::ProtobufDecoderState;
 use crate::source::{DecodeResult, SourceOutput};
@@ -55,6 +56,7 @@ use crate::types::transforms::LinearOperator;
 
 mod avro;
 mod csv;
+mod json;
 pub mod metrics;
 mod protobuf;
 
@@ -140,6 +142,7 @@ pub(crate) enum PreDelimitedFormat {
     Text,
     Regex(Regex, Row),
     Protobuf(ProtobufDecoderState),
+    Json(JsonDecoderState),
 }
 
 impl PreDelimitedFormat {
@@ -167,6 +170,7 @@ impl PreDelimitedFormat {
                 Ok(Some(row_buf.clone()))
             }
             PreDelimitedFormat::Protobuf(pb) => pb.get_value(bytes).transpose(),
+            PreDelimitedFormat::Json(json) => json.get_value(bytes).transpose(),
         }
     }
 }
@@ -284,6 +288,7 @@ fn get_decoder(
         }
         DataEncodingInner::Text
         | DataEncodingInner::Bytes
+        | DataEncodingInner::Json
         | DataEncodingInner::Protobuf(_)
         | DataEncodingInner::Regex(_) => {
             let after_delimiting = match encoding.inner {
@@ -298,6 +303,7 @@ fn get_decoder(
                 }
                 DataEncodingInner::Bytes => PreDelimitedFormat::Bytes,
                 DataEncodingInner::Text => PreDelimitedFormat::Text,
+                DataEncodingInner::Json => PreDelimitedFormat::Json(JsonDecoderState::default()),
                 _ => unreachable!(),
             };
             let inner = if is_connection_delimited {
@@ -12,6 +12,7 @@
 //! Materialize's storage layer.
 
 pub mod controller;
+pub mod copy;
 pub mod decode;
 pub mod protocol;
 pub mod render;
@@ -13,7 +13,7 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 use anyhow::anyhow;
-use mz_persist_client::PersistConfig;
+use mz_persist_client::{PartCompression, PersistConfig};
 use timely::communication::initialize::WorkerGuards;
 use tokio::sync::mpsc;
 
@@ -45,6 +45,8 @@ pub struct Config {
     pub metrics_registry: MetricsRegistry,
     /// Configuration for source and sink connection.
     pub connection_context: ConnectionContext,
+    /// The codec used to compress the parts written to persist.
+    pub persist_part_compression: PartCompression,
 }
 
 /// A handle to a running dataflow server.
@@ -91,8 +93,9 @@ pub fn serve(
 
     let tokio_executor = tokio::runtime::Handle::current();
     let now = config.now;
-    let persist_clients =
-        PersistClientCache::new(PersistConfig::new(now.clone()), &config.metrics_registry);
+    let mut persist_cfg = PersistConfig::new(now.clone());
+    persist_cfg.part_compression = config.persist_part_compression;
+    let persist_clients = PersistClientCache::new(persist_cfg, &config.metrics_registry);
     let persist_clients = Arc::new(tokio::sync::Mutex::new(persist_clients));
 
     let worker_guards = timely::execute::execute(config.timely_config, move |timely_worker| {
@@ -168,12 +168,10 @@ where
             Some(Datum::Null) => return default_hash,
             _ => panic!("Previously validated to be nullable list"),
         };
-        transaction
-            .iter()
-            .nth(data_transaction_id_idx)
-            .unwrap()
-            .unwrap_str()
-            .hashed()
+        match transaction.iter().nth(data_transaction_id_idx) {
+            Some(Datum::String(tx_id)) => tx_id.hashed(),
+            _ => default_hash,
+        }
     };
 
     tx_ok
@@ -216,22 +214,59 @@ where
                                         time,
                                         1,
                                     ));
+                                    continue;
                                 }
 
-                                let status = row.iter().nth(tx_status_idx).unwrap().unwrap_str();
+                                // Debezium producers may send null `status` or `id` fields. Such a record
+                                // can't be matched to any data, so report it rather than crash.
+                                let status = match row.iter().nth(tx_status_idx).unwrap() {
+                                    Datum::String(status) => status,
+                                    Datum::Null => {
+                                        output.session(&tx_metadata_cap).give((
+                                            Err(DataflowError::DecodeError(DecodeError::Text(
+                                                "Transaction metadata has null status".to_string(),
+                                            ))),
+                                            time,
+                                            1,
+                                        ));
+                                        continue;
+                                    }
+                                    d => panic!("status field previously validated to be string type.  Found {:?}", d),
+                                };
                                 if status != "END" {
                                     continue;
                                 }
 
-                                let tx_id = row.iter().nth(tx_transaction_id_idx).unwrap().unwrap_str().to_owned();
+                                let tx_id = match row.iter().nth(tx_transaction_id_idx).unwrap() {
+                                    Datum::String(tx_id) => tx_id.to_owned(),
+                                    Datum::Null => {
+                                        output.session(&tx_metadata_cap).give((
+                                            Err(DataflowError::DecodeError(DecodeError::Text(
+                                                "Transaction metadata END record has null id".to_string(),
+                                            ))),
+                                            time,
+                                            1,
+                                        ));
+                                        continue;
+                                    }
+                                    d => panic!("id field previously validated to be string type.  Found {:?}", d),
+                                };
 
-                                let event_count = match row.iter().nth(tx_data_collections_idx).unwrap() {
+                                // `data_collections`, its elements and their fields are allowed to be null, in
+                                // which case they cannot contain any events for this collection.
+                                let data_collections = match row.iter().nth(tx_data_collections_idx).unwrap() {
                                     Datum::List(dl) => dl,
                                     Datum::Array(dl) => dl.elements(),
+                                    Datum::Null => continue,
                                     _ => panic!("data_collections previously validated to be array or list"),
-                                }.iter()
-                                .find(|datum| datum.unwrap_list().iter().nth(tx_data_collections_data_collection_idx).unwrap().unwrap_str() == &tx_data_collection_name)
-                                .map(|datum| match datum.unwrap_list().iter().nth(tx_data_collections_event_count_idx).unwrap() {
+                                };
+                                let event_count = data_collections.iter()
+                                .filter_map(|datum| match datum {
+                                    Datum::List(l) => Some(l),
+                                    _ => None,
+                                })
+                                .find(|l| l.iter().nth(tx_data_collections_data_collection_idx) == Some(Datum::String(&tx_data_collection_name)))
+                                .map(|l| match l.iter().nth(tx_data_collections_event_count_idx).unwrap() {
                                     Datum::Int16(i) => i.into(),
                                     Datum::Int32(i) => i.into(),
                                     Datum::Int64(i) => i,
@@ -248,14 +283,37 @@ where
                                 if event_count == 0 {
                                     continue;
                                 }
+                                if event_count < 0 {
+                                    output.session(&tx_metadata_cap).give((
+                                        Err(DataflowError::DecodeError(DecodeError::Text(format!(
+                                            "Transaction metadata for {:?} has negative event_count {}",
+                                            tx_id, event_count
+                                        )))),
+                                        time,
+                                        1,
+                                    ));
+                                    continue;
+                                }
 
-                                match tx_mapping.insert(tx_id.clone(), time) {
-                                    None => {
+                                match tx_mapping.entry(tx_id.clone()) {
+                                    Entry::Vacant(e) => {
+                                        e.insert(time);
                                         tx_cap_event_count
-                                            .insert(tx_id.clone(), (tx_metadata_cap.clone(), event_count));
+                                            .insert(tx_id, (tx_metadata_cap.clone(), event_count));
+                                    }
+                                    Entry::Occupied(e) if *e.get() == time => {},
+                                    // A duplicate END record at a different time.  Keep the original mapping so that
+                                    // data for this transaction remains at a single timestamp, and report the mismatch.
+                                    Entry::Occupied(e) => {
+                                        output.session(&tx_metadata_cap).give((
+                                            Err(DataflowError::DecodeError(DecodeError::Text(format!(
+                                                "Mismatched timestamps in duplicate END record for transaction {:?}: {} vs {}",
+                                                tx_id, time, e.get()
+                                            )))),
+                                            time,
+                                            1,
+                                        ));
                                     }
-                                    Some(val) if val == time => {},
-                                    Some(val) => panic!("unexpected mismatch in duplicate END record for {:?}: {:?} vs {:?}", tx_id, time, val),
                                 }
                             }
                         }
@@ -285,7 +343,21 @@ where
 
                             let tx_id_and_time = match value.iter().nth(data_transaction_idx).unwrap() {
                                 Datum::List(l) => {
-                                    let tx_id = l.iter().nth(data_transaction_id_idx).unwrap().unwrap_str().to_owned();
+                                    let tx_id = match l.iter().nth(data_transaction_id_idx).unwrap() {
+                                        Datum::String(tx_id) => tx_id.to_owned(),
+                                        // A record without a transaction id cannot be matched to transaction
+                                        // metadata, which would block the source forever.
+                                        _ => {
+                                            output.session(&data_cap).give((
+                                                Err(DataflowError::DecodeError(DecodeError::Text(
+                                                    "Debezium record has a null transaction id".into()
+                                                ))),
+                                                *data_cap.time(),
+                                                1,
+                                            ));
+                                            continue;
+                                        }
+                                    };
                                     let tx_time: Timestamp = match tx_mapping.get(&tx_id) {
                                         Some(time) => *time,
                                         None => {
@@ -330,7 +402,21 @@ where
                                 (true, Some((tx_id, tx_time))) => {
                                     let mut tx_cap_event_count_entry = match tx_cap_event_count.entry(tx_id.clone()) {
                                         Entry::Occupied(e) => e,
-                                        Entry::Vacant(_) => panic!("Must have cap if using record"),
+                                        // We've already emitted `event_count` records for this transaction, so the
+                                        // upstream system produced more records than its transaction metadata
+                                        // promised.  We can't place this record at the transaction's timestamp
+                                        // anymore.
+                                        Entry::Vacant(_) => {
+                                            output.session(&data_cap).give((
+                                                Err(DataflowError::DecodeError(DecodeError::Text(format!(
+                                                    "Transaction {:?} contains more records than its event_count",
+                                                    tx_id
+                                                )))),
+                                                *data_cap.time(),
+                                                1,
+                                            ));
+                                            continue;
+                                        }
                                     };
 
                                     let mut session = output.session(&tx_cap_event_count_entry.get().0);
@@ -157,6 +157,7 @@ pub fn build_storage_dataflow<A: Allocate>(
                 region,
                 id,
                 description.storage_metadata,
+                description.typ,
                 source_data,
                 storage_state,
                 Rc::clone(&token),
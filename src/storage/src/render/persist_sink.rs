@@ -13,7 +13,7 @@ use std::rc::Rc;
 use std::sync::Arc;
 
 use differential_dataflow::{Collection, Hashable};
-use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_repr::{Diff, GlobalId, RelationType, Row, Timestamp};
 use mz_timely_util::operators_async_ext::OperatorBuilderExt;
 use timely::dataflow::channels::pact::Exchange;
 use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
@@ -26,12 +26,13 @@ use crate::storage_state::StorageState;
 
 use crate::controller::CollectionMetadata;
 use crate::types::errors::DataflowError;
-use crate::types::sources::SourceData;
+use crate::types::sources::{SourceData, SourceDataSchema};
 
 pub fn render<G>(
     scope: &mut G,
     src_id: GlobalId,
     metadata: CollectionMetadata,
+    typ: RelationType,
     source_data: Collection<G, Result<Row, DataflowError>, Diff>,
     storage_state: &mut StorageState,
     token: Rc<dyn Any>,
@@ -72,7 +73,10 @@ pub fn render<G>(
                 .open(metadata.persist_location)
                 .await
                 .expect("could not open persist client")
-                .open_writer::<SourceData, (), Timestamp, Diff>(metadata.data_shard)
+                .open_writer_with_schema::<SourceData, (), Timestamp, Diff>(
+                    metadata.data_shard,
+                    Arc::new(SourceDataSchema::new(&typ)),
+                )
                 .await
                 .expect("could not open persist shard");
 
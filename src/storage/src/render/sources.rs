@@ -326,6 +326,7 @@ where
                                     persist_clients,
                                     tx_storage_metadata,
                                     as_of,
+                                    None,
                                 );
                             let (tx_source_ok, tx_source_err) = (
                                 tx_source_ok_stream.as_collection(),
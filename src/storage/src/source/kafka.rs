@@ -29,7 +29,8 @@ use mz_ore::thread::{JoinHandleExt, UnparkOnDropHandle};
 use mz_repr::{adt::jsonb::Jsonb, GlobalId};
 
 use crate::source::{
-    NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
+    NextMessage, PersistedSourceOutput, SourceMessage, SourceMessageType, SourceReader,
+    SourceReaderError,
 };
 use crate::types::connections::{ConnectionContext, KafkaConnection, StringOrSecret};
 use crate::types::sources::encoding::SourceDataEncoding;
@@ -91,6 +92,7 @@ impl SourceReader for KafkaSourceReader {
         _: SourceDataEncoding,
         metrics: crate::source::metrics::SourceBaseMetrics,
         connection_context: ConnectionContext,
+        _: PersistedSourceOutput,
     ) -> Result<Self, anyhow::Error> {
         let kc = match connection {
             SourceConnection::Kafka(kc) => kc,
@@ -27,7 +27,8 @@ use mz_repr::GlobalId;
 
 use crate::source::metrics::KinesisMetrics;
 use crate::source::{
-    NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
+    NextMessage, PersistedSourceOutput, SourceMessage, SourceMessageType, SourceReader,
+    SourceReaderError,
 };
 use crate::types::connections::aws::AwsExternalIdPrefix;
 use crate::types::connections::ConnectionContext;
@@ -135,6 +136,7 @@ impl SourceReader for KinesisSourceReader {
         _encoding: SourceDataEncoding,
         metrics: crate::source::metrics::SourceBaseMetrics,
         connection_context: ConnectionContext,
+        _: PersistedSourceOutput,
     ) -> Result<Self, anyhow::Error> {
         let kc = match connection {
             SourceConnection::Kinesis(kc) => kc,
@@ -30,6 +30,7 @@ use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 
+use anyhow::{anyhow, Context as _};
 use async_trait::async_trait;
 use differential_dataflow::Hashable;
 use futures::stream::LocalBoxStream;
@@ -62,9 +63,9 @@ use crate::source::metrics::SourceBaseMetrics;
 use crate::source::reclock::ReclockOperator;
 use crate::source::util::source;
 use crate::types::connections::ConnectionContext;
-use crate::types::errors::{DecodeError, SourceError, SourceErrorDetails};
+use crate::types::errors::{DataflowError, DecodeError, SourceError, SourceErrorDetails};
 use crate::types::sources::encoding::SourceDataEncoding;
-use crate::types::sources::{MzOffset, SourceConnection};
+use crate::types::sources::{MzOffset, SourceConnection, SourceData};
 
 mod kafka;
 mod kinesis;
@@ -73,7 +74,7 @@ pub mod persist_source;
 mod postgres;
 mod pubnub;
 mod reclock;
-mod s3;
+pub(crate) mod s3;
 pub mod util;
 
 pub use kafka::KafkaSourceReader;
@@ -172,6 +173,7 @@ where
         encoding: SourceDataEncoding,
         metrics: crate::source::metrics::SourceBaseMetrics,
         connection_context: ConnectionContext,
+        persisted_output: PersistedSourceOutput,
     ) -> Result<Self, anyhow::Error> {
         S::new(
             source_name,
@@ -184,6 +186,7 @@ where
             encoding,
             metrics,
             connection_context,
+            persisted_output,
         )
         .map(Self)
     }
@@ -230,6 +233,17 @@ where
                     specific_diff,
                 },
             ))),
+            NextMessage::Ready(SourceMessageType::DefiniteError {
+                partition,
+                offset,
+                error,
+                finalized,
+            }) => Ok(NextMessage::Ready(SourceMessageType::DefiniteError {
+                partition,
+                offset,
+                error,
+                finalized,
+            })),
             NextMessage::Pending => Ok(NextMessage::Pending),
             NextMessage::TransientDelay => Ok(NextMessage::TransientDelay),
             NextMessage::Finished => Ok(NextMessage::Finished),
@@ -382,6 +396,79 @@ impl From<anyhow::Error> for SourceReaderError {
     }
 }
 
+/// A handle to the output a source has persisted before its [`SourceReader`] was
+/// created. Readers that keep state derived from their output can use it to
+/// rebuild that state when they resume ingestion.
+#[derive(Clone)]
+pub struct PersistedSourceOutput {
+    persist_clients: Arc<Mutex<PersistClientCache>>,
+    storage_metadata: CollectionMetadata,
+    as_of: Antichain<Timestamp>,
+}
+
+impl PersistedSourceOutput {
+    /// Returns the consolidated contents of the source's output as of the point
+    /// at which the reader resumes ingestion. Errors are omitted.
+    pub async fn snapshot(&self) -> Result<Vec<(Row, Diff)>, anyhow::Error> {
+        let mut updates = self
+            .read_snapshot()
+            .await?
+            .into_iter()
+            .filter_map(|(data, diff)| match data {
+                SourceData(Ok(row)) => Some((row, diff)),
+                SourceData(Err(_)) => None,
+            })
+            .collect::<Vec<_>>();
+        differential_dataflow::consolidation::consolidate(&mut updates);
+        Ok(updates)
+    }
+
+    /// Returns the consolidated errors in the source's output as of the point
+    /// at which the reader resumes ingestion. Readers that report definite
+    /// errors can use them to avoid reporting an error a second time.
+    pub async fn errors(&self) -> Result<Vec<(DataflowError, Diff)>, anyhow::Error> {
+        let mut updates = self
+            .read_snapshot()
+            .await?
+            .into_iter()
+            .filter_map(|(data, diff)| match data {
+                SourceData(Ok(_)) => None,
+                SourceData(Err(err)) => Some((err, diff)),
+            })
+            .collect::<Vec<_>>();
+        differential_dataflow::consolidation::consolidate(&mut updates);
+        Ok(updates)
+    }
+
+    async fn read_snapshot(&self) -> Result<Vec<(SourceData, Diff)>, anyhow::Error> {
+        let read = self
+            .persist_clients
+            .lock()
+            .await
+            .open(self.storage_metadata.persist_location.clone())
+            .await
+            .context("error creating persist client")?
+            .open_reader::<SourceData, (), Timestamp, Diff>(self.storage_metadata.data_shard)
+            .await
+            .context("error opening persist shard")?;
+
+        let mut snapshot = read
+            .snapshot(self.as_of.clone())
+            .await
+            .map_err(|since| anyhow!("cannot serve as_of {:?}: {:?}", self.as_of, since))?;
+        let mut updates = vec![];
+        while let Some(batch) = snapshot.next().await {
+            for ((key, _), _, diff) in batch {
+                if let Ok(data) = key {
+                    updates.push((data, diff));
+                }
+            }
+        }
+        read.expire().await;
+        Ok(updates)
+    }
+}
+
 /// This trait defines the interface between Materialize and external sources, and
 /// must be implemented for every new kind of source.
 ///
@@ -410,6 +497,7 @@ pub trait SourceReader {
         encoding: SourceDataEncoding,
         metrics: crate::source::metrics::SourceBaseMetrics,
         connection_context: ConnectionContext,
+        persisted_output: PersistedSourceOutput,
     ) -> Result<Self, anyhow::Error>
     where
         Self: Sized;
@@ -494,6 +582,21 @@ pub enum SourceMessageType<Key, Value, Diff> {
     /// Communicate that more [`SourceMessage`]'s
     /// will come later at the same offset as this one.
     InProgress(SourceMessage<Key, Value, Diff>),
+    /// Communicate that the source encountered an error at a
+    /// specific offset. Unlike errors returned by
+    /// [`SourceReader::next`], these errors are timestamped along
+    /// with the source's data and are therefore definite.
+    DefiniteError {
+        /// Partition at which the error occurred
+        partition: PartitionId,
+        /// Materialize offset at which the error occurred
+        offset: MzOffset,
+        /// The error
+        error: SourceReaderError,
+        /// Whether this is the final message at its offset, with
+        /// the same meaning as `Finalized` vs `InProgress`.
+        finalized: bool,
+    },
 }
 
 /// Source-agnostic wrapper for messages. Each source must implement a
@@ -747,6 +850,11 @@ where
         let mut source_reader = Box::pin(async_stream::stream!({
             let upper_ts = resume_upper.as_option().copied().unwrap();
             let as_of = Antichain::from_elem(upper_ts.saturating_sub(1));
+            let persisted_output = PersistedSourceOutput {
+                persist_clients: Arc::clone(&persist_clients),
+                storage_metadata: storage_metadata.clone(),
+                as_of: as_of.clone(),
+            };
             let mut timestamper = match ReclockOperator::new(
                 persist_clients,
                 storage_metadata,
@@ -779,6 +887,7 @@ where
                 encoding,
                 base_metrics,
                 connection_context.clone(),
+                persisted_output,
             );
             let source_stream = source_reader
                 .expect("Failed to create source")
@@ -806,18 +915,22 @@ where
                                 // 2. Requires that sources that produce `InProgress` messages
                                 //    NEVER produces messages at offsets below the most recent
                                 //    `Finalized` message.
-                                let is_final = matches!(message, SourceMessageType::Finalized(_));
-                                match message {
-                                    SourceMessageType::Finalized(message) | SourceMessageType::InProgress(message) => {
-                                        let pid = message.partition.clone();
-                                        let offset = message.offset;
-                                        // advance the _offset_ frontier if this the final message for that offset
-                                        if is_final {
-                                            *source_upper.entry(pid.clone()).or_default() = offset + 1;
-                                        }
-                                        untimestamped_messages.entry(pid).or_default().push((message, offset));
+                                let (pid, offset, message, is_final) = match message {
+                                    SourceMessageType::Finalized(message) => {
+                                        (message.partition.clone(), message.offset, Ok(message), true)
+                                    }
+                                    SourceMessageType::InProgress(message) => {
+                                        (message.partition.clone(), message.offset, Ok(message), false)
+                                    }
+                                    SourceMessageType::DefiniteError { partition, offset, error, finalized } => {
+                                        (partition, offset, Err(error), finalized)
                                     }
+                                };
+                                // advance the _offset_ frontier if this the final message for that offset
+                                if is_final {
+                                    *source_upper.entry(pid.clone()).or_default() = offset + 1;
                                 }
+                                untimestamped_messages.entry(pid).or_default().push((message, offset));
                             }
                             // TODO: make errors definite
                             Some(Err(e)) => non_definite_errors.push(e),
@@ -832,7 +945,7 @@ where
                         };
                         for (_, part_messages) in reclocked_msgs {
                             for (message, ts) in part_messages {
-                                yield Event::Message(ts, Ok(message));
+                                yield Event::Message(ts, message);
                             }
                         }
 
@@ -906,9 +1019,13 @@ where
                             &mut metric_updates,
                             ts,
                         ),
+                        // Non-definite errors are yielded at time 0 and are emitted at the
+                        // current capability. Definite errors carry the time their offset was
+                        // reclocked to.
                         // TODO: make errors definite
                         Err(e) => {
-                            output.session(&cap).give(Err(SourceError {
+                            let ts_cap = cap.delayed(&std::cmp::max(ts, *cap.time()));
+                            output.session(&ts_cap).give(Err(SourceError {
                                 source_id: id,
                                 error: e.inner,
                             }));
@@ -10,6 +10,8 @@
 //! A source that reads from an a persist shard.
 
 use std::any::Any;
+use std::collections::BTreeSet;
+use std::mem;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -23,20 +25,37 @@ use timely::progress::Antichain;
 use tokio::sync::Mutex;
 use tracing::trace;
 
+use mz_expr::{BinaryFunc, EvalError, MirScalarExpr, UnaryFunc, UnmaterializableFunc};
 use mz_persist::location::ExternalError;
-use mz_persist_client::read::ListenEvent;
-use mz_repr::{Diff, Row, Timestamp};
+use mz_persist_client::read::{ListenEvent, ReadFilter};
+use mz_persist_client::stats::{PartFilter, PartStats};
+use mz_persist_types::columnar::ColumnBounds;
+use mz_repr::adt::numeric::Numeric;
+use mz_repr::{Datum, Diff, RelationType, Row, RowArena, Timestamp};
 
 use crate::controller::CollectionMetadata;
 use crate::source::{SourceStatus, YIELD_INTERVAL};
 use crate::types::errors::DataflowError;
-use crate::types::sources::SourceData;
+use crate::types::sources::{SourceData, SourceDataSchema};
+use crate::types::transforms::LinearOperator;
 
 /// Creates a new source that reads from a persist shard.
 ///
 /// All times emitted will have been [advanced by] the given `as_of` frontier.
 ///
+/// If `linear_operators` are given for a collection of the given type, the
+/// source only decodes the columns they use and replaces the others with
+/// [Datum::Dummy]. It also skips fetching the parts of the shard in which,
+/// according to their statistics, no row satisfies the predicates. The
+/// predicates are not otherwise applied, so rows that don't satisfy them may
+/// still be emitted.
+///
+/// If the collection's rows are stored with a [`ColumnLayout`], they are
+/// converted back into rows of the collection as they are read, using the
+/// layout in `metadata`.
+///
 /// [advanced by]: differential_dataflow::lattice::Lattice::advance_by
+/// [`ColumnLayout`]: crate::controller::ColumnLayout
 //
 // TODO(aljoscha): We need to change the `shard_id` parameter to be a `Vec<ShardId>` and teach the
 // operator to concurrently poll from multiple `Listen` instances. This will require us to put in
@@ -48,6 +67,7 @@ pub fn persist_source<G>(
     persist_clients: Arc<Mutex<PersistClientCache>>,
     metadata: CollectionMetadata,
     as_of: Antichain<Timestamp>,
+    linear_operators: Option<(RelationType, LinearOperator)>,
 ) -> (
     Stream<G, (Row, Timestamp, Diff)>,
     Stream<G, (DataflowError, Timestamp, Diff)>,
@@ -58,6 +78,49 @@ where
 {
     let worker_index = scope.index();
 
+    let (filter, schema) = match linear_operators {
+        Some((typ, operators)) if !operators.is_trivial(typ.arity()) => {
+            // The typed columns and statistics of the shard are those of the
+            // stored rows, so translate the operators to refer to those.
+            let (typ, operators) = match &metadata.layout {
+                Some(layout) => {
+                    let LinearOperator {
+                        mut predicates,
+                        projection,
+                    } = operators;
+                    for predicate in predicates.iter_mut() {
+                        predicate.permute(&layout.columns);
+                    }
+                    let projection = projection.iter().map(|c| layout.columns[*c]).collect();
+                    let operators = LinearOperator {
+                        predicates,
+                        projection,
+                    };
+                    (layout.typ.clone(), operators)
+                }
+                None => (typ, operators),
+            };
+            // Predicates may refer to columns outside of the projection.
+            let mut columns = operators
+                .projection
+                .iter()
+                .copied()
+                .collect::<BTreeSet<_>>();
+            for predicate in operators.predicates.iter() {
+                columns.extend(predicate.support());
+            }
+            let schema = SourceDataSchema::projected(&typ, &columns);
+            let part_filter = SourceDataPartFilter::new(&typ, &operators.predicates, &as_of);
+            let filter = ReadFilter::default()
+                .with_projection(Arc::new(schema.clone()))
+                .with_part_filter(Arc::new(part_filter));
+            (filter, Some(schema))
+        }
+        _ => (ReadFilter::default(), None),
+    };
+
+    let layout = metadata.layout.clone();
+
     // This source is split into two parts: a first part that sets up `async_stream` and a timely
     // source operator that the continuously reads from that stream.
     //
@@ -90,7 +153,7 @@ where
         read.downgrade_since(as_of.clone()).await;
 
         let mut snapshot_iter = read
-            .snapshot(as_of.clone())
+            .snapshot_filtered(as_of.clone(), filter.clone())
             .await
             .expect("cannot serve requested as_of");
 
@@ -102,7 +165,7 @@ where
         // Then, listen continously and yield any new updates. This loop is expected to never
         // finish.
         let mut listen = read
-            .listen(as_of)
+            .listen_filtered(as_of, filter)
             .await
             .expect("cannot serve requested as_of");
 
@@ -179,8 +242,20 @@ where
             }
         });
 
-    let (ok_stream, err_stream) = timely_stream.ok_err(|x| match x {
-        ((Ok(SourceData(Ok(row))), Ok(())), ts, diff) => Ok((row, ts, diff)),
+    let (ok_stream, err_stream) = timely_stream.ok_err(move |x| match x {
+        ((Ok(SourceData(Ok(row))), Ok(())), ts, diff) => {
+            // Parts without typed columns are decoded in full, so project
+            // their rows to match the others.
+            let row = match &schema {
+                Some(schema) => schema.project(&row),
+                None => row,
+            };
+            let row = match &layout {
+                Some(layout) => layout.from_stored(&row),
+                None => row,
+            };
+            Ok((row, ts, diff))
+        }
         ((Ok(SourceData(Err(err))), Ok(())), ts, diff) => Err((err, ts, diff)),
         // TODO(petrosagg): error handling
         _ => panic!("decoding failed"),
@@ -190,3 +265,424 @@ where
 
     (ok_stream, err_stream, token)
 }
+
+/// Skips the parts of a shard that, according to their [PartStats], contain no
+/// errors and no rows that satisfy some predicate.
+///
+/// Two kinds of predicates are understood: comparisons of a column with a
+/// literal, and temporal filters that compare `mz_logical_timestamp()` with an
+/// expression that only increases with a single column. The latter skip parts
+/// in which every row stopped being visible before the `as_of`. All other
+/// predicates are assumed to be satisfiable.
+#[derive(Debug)]
+struct SourceDataPartFilter {
+    predicates: Vec<ColumnPredicate>,
+}
+
+impl SourceDataPartFilter {
+    fn new(typ: &RelationType, predicates: &[MirScalarExpr], as_of: &Antichain<Timestamp>) -> Self {
+        let as_of = as_of
+            .as_option()
+            .map(|as_of| Row::pack_slice(&[Datum::from(Numeric::from(*as_of))]));
+        let predicates = predicates
+            .iter()
+            .filter_map(|predicate| ColumnPredicate::new(predicate, typ.arity(), as_of.as_ref()))
+            .collect();
+        SourceDataPartFilter { predicates }
+    }
+}
+
+impl PartFilter for SourceDataPartFilter {
+    fn should_fetch(&self, stats: &PartStats) -> bool {
+        // Errors have to be emitted no matter what the predicates say.
+        match stats.key_column("err") {
+            Some(err) if err.nulls == stats.len => {}
+            _ => return true,
+        }
+        self.predicates
+            .iter()
+            .all(|predicate| predicate.may_match(stats))
+    }
+}
+
+/// A [PartFilter] that only fetches the parts of a collection that may contain
+/// rows with certain keys, e.g. the rows that conflict with the ones inserted
+/// into a table with unique constraints.
+///
+/// Each lookup is a set of columns and the values of those columns in the rows
+/// of interest. A part is fetched if, for some lookup, the bounds of each of
+/// its columns overlap those of the values.
+#[derive(Debug)]
+pub(crate) struct KeyPartFilter {
+    lookups: Vec<Vec<ColumnPredicate>>,
+}
+
+impl KeyPartFilter {
+    pub(crate) fn new(lookups: &[(Vec<usize>, Vec<Row>)]) -> Self {
+        let lookups = lookups
+            .iter()
+            .filter(|(_, keys)| !keys.is_empty())
+            .map(|(columns, keys)| {
+                let mut predicates = vec![];
+                for (i, column) in columns.iter().enumerate() {
+                    let values = keys
+                        .iter()
+                        .map(|key| key.iter().nth(i).unwrap_or(Datum::Null));
+                    let (lower, upper) = match (values.clone().min(), values.max()) {
+                        (Some(lower), Some(upper)) if !lower.is_null() && !upper.is_null() => {
+                            (lower, upper)
+                        }
+                        _ => continue,
+                    };
+                    for (func, literal) in [(BinaryFunc::Gte, lower), (BinaryFunc::Lte, upper)] {
+                        predicates.push(ColumnPredicate::Literal {
+                            column: *column,
+                            func,
+                            literal: Row::pack_slice(&[literal]),
+                        });
+                    }
+                }
+                predicates
+            })
+            .collect();
+        KeyPartFilter { lookups }
+    }
+}
+
+impl PartFilter for KeyPartFilter {
+    fn should_fetch(&self, stats: &PartStats) -> bool {
+        self.lookups.iter().any(|predicates| {
+            predicates
+                .iter()
+                .all(|predicate| predicate.may_match(stats))
+        })
+    }
+}
+
+/// A predicate on a single column of a collection.
+#[derive(Debug)]
+enum ColumnPredicate {
+    /// `#column <func> literal`, where `func` is one of `=`, `<`, `<=`, `>`,
+    /// and `>=`.
+    Literal {
+        column: usize,
+        func: BinaryFunc,
+        literal: Row,
+    },
+    /// `mz_logical_timestamp() <func> expr`, where `func` is one of `=`, `<`,
+    /// and `<=`, and `expr` is non-decreasing in `column`.
+    Temporal {
+        column: usize,
+        arity: usize,
+        func: BinaryFunc,
+        expr: MirScalarExpr,
+        as_of: Row,
+    },
+}
+
+impl ColumnPredicate {
+    fn new(predicate: &MirScalarExpr, arity: usize, as_of: Option<&Row>) -> Option<Self> {
+        let (func, expr1, expr2) = match predicate {
+            MirScalarExpr::CallBinary { func, expr1, expr2 } => (func, expr1, expr2),
+            _ => return None,
+        };
+        let mz_logical_timestamp =
+            MirScalarExpr::CallUnmaterializable(UnmaterializableFunc::MzLogicalTimestamp);
+        // Put the column or `mz_logical_timestamp()` on the left.
+        let (func, expr1, expr2) = if expr1.as_column().is_some() || **expr1 == mz_logical_timestamp
+        {
+            (func.clone(), expr1, expr2)
+        } else {
+            let func = match func {
+                BinaryFunc::Eq => BinaryFunc::Eq,
+                BinaryFunc::Lt => BinaryFunc::Gt,
+                BinaryFunc::Lte => BinaryFunc::Gte,
+                BinaryFunc::Gt => BinaryFunc::Lt,
+                BinaryFunc::Gte => BinaryFunc::Lte,
+                _ => return None,
+            };
+            (func, expr2, expr1)
+        };
+
+        if let Some(column) = expr1.as_column() {
+            match (&func, expr2.as_literal()) {
+                (
+                    BinaryFunc::Eq
+                    | BinaryFunc::Lt
+                    | BinaryFunc::Lte
+                    | BinaryFunc::Gt
+                    | BinaryFunc::Gte,
+                    Some(Ok(literal)),
+                ) if !literal.is_null() => Some(ColumnPredicate::Literal {
+                    column,
+                    func: func.clone(),
+                    literal: Row::pack_slice(&[literal]),
+                }),
+                _ => None,
+            }
+        } else if **expr1 == mz_logical_timestamp {
+            match (&func, non_decreasing_in(expr2), as_of) {
+                (BinaryFunc::Eq | BinaryFunc::Lt | BinaryFunc::Lte, Some(column), Some(as_of)) => {
+                    Some(ColumnPredicate::Temporal {
+                        column,
+                        arity,
+                        func: func.clone(),
+                        expr: (**expr2).clone(),
+                        as_of: as_of.clone(),
+                    })
+                }
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Returns false if no row in a part with the given statistics can satisfy
+    /// this predicate.
+    fn may_match(&self, stats: &PartStats) -> bool {
+        let column = match self {
+            ColumnPredicate::Literal { column, .. } | ColumnPredicate::Temporal { column, .. } => {
+                *column
+            }
+        };
+        let column_stats = match stats.key_column(&SourceDataSchema::ok_column_name(column)) {
+            Some(x) => x,
+            None => return true,
+        };
+        let (lower, upper) = match &column_stats.bounds {
+            Some(bounds) => bounds_datums(bounds),
+            // The predicate is null, rather than true, for rows where the
+            // column is null.
+            None => return column_stats.nulls < stats.len,
+        };
+        match self {
+            ColumnPredicate::Literal { func, literal, .. } => {
+                let literal = literal.unpack_first();
+                if mem::discriminant(&literal) != mem::discriminant(&lower) {
+                    return true;
+                }
+                match func {
+                    BinaryFunc::Eq => lower <= literal && literal <= upper,
+                    BinaryFunc::Lt => lower < literal,
+                    BinaryFunc::Lte => lower <= literal,
+                    BinaryFunc::Gt => upper > literal,
+                    BinaryFunc::Gte => upper >= literal,
+                    _ => true,
+                }
+            }
+            ColumnPredicate::Temporal {
+                column,
+                arity,
+                func,
+                expr,
+                as_of,
+            } => {
+                let arena = RowArena::new();
+                // Rows for which `expr` fails have to be fetched so that the
+                // dataflow reports the error. Since `expr` is monotonic, it
+                // can only fail on the bounds if it fails on any row.
+                let upper = match (
+                    eval_at(expr, *arity, *column, lower, &arena),
+                    eval_at(expr, *arity, *column, upper, &arena),
+                ) {
+                    (Ok(_), Ok(upper)) => upper,
+                    _ => return true,
+                };
+                let as_of = as_of.unpack_first();
+                if mem::discriminant(&upper) != mem::discriminant(&as_of) {
+                    return true;
+                }
+                // Every emitted time is at least `as_of`, and rows are only
+                // visible at times before `upper` (or at `upper`, for `<=` and
+                // `=`).
+                match func {
+                    BinaryFunc::Lt => upper > as_of,
+                    _ => upper >= as_of,
+                }
+            }
+        }
+    }
+}
+
+/// Returns the column that `expr` depends on, if it depends on exactly one
+/// column and never decreases when that column increases.
+fn non_decreasing_in(expr: &MirScalarExpr) -> Option<usize> {
+    match expr {
+        MirScalarExpr::Column(column) => Some(*column),
+        MirScalarExpr::CallUnary { func, expr } => match func {
+            UnaryFunc::CastInt16ToInt32(_)
+            | UnaryFunc::CastInt16ToInt64(_)
+            | UnaryFunc::CastInt32ToInt64(_)
+            | UnaryFunc::CastInt16ToNumeric(_)
+            | UnaryFunc::CastInt32ToNumeric(_)
+            | UnaryFunc::CastInt64ToNumeric(_) => non_decreasing_in(expr),
+            _ => None,
+        },
+        MirScalarExpr::CallBinary { func, expr1, expr2 } => {
+            let (expr, literal) = match (expr1.as_literal(), expr2.as_literal()) {
+                (None, Some(Ok(literal))) => (expr1, literal),
+                (Some(Ok(literal)), None) => (expr2, literal),
+                _ => return None,
+            };
+            let non_decreasing = match (func, literal) {
+                (
+                    BinaryFunc::AddInt16
+                    | BinaryFunc::AddInt32
+                    | BinaryFunc::AddInt64
+                    | BinaryFunc::AddNumeric,
+                    _,
+                ) => true,
+                (BinaryFunc::MulInt64, Datum::Int64(x)) => x >= 0,
+                (BinaryFunc::MulNumeric, Datum::Numeric(x)) => !x.0.is_negative(),
+                _ => false,
+            };
+            if non_decreasing {
+                non_decreasing_in(expr)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Evaluates `expr` on a row in which `column` is `datum`.
+fn eval_at<'a>(
+    expr: &'a MirScalarExpr,
+    arity: usize,
+    column: usize,
+    datum: Datum<'a>,
+    arena: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let mut datums = vec![Datum::Null; arity];
+    datums[column] = datum;
+    expr.eval(&datums, arena)
+}
+
+fn bounds_datums(bounds: &ColumnBounds) -> (Datum, Datum) {
+    match bounds {
+        ColumnBounds::Bool(lower, upper) => (Datum::from(*lower), Datum::from(*upper)),
+        ColumnBounds::Int16(lower, upper) => (Datum::from(*lower), Datum::from(*upper)),
+        ColumnBounds::Int32(lower, upper) => (Datum::from(*lower), Datum::from(*upper)),
+        ColumnBounds::Int64(lower, upper) => (Datum::from(*lower), Datum::from(*upper)),
+        ColumnBounds::Float32(lower, upper) => (Datum::from(*lower), Datum::from(*upper)),
+        ColumnBounds::Float64(lower, upper) => (Datum::from(*lower), Datum::from(*upper)),
+        ColumnBounds::String(lower, upper) => {
+            (Datum::from(lower.as_str()), Datum::from(upper.as_str()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_expr::func::CastInt64ToNumeric;
+    use mz_persist_types::columnar::ColumnStats;
+    use mz_repr::ScalarType;
+
+    use super::*;
+
+    fn stats(len: usize, errs: usize, columns: &[(usize, usize, Option<(i64, i64)>)]) -> PartStats {
+        let mut key = vec![(
+            "err".to_owned(),
+            ColumnStats {
+                nulls: len - errs,
+                bounds: None,
+            },
+        )];
+        for (column, nulls, bounds) in columns {
+            let bounds = bounds.map(|(lower, upper)| ColumnBounds::Int64(lower, upper));
+            key.push((
+                SourceDataSchema::ok_column_name(*column),
+                ColumnStats {
+                    nulls: *nulls,
+                    bounds,
+                },
+            ));
+        }
+        PartStats { len, key }
+    }
+
+    #[test]
+    fn part_filter() {
+        let typ = RelationType::new(vec![
+            ScalarType::Int64.nullable(true),
+            ScalarType::Int64.nullable(false),
+        ]);
+        let literal = |x| MirScalarExpr::literal_ok(Datum::Int64(x), ScalarType::Int64);
+        // #0 >= 5 AND mz_logical_timestamp() < (#1 + 1000)::numeric
+        let predicates = vec![
+            literal(5).call_binary(MirScalarExpr::Column(0), BinaryFunc::Lte),
+            MirScalarExpr::CallUnmaterializable(UnmaterializableFunc::MzLogicalTimestamp)
+                .call_binary(
+                    MirScalarExpr::Column(1)
+                        .call_binary(literal(1000), BinaryFunc::AddInt64)
+                        .call_unary(UnaryFunc::CastInt64ToNumeric(CastInt64ToNumeric(None))),
+                    BinaryFunc::Lt,
+                ),
+        ];
+        let filter = SourceDataPartFilter::new(&typ, &predicates, &Antichain::from_elem(2000));
+
+        // Parts with rows that may satisfy both predicates are fetched.
+        assert!(filter.should_fetch(&stats(
+            2,
+            0,
+            &[(0, 0, Some((1, 5))), (1, 0, Some((0, 1001)))]
+        )));
+        // As are parts without stats, or with errors.
+        assert!(filter.should_fetch(&PartStats::default()));
+        assert!(filter.should_fetch(&stats(2, 1, &[(0, 1, Some((1, 1))), (1, 1, Some((0, 0)))])));
+        // But not those where every row fails the first predicate...
+        assert!(!filter.should_fetch(&stats(
+            2,
+            0,
+            &[(0, 0, Some((1, 4))), (1, 0, Some((0, 1001)))]
+        )));
+        assert!(!filter.should_fetch(&stats(2, 0, &[(0, 2, None), (1, 0, Some((0, 1001)))])));
+        // ...or was no longer visible at the as_of.
+        assert!(!filter.should_fetch(&stats(
+            2,
+            0,
+            &[(0, 0, Some((1, 5))), (1, 0, Some((0, 1000)))]
+        )));
+        // Unless computing the temporal bound fails for some row.
+        assert!(filter.should_fetch(&stats(
+            2,
+            0,
+            &[(0, 0, Some((1, 5))), (1, 0, Some((0, i64::MAX)))]
+        )));
+    }
+
+    #[test]
+    fn key_part_filter() {
+        let key = |x: i64, y: i64| Row::pack_slice(&[Datum::Int64(x), Datum::Int64(y)]);
+        // Rows whose (#0, #1) is one of (1, 10) or (5, 20), or whose #1 is 100.
+        let filter = KeyPartFilter::new(&[
+            (vec![0, 1], vec![key(1, 10), key(5, 20)]),
+            (vec![1], vec![Row::pack_slice(&[Datum::Int64(100)])]),
+        ]);
+
+        // Parts whose bounds overlap those of some lookup's keys are fetched.
+        assert!(filter.should_fetch(&stats(2, 0, &[(0, 0, Some((4, 8))), (1, 0, Some((0, 12)))])));
+        assert!(filter.should_fetch(&stats(
+            2,
+            0,
+            &[(0, 0, Some((9, 9))), (1, 0, Some((90, 110)))]
+        )));
+        // As are parts without stats.
+        assert!(filter.should_fetch(&PartStats::default()));
+        // But not those whose bounds miss the keys of every lookup.
+        assert!(!filter.should_fetch(&stats(2, 0, &[(0, 0, Some((6, 8))), (1, 0, Some((0, 12)))])));
+        assert!(!filter.should_fetch(&stats(
+            2,
+            0,
+            &[(0, 0, Some((1, 5))), (1, 0, Some((21, 99)))]
+        )));
+        // Or that only have nulls in a key column.
+        assert!(!filter.should_fetch(&stats(2, 0, &[(0, 2, None), (1, 2, None)])));
+
+        // Lookups without keys fetch nothing.
+        let filter = KeyPartFilter::new(&[(vec![0], vec![])]);
+        assert!(!filter.should_fetch(&PartStats::default()));
+    }
+}
@@ -22,8 +22,8 @@ use timely::scheduling::SyncActivator;
 use tokio::sync::mpsc::{Receiver, Sender};
 use tokio_postgres::error::{DbError, Severity, SqlState};
 use tokio_postgres::replication::LogicalReplicationStream;
-use tokio_postgres::types::PgLsn;
-use tokio_postgres::SimpleQueryMessage;
+use tokio_postgres::types::{PgLsn, Type as PgType};
+use tokio_postgres::{Client, SimpleQueryMessage};
 use tracing::{error, info, warn};
 
 use mz_expr::PartitionId;
@@ -34,10 +34,11 @@ use mz_repr::{Datum, Diff, GlobalId, Row};
 use self::metrics::PgSourceMetrics;
 use super::metrics::SourceBaseMetrics;
 use crate::source::{
-    NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
+    NextMessage, PersistedSourceOutput, SourceMessage, SourceMessageType, SourceReader,
+    SourceReaderError,
 };
 use crate::types::connections::ConnectionContext;
-use crate::types::errors::SourceErrorDetails;
+use crate::types::errors::{DataflowError, SourceError, SourceErrorDetails};
 use crate::types::sources::{encoding::SourceDataEncoding, MzOffset, SourceConnection};
 
 mod metrics;
@@ -125,8 +126,9 @@ macro_rules! try_recoverable {
 // Message used to communicate between `get_next_message` and the tokio task
 enum InternalMessage {
     Err(SourceReaderError),
+    /// A row, or a definite error, at an lsn
     Value {
-        value: Row,
+        value: Result<Row, SourceErrorDetails>,
         lsn: PgLsn,
         diff: Diff,
         end: bool,
@@ -148,10 +150,69 @@ struct PostgresTaskInfo {
     lsn: PgLsn,
     metrics: PgSourceMetrics,
     source_tables: HashMap<u32, PostgresTableDesc>,
+    /// The last known rows of the source tables that are replicated by primary key
+    table_state: HashMap<u32, TableState>,
+    /// The output of the source so far, used to rebuild `table_state` when resuming
+    persisted_output: PersistedSourceOutput,
     row_sender: RowSender,
     sender: Sender<InternalMessage>,
 }
 
+/// The last known value of every row of a table with `REPLICA IDENTITY DEFAULT`, keyed by the
+/// table's primary key.
+///
+/// For such tables Postgres only includes the primary key columns of the old row of an update or
+/// delete in the replication stream, and omits TOASTed values that an update did not change, so
+/// we keep the full rows around to produce retractions and fill in the unchanged values.
+struct TableState {
+    /// The indices of the table's primary key columns
+    key_indices: Vec<usize>,
+    /// The last known row for each primary key, in the form produced by the source
+    rows: HashMap<Row, Row>,
+}
+
+impl TableState {
+    fn new(table: &PostgresTableDesc) -> Self {
+        TableState {
+            key_indices: table
+                .columns
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.primary_key)
+                .map(|(i, _)| i)
+                .collect(),
+            rows: HashMap::new(),
+        }
+    }
+
+    /// Packs the primary key of a tuple received in the replication stream.
+    fn key_from_tuple(&self, tuple_data: &[TupleData]) -> Result<Row, anyhow::Error> {
+        let mut key = Row::default();
+        let mut packer = key.packer();
+        for i in &self.key_indices {
+            match tuple_data.get(*i) {
+                Some(TupleData::Null) => packer.push(Datum::Null),
+                Some(TupleData::Text(b)) => packer.push(Datum::String(std::str::from_utf8(b)?)),
+                Some(TupleData::UnchangedToast) | None => {
+                    bail!("primary key column missing from replication stream")
+                }
+            }
+        }
+        Ok(key)
+    }
+
+    /// Packs the primary key of a row produced by the source.
+    fn key_from_row(&self, row: &Row) -> Row {
+        let values = row
+            .iter()
+            .nth(1)
+            .expect("source rows have two columns")
+            .unwrap_list();
+        let values = values.iter().collect::<Vec<_>>();
+        Row::pack(self.key_indices.iter().map(|i| values[*i]))
+    }
+}
+
 impl SourceReader for PostgresSourceReader {
     type Key = ();
     type Value = Row;
@@ -169,6 +230,7 @@ impl SourceReader for PostgresSourceReader {
         _encoding: SourceDataEncoding,
         metrics: SourceBaseMetrics,
         connection_context: ConnectionContext,
+        persisted_output: PersistedSourceOutput,
     ) -> Result<Self, anyhow::Error> {
         let connection = match connection {
             SourceConnection::Postgres(pg) => pg,
@@ -213,6 +275,8 @@ impl SourceReader for PostgresSourceReader {
             source_tables: HashMap::from_iter(
                 connection.details.tables.iter().map(|t| (t.oid, t.clone())),
             ),
+            table_state: HashMap::new(),
+            persisted_output,
             row_sender: RowSender::new(dataflow_tx.clone(), consumer_activator),
             sender: dataflow_tx,
         };
@@ -233,7 +297,18 @@ impl SourceReader for PostgresSourceReader {
         // TODO(guswynn): consider if `try_recv` is better or the same as `now_or_never`
         let ret = match self.receiver_stream.recv().now_or_never() {
             Some(Some(InternalMessage::Value {
-                value,
+                value: Err(error),
+                lsn,
+                end,
+                ..
+            })) => Ok(NextMessage::Ready(SourceMessageType::DefiniteError {
+                partition: PartitionId::None,
+                offset: lsn.into(),
+                error: SourceReaderError { inner: error },
+                finalized: end,
+            })),
+            Some(Some(InternalMessage::Value {
+                value: Ok(value),
                 diff,
                 lsn,
                 end,
@@ -327,6 +402,24 @@ async fn postgres_replication_loop_inner(
                 })
             }
         }
+    } else {
+        loop {
+            match task_info.rehydrate_table_state().await {
+                Ok(()) => break,
+                Err(ReplicationError::Recoverable(e)) => {
+                    warn!(
+                        "rehydrating state for source {} failed, retrying: {}",
+                        task_info.source_id, e
+                    );
+                    tokio::time::sleep(Duration::from_secs(3)).await;
+                }
+                Err(ReplicationError::Fatal(e)) => {
+                    return Err(SourceReaderError {
+                        inner: SourceErrorDetails::Initialization(e.to_string()),
+                    })
+                }
+            }
+        }
     }
 
     loop {
@@ -363,7 +456,7 @@ async fn postgres_replication_loop_inner(
 struct RowSender {
     sender: Sender<InternalMessage>,
     activator: SyncActivator,
-    buffered_message: Option<(Row, PgLsn, i64)>,
+    buffered_message: Option<(Result<Row, SourceErrorDetails>, PgLsn, i64)>,
 }
 
 impl RowSender {
@@ -384,7 +477,7 @@ impl RowSender {
                 .await;
         }
 
-        self.buffered_message = Some((row, lsn, 1));
+        self.buffered_message = Some((Ok(row), lsn, 1));
     }
     /// Delete a row at an lsn.
     pub async fn delete(&mut self, row: Row, lsn: PgLsn) {
@@ -394,7 +487,18 @@ impl RowSender {
                 .await;
         }
 
-        self.buffered_message = Some((row, lsn, -1));
+        self.buffered_message = Some((Ok(row), lsn, -1));
+    }
+
+    /// Report a definite error at an lsn.
+    pub async fn error(&mut self, error: SourceErrorDetails, lsn: PgLsn) {
+        if let Some((buffered_row, buffered_lsn, buffered_diff)) = self.buffered_message.take() {
+            assert_eq!(buffered_lsn, lsn);
+            self.send_row(buffered_row, buffered_lsn, buffered_diff, false)
+                .await;
+        }
+
+        self.buffered_message = Some((Err(error), lsn, 1));
     }
 
     /// Finalize an lsn, making sure all messages that my be buffered are flushed, and that the
@@ -408,7 +512,13 @@ impl RowSender {
         }
     }
 
-    async fn send_row(&self, row: Row, lsn: PgLsn, diff: i64, end: bool) {
+    async fn send_row(
+        &self,
+        row: Result<Row, SourceErrorDetails>,
+        lsn: PgLsn,
+        diff: i64,
+        end: bool,
+    ) {
         // a closed receiver means the source has been shutdown
         // (dropped or the process is dying), so just continue on
         // without activation
@@ -431,19 +541,34 @@ impl RowSender {
 
 // implement the core pg logic in this impl block
 impl PostgresTaskInfo {
-    /// Validates that all expected tables exist in the publication tables and they have the same schema
-    fn validate_tables(&self, tables: Vec<PostgresTableDesc>) -> Result<(), anyhow::Error> {
+    /// Validates that all expected tables exist in the publication tables and that their schemas
+    /// are compatible with the ones the source was created with.
+    ///
+    /// Returns the oids of the tables whose schemas have changed incompatibly, along with the
+    /// error to report for each of them.
+    fn validate_tables(
+        &self,
+        tables: Vec<PostgresTableDesc>,
+    ) -> Result<Vec<(u32, SourceErrorDetails)>, anyhow::Error> {
         let pub_tables: HashMap<u32, PostgresTableDesc> =
             tables.into_iter().map(|t| (t.oid, t)).collect();
+        let mut incompatible = vec![];
         for (id, schema) in self.source_tables.iter() {
             match pub_tables.get(id) {
                 Some(pub_schema) => {
-                    if pub_schema != schema {
+                    let columns = pub_schema
+                        .columns
+                        .iter()
+                        .map(|c| (c.name.as_str(), c.type_oid, c.type_mod));
+                    let compatible = pub_schema.name == schema.name
+                        && pub_schema.namespace == schema.namespace
+                        && columns_compatible(schema, columns);
+                    if !compatible {
                         error!(
                             "Error validating table in publication. Expected: {:?} Actual: {:?}",
                             schema, pub_schema
                         );
-                        bail!("Schema for table {} differs, recreate Materialize source to use new schema", schema.name)
+                        incompatible.push((*id, table_altered_error(schema)));
                     }
                 }
                 None => {
@@ -456,9 +581,106 @@ impl PostgresTaskInfo {
                 }
             }
         }
+        Ok(incompatible)
+    }
+
+    /// Sets up empty [`TableState`]s for the source tables that are replicated by primary key,
+    /// i.e. tables that have a primary key and `REPLICA IDENTITY DEFAULT`.
+    async fn init_table_state(&mut self, client: &Client) -> Result<(), anyhow::Error> {
+        self.table_state.clear();
+        let keyed_tables = self
+            .source_tables
+            .values()
+            .filter(|t| t.columns.iter().any(|c| c.primary_key))
+            .map(|t| t.oid.to_string())
+            .collect::<Vec<_>>();
+        if keyed_tables.is_empty() {
+            return Ok(());
+        }
+        let query = format!(
+            "SELECT oid, relreplident FROM pg_catalog.pg_class WHERE oid IN ({})",
+            keyed_tables.join(", ")
+        );
+        for msg in client.simple_query(&query).await? {
+            if let SimpleQueryMessage::Row(row) = msg {
+                let oid: u32 = row
+                    .get("oid")
+                    .ok_or_else(|| anyhow!("missing expected column: `oid`"))?
+                    .parse()?;
+                if row.get("relreplident") == Some("d") {
+                    self.table_state
+                        .insert(oid, TableState::new(&self.source_tables[&oid]));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the [`TableState`]s from the output the source has produced so far, so that
+    /// replication can resume where it left off.
+    ///
+    /// Tables whose incompatible alteration has already been reported are no longer replicated.
+    /// The replication stream shows their new schema again when it is replayed, and their error
+    /// must not be reported a second time.
+    async fn rehydrate_table_state(&mut self) -> Result<(), ReplicationError> {
+        let errors = try_recoverable!(self.persisted_output.errors().await);
+        let source_id = self.source_id;
+        self.source_tables.retain(|_, table| {
+            let error =
+                DataflowError::SourceError(SourceError::new(source_id, table_altered_error(table)));
+            !errors
+                .iter()
+                .any(|(committed, diff)| *committed == error && *diff > 0)
+        });
+
+        let client = try_recoverable!(
+            mz_postgres_util::connect_replication(self.connection_config.clone()).await
+        );
+        try_recoverable!(self.init_table_state(&client).await);
+        if self.table_state.is_empty() {
+            return Ok(());
+        }
+
+        let output = try_recoverable!(self.persisted_output.snapshot().await);
+        for (row, diff) in output {
+            let rel_id = match row.iter().next() {
+                Some(Datum::Int32(rel_id)) => rel_id as u32,
+                _ => continue,
+            };
+            if let Some(state) = self.table_state.get_mut(&rel_id) {
+                if diff != 1 {
+                    return Err(ReplicationError::Fatal(anyhow!(
+                        "unexpected multiplicity {} for row of table with OID = {}",
+                        diff,
+                        rel_id
+                    )));
+                }
+                state.rows.insert(state.key_from_row(&row), row);
+            }
+        }
         Ok(())
     }
 
+    /// Returns the last known row with primary key `key` of the table with OID `rel_id`, taking
+    /// the changes made by the transaction that is being replicated into account.
+    fn last_known_row(
+        &self,
+        pending_state: &HashMap<u32, HashMap<Row, Option<Row>>>,
+        rel_id: u32,
+        key: &Row,
+    ) -> Result<Row, anyhow::Error> {
+        let row = match pending_state.get(&rel_id).and_then(|rows| rows.get(key)) {
+            Some(row) => row.as_ref(),
+            None => self.table_state[&rel_id].rows.get(key),
+        };
+        row.cloned().ok_or_else(|| {
+            anyhow!(
+                "Unknown row in replication stream for table with OID = {}",
+                rel_id
+            )
+        })
+    }
+
     /// Creates the replication slot and produces the initial snapshot of the data
     ///
     /// After the initial snapshot has been produced it returns the name of the created slot and
@@ -478,8 +700,12 @@ impl PostgresTaskInfo {
         let publication_tables = try_recoverable!(
             mz_postgres_util::publication_info(&self.connection_config, &self.publication).await
         );
-        // Validate publication tables against the state snapshot
-        try_fatal!(self.validate_tables(publication_tables));
+        // Validate publication tables against the state snapshot. Tables that have been altered
+        // incompatibly are excluded from the source and reported as errors.
+        let incompatible_tables = try_fatal!(self.validate_tables(publication_tables));
+        for (oid, _) in &incompatible_tables {
+            self.source_tables.remove(oid);
+        }
 
         // Start a transaction and immediately create a replication slot with the USE SNAPSHOT
         // directive. This makes the starting point of the slot and the snapshot of the transaction
@@ -514,13 +740,25 @@ impl PostgresTaskInfo {
         self.lsn = try_fatal!(consistent_point
             .parse()
             .or_else(|_| Err(anyhow!("invalid lsn"))));
+        for (_, error) in incompatible_tables {
+            self.row_sender.error(error, self.lsn).await;
+        }
+        try_fatal!(self.init_table_state(&client).await);
         for info in self.source_tables.values() {
             let relation_id: Datum = (i32::try_from(info.oid).unwrap()).into();
+            // Name the columns explicitly so that columns added upstream after the source was
+            // created are not included in the snapshot.
+            let columns = info
+                .columns
+                .iter()
+                .map(|c| format!("{:?}", c.name))
+                .collect::<Vec<_>>()
+                .join(", ");
             let reader = client
                 .copy_out_simple(
                     format!(
-                        "COPY {:?}.{:?} TO STDOUT (FORMAT TEXT, DELIMITER '\t')",
-                        info.namespace, info.name
+                        "COPY {:?}.{:?} ({}) TO STDOUT (FORMAT TEXT, DELIMITER '\t')",
+                        info.namespace, info.name, columns
                     )
                     .as_str(),
                 )
@@ -551,6 +789,11 @@ impl PostgresTaskInfo {
                     Ok(())
                 }));
 
+                if let Some(state) = self.table_state.get_mut(&info.oid) {
+                    state
+                        .rows
+                        .insert(state.key_from_row(&mz_row), mz_row.clone());
+                }
                 self.row_sender.insert(mz_row.clone(), self.lsn).await;
             }
 
@@ -569,26 +812,45 @@ impl PostgresTaskInfo {
     /// replication protocol doesn't use the binary encoding for column values so contrary to the
     /// initial snapshot here we need to parse the textual form of each column.
     ///
-    /// The `old_tuple` argument can be used as a source of data to use when encountering unchanged
+    /// Only the first `num_columns` values are kept, which projects tuples of tables that have
+    /// had columns added upstream onto the columns the source was created with.
+    ///
+    /// The `last_row` argument can be used as a source of data to use when encountering unchanged
     /// TOAST values.
-    fn row_from_tuple<'a, T>(rel_id: u32, tuple_data: T) -> Result<Row, anyhow::Error>
+    fn row_from_tuple<'a, T>(
+        rel_id: u32,
+        num_columns: usize,
+        tuple_data: T,
+        last_row: Option<&Row>,
+    ) -> Result<Row, anyhow::Error>
     where
         T: IntoIterator<Item = &'a TupleData>,
     {
         let mut row = Row::default();
         let mut packer = row.packer();
 
+        let last_values = last_row.map(|row| {
+            row.iter()
+                .nth(1)
+                .expect("source rows have two columns")
+                .unwrap_list()
+                .iter()
+                .collect::<Vec<_>>()
+        });
         let rel_id: Datum = (rel_id as i32).into();
         packer.push(rel_id);
         packer.push_list_with(move |packer| {
-            for val in tuple_data.into_iter() {
+            for (i, val) in tuple_data.into_iter().take(num_columns).enumerate() {
                 let datum = match val {
                     TupleData::Null => Datum::Null,
-                    TupleData::UnchangedToast => bail!(
-                        "Missing TOASTed value from table with OID = {}. \
-                        Did you forget to set REPLICA IDENTITY to FULL for your table?",
-                        rel_id
-                    ),
+                    TupleData::UnchangedToast => match &last_values {
+                        Some(values) => values[i],
+                        None => bail!(
+                            "Missing TOASTed value from table with OID = {}. \
+                            Did you forget to set REPLICA IDENTITY to FULL for your table?",
+                            rel_id
+                        ),
+                    },
                     TupleData::Text(b) => std::str::from_utf8(&b)?.into(),
                 };
                 packer.push(datum);
@@ -621,6 +883,9 @@ impl PostgresTaskInfo {
         let mut last_keepalive = Instant::now();
         let mut inserts = vec![];
         let mut deletes = vec![];
+        let mut errors = vec![];
+        // Changes to `table_state` made by the current transaction, applied once it commits
+        let mut pending_state: HashMap<u32, HashMap<Row, Option<Row>>> = HashMap::new();
         let closer = self.sender.clone();
 
         loop {
@@ -671,7 +936,7 @@ impl PostgresTaskInfo {
 
                                 match xlog_data.data() {
                                     Begin(_) => {
-                                        if !inserts.is_empty() || !deletes.is_empty() {
+                                        if !inserts.is_empty() || !deletes.is_empty() || !errors.is_empty() {
                                             return Err(Fatal(anyhow!(
                                                 "got BEGIN statement after uncommitted data"
                                             )));
@@ -680,17 +945,41 @@ impl PostgresTaskInfo {
                                     Insert(insert) => {
                                         self.metrics.inserts.inc();
                                         let rel_id = insert.rel_id();
-                                        if !self.source_tables.contains_key(&rel_id) {
-                                            continue;
-                                        }
+                                        let num_columns = match self.source_tables.get(&rel_id) {
+                                            Some(table) => table.columns.len(),
+                                            None => continue,
+                                        };
                                         let new_tuple = insert.tuple().tuple_data();
-                                        let row = try_fatal!(PostgresTaskInfo::row_from_tuple(rel_id, new_tuple));
+                                        let row = try_fatal!(PostgresTaskInfo::row_from_tuple(rel_id, num_columns, new_tuple, None));
+                                        if let Some(state) = self.table_state.get(&rel_id) {
+                                            let key = try_fatal!(state.key_from_tuple(new_tuple));
+                                            pending_state.entry(rel_id).or_default().insert(key, Some(row.clone()));
+                                        }
                                         inserts.push(row);
                                     }
                                     Update(update) => {
                                         self.metrics.updates.inc();
                                         let rel_id = update.rel_id();
-                                        if !self.source_tables.contains_key(&rel_id) {
+                                        let num_columns = match self.source_tables.get(&rel_id) {
+                                            Some(table) => table.columns.len(),
+                                            None => continue,
+                                        };
+                                        let new_tuple = update.new_tuple().tuple_data();
+                                        if let Some(state) = self.table_state.get(&rel_id) {
+                                            // The old row is only included if the primary key changed
+                                            let old_key = match update.key_tuple().or(update.old_tuple()) {
+                                                Some(old_tuple) => try_fatal!(state.key_from_tuple(old_tuple.tuple_data())),
+                                                None => try_fatal!(state.key_from_tuple(new_tuple)),
+                                            };
+                                            let old_row = try_fatal!(self.last_known_row(&pending_state, rel_id, &old_key));
+                                            let new_row = try_fatal!(PostgresTaskInfo::row_from_tuple(rel_id, num_columns, new_tuple, Some(&old_row)));
+                                            let new_key = state.key_from_row(&new_row);
+
+                                            let pending_rows = pending_state.entry(rel_id).or_default();
+                                            pending_rows.insert(old_key, None);
+                                            pending_rows.insert(new_key, Some(new_row.clone()));
+                                            deletes.push(old_row);
+                                            inserts.push(new_row);
                                             continue;
                                         }
                                         let old_tuple = try_fatal!(update
@@ -699,14 +988,12 @@ impl PostgresTaskInfo {
                                                     Did you forget to set REPLICA IDENTITY to FULL for your table?", rel_id)))
                                         .tuple_data();
                                         let old_row =
-                                            try_fatal!(PostgresTaskInfo::row_from_tuple(rel_id, old_tuple));
+                                            try_fatal!(PostgresTaskInfo::row_from_tuple(rel_id, num_columns, old_tuple, None));
                                         deletes.push(old_row);
 
                                         // If the new tuple contains unchanged toast values, reuse the ones
                                         // from the old tuple
-                                        let new_tuple = update
-                                            .new_tuple()
-                                            .tuple_data()
+                                        let new_tuple = new_tuple
                                             .iter()
                                             .zip(old_tuple.iter())
                                             .map(|(new, old)| match new {
@@ -714,13 +1001,26 @@ impl PostgresTaskInfo {
                                                 _ => new,
                                             });
                                         let new_row =
-                                            try_fatal!(PostgresTaskInfo::row_from_tuple(rel_id, new_tuple));
+                                            try_fatal!(PostgresTaskInfo::row_from_tuple(rel_id, num_columns, new_tuple, None));
                                         inserts.push(new_row);
                                     }
                                     Delete(delete) => {
                                         self.metrics.deletes.inc();
                                         let rel_id = delete.rel_id();
-                                        if !self.source_tables.contains_key(&rel_id) {
+                                        let num_columns = match self.source_tables.get(&rel_id) {
+                                            Some(table) => table.columns.len(),
+                                            None => continue,
+                                        };
+                                        if let Some(state) = self.table_state.get(&rel_id) {
+                                            let old_tuple = try_fatal!(delete
+                                                .key_tuple()
+                                                .or(delete.old_tuple())
+                                                .ok_or_else(|| anyhow!("Old row missing from replication stream for table with OID = {}", rel_id)))
+                                            .tuple_data();
+                                            let key = try_fatal!(state.key_from_tuple(old_tuple));
+                                            let row = try_fatal!(self.last_known_row(&pending_state, rel_id, &key));
+                                            pending_state.entry(rel_id).or_default().insert(key, None);
+                                            deletes.push(row);
                                             continue;
                                         }
                                         let old_tuple = try_fatal!(delete
@@ -728,7 +1028,7 @@ impl PostgresTaskInfo {
                                             .ok_or_else(|| anyhow!("Old row missing from replication stream for table with OID = {}. \
                                                     Did you forget to set REPLICA IDENTITY to FULL for your table?", rel_id)))
                                         .tuple_data();
-                                        let row = try_fatal!(PostgresTaskInfo::row_from_tuple(rel_id, old_tuple));
+                                        let row = try_fatal!(PostgresTaskInfo::row_from_tuple(rel_id, num_columns, old_tuple, None));
                                         deletes.push(row);
                                     }
                                     Commit(commit) => {
@@ -741,62 +1041,60 @@ impl PostgresTaskInfo {
                                         for row in inserts.drain(..) {
                                             self.row_sender.insert(row, self.lsn).await;
                                         }
+                                        for error in errors.drain(..) {
+                                            self.row_sender.error(error, self.lsn).await;
+                                        }
+                                        for (rel_id, rows) in pending_state.drain() {
+                                            if let Some(state) = self.table_state.get_mut(&rel_id) {
+                                                for (key, row) in rows {
+                                                    match row {
+                                                        Some(row) => state.rows.insert(key, row),
+                                                        None => state.rows.remove(&key),
+                                                    };
+                                                }
+                                            }
+                                        }
 
                                         self.row_sender.close_lsn(self.lsn).await;
                                         self.metrics.lsn.set(self.lsn.into());
                                     }
                                     Relation(relation) => {
                                         let rel_id = relation.rel_id();
-                                        match self.source_tables.get(&rel_id) {
-                                            Some(source_table) => {
-                                                // Start with the cheapest check first, this will catch the majority of alters
-                                                if source_table.columns.len() != relation.columns().len() {
-                                                    error!(
-                                                        "alter table detected on {} with id {}",
-                                                        source_table.name, source_table.oid
-                                                    );
-                                                    return Err(Fatal(anyhow!(
-                                                        "source table {} with oid {} has been altered",
-                                                        source_table.name,
-                                                        source_table.oid
-                                                    )));
-                                                }
-                                                if source_table.name.ne(relation.name().unwrap())
-                                                    || source_table.namespace.ne(relation.namespace().unwrap())
-                                                {
-                                                    error!(
-                                                        "table name changed on {}.{} with id {} to {}.{}",
-                                                        source_table.namespace,
-                                                        source_table.name,
-                                                        source_table.oid,
-                                                        relation.namespace().unwrap(),
-                                                        relation.name().unwrap()
-                                                    );
-                                                    return Err(Fatal(anyhow!(
-                                                        "source table {} with oid {} has been altered",
-                                                        source_table.name,
-                                                        source_table.oid
-                                                    )));
-                                                }
-                                                // Relation messages do not include nullability/primary_key data
-                                                // so we check the name, type_oid, and type_mod explicitly and error if any of them differ
-                                                if !source_table.columns.iter().zip(relation.columns()).all(
-                                                    |(src, rel)| {
-                                                        src.name == rel.name().unwrap()
-                                                            && src.type_oid == u32::from_be_bytes(rel.type_id().to_be_bytes())
-                                                            && src.type_mod == rel.type_modifier()
-                                                    },
-                                                ) {
-                                                    error!("alter table error: name {}, oid {}, old_schema {:?}, new_schema {:?}", source_table.name, source_table.oid, source_table.columns, relation.columns());
-                                                    return Err(Fatal(anyhow!(
-                                                        "source table {} with oid {} has been altered",
-                                                        source_table.name,
-                                                        source_table.oid
-                                                    )));
-                                                }
-                                            }
+                                        let source_table = match self.source_tables.get(&rel_id) {
+                                            Some(source_table) => source_table,
                                             // Ignore messages for tables we do not know about
                                             None => continue,
+                                        };
+                                        // Relation messages do not include nullability/primary_key data
+                                        // so we check the name, type_oid, and type_mod explicitly
+                                        let columns = try_fatal!(relation
+                                            .columns()
+                                            .iter()
+                                            .map(|c| {
+                                                Ok((
+                                                    c.name()?,
+                                                    u32::from_be_bytes(c.type_id().to_be_bytes()),
+                                                    c.type_modifier(),
+                                                ))
+                                            })
+                                            .collect::<Result<Vec<_>, std::io::Error>>());
+                                        let compatible = source_table.name == try_fatal!(relation.name())
+                                            && source_table.namespace == try_fatal!(relation.namespace())
+                                            && columns_compatible(source_table, columns);
+                                        if !compatible {
+                                            error!(
+                                                "alter table error: name {}, oid {}, old_schema {:?}, new_schema {:?}",
+                                                source_table.name, source_table.oid, source_table.columns, relation.columns()
+                                            );
+                                            // Stop replicating the table and report the change as a
+                                            // definite error once the transaction commits. Relation
+                                            // messages are decoded with the schema as of their position
+                                            // in the WAL, so a replayed stream reports the error at the
+                                            // same lsn. The other tables in the publication are
+                                            // unaffected.
+                                            errors.push(table_altered_error(source_table));
+                                            self.source_tables.remove(&rel_id);
+                                            self.table_state.remove(&rel_id);
                                         }
                                     }
                                     Origin(_) | Type(_) => {
@@ -835,3 +1133,73 @@ impl PostgresTaskInfo {
         }
     }
 }
+
+/// Reports whether the upstream `columns` of a table are compatible with the columns the source
+/// was created with.
+///
+/// Upstream columns are compatible if they start with the source's columns, in the same order,
+/// with the same names, and with the same or a wider type. Any columns after those are ignored,
+/// as `ALTER TABLE ... ADD COLUMN` always appends new columns to the end of the table.
+fn columns_compatible<'a, I>(table: &PostgresTableDesc, columns: I) -> bool
+where
+    I: IntoIterator<Item = (&'a str, u32, i32)>,
+{
+    let mut columns = columns.into_iter();
+    table.columns.iter().all(|expected| match columns.next() {
+        Some((name, type_oid, type_mod)) => {
+            expected.name == name
+                && is_widened_type((expected.type_oid, expected.type_mod), (type_oid, type_mod))
+        }
+        None => false,
+    })
+}
+
+/// Reports whether every value of the type `old` is also a value of the type `new`, where both
+/// types are given as a pair of type OID and type modifier.
+///
+/// Values replicated after a column has been widened are cast back to the column's original
+/// type when the source's data is read, so values that do not fit the original type are
+/// reported as errors or truncated according to the usual cast rules.
+fn is_widened_type(old: (u32, i32), new: (u32, i32)) -> bool {
+    // The header size that Postgres adds to the type modifiers of variable length types.
+    const VARHDRSZ: i32 = 4;
+
+    if old == new {
+        return true;
+    }
+    let (old_type, new_type) = match (PgType::from_oid(old.0), PgType::from_oid(new.0)) {
+        (Some(old_type), Some(new_type)) => (old_type, new_type),
+        _ => return false,
+    };
+    let (old_mod, new_mod) = (old.1, new.1);
+    if old_type == PgType::INT2 {
+        new_type == PgType::INT4 || new_type == PgType::INT8
+    } else if old_type == PgType::INT4 {
+        new_type == PgType::INT8
+    } else if old_type == PgType::FLOAT4 {
+        new_type == PgType::FLOAT8
+    } else if old_type == PgType::VARCHAR {
+        // A type modifier of -1 means that the length is unbounded.
+        new_type == PgType::TEXT
+            || (new_type == PgType::VARCHAR
+                && old_mod != -1
+                && (new_mod == -1 || new_mod >= old_mod))
+    } else if old_type == PgType::NUMERIC && new_type == PgType::NUMERIC && old_mod != -1 {
+        // The type modifier of `numeric(p, s)` is `((p << 16) | s) + VARHDRSZ`. Only increasing
+        // the precision while preserving the scale widens the type.
+        let precision = |m: i32| ((m - VARHDRSZ) >> 16) & 0xffff;
+        let scale = |m: i32| (m - VARHDRSZ) & 0xffff;
+        new_mod == -1
+            || (precision(new_mod) >= precision(old_mod) && scale(new_mod) == scale(old_mod))
+    } else {
+        false
+    }
+}
+
+/// The error reported for a source table that has been altered incompatibly upstream.
+fn table_altered_error(table: &PostgresTableDesc) -> SourceErrorDetails {
+    SourceErrorDetails::Other(format!(
+        "source table {} with oid {} has been altered",
+        table.name, table.oid
+    ))
+}
@@ -20,7 +20,9 @@ use tracing::info;
 use mz_expr::PartitionId;
 use mz_repr::{Datum, GlobalId, Row};
 
-use crate::source::{SourceMessage, SourceMessageType, SourceReader, SourceReaderError};
+use crate::source::{
+    PersistedSourceOutput, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
+};
 use crate::types::connections::ConnectionContext;
 use crate::types::sources::{encoding::SourceDataEncoding, MzOffset, SourceConnection};
 
@@ -48,6 +50,7 @@ impl SourceReader for PubNubSourceReader {
         _encoding: SourceDataEncoding,
         _: crate::source::metrics::SourceBaseMetrics,
         _: ConnectionContext,
+        _: PersistedSourceOutput,
     ) -> Result<Self, anyhow::Error> {
         let pubnub_conn = match connection {
             SourceConnection::PubNub(pubnub_conn) => pubnub_conn,
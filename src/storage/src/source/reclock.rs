@@ -516,6 +516,7 @@ mod tests {
             },
             remap_shard: shard,
             data_shard: ShardId::new(),
+            layout: None,
         };
 
         ReclockOperator::new(
@@ -55,7 +55,8 @@ use mz_repr::GlobalId;
 use self::metrics::{BucketMetrics, ScanBucketMetrics};
 use self::notifications::{Event, EventType, TestEvent};
 use crate::source::{
-    NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
+    NextMessage, PersistedSourceOutput, SourceMessage, SourceMessageType, SourceReader,
+    SourceReaderError,
 };
 use crate::types::connections::aws::{AwsConfig, AwsExternalIdPrefix};
 use crate::types::connections::ConnectionContext;
@@ -645,15 +646,16 @@ impl From<S3Error> for std::io::Error {
 
 type S3Result<R> = Result<R, S3Error>;
 
-async fn download_object(
-    tx: &Sender<S3Result<InternalMessage>>,
-    activator: &SyncActivator,
-    client: &S3Client,
-    bucket: &str,
-    key: &str,
+/// Returns a reader over the contents of the object at `key` in `bucket`.
+///
+/// Failed downloads are transparently resumed from the last byte read. The
+/// object is returned as stored; decompressing it is left to the caller.
+pub(crate) fn object_reader<'a>(
+    client: &'a S3Client,
+    bucket: &'a str,
+    key: &'a str,
     compression: Compression,
-    source_id: &str,
-) -> Result<DownloadMetricUpdate, DownloadError> {
+) -> BufReader<impl AsyncRead + 'a> {
     let retry_reader: RetryReader<_, _, _> = RetryReader::new(|state, offset| async move {
         let range = if offset == 0 {
             None
@@ -698,7 +700,19 @@ async fn download_object(
         })))
     });
 
-    let mut reader = Box::pin(BufReader::new(retry_reader));
+    BufReader::new(retry_reader)
+}
+
+async fn download_object(
+    tx: &Sender<S3Result<InternalMessage>>,
+    activator: &SyncActivator,
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    compression: Compression,
+    source_id: &str,
+) -> Result<DownloadMetricUpdate, DownloadError> {
+    let mut reader = Box::pin(object_reader(client, bucket, key, compression));
 
     // Check for empty files by filling up the buffer of bufreader and checking if it got any bytes
     match reader.fill_buf().await {
@@ -796,6 +810,7 @@ impl SourceReader for S3SourceReader {
         _encoding: SourceDataEncoding,
         metrics: crate::source::metrics::SourceBaseMetrics,
         connection_context: ConnectionContext,
+        _: PersistedSourceOutput,
     ) -> Result<Self, anyhow::Error> {
         let s3_conn = match connection {
             SourceConnection::S3(s3_conn) => s3_conn,
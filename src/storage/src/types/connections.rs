@@ -30,7 +30,7 @@ use mz_repr::GlobalId;
 use mz_secrets::SecretsReader;
 use mz_sql_parser::ast::KafkaConnectionOptionName;
 
-use crate::types::connections::aws::AwsExternalIdPrefix;
+use crate::types::connections::aws::{AwsConfig, AwsExternalIdPrefix};
 
 pub mod aws;
 
@@ -148,6 +148,7 @@ pub enum Connection {
     Csr(CsrConnection),
     Postgres(PostgresConnection),
     Ssh(SshConnection),
+    Aws(AwsConfig),
 }
 
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
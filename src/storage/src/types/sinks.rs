@@ -90,27 +90,43 @@ impl RustType<ProtoSinkDesc> for SinkDesc<CollectionMetadata, mz_repr::Timestamp
 pub enum SinkEnvelope {
     Debezium,
     Upsert,
+    /// Only insertions are written to the sink, each as a bare value.
+    ///
+    /// If `drop_retractions` is set, retractions are silently discarded.
+    /// Otherwise, encountering a retraction is an error that shuts down the
+    /// sink.
+    AppendOnly {
+        drop_retractions: bool,
+    },
 }
 
 impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
     fn into_proto(&self) -> ProtoSinkEnvelope {
-        use proto_sink_envelope::Kind;
+        use proto_sink_envelope::{Kind, ProtoAppendOnly};
         ProtoSinkEnvelope {
             kind: Some(match self {
                 SinkEnvelope::Debezium => Kind::Debezium(()),
                 SinkEnvelope::Upsert => Kind::Upsert(()),
+                SinkEnvelope::AppendOnly { drop_retractions } => {
+                    Kind::AppendOnly(ProtoAppendOnly {
+                        drop_retractions: *drop_retractions,
+                    })
+                }
             }),
         }
     }
 
     fn from_proto(proto: ProtoSinkEnvelope) -> Result<Self, TryFromProtoError> {
-        use proto_sink_envelope::Kind;
+        use proto_sink_envelope::{Kind, ProtoAppendOnly};
         let kind = proto
             .kind
             .ok_or_else(|| TryFromProtoError::missing_field("ProtoSinkEnvelope::kind"))?;
         Ok(match kind {
             Kind::Debezium(()) => SinkEnvelope::Debezium,
             Kind::Upsert(()) => SinkEnvelope::Upsert,
+            Kind::AppendOnly(ProtoAppendOnly { drop_retractions }) => {
+                SinkEnvelope::AppendOnly { drop_retractions }
+            }
         })
     }
 }
@@ -189,21 +205,64 @@ impl RustType<ProtoSinkConnection> for SinkConnection<CollectionMetadata> {
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct KafkaSinkConsistencyConnection {
     pub topic: String,
-    pub schema_id: i32,
+    pub format: KafkaSinkConsistencyFormat,
+    /// The name of the data collection reported in the `data_collections`
+    /// field of the transaction metadata records.
+    pub data_collection: String,
 }
 
 impl RustType<ProtoKafkaSinkConsistencyConnection> for KafkaSinkConsistencyConnection {
     fn into_proto(&self) -> ProtoKafkaSinkConsistencyConnection {
         ProtoKafkaSinkConsistencyConnection {
             topic: self.topic.clone(),
-            schema_id: self.schema_id,
+            format: Some(self.format.into_proto()),
+            data_collection: self.data_collection.clone(),
         }
     }
 
     fn from_proto(proto: ProtoKafkaSinkConsistencyConnection) -> Result<Self, TryFromProtoError> {
         Ok(KafkaSinkConsistencyConnection {
             topic: proto.topic,
-            schema_id: proto.schema_id,
+            format: proto
+                .format
+                .into_rust_if_some("ProtoKafkaSinkConsistencyConnection::format")?,
+            data_collection: proto.data_collection,
+        })
+    }
+}
+
+/// The encoding of the records written to the consistency topic of a Kafka
+/// sink.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KafkaSinkConsistencyFormat {
+    /// Avro, using the schema with the given ID in the schema registry.
+    Avro {
+        schema_id: i32,
+    },
+    Json,
+}
+
+impl RustType<ProtoKafkaSinkConsistencyFormat> for KafkaSinkConsistencyFormat {
+    fn into_proto(&self) -> ProtoKafkaSinkConsistencyFormat {
+        use proto_kafka_sink_consistency_format::{Kind, ProtoAvro};
+        ProtoKafkaSinkConsistencyFormat {
+            kind: Some(match self {
+                KafkaSinkConsistencyFormat::Avro { schema_id } => Kind::Avro(ProtoAvro {
+                    schema_id: *schema_id,
+                }),
+                KafkaSinkConsistencyFormat::Json => Kind::Json(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoKafkaSinkConsistencyFormat) -> Result<Self, TryFromProtoError> {
+        use proto_kafka_sink_consistency_format::{Kind, ProtoAvro};
+        let kind = proto.kind.ok_or_else(|| {
+            TryFromProtoError::missing_field("ProtoKafkaSinkConsistencyFormat::kind")
+        })?;
+        Ok(match kind {
+            Kind::Avro(ProtoAvro { schema_id }) => KafkaSinkConsistencyFormat::Avro { schema_id },
+            Kind::Json(()) => KafkaSinkConsistencyFormat::Json,
         })
     }
 }
@@ -368,6 +427,7 @@ impl RustType<ProtoKafkaSinkConnection> for KafkaSinkConnection {
 pub struct PublishedSchemaInfo {
     pub key_schema_id: Option<i32>,
     pub value_schema_id: i32,
+    pub format: PublishedSchemaFormat,
 }
 
 impl RustType<ProtoPublishedSchemaInfo> for PublishedSchemaInfo {
@@ -375,6 +435,7 @@ impl RustType<ProtoPublishedSchemaInfo> for PublishedSchemaInfo {
         ProtoPublishedSchemaInfo {
             key_schema_id: self.key_schema_id.clone(),
             value_schema_id: self.value_schema_id,
+            format: Some(self.format.into_proto()),
         }
     }
 
@@ -382,6 +443,40 @@ impl RustType<ProtoPublishedSchemaInfo> for PublishedSchemaInfo {
         Ok(PublishedSchemaInfo {
             key_schema_id: proto.key_schema_id,
             value_schema_id: proto.value_schema_id,
+            format: proto
+                .format
+                .into_rust_if_some("ProtoPublishedSchemaInfo::format")?,
+        })
+    }
+}
+
+/// The format of the schemas that a Kafka sink published to the schema
+/// registry, which determines how the sink encodes its records.
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PublishedSchemaFormat {
+    Avro,
+    Protobuf,
+}
+
+impl RustType<ProtoPublishedSchemaFormat> for PublishedSchemaFormat {
+    fn into_proto(&self) -> ProtoPublishedSchemaFormat {
+        use proto_published_schema_format::Kind;
+        ProtoPublishedSchemaFormat {
+            kind: Some(match self {
+                PublishedSchemaFormat::Avro => Kind::Avro(()),
+                PublishedSchemaFormat::Protobuf => Kind::Protobuf(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoPublishedSchemaFormat) -> Result<Self, TryFromProtoError> {
+        use proto_published_schema_format::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoPublishedSchemaFormat::kind"))?;
+        Ok(match kind {
+            Kind::Avro(()) => PublishedSchemaFormat::Avro,
+            Kind::Protobuf(()) => PublishedSchemaFormat::Protobuf,
         })
     }
 }
@@ -476,6 +571,9 @@ pub struct KafkaSinkConnectionBuilder {
     pub topic_prefix: String,
     pub consistency_topic_prefix: Option<String>,
     pub consistency_format: Option<KafkaSinkFormat>,
+    /// The data collection name to use in transaction metadata records, if
+    /// different from the topic prefix.
+    pub consistency_data_collection: Option<String>,
     pub topic_suffix_nonce: String,
     pub partition_count: i32,
     pub replication_factor: i32,
@@ -513,5 +611,10 @@ pub enum KafkaSinkFormat {
         value_schema: String,
         csr_connection: CsrConnection,
     },
+    Protobuf {
+        key_schema: Option<String>,
+        value_schema: String,
+        csr_connection: CsrConnection,
+    },
     Json,
 }
@@ -9,7 +9,7 @@
 
 //! Types and traits related to the introduction of changing collections into `dataflow`.
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::num::TryFromIntError;
 use std::ops::{Add, AddAssign, Deref, DerefMut};
 use std::str::FromStr;
@@ -25,10 +25,11 @@ use prost::Message;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use mz_persist_types::columnar::{self, ColumnData, Schema};
 use mz_persist_types::Codec;
 use mz_proto::{any_uuid, TryFromProtoError};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType};
-use mz_repr::{ColumnType, GlobalId, RelationDesc, RelationType, Row, ScalarType};
+use mz_repr::{ColumnType, Datum, GlobalId, RelationDesc, RelationType, Row, ScalarType};
 
 pub mod encoding;
 
@@ -1664,6 +1665,156 @@ impl Codec for SourceData {
         let proto = ProtoSourceData::decode(buf).map_err(|err| err.to_string())?;
         proto.into_rust().map_err(|err| err.to_string())
     }
+
+    /// Decodes the value at `idx` of the columns of a [SourceDataSchema]
+    /// without the collection's type, which the columns themselves describe.
+    fn decode_columns(columns: &[ColumnData], idx: usize) -> Result<Self, String> {
+        let (err_col, ok_cols) = match columns.split_first() {
+            Some((ColumnData::Bytes(err_col), ok_cols)) => (err_col, ok_cols),
+            _ => return Err(format!("missing err column in {:?}", columns)),
+        };
+        match &err_col[idx] {
+            Some(buf) => Ok(SourceData(Err(DataflowError::decode(buf)?))),
+            None => Ok(SourceData(Ok(Row::decode_columns(ok_cols, idx)?))),
+        }
+    }
+}
+
+/// A [Schema] for [SourceData] in a collection of a given [RelationType].
+///
+/// Errors are stored in a nullable `err` column using their [Codec]. Rows are
+/// stored in the columns given by the [Schema] impl for [RelationDesc], all of
+/// which are null for errors. These are named after their position in the
+/// collection (see [Self::ok_column_name]) rather than after the columns of the
+/// collection, so that readers, which only know the collection's type, can
+/// find them. `ALTER TABLE` keeps the collection in its shard and evolves its
+/// [`ColumnLayout`](crate::controller::ColumnLayout) instead, which only ever
+/// appends columns, so a position always names the same column of the shard.
+/// Such collections use the schema of the layout's type rather than of the
+/// collection's.
+///
+/// A [projected](Self::projected) schema only decodes some of the columns of
+/// each row, and fills in the rest with [Datum::Dummy]. It can't encode.
+#[derive(Debug, Clone)]
+pub struct SourceDataSchema {
+    /// The columns this schema encodes and decodes.
+    desc: RelationDesc,
+    /// The position of each column of `desc` in the collection along with the
+    /// collection's arity, if `desc` is a projection of it.
+    projection: Option<(Vec<usize>, usize)>,
+}
+
+impl SourceDataSchema {
+    /// Returns the schema of a collection with the given type.
+    pub fn new(typ: &RelationType) -> Self {
+        SourceDataSchema {
+            desc: Self::positional_desc(typ, 0..typ.arity()),
+            projection: None,
+        }
+    }
+
+    /// Returns the schema of a collection with the given type that only
+    /// decodes the columns at the given positions.
+    pub fn projected(typ: &RelationType, projection: &BTreeSet<usize>) -> Self {
+        SourceDataSchema {
+            desc: Self::positional_desc(typ, projection.iter().copied()),
+            projection: Some((projection.iter().copied().collect(), typ.arity())),
+        }
+    }
+
+    /// Returns the name of the typed column that stores the column at the
+    /// given position of the collection.
+    pub fn ok_column_name(idx: usize) -> String {
+        format!("ok.column{}", idx + 1)
+    }
+
+    /// Replaces the columns of `row` that this schema doesn't decode with
+    /// [Datum::Dummy], so that it matches the rows that this schema decodes.
+    pub fn project(&self, row: &Row) -> Row {
+        match &self.projection {
+            None => row.clone(),
+            Some((positions, _arity)) => {
+                let datums = row.iter().enumerate().map(|(idx, datum)| {
+                    if positions.contains(&idx) {
+                        datum
+                    } else {
+                        Datum::Dummy
+                    }
+                });
+                Row::pack(datums)
+            }
+        }
+    }
+
+    fn positional_desc<I: Iterator<Item = usize>>(
+        typ: &RelationType,
+        positions: I,
+    ) -> RelationDesc {
+        let (types, names): (Vec<_>, Vec<_>) = positions
+            .map(|idx| (typ.column_types[idx].clone(), format!("column{}", idx + 1)))
+            .unzip();
+        RelationDesc::new(RelationType::new(types), names)
+    }
+}
+
+impl Schema<SourceData> for SourceDataSchema {
+    fn columns(&self) -> Vec<(String, columnar::ColumnType)> {
+        let mut columns = vec![("err".to_owned(), columnar::ColumnType::Bytes)];
+        columns.extend(
+            self.desc
+                .columns()
+                .into_iter()
+                .map(|(name, typ)| (format!("ok.{}", name), typ)),
+        );
+        columns
+    }
+
+    fn encode(&self, val: &SourceData, columns: &mut [ColumnData]) -> Result<(), String> {
+        if self.projection.is_some() {
+            return Err("cannot encode with a projected schema".to_owned());
+        }
+        let (err_col, ok_cols) = match columns.split_first_mut() {
+            Some((ColumnData::Bytes(err_col), ok_cols)) => (err_col, ok_cols),
+            _ => return Err(format!("missing err column in {:?}", columns)),
+        };
+        match &val.0 {
+            Ok(row) => {
+                err_col.push(None);
+                self.desc.encode(row, ok_cols)
+            }
+            Err(err) => {
+                let mut buf = Vec::new();
+                err.encode(&mut buf);
+                err_col.push(Some(buf));
+                for col in ok_cols.iter_mut() {
+                    col.push_null();
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn decode(&self, columns: &[ColumnData], idx: usize) -> Result<SourceData, String> {
+        let (err_col, ok_cols) = match columns.split_first() {
+            Some((ColumnData::Bytes(err_col), ok_cols)) => (err_col, ok_cols),
+            _ => return Err(format!("missing err column in {:?}", columns)),
+        };
+        if let Some(buf) = &err_col[idx] {
+            return Ok(SourceData(Err(DataflowError::decode(buf)?)));
+        }
+        let row = self.desc.decode(ok_cols, idx)?;
+        let row = match &self.projection {
+            None => row,
+            Some((positions, arity)) => {
+                let mut datums = vec![Datum::Dummy; *arity];
+                for (position, datum) in positions.iter().zip(row.iter()) {
+                    datums[*position] = datum;
+                }
+                Row::pack_slice(&datums)
+            }
+        };
+        Ok(SourceData(Ok(row)))
+    }
 }
 
 #[test]
@@ -139,6 +139,7 @@ pub enum DataEncodingInner {
     Postgres,
     Bytes,
     Text,
+    Json,
     RowCodec(RelationDesc),
 }
 
@@ -154,6 +155,7 @@ impl RustType<ProtoDataEncodingInner> for DataEncodingInner {
                 DataEncodingInner::Postgres => Kind::Postgres(()),
                 DataEncodingInner::Bytes => Kind::Bytes(()),
                 DataEncodingInner::Text => Kind::Text(()),
+                DataEncodingInner::Json => Kind::Json(()),
                 DataEncodingInner::RowCodec(e) => Kind::RowCodec(e.into_proto()),
             }),
         }
@@ -172,6 +174,7 @@ impl RustType<ProtoDataEncodingInner> for DataEncodingInner {
             Kind::Postgres(()) => DataEncodingInner::Postgres,
             Kind::Bytes(()) => DataEncodingInner::Bytes,
             Kind::Text(()) => DataEncodingInner::Text,
+            Kind::Json(()) => DataEncodingInner::Json,
             Kind::RowCodec(e) => DataEncodingInner::RowCodec(e.into_rust()?),
         })
     }
@@ -273,6 +276,9 @@ impl DataEncoding {
             DataEncodingInner::Text => {
                 RelationDesc::empty().with_column("text", ScalarType::String.nullable(false))
             }
+            DataEncodingInner::Json => {
+                RelationDesc::empty().with_column("data", ScalarType::Jsonb.nullable(false))
+            }
             DataEncodingInner::Postgres => RelationDesc::empty()
                 .with_column("oid", ScalarType::Int32.nullable(false))
                 .with_column(
@@ -304,6 +310,7 @@ impl DataEncoding {
             DataEncodingInner::Regex { .. } => "Regex",
             DataEncodingInner::Csv(_) => "Csv",
             DataEncodingInner::Text => "Text",
+            DataEncodingInner::Json => "Json",
             DataEncodingInner::Postgres => "Postgres",
             DataEncodingInner::RowCodec(_) => "RowCodec",
         }
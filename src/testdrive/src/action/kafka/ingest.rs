@@ -17,12 +17,14 @@ use byteorder::{NetworkEndian, WriteBytesExt};
 use futures::stream::{FuturesUnordered, StreamExt};
 use maplit::hashmap;
 use prost::Message;
-use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor};
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, ReflectMessage};
 use rdkafka::message::{Header, OwnedHeaders};
 use rdkafka::producer::FutureRecord;
 use serde::de::DeserializeOwned;
 use tokio::fs;
 
+use mz_interchange::{confluent, protobuf};
+
 use crate::action::{self, Action, ControlFlow, State};
 use crate::format::avro::{self, Schema};
 use crate::format::bytes;
@@ -56,7 +58,7 @@ enum Format {
         message: String,
         confluent_wire_format: bool,
         schema_id_subject: Option<String>,
-        schema_message_id: u8,
+        schema_message_id: Option<u8>,
     },
     Bytes {
         terminator: Option<u8>,
@@ -73,7 +75,7 @@ enum Transcoder {
         message: MessageDescriptor,
         confluent_wire_format: bool,
         schema_id: i32,
-        schema_message_id: u8,
+        schema_message_id: Option<u8>,
     },
     Bytes {
         terminator: Option<u8>,
@@ -133,15 +135,21 @@ impl Transcoder {
                         .context("parsing protobuf JSON")?;
                     let mut out = vec![];
                     if *confluent_wire_format {
-                        // See: https://github.com/MaterializeInc/materialize/issues/9250
                         // The first byte is a magic byte (0) that indicates the Confluent
                         // serialization format version, and the next four bytes are a
                         // 32-bit schema ID, which we default to something fun.
-                        // And, as we only support single-message proto files for now,
-                        // we also set the following message id to 0.
+                        // The following bytes are the message index array, which
+                        // identifies the message in the schema, unless overridden by a
+                        // raw byte.
                         out.write_u8(0).unwrap();
                         out.write_i32::<NetworkEndian>(*schema_id).unwrap();
-                        out.write_u8(*schema_message_id).unwrap();
+                        match schema_message_id {
+                            Some(schema_message_id) => out.write_u8(*schema_message_id).unwrap(),
+                            None => confluent::write_protobuf_message_indexes(
+                                &mut out,
+                                &protobuf::message_indexes(message.descriptor()),
+                            ),
+                        }
                     }
                     message.encode(&mut out)?;
                     Ok(Some(out))
@@ -193,7 +201,7 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, anyhow::Err
                 // false
                 confluent_wire_format: cmd.args.opt_bool("confluent-wire-format")?.unwrap_or(false),
                 schema_id_subject: cmd.args.opt_string("schema-id-subject"),
-                schema_message_id: cmd.args.opt_parse::<u8>("schema-message-id")?.unwrap_or(0),
+                schema_message_id: cmd.args.opt_parse::<u8>("schema-message-id")?,
             }
         }
         "bytes" => Format::Bytes { terminator: None },
@@ -212,10 +220,7 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, anyhow::Err
                 message,
                 confluent_wire_format: cmd.args.opt_bool("confluent-wire-format")?.unwrap_or(false),
                 schema_id_subject: cmd.args.opt_string("key-schema-id-subject"),
-                schema_message_id: cmd
-                    .args
-                    .opt_parse::<u8>("key-schema-message-id")?
-                    .unwrap_or(0),
+                schema_message_id: cmd.args.opt_parse::<u8>("key-schema-message-id")?,
             })
         }
         Some("bytes") => Some(Format::Bytes {
@@ -234,18 +234,20 @@ impl Action for VerifyAction {
                     self.partial_search.is_some(),
                 )?
             }
-            SinkFormat::Json { key } => {
+            SinkFormat::Json { key: verify_key } => {
                 assert!(
                     self.partial_search.is_none(),
                     "partial search not yet implemented for json formatted sinks"
                 );
                 let mut actual_messages = vec![];
                 for (key, value) in actual_bytes {
+                    // Keys are only decoded when they are verified, as e.g. the
+                    // keys of consistency records are not JSON-encoded.
                     let key_datum = match key {
-                        None => None,
-                        Some(bytes) => {
+                        Some(bytes) if *verify_key => {
                             Some(serde_json::from_slice(&bytes).context("decoding json")?)
                         }
+                        _ => None,
                     };
                     let value_datum = match value {
                         None => None,
@@ -262,7 +264,7 @@ impl Action for VerifyAction {
                 }
 
                 json::validate_sink(
-                    *key,
+                    *verify_key,
                     &self.expected_messages,
                     &actual_messages,
                     &state.regex,
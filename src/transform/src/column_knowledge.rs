@@ -100,6 +100,21 @@ impl ColumnKnowledge {
                     }
                     Ok(body_knowledge)
                 }
+                MirRelationExpr::LetRec { id, value, body } => {
+                    // Nothing is known about the previous iteration of the value,
+                    // beyond its type. What we learn about the value holds for
+                    // every iteration, and so also for the uses in the body.
+                    let id = mz_expr::Id::Local(id.clone());
+                    let prior_knowledge = knowledge.remove(&id);
+                    let value_knowledge = self.harvest(value, knowledge, knowledge_stack)?;
+                    knowledge.insert(id, value_knowledge);
+                    let body_knowledge = self.harvest(body, knowledge, knowledge_stack)?;
+                    knowledge.remove(&id);
+                    if let Some(prior_knowledge) = prior_knowledge {
+                        knowledge.insert(id, prior_knowledge);
+                    }
+                    Ok(body_knowledge)
+                }
                 MirRelationExpr::Project { input, outputs } => {
                     let input_knowledge = self.harvest(input, knowledge, knowledge_stack)?;
                     Ok(outputs
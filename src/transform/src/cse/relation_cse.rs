@@ -32,6 +32,11 @@ impl crate::Transform for RelationCSE {
         relation: &mut MirRelationExpr,
         _: TransformArgs,
     ) -> Result<(), crate::TransformError> {
+        // Recursive bindings cannot be flattened into a sequence of `Let`
+        // bindings, so we leave such expressions unchanged.
+        if relation.is_recursive() {
+            return Ok(());
+        }
         let mut bindings = Bindings::default();
         bindings.intern_expression(relation)?;
         bindings.populate_expression(relation);
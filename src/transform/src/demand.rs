@@ -104,6 +104,20 @@ impl Demand {
 
                     self.action(value, needs, gets)
                 }
+                MirRelationExpr::LetRec { id, value, body } => {
+                    // The value may demand any of its own columns from the
+                    // previous iteration, so we demand all of them.
+                    let id = Id::Local(*id);
+                    let prior = gets.insert(id, HashSet::new());
+                    self.action(body, columns, gets)?;
+                    let arity = value.arity();
+                    self.action(value, (0..arity).collect(), gets)?;
+                    gets.remove(&id);
+                    if let Some(prior) = prior {
+                        gets.insert(id, prior);
+                    }
+                    Ok(())
+                }
                 MirRelationExpr::Project { input, outputs } => self.action(
                     input,
                     columns.into_iter().map(|c| outputs[c]).collect(),
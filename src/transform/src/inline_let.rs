@@ -61,17 +61,26 @@ impl crate::Transform for InlineLet {
     ) -> Result<(), crate::TransformError> {
         let mut lets = vec![];
         self.action(relation, &mut lets)?;
-        for (id, value) in lets.into_iter().rev() {
-            *relation = MirRelationExpr::Let {
-                id,
-                value: Box::new(value),
-                body: Box::new(relation.take_dangerous()),
-            };
-        }
+        wrap_lets(relation, lets);
         Ok(())
     }
 }
 
+/// Wraps `relation` in the given bindings, the first of which ends up outermost.
+///
+/// The `bool` of each binding indicates whether it is recursive.
+fn wrap_lets(relation: &mut MirRelationExpr, lets: Vec<(LocalId, MirRelationExpr, bool)>) {
+    for (id, value, recursive) in lets.into_iter().rev() {
+        let value = Box::new(value);
+        let body = Box::new(relation.take_dangerous());
+        *relation = if recursive {
+            MirRelationExpr::LetRec { id, value, body }
+        } else {
+            MirRelationExpr::Let { id, value, body }
+        };
+    }
+}
+
 impl InlineLet {
     /// Install replace certain `Get` operators with their `Let` value.
     ///
@@ -83,7 +92,7 @@ impl InlineLet {
     pub fn action(
         &self,
         relation: &mut MirRelationExpr,
-        lets: &mut Vec<(LocalId, MirRelationExpr)>,
+        lets: &mut Vec<(LocalId, MirRelationExpr, bool)>,
     ) -> Result<(), crate::TransformError> {
         self.checked_recur(|_| {
             if let MirRelationExpr::Let { id, value, body } = relation {
@@ -107,6 +116,9 @@ impl InlineLet {
                 };
                 let inlinable = match stripped_value {
                     MirRelationExpr::Get { .. } | MirRelationExpr::Constant { .. } => true,
+                    // Recursive bindings are not inlined into other expressions, as
+                    // they could end up nested within the value of another one.
+                    _ if stripped_value.is_recursive() => num_gets == 0,
                     _ => num_gets <= 1,
                 };
 
@@ -125,12 +137,21 @@ impl InlineLet {
                     })?;
                 } else {
                     // otherwise lift it to the top so it's out of the way
-                    lets.push((*id, value.take_dangerous()));
+                    lets.push((*id, value.take_dangerous(), false));
                 }
 
                 *relation = body.take_dangerous();
                 // might be another Let in the body so have to recur here
                 self.action(relation, lets)
+            } else if let MirRelationExpr::LetRec { id, value, body } = relation {
+                // Recursive bindings are never inlined. Bindings within their
+                // value may refer to `id`, and so must stay within the value.
+                let mut value_lets = vec![];
+                self.action(value, &mut value_lets)?;
+                wrap_lets(value, value_lets);
+                lets.push((*id, value.take_dangerous(), true));
+                *relation = body.take_dangerous();
+                self.action(relation, lets)
             } else {
                 relation.try_visit_mut_children(|child| self.action(child, lets))
             }
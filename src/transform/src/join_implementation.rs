@@ -23,7 +23,7 @@ use mz_expr::{JoinInputMapper, MapFilterProject, MirRelationExpr, MirScalarExpr,
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
 
 use self::index_map::IndexMap;
-use crate::{TransformArgs, TransformError};
+use crate::{EmptyIndexOracle, TransformArgs, TransformError};
 
 /// Determines the join implementation for join operators.
 #[derive(Debug)]
@@ -86,6 +86,11 @@ impl JoinImplementation {
             self.action_recursive(body, indexes)?;
             indexes.remove_local(*id);
             Ok(())
+        } else if let MirRelationExpr::LetRec { value, body, .. } = relation {
+            // The value is rendered in its own iterative scope, into which no
+            // arrangements are imported, so joins in it must not rely on them.
+            self.action_recursive(value, &mut IndexMap::new(&EmptyIndexOracle))?;
+            self.action_recursive(body, indexes)
         } else {
             relation.try_visit_mut_children(|e| self.action_recursive(e, indexes))?;
             self.action(relation, indexes);
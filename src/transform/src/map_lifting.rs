@@ -255,6 +255,15 @@ impl LiteralLifting {
                     gets.remove(&id);
                     result
                 }
+                MirRelationExpr::LetRec { value, body, .. } => {
+                    // Literals cannot be lifted out of a recursive binding, as
+                    // its value must keep producing the columns it refers to.
+                    let literals = self.action(value, gets)?;
+                    if !literals.is_empty() {
+                        **value = value.take_dangerous().map(literals);
+                    }
+                    self.action(body, gets)
+                }
                 MirRelationExpr::Project { input, outputs } => {
                     // We do not want to lift literals around projections.
                     // Projections are the highest lifted operator and lifting
@@ -116,6 +116,17 @@ impl MonotonicFlag {
                     }
                     result
                 }
+                MirRelationExpr::LetRec { id, value, body } => {
+                    // The iterations of a recursive binding may retract records,
+                    // so neither the binding nor its uses are monotonic.
+                    let prior = locals.remove(id);
+                    self.apply(value, mon_ids, locals)?;
+                    let result = self.apply(body, mon_ids, locals)?;
+                    if prior {
+                        locals.insert(*id);
+                    }
+                    result
+                }
                 // The default behavior.
                 // TODO: check that this is the behavior we want.
                 MirRelationExpr::Negate { .. } | MirRelationExpr::Constant { rows: Err(_), .. } => {
@@ -102,6 +102,20 @@ impl NonNullRequirements {
                     }
                     Ok(())
                 }
+                MirRelationExpr::LetRec { id, value, body } => {
+                    // Requirements of the uses of a recursive binding cannot be
+                    // pushed at its value, as the value would then discard rows
+                    // that its next iteration may depend on.
+                    let id = Id::Local(*id);
+                    let prior = gets.insert(id, Vec::new());
+                    self.action(body, columns, gets)?;
+                    self.action(value, HashSet::new(), gets)?;
+                    gets.remove(&id);
+                    if let Some(prior) = prior {
+                        gets.insert(id, prior);
+                    }
+                    Ok(())
+                }
                 MirRelationExpr::Project { input, outputs } => self.action(
                     input,
                     columns.into_iter().map(|c| outputs[c]).collect(),
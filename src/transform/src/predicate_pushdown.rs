@@ -488,6 +488,16 @@ impl PredicatePushdown {
                     // Continue recursively on the value.
                     self.action(value, get_predicates)
                 }
+                MirRelationExpr::LetRec { id, body, value } => {
+                    // Predicates applied to the uses of a recursive binding
+                    // cannot be moved to its value, as they would also apply
+                    // to the previous iteration of the value itself.
+                    self.action(body, get_predicates)?;
+                    get_predicates.remove(&Id::Local(*id));
+                    self.action(value, get_predicates)?;
+                    get_predicates.remove(&Id::Local(*id));
+                    Ok(())
+                }
                 MirRelationExpr::Join {
                     inputs,
                     equivalences,
@@ -83,6 +83,12 @@ impl ProjectionLifting {
                     gets.remove(&id);
                     Ok(())
                 }
+                MirRelationExpr::LetRec { value, body, .. } => {
+                    // Projections are not lifted out of recursive bindings, as
+                    // that would change the arity the recursive references observe.
+                    self.action(value, gets)?;
+                    self.action(body, gets)
+                }
                 MirRelationExpr::Project { input, outputs } => {
                     self.action(input, gets)?;
                     if let MirRelationExpr::Project {
@@ -111,6 +111,20 @@ impl ProjectionPushdown {
                 )?;
                 desired_projection.clone()
             }
+            MirRelationExpr::LetRec { id, value, body } => {
+                // Recursive bindings keep all of their columns, as the value
+                // must produce whatever its own references consume.
+                let id = Id::Local(*id);
+                let prior = gets.remove(&id);
+                self.action(body, desired_projection, gets)?;
+                let value_projection = (0..value.arity()).collect::<Vec<_>>();
+                self.action(value, &value_projection, gets)?;
+                gets.remove(&id);
+                if let Some(prior) = prior {
+                    gets.insert(id, prior);
+                }
+                desired_projection.clone()
+            }
             MirRelationExpr::Join {
                 inputs,
                 equivalences,
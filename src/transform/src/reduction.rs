@@ -65,7 +65,9 @@ impl FoldConstants {
         match relation {
             MirRelationExpr::Constant { .. } => { /* handled after match */ }
             MirRelationExpr::Get { .. } => {}
-            MirRelationExpr::Let { .. } => { /* constant prop done in InlineLet */ }
+            MirRelationExpr::Let { .. } | MirRelationExpr::LetRec { .. } => {
+                /* constant prop done in InlineLet */
+            }
             MirRelationExpr::Reduce {
                 input,
                 group_key,
@@ -94,6 +94,17 @@ impl RedundantJoin {
                     }
                     Ok(result)
                 }
+                MirRelationExpr::LetRec { id, value, body } => {
+                    // We do not attempt to determine the provenance of a recursive
+                    // binding, and treat it as if it were an imported collection.
+                    let old = lets.remove(&Id::Local(*id));
+                    self.action(value, lets)?;
+                    let result = self.action(body, lets)?;
+                    if let Some(old) = old {
+                        lets.insert(Id::Local(*id), old);
+                    }
+                    Ok(result)
+                }
                 MirRelationExpr::Get { id, typ } => {
                     // Extract the value provenance, or an empty list if unavailable.
                     let mut val_info = lets.get(id).cloned().unwrap_or_default();
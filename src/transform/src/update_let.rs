@@ -78,6 +78,23 @@ impl UpdateLet {
                     *id = new_id;
                     Ok(())
                 }
+                MirRelationExpr::LetRec { id, value, body } => {
+                    // The value may refer to `id`, so the new identifier must be
+                    // in scope for it. Every iteration starts from the output of
+                    // the previous one (and the first from an empty collection),
+                    // so the type of the value is also valid for its own `Get`s.
+                    let new_id = LocalId::new(id_gen.allocate_id());
+                    let prev = remap.insert(id.clone(), (new_id, value.typ()));
+                    self.action(value, remap, id_gen)?;
+                    remap.insert(id.clone(), (new_id, value.typ()));
+                    self.action(body, remap, id_gen)?;
+                    remap.remove(id);
+                    if let Some(prev_stuff) = prev {
+                        remap.insert(id.clone(), prev_stuff);
+                    }
+                    *id = new_id;
+                    Ok(())
+                }
                 MirRelationExpr::Get { id, typ } => {
                     if let Id::Local(local_id) = id {
                         if let Some((new_id, new_type)) = remap.get(local_id) {
@@ -122,7 +122,7 @@ def restart_pg_during_replication(c: Composition) -> None:
 
     restart_pg(c)
 
-    c.run("testdrive", "delete-rows-t2.td")
+    c.run("testdrive", "delete-rows-t2.td", "insert-altered.td")
 
 
 def restart_mz_during_replication(c: Composition) -> None:
@@ -130,4 +130,4 @@ def restart_mz_during_replication(c: Composition) -> None:
 
     restart_mz(c)
 
-    c.run("testdrive", "delete-rows-t2.td")
+    c.run("testdrive", "delete-rows-t2.td", "insert-altered.td")